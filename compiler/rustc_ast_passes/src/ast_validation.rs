@@ -371,6 +371,7 @@ fn check_decl_attrs(&self, fn_decl: &FnDecl) {
                     sym::deny,
                     sym::expect,
                     sym::forbid,
+                    sym::secret,
                     sym::warn,
                 ];
                 !attr.has_any_name(&arr) && rustc_attr_parsing::is_builtin_attr(*attr)
@@ -1,4 +1,6 @@
-use rustc_hir::attrs::{CoverageAttrKind, OptimizeAttr, RtsanSetting, SanitizerSet, UsedBy};
+use rustc_hir::attrs::{
+    CoverageAttrKind, CtSelectLoweringAttr, OptimizeAttr, RtsanSetting, SanitizerSet, UsedBy,
+};
 use rustc_session::parse::feature_err;
 
 use super::prelude::*;
@@ -64,6 +66,138 @@ impl<S: Stage> NoArgsAttributeParser<S> for ColdParser {
     const CREATE: fn(Span) -> AttributeKind = AttributeKind::Cold;
 }
 
+pub(crate) struct ConstantTimeParser;
+
+impl<S: Stage> SingleAttributeParser<S> for ConstantTimeParser {
+    const PATH: &[Symbol] = &[sym::constant_time];
+    const ATTRIBUTE_ORDER: AttributeOrder = AttributeOrder::KeepOutermost;
+    const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Warn;
+    const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowList(&[
+        Allow(Target::Fn),
+        Allow(Target::Method(MethodKind::Trait { body: true })),
+        Allow(Target::Method(MethodKind::TraitImpl)),
+        Allow(Target::Method(MethodKind::Inherent)),
+    ]);
+    const TEMPLATE: AttributeTemplate = template!(Word, List: &["speculative_load_hardening"]);
+
+    fn convert(cx: &mut AcceptContext<'_, '_, S>, args: &ArgParser) -> Option<AttributeKind> {
+        let speculative_load_hardening = match args {
+            ArgParser::NoArgs => false,
+            ArgParser::List(list) => {
+                let Some(single) = list.single() else {
+                    cx.expected_single_argument(list.span);
+                    return None;
+                };
+                match single.meta_item().and_then(|i| i.path().word_sym()) {
+                    Some(sym::speculative_load_hardening) => true,
+                    _ => {
+                        cx.expected_specific_argument(
+                            single.span(),
+                            &[sym::speculative_load_hardening],
+                        );
+                        return None;
+                    }
+                }
+            }
+            ArgParser::NameValue(_) => {
+                cx.expected_list_or_no_args(cx.attr_span);
+                return None;
+            }
+        };
+        Some(AttributeKind::ConstantTime { span: cx.attr_span, speculative_load_hardening })
+    }
+}
+
+pub(crate) struct DeclassifyParser;
+
+impl<S: Stage> SingleAttributeParser<S> for DeclassifyParser {
+    const PATH: &[Symbol] = &[sym::declassify];
+    const ATTRIBUTE_ORDER: AttributeOrder = AttributeOrder::KeepOutermost;
+    const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Error;
+    const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowList(&[Allow(Target::Expression)]);
+    const TEMPLATE: AttributeTemplate = template!(NameValueStr: "justification");
+
+    fn convert(cx: &mut AcceptContext<'_, '_, S>, args: &ArgParser) -> Option<AttributeKind> {
+        let Some(name_value) = args.name_value() else {
+            cx.expected_name_value(cx.attr_span, Some(sym::declassify));
+            return None;
+        };
+        let Some(justification) = name_value.value_as_str() else {
+            cx.expected_string_literal(name_value.value_span, None);
+            return None;
+        };
+        // The justification is what an audit of the declassification reads, so it is required.
+        if justification.as_str().trim().is_empty() {
+            cx.expected_non_empty_string_literal(name_value.value_span);
+            return None;
+        }
+        Some(AttributeKind::Declassify { justification, span: cx.attr_span })
+    }
+}
+
+pub(crate) struct SecretParser;
+
+impl<S: Stage> NoArgsAttributeParser<S> for SecretParser {
+    const PATH: &[Symbol] = &[sym::secret];
+    const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Warn;
+    const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowList(&[Allow(Target::Param)]);
+    const CREATE: fn(Span) -> AttributeKind = AttributeKind::Secret;
+}
+
+pub(crate) struct BranchlessParser;
+
+impl<S: Stage> NoArgsAttributeParser<S> for BranchlessParser {
+    const PATH: &[Symbol] = &[sym::branchless];
+    const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Warn;
+    const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowList(&[
+        Allow(Target::Fn),
+        Allow(Target::Method(MethodKind::Trait { body: true })),
+        Allow(Target::Method(MethodKind::TraitImpl)),
+        Allow(Target::Method(MethodKind::Inherent)),
+    ]);
+    const CREATE: fn(Span) -> AttributeKind = AttributeKind::Branchless;
+}
+
+pub(crate) struct CtSelectLoweringParser;
+
+impl<S: Stage> SingleAttributeParser<S> for CtSelectLoweringParser {
+    const PATH: &[Symbol] = &[sym::ct_select_lowering];
+    const ATTRIBUTE_ORDER: AttributeOrder = AttributeOrder::KeepOutermost;
+    const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Error;
+    const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowList(&[
+        Allow(Target::Fn),
+        Allow(Target::Closure),
+        Allow(Target::Method(MethodKind::Trait { body: true })),
+        Allow(Target::Method(MethodKind::TraitImpl)),
+        Allow(Target::Method(MethodKind::Inherent)),
+    ]);
+    const TEMPLATE: AttributeTemplate = template!(List: &["cmov", "masking", "asm"]);
+
+    fn convert(cx: &mut AcceptContext<'_, '_, S>, args: &ArgParser) -> Option<AttributeKind> {
+        let Some(list) = args.list() else {
+            cx.expected_list(cx.attr_span, args);
+            return None;
+        };
+
+        let Some(single) = list.single() else {
+            cx.expected_single_argument(list.span);
+            return None;
+        };
+
+        let res = match single.meta_item().and_then(|i| i.path().word_sym()) {
+            Some(sym::cmov) => CtSelectLoweringAttr::Cmov,
+            Some(sym::masking) => CtSelectLoweringAttr::Masking,
+            Some(sym::asm) => CtSelectLoweringAttr::Asm,
+            _ => {
+                cx.expected_specific_argument(single.span(), &[sym::cmov, sym::masking, sym::asm]);
+                return None;
+            }
+        };
+
+        Some(AttributeKind::CtSelectLowering(res, cx.attr_span))
+    }
+}
+
 pub(crate) struct CoverageParser;
 
 impl<S: Stage> SingleAttributeParser<S> for CoverageParser {
@@ -267,6 +401,7 @@ fn finalize(self, cx: &FinalizeContext<'_, '_, S>) -> Option<AttributeKind> {
             sym::ignore,
             sym::should_panic,
             sym::bench,
+            sym::ct_test,
             // diagnostics
             sym::allow,
             sym::warn,
@@ -511,6 +511,20 @@ impl<S: Stage> NoArgsAttributeParser<S> for RustcTrivialFieldReadsParser {
     const CREATE: fn(Span) -> AttributeKind = |_| AttributeKind::RustcTrivialFieldReads;
 }
 
+pub(crate) struct RustcNoBranchParser;
+
+impl<S: Stage> NoArgsAttributeParser<S> for RustcNoBranchParser {
+    const PATH: &[Symbol] = &[sym::rustc_no_branch];
+    const ON_DUPLICATE: OnDuplicate<S> = OnDuplicate::Warn;
+    const ALLOWED_TARGETS: AllowedTargets = AllowedTargets::AllowList(&[
+        Allow(Target::Fn),
+        Allow(Target::Method(MethodKind::Inherent)),
+        Allow(Target::Method(MethodKind::Trait { body: true })),
+        Allow(Target::Method(MethodKind::TraitImpl)),
+    ]);
+    const CREATE: fn(Span) -> AttributeKind = |_| AttributeKind::RustcNoBranch;
+}
+
 pub(crate) struct RustcNoMirInlineParser;
 
 impl<S: Stage> NoArgsAttributeParser<S> for RustcNoMirInlineParser {
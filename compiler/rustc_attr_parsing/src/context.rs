@@ -171,9 +171,12 @@ mod late {
         // tidy-alphabetical-start
         Single<CfiEncodingParser>,
         Single<CollapseDebugInfoParser>,
+        Single<ConstantTimeParser>,
         Single<CoverageParser>,
         Single<CrateNameParser>,
+        Single<CtSelectLoweringParser>,
         Single<CustomMirParser>,
+        Single<DeclassifyParser>,
         Single<DeprecationParser>,
         Single<DoNotRecommendParser>,
         Single<DummyParser>,
@@ -226,6 +229,7 @@ mod late {
         Single<WithoutArgs<AllowInternalUnsafeParser>>,
         Single<WithoutArgs<AsPtrParser>>,
         Single<WithoutArgs<AutomaticallyDerivedParser>>,
+        Single<WithoutArgs<BranchlessParser>>,
         Single<WithoutArgs<CoinductiveParser>>,
         Single<WithoutArgs<ColdParser>>,
         Single<WithoutArgs<CompilerBuiltinsParser>>,
@@ -287,6 +291,7 @@ mod late {
         Single<WithoutArgs<RustcLintUntrackedQueryInformationParser>>,
         Single<WithoutArgs<RustcMainParser>>,
         Single<WithoutArgs<RustcNeverReturnsNullPointerParser>>,
+        Single<WithoutArgs<RustcNoBranchParser>>,
         Single<WithoutArgs<RustcNoImplicitAutorefsParser>>,
         Single<WithoutArgs<RustcNoImplicitBoundsParser>>,
         Single<WithoutArgs<RustcNoMirInlineParser>>,
@@ -304,6 +309,7 @@ mod late {
         Single<WithoutArgs<RustcTrivialFieldReadsParser>>,
         Single<WithoutArgs<RustcVarianceOfOpaquesParser>>,
         Single<WithoutArgs<RustcVarianceParser>>,
+        Single<WithoutArgs<SecretParser>>,
         Single<WithoutArgs<SpecializationTraitParser>>,
         Single<WithoutArgs<StdInternalSymbolParser>>,
         Single<WithoutArgs<ThreadLocalParser>>,
@@ -150,6 +150,15 @@ pub(crate) struct BenchSig {
     pub(crate) span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(
+    "functions used as constant-time tests must have signature `fn(&mut CtTester) -> impl Termination`"
+)]
+pub(crate) struct CtTestSig {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag("allocators must be statics")]
 pub(crate) struct AllocMustStatics {
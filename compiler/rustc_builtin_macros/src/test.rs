@@ -93,7 +93,7 @@ pub(crate) fn expand_test(
 ) -> Vec<Annotatable> {
     check_builtin_macro_attribute(cx, meta_item, sym::test);
     warn_on_duplicate_attribute(cx, &item, sym::test);
-    expand_test_or_bench(cx, attr_sp, item, false)
+    expand_test_or_bench(cx, attr_sp, item, TestKind::Test)
 }
 
 pub(crate) fn expand_bench(
@@ -104,26 +104,58 @@ pub(crate) fn expand_bench(
 ) -> Vec<Annotatable> {
     check_builtin_macro_attribute(cx, meta_item, sym::bench);
     warn_on_duplicate_attribute(cx, &item, sym::bench);
-    expand_test_or_bench(cx, attr_sp, item, true)
+    expand_test_or_bench(cx, attr_sp, item, TestKind::Bench)
+}
+
+pub(crate) fn expand_ct_test(
+    cx: &mut ExtCtxt<'_>,
+    attr_sp: Span,
+    meta_item: &ast::MetaItem,
+    item: Annotatable,
+) -> Vec<Annotatable> {
+    check_builtin_macro_attribute(cx, meta_item, sym::ct_test);
+    warn_on_duplicate_attribute(cx, &item, sym::ct_test);
+    expand_test_or_bench(cx, attr_sp, item, TestKind::CtTest)
+}
+
+/// The attribute that a test function is annotated with.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TestKind {
+    /// `#[test]`
+    Test,
+    /// `#[bench]`
+    Bench,
+    /// `#[ct_test]`, a statistical test that the function is constant-time.
+    CtTest,
+}
+
+impl TestKind {
+    fn attr_name(self) -> &'static str {
+        match self {
+            TestKind::Test => "test",
+            TestKind::Bench => "bench",
+            TestKind::CtTest => "ct_test",
+        }
+    }
 }
 
 pub(crate) fn expand_test_or_bench(
     cx: &ExtCtxt<'_>,
     attr_sp: Span,
     item: Annotatable,
-    is_bench: bool,
+    kind: TestKind,
 ) -> Vec<Annotatable> {
     let (item, is_stmt) = match item {
         Annotatable::Item(i) => (i, false),
         Annotatable::Stmt(box ast::Stmt { kind: ast::StmtKind::Item(i), .. }) => (i, true),
         other => {
-            not_testable_error(cx, is_bench, attr_sp, None);
+            not_testable_error(cx, kind, attr_sp, None);
             return vec![other];
         }
     };
 
     let ast::ItemKind::Fn(fn_) = &item.kind else {
-        not_testable_error(cx, is_bench, attr_sp, Some(&item));
+        not_testable_error(cx, kind, attr_sp, Some(&item));
         return if is_stmt {
             vec![Annotatable::Stmt(Box::new(cx.stmt_item(item.span, item)))]
         } else {
@@ -147,10 +179,10 @@ pub(crate) fn expand_test_or_bench(
     // check_*_signature will report any errors in the type so compilation
     // will fail. We shouldn't try to expand in this case because the errors
     // would be spurious.
-    let check_result = if is_bench {
-        check_bench_signature(cx, &item, fn_)
-    } else {
-        check_test_signature(cx, &item, fn_)
+    let check_result = match kind {
+        TestKind::Test => check_test_signature(cx, &item, fn_),
+        TestKind::Bench => check_bench_signature(cx, &item, fn_),
+        TestKind::CtTest => check_ct_test_signature(cx, &item, fn_),
     };
     if check_result.is_err() {
         return if is_stmt {
@@ -206,7 +238,13 @@ pub(crate) fn expand_test_or_bench(
         expr
     };
 
-    let test_fn = if is_bench {
+    let test_path_symbol = Symbol::intern(&item_path(
+        // skip the name of the root module
+        &cx.current_expansion.module.mod_path[1..],
+        &fn_.ident,
+    ));
+
+    let test_fn = if kind == TestKind::Bench {
         // avoid name collisions by using the function name within the identifier, see bug #148275
         let bencher_param =
             Ident::from_str_and_span(&format!("__bench_{}", fn_.ident.name), attr_sp);
@@ -234,6 +272,38 @@ pub(crate) fn expand_test_or_bench(
                 )), // )
             ],
         )
+    } else if kind == TestKind::CtTest {
+        // avoid name collisions by using the function name within the identifier
+        let tester_param = Ident::from_str_and_span(&format!("__ct_{}", fn_.ident.name), attr_sp);
+        cx.expr_call(
+            sp,
+            cx.expr_path(test_path("StaticTestFn")),
+            thin_vec![
+                // #[coverage(off)]
+                // || test::run_ct_test(
+                coverage_off(cx.lambda0(
+                    sp,
+                    cx.expr_call(
+                        sp,
+                        cx.expr_path(test_path("run_ct_test")),
+                        thin_vec![
+                            // "path::to::test",
+                            cx.expr_str(sp, test_path_symbol),
+                            // |__ct_fn_name| $test_fn(__ct_fn_name)
+                            cx.lambda1(
+                                sp,
+                                cx.expr_call(
+                                    ret_ty_sp,
+                                    cx.expr_path(cx.path(sp, vec![fn_.ident])),
+                                    thin_vec![cx.expr_ident(sp, tester_param)],
+                                ),
+                                tester_param,
+                            ),
+                        ],
+                    ), // )
+                )), // )
+            ],
+        )
     } else {
         cx.expr_call(
             sp,
@@ -261,12 +331,6 @@ pub(crate) fn expand_test_or_bench(
         )
     };
 
-    let test_path_symbol = Symbol::intern(&item_path(
-        // skip the name of the root module
-        &cx.current_expansion.module.mod_path[1..],
-        &fn_.ident,
-    ));
-
     let location_info = get_location_info(cx, &fn_);
 
     let mut test_const =
@@ -405,9 +469,9 @@ pub(crate) fn expand_test_or_bench(
     }
 }
 
-fn not_testable_error(cx: &ExtCtxt<'_>, is_bench: bool, attr_sp: Span, item: Option<&ast::Item>) {
+fn not_testable_error(cx: &ExtCtxt<'_>, kind: TestKind, attr_sp: Span, item: Option<&ast::Item>) {
     let dcx = cx.dcx();
-    let name = if is_bench { "bench" } else { "test" };
+    let name = kind.attr_name();
     let msg = format!("the `#[{name}]` attribute may only be used on a free function");
     let level = match item.map(|i| &i.kind) {
         // These were a warning before #92959 and need to continue being that to avoid breaking
@@ -429,7 +493,7 @@ fn not_testable_error(cx: &ExtCtxt<'_>, is_bench: bool, attr_sp: Span, item: Opt
     }
     err.span_label(attr_sp, format!("the `#[{name}]` macro causes a function to be run as a test and has no effect on non-functions"));
 
-    if !is_bench {
+    if kind == TestKind::Test {
         err.with_span_suggestion(attr_sp,
             "replace with conditional compilation to make the item only exist when tests are being run",
             "#[cfg(test)]",
@@ -600,3 +664,15 @@ fn check_bench_signature(
     }
     Ok(())
 }
+
+fn check_ct_test_signature(
+    cx: &ExtCtxt<'_>,
+    i: &ast::Item,
+    f: &ast::Fn,
+) -> Result<(), ErrorGuaranteed> {
+    // Like for benches, the type of the argument is checked after expansion.
+    if f.sig.decl.inputs.len() != 1 {
+        return Err(cx.dcx().emit_err(errors::CtTestSig { span: i.span }));
+    }
+    Ok(())
+}
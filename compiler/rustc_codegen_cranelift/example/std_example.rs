@@ -110,6 +110,7 @@ fn main() {
     }
 
     test_checked_mul();
+    test_ct_select();
 
     let _a = 1u32 << 2u8;
 
@@ -576,6 +577,24 @@ unsafe fn test_mm_cvtps_ph() {
     assert_eq_m128i(r, e);
 }
 
+fn test_ct_select() {
+    use std::intrinsics::*;
+
+    for c in [false, true] {
+        let c = black_box(c);
+        assert_eq!(ct_select::<i8>(c, -1, 2), if c { -1 } else { 2 });
+        assert_eq!(ct_select::<i16>(c, -1, 2), if c { -1 } else { 2 });
+        assert_eq!(ct_select::<i32>(c, -1, 2), if c { -1 } else { 2 });
+        assert_eq!(ct_select(c, i64::MIN, 2), if c { i64::MIN } else { 2 });
+        assert_eq!(ct_select(c, u128::MAX, 2), if c { u128::MAX } else { 2 });
+        assert_eq!(ct_select(c, -1.5f64, 2.0), if c { -1.5 } else { 2.0 });
+        assert_eq!(ct_select(c, false, true), !c);
+        assert_eq!(ct_select(c, (1u64, 2u8), (3, 4)), if c { (1, 2) } else { (3, 4) });
+        let (a, b) = (&1u8 as *const u8, &2u8 as *const u8);
+        assert_eq!(ct_select(c, a, b), if c { a } else { b });
+    }
+}
+
 fn test_checked_mul() {
     let u: Option<u8> = u8::from_str_radix("1000", 10).ok();
     assert_eq!(u, None);
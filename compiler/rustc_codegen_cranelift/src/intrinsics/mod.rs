@@ -20,18 +20,22 @@ macro_rules! intrinsic_args {
 use cranelift_codegen::ir::{
     AtomicRmwOp, BlockArg, ExceptionTableData, ExceptionTableItem, ExceptionTag,
 };
+use rustc_ast::ast::{InlineAsmOptions, InlineAsmTemplatePiece};
+use rustc_hir::attrs::CtSelectLoweringAttr;
 use rustc_middle::ty;
 use rustc_middle::ty::GenericArgsRef;
 use rustc_middle::ty::layout::ValidityRequirement;
 use rustc_middle::ty::print::{with_no_trimmed_paths, with_no_visible_paths};
+use rustc_session::config::CtSelectStrategy;
 use rustc_span::source_map::Spanned;
 use rustc_span::{Symbol, sym};
-use rustc_target::spec::PanicStrategy;
+use rustc_target::spec::{CtSelectSupport, PanicStrategy};
 
 pub(crate) use self::llvm::codegen_llvm_intrinsic_call;
 use crate::cast::clif_intcast;
 use crate::codegen_f16_f128;
 use crate::debuginfo::EXCEPTION_HANDLER_CATCH;
+use crate::inline_asm::codegen_inline_asm_inner;
 use crate::prelude::*;
 
 fn bug_on_incorrect_arg_count(intrinsic: impl std::fmt::Display) -> ! {
@@ -55,6 +59,46 @@ fn report_atomic_type_validation_error<'tcx>(
     fx.bcx.ins().trap(TrapCode::user(1 /* unreachable */).unwrap());
 }
 
+/// Selects between the scalars `a` and `b` without branching on `c`.
+fn codegen_ct_select<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    c: Value,
+    a: Value,
+    b: Value,
+) -> Value {
+    // `select_spectre_guard` is already opaque to the optimizations, so `asm` is the same as
+    // `cmov` here.
+    let masking = match fx.tcx.codegen_instance_attrs(fx.instance.def).ct_select_lowering {
+        Some(lowering) => lowering == CtSelectLoweringAttr::Masking,
+        None => match fx.tcx.sess.opts.unstable_opts.ct_select_lowering {
+            Some(strategy) => strategy == CtSelectStrategy::Masking,
+            None => fx.tcx.sess.target.ct_select == Some(CtSelectSupport::Masking),
+        },
+    };
+    if masking {
+        // The target or the chosen strategy does not want conditional moves, so select with a
+        // mask instead.
+        let ty = fx.bcx.func.dfg.value_type(a);
+        if ty.is_float() {
+            // Floating-point values are masked as their bits.
+            let int_ty = Type::int(ty.bits() as u16).unwrap();
+            let a = fx.bcx.ins().bitcast(int_ty, MemFlags::new(), a);
+            let b = fx.bcx.ins().bitcast(int_ty, MemFlags::new(), b);
+            let res = codegen_ct_select(fx, c, a, b);
+            return fx.bcx.ins().bitcast(ty, MemFlags::new(), res);
+        }
+        let c = if ty.bits() > 8 { fx.bcx.ins().uextend(ty, c) } else { c };
+        let mask = fx.bcx.ins().ineg(c);
+        let diff = fx.bcx.ins().bxor(a, b);
+        let diff = fx.bcx.ins().band(mask, diff);
+        fx.bcx.ins().bxor(b, diff)
+    } else {
+        // Unlike `select`, `select_spectre_guard` is guaranteed to be lowered to a
+        // conditional move and never turned into a branch by the mid-end.
+        fx.bcx.ins().select_spectre_guard(c, a, b)
+    }
+}
+
 pub(crate) fn clif_vector_type<'tcx>(tcx: TyCtxt<'tcx>, layout: TyAndLayout<'tcx>) -> Type {
     let (element, count) = match layout.backend_repr {
         BackendRepr::SimdVector { element, count } => (element, count),
@@ -687,6 +731,56 @@ fn codegen_regular_intrinsic_call<'tcx>(
             ret.write_cvalue(fx, CValue::by_val(res, ptr_layout));
         }
 
+        // The per-width variants are deprecated aliases of `ct_select`.
+        sym::ct_select
+        | sym::ct_select_i8
+        | sym::ct_select_i16
+        | sym::ct_select_i32
+        | sym::ct_select_i64
+        | sym::ct_select_bool
+        | sym::ct_select_ptr => {
+            intrinsic_args!(fx, args => (c, a, b); intrinsic);
+            let c = c.load_scalar(fx);
+            let layout = a.layout();
+
+            match layout.backend_repr {
+                _ if layout.is_zst() => {}
+                BackendRepr::Scalar(_) => {
+                    let a = a.load_scalar(fx);
+                    let b = b.load_scalar(fx);
+                    let res = codegen_ct_select(fx, c, a, b);
+                    ret.write_cvalue(fx, CValue::by_val(res, layout));
+                }
+                BackendRepr::ScalarPair(..) => {
+                    // Both halves are selected on the same condition, so the result is always
+                    // one of the two values, never a mix of them.
+                    let (a_lo, a_hi) = a.load_scalar_pair(fx);
+                    let (b_lo, b_hi) = b.load_scalar_pair(fx);
+                    let lo = codegen_ct_select(fx, c, a_lo, b_lo);
+                    let hi = codegen_ct_select(fx, c, a_hi, b_hi);
+                    ret.write_cvalue(fx, CValue::by_val_pair(lo, hi, layout));
+                }
+                BackendRepr::SimdVector { .. } => {
+                    simd_pair_for_each_lane(fx, a, b, ret, &|fx, _lane_ty, _res_lane_ty, a, b| {
+                        codegen_ct_select(fx, c, a, b)
+                    });
+                }
+                _ => {
+                    fx.tcx.dcx().span_err(
+                        source_info.span,
+                        format!(
+                            "`{intrinsic}` intrinsic: expected a type passed as one or two scalars \
+                             or as a SIMD vector, found `{}`",
+                            layout.ty
+                        ),
+                    );
+                    // Prevent verifier error
+                    fx.bcx.ins().trap(TrapCode::user(1 /* unreachable */).unwrap());
+                    return Ok(());
+                }
+            }
+        }
+
         sym::write_bytes | sym::volatile_set_memory => {
             intrinsic_args!(fx, args => (dst, val, count); intrinsic);
             let val = val.load_scalar(fx);
@@ -1505,6 +1599,76 @@ fn codegen_regular_intrinsic_call<'tcx>(
             ret.write_cvalue(fx, a);
         }
 
+        sym::ct_black_box => {
+            intrinsic_args!(fx, args => (a); intrinsic);
+
+            // Unlike `black_box`, this must hide the value. Cranelift cannot see into the
+            // functions generated for inline assembly, so it has to assume that the empty
+            // assembly overwrote the stack slot that the value goes through.
+            let place = CPlace::new_stack_slot(fx, a.layout());
+            place.write_cvalue(fx, a);
+            codegen_inline_asm_inner(fx, &[], &[], InlineAsmOptions::NOSTACK);
+            ret.write_cvalue(fx, place.to_cvalue(fx));
+        }
+
+        sym::ct_select_spec => {
+            intrinsic_args!(fx, args => (c, a, b); intrinsic);
+            let c = c.load_scalar(fx);
+            let layout = a.layout();
+
+            let Some(barrier) = fx.tcx.sess.target.speculation_barrier() else {
+                fx.tcx.dcx().span_err(
+                    source_info.span,
+                    format!(
+                        "`{intrinsic}` is not supported on `{}`",
+                        fx.tcx.sess.target.llvm_target
+                    ),
+                );
+                // Prevent verifier error
+                fx.bcx.ins().trap(TrapCode::user(1 /* unreachable */).unwrap());
+                return Ok(());
+            };
+            let res = match layout.backend_repr {
+                BackendRepr::Scalar(_) => {
+                    let a = a.load_scalar(fx);
+                    let b = b.load_scalar(fx);
+                    CValue::by_val(codegen_ct_select(fx, c, a, b), layout)
+                }
+                BackendRepr::ScalarPair(..) => {
+                    let (a_lo, a_hi) = a.load_scalar_pair(fx);
+                    let (b_lo, b_hi) = b.load_scalar_pair(fx);
+                    let lo = codegen_ct_select(fx, c, a_lo, b_lo);
+                    let hi = codegen_ct_select(fx, c, a_hi, b_hi);
+                    CValue::by_val_pair(lo, hi, layout)
+                }
+                _ => {
+                    fx.tcx.dcx().span_err(
+                        source_info.span,
+                        format!(
+                            "`{intrinsic}` intrinsic: expected a type passed as one or two \
+                             integers or pointers, found `{}`",
+                            layout.ty
+                        ),
+                    );
+                    // Prevent verifier error
+                    fx.bcx.ins().trap(TrapCode::user(1 /* unreachable */).unwrap());
+                    return Ok(());
+                }
+            };
+
+            // The selected value goes through a stack slot, which the barrier may have
+            // overwritten as far as Cranelift knows, so the loads through it wait for the barrier.
+            let place = CPlace::new_stack_slot(fx, layout);
+            place.write_cvalue(fx, res);
+            codegen_inline_asm_inner(
+                fx,
+                &[InlineAsmTemplatePiece::String(barrier.into())],
+                &[],
+                InlineAsmOptions::NOSTACK,
+            );
+            ret.write_cvalue(fx, place.to_cvalue(fx));
+        }
+
         // FIXME implement variadics in cranelift
         sym::va_arg | sym::va_end => {
             fx.tcx.dcx().span_fatal(
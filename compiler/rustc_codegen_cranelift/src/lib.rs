@@ -46,7 +46,7 @@
 use rustc_session::Session;
 use rustc_session::config::OutputFilenames;
 use rustc_span::{Symbol, sym};
-use rustc_target::spec::{Abi, Arch, Env, Os};
+use rustc_target::spec::{Abi, Arch, CtSelectSupport, Env, Os};
 
 pub use crate::config::*;
 use crate::prelude::*;
@@ -179,6 +179,7 @@ fn target_config(&self, sess: &Session) -> TargetConfig {
             && sess.target.os == Os::Windows
             && sess.target.env == Env::Gnu
             && sess.target.abi != Abi::Llvm);
+        let ct_select_masking = sess.target.ct_select == Some(CtSelectSupport::Masking);
 
         TargetConfig {
             target_features,
@@ -189,6 +190,17 @@ fn target_config(&self, sess: &Session) -> TargetConfig {
             has_reliable_f16_math: has_reliable_f16_f128,
             has_reliable_f128: has_reliable_f16_f128,
             has_reliable_f128_math: has_reliable_f16_f128,
+            // `select_spectre_guard` is never turned into a branch, but targets may still declare
+            // that they cannot select in constant time.
+            has_reliable_ct_select: sess.target.ct_select != Some(CtSelectSupport::Unsupported),
+            has_reliable_ct_select_cmov: sess.target.ct_select
+                != Some(CtSelectSupport::Unsupported),
+            ct_select_lowering: if ct_select_masking { "masking" } else { "select-spectre-guard" },
+            ct_select_instructions: if ct_select_masking {
+                &["uextend", "ineg", "bxor", "band", "bxor"]
+            } else {
+                &["select_spectre_guard"]
+            },
         }
     }
 
@@ -555,7 +555,9 @@ fn codegen_intrinsic_call(
                 self.sext(cmp, self.type_ix(32))
             }
 
-            sym::black_box => {
+            // The memory clobber of the volatile assembly hides the value from GCC, which
+            // `ct_black_box` guarantees.
+            sym::black_box | sym::ct_black_box => {
                 args[0].val.store(self, result);
 
                 let block = self.llbb();
@@ -568,6 +570,45 @@ fn codegen_intrinsic_call(
                 return Ok(());
             }
 
+            // The per-width variants are deprecated aliases of `ct_select`.
+            sym::ct_select
+            | sym::ct_select_i8
+            | sym::ct_select_i16
+            | sym::ct_select_i32
+            | sym::ct_select_i64
+            | sym::ct_select_bool
+            | sym::ct_select_ptr => {
+                let cond = args[0].immediate();
+                match (args[1].val, args[2].val) {
+                    (OperandValue::Immediate(then_val), OperandValue::Immediate(else_val)) => {
+                        self.ct_select(cond, then_val, else_val)
+                    }
+                    (OperandValue::Pair(then_a, then_b), OperandValue::Pair(else_a, else_b)) => {
+                        let a = self.ct_select(cond, then_a, else_a);
+                        let b = self.ct_select(cond, then_b, else_b);
+                        OperandValue::Pair(a, b).store(self, result);
+                        return Ok(());
+                    }
+                    _ => bug!("unexpected operands for `{name}`"),
+                }
+            }
+
+            sym::ct_select_spec => {
+                let cond = args[0].immediate();
+                match (args[1].val, args[2].val) {
+                    (OperandValue::Immediate(then_val), OperandValue::Immediate(else_val)) => {
+                        self.ct_select_spec(cond, &[then_val], &[else_val])[0]
+                    }
+                    (OperandValue::Pair(then_a, then_b), OperandValue::Pair(else_a, else_b)) => {
+                        let selected =
+                            self.ct_select_spec(cond, &[then_a, then_b], &[else_a, else_b]);
+                        OperandValue::Pair(selected[0], selected[1]).store(self, result);
+                        return Ok(());
+                    }
+                    _ => bug!("unexpected operands for `{name}`"),
+                }
+            }
+
             sym::ptr_mask => {
                 let usize_type = self.context.new_type::<usize>();
                 let void_ptr_type = self.context.new_type::<*const ()>();
@@ -888,6 +929,111 @@ fn int_type_width_signed<'gcc, 'tcx>(
 }
 
 impl<'a, 'gcc, 'tcx> Builder<'a, 'gcc, 'tcx> {
+    /// Selects between `then_val` and `else_val` without branching on `cond`.
+    ///
+    /// GCC has no builtin that guarantees a conditional move, and it happily turns a `?:` into a
+    /// branch, so this selects with a mask instead. The mask goes through an empty asm statement,
+    /// which hides from the optimizers that it is derived from a boolean and would otherwise let
+    /// them turn the masking back into a branch.
+    fn ct_select(
+        &mut self,
+        cond: RValue<'gcc>,
+        then_val: RValue<'gcc>,
+        else_val: RValue<'gcc>,
+    ) -> RValue<'gcc> {
+        let typ = then_val.get_type();
+        if typ.is_floating_point() {
+            // Floating-point values are selected as their bits.
+            let int_type = self.type_ix(typ.get_size() as u64 * 8);
+            let then_int = self.bitcast(then_val, int_type);
+            let else_int = self.bitcast(else_val, int_type);
+            let res = self.ct_select(cond, then_int, else_int);
+            return self.bitcast(res, typ);
+        }
+        if let Some(vector_type) = typ.unqualified().dyncast_vector() {
+            // Vectors are selected one element at a time, all with the same mask.
+            let elements: Vec<_> = (0..vector_type.get_num_units())
+                .map(|i| {
+                    let index = self.context.new_rvalue_from_long(self.ulong_type, i as i64);
+                    let then_element = self.extract_element(then_val, index);
+                    let else_element = self.extract_element(else_val, index);
+                    self.ct_select(cond, then_element, else_element)
+                })
+                .collect();
+            return self.context.new_rvalue_from_vector(self.location, typ, &elements);
+        }
+        let is_ptr = typ.get_pointee().is_some();
+        let is_bool = typ.is_compatible_with(self.bool_type);
+        // Pointers and booleans are masked as integers of the same width.
+        let int_type = if is_ptr {
+            self.usize_type
+        } else if is_bool {
+            self.u8_type
+        } else {
+            typ
+        };
+        let to_int = |bx: &mut Self, value: RValue<'gcc>| {
+            if is_ptr {
+                bx.bitcast(value, int_type)
+            } else if is_bool {
+                bx.context.new_cast(bx.location, value, int_type)
+            } else {
+                value
+            }
+        };
+        let then_int = to_int(self, then_val);
+        let else_int = to_int(self, else_val);
+
+        let cond = self.context.new_cast(self.location, cond, int_type);
+        let mask = self.neg(cond);
+        let mask_var = self.current_func().new_local(self.location, int_type, "ct_select_mask");
+        self.llbb().add_assignment(self.location, mask_var, mask);
+        let extended_asm = self.llbb().add_extended_asm(self.location, "");
+        extended_asm.add_output_operand(None, "+r", mask_var);
+        let mask = mask_var.to_rvalue();
+
+        let diff = self.xor(then_int, else_int);
+        let masked = self.and(mask, diff);
+        let res = self.xor(else_int, masked);
+        if is_ptr {
+            self.bitcast(res, typ)
+        } else if is_bool {
+            self.context.new_cast(self.location, res, typ)
+        } else {
+            res
+        }
+    }
+
+    /// Selects the values like `ct_select`, then passes them through the speculation barrier of
+    /// the target, so that the loads through a selected pointer wait for the condition.
+    fn ct_select_spec(
+        &mut self,
+        cond: RValue<'gcc>,
+        then_vals: &[RValue<'gcc>],
+        else_vals: &[RValue<'gcc>],
+    ) -> Vec<RValue<'gcc>> {
+        let barrier =
+            self.sess().target.speculation_barrier().unwrap_or_else(|| {
+                bug!("`ct_select_spec` on a target without a speculation barrier")
+            });
+        let vars: Vec<_> = std::iter::zip(then_vals, else_vals)
+            .map(|(&then_val, &else_val)| {
+                let res = self.ct_select(cond, then_val, else_val);
+                let var =
+                    self.current_func().new_local(self.location, res.get_type(), "ct_select_spec");
+                self.llbb().add_assignment(self.location, var, res);
+                var
+            })
+            .collect();
+        let extended_asm = self.llbb().add_extended_asm(self.location, barrier);
+        for &var in &vars {
+            extended_asm.add_output_operand(None, "+r", var);
+        }
+        extended_asm.add_clobber("memory");
+        extended_asm.set_volatile_flag(true);
+        vars.iter().map(|var| var.to_rvalue()).collect()
+    }
+
     fn bit_reverse(&mut self, width: u64, value: RValue<'gcc>) -> RValue<'gcc> {
         let result_type = value.get_type();
         let typ = result_type.to_unsigned(self.cx);
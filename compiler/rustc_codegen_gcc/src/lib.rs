@@ -99,7 +99,7 @@
 use rustc_session::Session;
 use rustc_session::config::{OptLevel, OutputFilenames};
 use rustc_span::Symbol;
-use rustc_target::spec::{Arch, RelocModel};
+use rustc_target::spec::{Arch, CtSelectSupport, RelocModel};
 use tempfile::TempDir;
 
 use crate::back::lto::ModuleBuffer;
@@ -565,5 +565,11 @@ fn target_config(sess: &Session, target_info: &LockedTargetInfo) -> TargetConfig
         has_reliable_f16_math: has_reliable_f16,
         has_reliable_f128,
         has_reliable_f128_math: has_reliable_f128,
+        // Selections are lowered to masking that GCC cannot see through, unless the target
+        // declares that it cannot select in constant time.
+        has_reliable_ct_select: sess.target.ct_select != Some(CtSelectSupport::Unsupported),
+        has_reliable_ct_select_cmov: sess.target.ct_select != Some(CtSelectSupport::Unsupported),
+        ct_select_lowering: "opaque-masking",
+        ct_select_instructions: &["neg", "xor", "and", "xor"],
     }
 }
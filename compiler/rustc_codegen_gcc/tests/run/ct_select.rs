@@ -0,0 +1,63 @@
+// Compiler:
+//
+// Run-time:
+#![feature(core_intrinsics, intrinsics)]
+#![no_main]
+
+use std::intrinsics::black_box;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(_b: bool, _true_val: i8, _false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i16(_b: bool, _true_val: i16, _false_val: i16) -> i16;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(_b: bool, _true_val: i32, _false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(_b: bool, _true_val: i64, _false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_bool(_b: bool, _true_val: bool, _false_val: bool) -> bool;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(_b: bool, _true_val: *const T, _false_val: *const T) -> *const T;
+
+#[rustc_intrinsic]
+pub fn ct_select<T: Copy>(_b: bool, _true_val: T, _false_val: T) -> T;
+
+#[no_mangle]
+extern "C" fn main(_argc: i32, _argv: *const *const u8) -> i32 {
+    macro_rules! check {
+        ($func_name:ident, $a:expr, $b:expr, $res_ident:ident) => {{
+            $res_ident += 1;
+            if $func_name(black_box(true), $a, $b) != $a {
+                return $res_ident;
+            }
+            $res_ident += 1;
+            if $func_name(black_box(false), $a, $b) != $b {
+                return $res_ident;
+            }
+        }};
+    }
+    let mut res = 0;
+    check!(ct_select_i8, -1_i8, 0x5a_i8, res);
+    check!(ct_select_i16, -1_i16, 0x5a5a_i16, res);
+    check!(ct_select_i32, -1_i32, 0x5a5a_5a5a_i32, res);
+    check!(ct_select_i64, -1_i64, 0x5a5a_5a5a_5a5a_5a5a_i64, res);
+    check!(ct_select_bool, true, false, res);
+    check!(ct_select_bool, false, true, res);
+
+    let x = 1_u32;
+    let y = 2_u32;
+    check!(ct_select_ptr, &x as *const u32, &y as *const u32, res);
+
+    check!(ct_select, -1_i64, 0x5a5a_5a5a_5a5a_5a5a_i64, res);
+    check!(ct_select, u128::MAX, 0x5a5a_5a5a_5a5a_5a5a_5a5a_5a5a_5a5a_5a5a_u128, res);
+    check!(ct_select, -1.5_f32, 2.0_f32, res);
+    check!(ct_select, -1.5_f64, 2.0_f64, res);
+    check!(ct_select, (1_u64, 2_u8), (3_u64, 4_u8), res);
+
+    0
+}
@@ -194,6 +194,22 @@ fn function_return_attr<'ll>(cx: &SimpleCx<'ll>, sess: &Session) -> Option<&'ll
     Some(function_return_attr.create_attr(cx.llcx))
 }
 
+/// Clears the caller-saved registers that a `#[constant_time]` function used, other than those
+/// holding its return value, before it returns, so that the secrets they held do not outlive the
+/// call. LLVM only implements this on x86 and AArch64.
+fn zero_call_used_regs_attr<'ll>(
+    cx: &SimpleCx<'ll>,
+    sess: &Session,
+    codegen_fn_attrs: &CodegenFnAttrs,
+) -> Option<&'ll Attribute> {
+    if !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::CONSTANT_TIME)
+        || !matches!(sess.target.arch, Arch::X86 | Arch::X86_64 | Arch::AArch64)
+    {
+        return None;
+    }
+    Some(llvm::CreateAttrStringValue(cx.llcx, "zero-call-used-regs", "used"))
+}
+
 /// Tell LLVM what instrument function to insert.
 #[inline]
 fn instrument_function_attr<'ll>(
@@ -355,6 +371,59 @@ pub(crate) fn default_optimisation_attrs<'ll>(
     attrs
 }
 
+/// Returns the attribute of the definitions of functions that compute conditions from secrets:
+/// the constant-time code, the functions with secret parameters and those that hold values of
+/// the constant-time types of `core::select`, such as `Choice`, once their callees are inlined.
+///
+/// Loop unswitching and jump threading would turn such conditions, selects included, into
+/// branches. The pass instrumentation of `LLVMRustOptimize` skips them for these functions. The
+/// copies that LLVM inlines into other functions are optimized like those functions instead.
+pub(crate) fn secret_conditions_attr<'ll, 'tcx>(
+    cx: &SimpleCx<'ll>,
+    tcx: TyCtxt<'tcx>,
+    instance: ty::Instance<'tcx>,
+    codegen_fn_attrs: &CodegenFnAttrs,
+) -> Option<&'ll Attribute> {
+    let has_secrets = codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NO_BUILTIN_CMP)
+        || !codegen_fn_attrs.secret_params.is_empty()
+        || tcx.instance_mir(instance.def).local_decls.iter().any(|decl| {
+            instance
+                .instantiate_mir_and_normalize_erasing_regions(
+                    tcx,
+                    ty::TypingEnv::fully_monomorphized(),
+                    ty::EarlyBinder::bind(decl.ty),
+                )
+                .is_ct_secret(tcx)
+        });
+    has_secrets.then(|| llvm::CreateAttrString(cx.llcx, "rust-secret-conditions"))
+}
+
+/// The attribute of the functions whose spills are reported under `-Zct-pin-secrets`.
+pub(crate) const PIN_SECRETS_ATTR: &str = "rust-pin-secrets";
+
+/// Under `-Zct-pin-secrets`, returns the attribute of the definitions of functions that hold the
+/// scalar values of `Secret`, such as `Secret<u64>`. The spills that the register allocator
+/// reports for these functions are forwarded as remarks, whether or not `-Cremark` asks for them.
+pub(crate) fn pin_secrets_attr<'ll, 'tcx>(
+    cx: &SimpleCx<'ll>,
+    tcx: TyCtxt<'tcx>,
+    instance: ty::Instance<'tcx>,
+) -> Option<&'ll Attribute> {
+    if !tcx.sess.opts.unstable_opts.ct_pin_secrets {
+        return None;
+    }
+    let has_secret_scalars = tcx.instance_mir(instance.def).local_decls.iter().any(|decl| {
+        instance
+            .instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                ty::TypingEnv::fully_monomorphized(),
+                ty::EarlyBinder::bind(decl.ty),
+            )
+            .is_ct_secret_scalar(tcx)
+    });
+    has_secret_scalars.then(|| llvm::CreateAttrString(cx.llcx, PIN_SECRETS_ATTR))
+}
+
 fn create_alloc_family_attr(llcx: &llvm::Context) -> &llvm::Attribute {
     llvm::CreateAttrStringValue(llcx, "alloc-family", "__rust_alloc")
 }
@@ -413,6 +482,38 @@ pub(crate) fn llfn_attrs_from_instance<'ll, 'tcx>(
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
         to_add.push(AttributeKind::Cold.create_attr(cx.llcx));
     }
+    // A switch lowered to a jump table loads its target from an address that depends on the
+    // value switched on. `-Cjump-tables=no` already adds the attribute to every function.
+    if codegen_fn_attrs
+        .flags
+        .intersects(CodegenFnAttrFlags::CONSTANT_TIME | CodegenFnAttrFlags::NO_BRANCH)
+        && sess.opts.cg.jump_tables
+    {
+        to_add.push(llvm::CreateAttrStringValue(cx.llcx, "no-jump-tables", "true"));
+    }
+    // Read by the pass instrumentation of `LLVMRustOptimize`, which skips the passes that would
+    // introduce branches into the function, and marks its selects as unpredictable.
+    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NO_BRANCH) {
+        to_add.push(llvm::CreateAttrString(cx.llcx, "rust-no-branch"));
+    }
+    // Makes `memcmp` and `bcmp` unavailable to the transforms that recognize comparisons, such as
+    // the merging of comparison chains. Functions with these attributes are only inlined by LLVM
+    // into functions that have them too.
+    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NO_BUILTIN_CMP) {
+        to_add.push(llvm::CreateAttrString(cx.llcx, "no-builtin-memcmp"));
+        to_add.push(llvm::CreateAttrString(cx.llcx, "no-builtin-bcmp"));
+    }
+    // Read by the pass of `LLVMRustOptimize` that moves the body of the function to another, so
+    // that the function can zero its frame once it returns.
+    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::CONSTANT_TIME)
+        && !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED)
+        && sess.opts.unstable_opts.ct_scrub_stack
+    {
+        to_add.push(llvm::CreateAttrString(cx.llcx, "rust-scrub-stack"));
+    }
+    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::SPECULATIVE_LOAD_HARDENING) {
+        to_add.push(AttributeKind::SpeculativeLoadHardening.create_attr(cx.llcx));
+    }
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::FFI_PURE) {
         to_add.push(MemoryEffects::ReadOnly.create_attr(cx.llcx));
     }
@@ -426,6 +527,7 @@ pub(crate) fn llfn_attrs_from_instance<'ll, 'tcx>(
     } else {
         // Do not set sanitizer attributes for naked functions.
         to_add.extend(sanitize_attrs(cx, tcx, codegen_fn_attrs.sanitizers));
+        to_add.extend(zero_call_used_regs_attr(cx, sess, codegen_fn_attrs));
 
         // For non-naked functions, set branch protection attributes on aarch64.
         if let Some(BranchProtection { bti, pac_ret, gcs }) =
@@ -37,9 +37,11 @@
 use crate::builder::SBuilder;
 use crate::builder::gpu_offload::scalar_width;
 use crate::common::AsCCharPtr;
+use crate::ct_select::{CT_SELECT_BEGIN_MARKER, CT_SELECT_END_MARKER};
 use crate::errors::{
-    CopyBitcode, FromLlvmDiag, FromLlvmOptimizationDiag, LlvmError, ParseTargetMachineConfig,
-    UnknownCompression, WithLlvmError, WriteBytecode,
+    CopyBitcode, CtSecretsSpilled, CtSelectBranch, FromLlvmDiag, FromLlvmOptimizationDiag,
+    LlvmError, ParseTargetMachineConfig, UnknownCompression, VerifyCtSelectRead, WithLlvmError,
+    WriteBytecode,
 };
 use crate::llvm::diagnostic::OptimizationDiagnosticKind::*;
 use crate::llvm::{self, DiagnosticInfo};
@@ -347,6 +349,9 @@ fn write_bitcode_to_file(module: &ModuleCodegen<ModuleLlvm>, path: &Path) {
     }
 }
 
+/// The name under which the register allocator emits its remarks.
+const REGALLOC_PASS: &str = "regalloc";
+
 /// In what context is a diagnostic handler being attached to a codegen unit?
 pub(crate) enum CodegenDiagnosticsStage {
     /// Prelink optimization stage.
@@ -372,7 +377,7 @@ pub(crate) fn new(
         stage: CodegenDiagnosticsStage,
     ) -> Self {
         let remark_passes_all: bool;
-        let remark_passes: Vec<CString>;
+        let mut remark_passes: Vec<CString>;
         match &cgcx.remark {
             Passes::All => {
                 remark_passes_all = true;
@@ -384,6 +389,11 @@ pub(crate) fn new(
                     passes.iter().map(|name| CString::new(name.as_str()).unwrap()).collect();
             }
         };
+        // The spills in the functions that hold secrets are reported from the remarks of the
+        // register allocator, which are otherwise dropped by `diagnostic_handler`.
+        if cgcx.module_config.ct_pin_secrets && !remark_passes_all {
+            remark_passes.push(CString::new(REGALLOC_PASS).unwrap());
+        }
         let remark_passes: Vec<*const c_char> =
             remark_passes.iter().map(|name: &CString| name.as_ptr()).collect();
         let remark_file = cgcx
@@ -481,6 +491,29 @@ fn report_inline_asm(
         }
 
         llvm::diagnostic::Optimization(opt) => {
+            if opt.pass_name == REGALLOC_PASS && cgcx.module_config.ct_pin_secrets {
+                if opt.message.contains("spill")
+                    && attributes::has_string_attr(opt.function, attributes::PIN_SECRETS_ATTR)
+                {
+                    let symbol = llvm::get_value_name(opt.function);
+                    let symbol = str::from_utf8(&symbol).unwrap_or("<unknown>");
+                    let demangled = rustc_demangle::demangle(symbol);
+                    dcx.emit_note(CtSecretsSpilled {
+                        filename: &opt.filename,
+                        line: opt.line,
+                        column: opt.column,
+                        function: format!("{demangled:#}"),
+                        message: &opt.message,
+                    });
+                }
+                let requested = match &cgcx.remark {
+                    Passes::All => true,
+                    Passes::Some(passes) => passes.iter().any(|pass| pass == REGALLOC_PASS),
+                };
+                if !requested {
+                    return;
+                }
+            }
             dcx.emit_note(FromLlvmOptimizationDiag {
                 filename: &opt.filename,
                 line: opt.line,
@@ -637,6 +670,7 @@ pub(crate) unsafe fn llvm_optimize(
             sanitize_address: config.sanitizer.contains(SanitizerSet::ADDRESS),
             sanitize_address_recover: config.sanitizer_recover.contains(SanitizerSet::ADDRESS),
             sanitize_cfi: config.sanitizer.contains(SanitizerSet::CFI),
+            sanitize_constant_time: config.sanitizer.contains(SanitizerSet::CONSTANT_TIME),
             sanitize_dataflow: config.sanitizer.contains(SanitizerSet::DATAFLOW),
             sanitize_dataflow_abilist: sanitize_dataflow_abilist_ptrs.as_ptr(),
             sanitize_dataflow_abilist_len: sanitize_dataflow_abilist_ptrs.len(),
@@ -1138,6 +1172,52 @@ extern "C" fn demangle_callback(
             );
         }
 
+        if config.verify_ct_select {
+            let _timer = prof
+                .generic_activity_with_arg("LLVM_module_codegen_verify_ct_select", &*module.name);
+            // Modules that take part in LTO only get here once LTO has merged and optimized them
+            // again, so this checks the selections of every crate that was compiled with the
+            // markers in the code that is finally emitted, not the code LTO started from.
+            //
+            // Reuse the assembly if it was requested anyway, and write it to a temporary file
+            // otherwise.
+            let path = if config.emit_asm {
+                cgcx.output_filenames.temp_path_for_cgu(
+                    OutputType::Assembly,
+                    &module.name,
+                    cgcx.invocation_temp.as_deref(),
+                )
+            } else {
+                let path = cgcx.output_filenames.temp_path_ext_for_cgu(
+                    "ct-select.s",
+                    &module.name,
+                    cgcx.invocation_temp.as_deref(),
+                );
+                let llmod = llvm::LLVMCloneModule(llmod);
+                write_output_file(
+                    dcx,
+                    tm.raw(),
+                    config.no_builtins,
+                    llmod,
+                    &path,
+                    None,
+                    llvm::FileType::AssemblyFile,
+                    prof,
+                    config.verify_llvm_ir,
+                );
+                path
+            };
+            match fs::read_to_string(&path) {
+                Ok(asm) => verify_ct_select(dcx, &asm),
+                Err(err) => {
+                    dcx.emit_err(VerifyCtSelectRead { path: &path, err });
+                }
+            }
+            if !config.emit_asm {
+                ensure_removed(dcx, &path);
+            }
+        }
+
         match config.emit_obj {
             EmitObj::ObjectCode(_) => {
                 let _timer =
@@ -1214,6 +1294,52 @@ extern "C" fn demangle_callback(
     )
 }
 
+/// Reports the functions in `asm` in which a `ct_select` was lowered to a branch.
+///
+/// Under `-Zverify-ct-select`, each `ct_select` is bracketed by marker comments whose operands
+/// keep the select between them. A select that became control flow splits the code between the
+/// markers into several basic blocks, which shows up in the assembly as a label, or as a comment
+/// naming a fall-through block, before the closing marker. Looking for those instead of branch
+/// mnemonics works the same on every target.
+fn verify_ct_select(dcx: DiagCtxtHandle<'_>, asm: &str) {
+    let mut function = None;
+    let mut in_select = false;
+    let mut reported = false;
+    for line in asm.lines() {
+        if line.contains(CT_SELECT_BEGIN_MARKER) {
+            in_select = true;
+            continue;
+        }
+        if line.contains(CT_SELECT_END_MARKER) {
+            in_select = false;
+            continue;
+        }
+        let line = line.trim();
+        let is_block = line.ends_with(':') || line.contains("%bb.");
+        if in_select {
+            // Report each function once, however many of its selects are affected.
+            if is_block && !reported {
+                let symbol = function.unwrap_or("<unknown>");
+                let demangled = rustc_demangle::demangle(symbol);
+                dcx.emit_err(CtSelectBranch { function: format!("{demangled:#}") });
+                reported = true;
+            }
+        } else if let Some(label) = line.strip_suffix(':')
+            && !label.starts_with('.')
+            && !label.starts_with('$')
+            && !label.starts_with('"')
+            && !label.starts_with("LBB")
+            && !label.bytes().all(|b| b.is_ascii_digit())
+        {
+            // A label outside a select that is not local to a function starts a new function.
+            if function != Some(label) {
+                function = Some(label);
+                reported = false;
+            }
+        }
+    }
+}
+
 fn create_section_with_flags_asm(section_name: &str, section_flags: &str, data: &[u8]) -> Vec<u8> {
     let mut asm = format!(".section {section_name},\"{section_flags}\"\n").into_bytes();
     asm.extend_from_slice(b".ascii \"");
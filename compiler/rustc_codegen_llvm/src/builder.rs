@@ -1528,6 +1528,10 @@ pub(crate) fn set_unpredictable(&mut self, inst: &'ll Value) {
         self.set_metadata_node(inst, llvm::MD_unpredictable, &[]);
     }
 
+    pub(crate) fn freeze(&mut self, val: &'ll Value) -> &'ll Value {
+        unsafe { llvm::LLVMBuildFreeze(self.llbuilder, val, UNNAMED) }
+    }
+
     fn write_operand_repeatedly_optimized(
         &mut self,
         cg_elem: OperandRef<'tcx, &'ll Value>,
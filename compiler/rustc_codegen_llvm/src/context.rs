@@ -23,7 +23,8 @@
 use rustc_middle::{bug, span_bug};
 use rustc_session::Session;
 use rustc_session::config::{
-    BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, FunctionReturn, PAuthKey, PacRet,
+    BranchProtection, CFGuard, CFProtection, CrateType, CtSelectStrategy, DebugInfo,
+    FunctionReturn, PAuthKey, PacRet,
 };
 use rustc_span::source_map::Spanned;
 use rustc_span::{DUMMY_SP, Span, Symbol};
@@ -139,6 +140,13 @@ pub(crate) struct FullCx<'ll, 'tcx> {
     eh_catch_typeinfo: Cell<Option<&'ll Value>>,
     pub rust_try_fn: Cell<Option<(&'ll Type, &'ll Value)>>,
 
+    /// Cache for `has_llvm_feature`.
+    llvm_features: RefCell<FxHashMap<&'static str, bool>>,
+
+    /// The strategy chosen with `#[ct_select_lowering]` for the `ct_select`s of each function
+    /// that has the attribute.
+    pub ct_select_strategies: RefCell<FxHashMap<&'ll Value, CtSelectStrategy>>,
+
     intrinsics:
         RefCell<FxHashMap<(Cow<'static, str>, SmallVec<[&'ll Type; 2]>), (&'ll Type, &'ll Value)>>,
 
@@ -658,6 +666,8 @@ pub(crate) fn new(
                 eh_personality: Cell::new(None),
                 eh_catch_typeinfo: Cell::new(None),
                 rust_try_fn: Cell::new(None),
+                llvm_features: Default::default(),
+                ct_select_strategies: Default::default(),
                 intrinsics: Default::default(),
                 local_gen_sym_counter: Cell::new(0),
                 renamed_statics: Default::default(),
@@ -706,6 +716,18 @@ pub(crate) fn objc_abi_version(&self) -> u32 {
         }
     }
 
+    /// Returns whether LLVM considers `feature` enabled for the target, for lowerings that depend
+    /// on features Rust does not know about, such as `cmov` on x86.
+    ///
+    /// This ignores `#[target_feature]` on the function being compiled.
+    pub(crate) fn has_llvm_feature(&self, feature: &'static str) -> bool {
+        *self
+            .llvm_features
+            .borrow_mut()
+            .entry(feature)
+            .or_insert_with(|| llvm_util::has_llvm_feature(self.tcx.sess, feature))
+    }
+
     // We do our best here to match what Clang does when compiling Objective-C natively.
     // See Clang's `CGObjCCommonMac::EmitImageInfo`:
     // https://github.com/llvm/llvm-project/blob/llvmorg-20.1.8/clang/lib/CodeGen/CGObjCMac.cpp#L5085
@@ -0,0 +1,119 @@
+//! Lowering of the `ct_classify` and `ct_declassify` intrinsics.
+//!
+//! Valgrind's Memcheck and MemorySanitizer both report the branches and memory accesses that
+//! depend on undefined memory. Marking a secret as undefined turns them into checkers for
+//! constant-time code: every branch on the secret, or on a value computed from it, is reported as
+//! a use of undefined memory, until the result is declassified by marking it as defined again.
+//!
+//! The constant-time sanitizer instead labels secrets with DataFlowSanitizer, and aborts as soon as
+//! a labeled value reaches a branch or the address of a memory access.
+
+use rustc_codegen_ssa::mir::place::PlaceRef;
+use rustc_codegen_ssa::traits::*;
+use rustc_middle::bug;
+use rustc_session::config::SanitizerSet;
+use rustc_span::Span;
+use rustc_target::spec::Arch;
+
+use crate::builder::Builder;
+use crate::llvm::{self, Value};
+
+/// The Memcheck client requests, from `memcheck.h`.
+const VG_USERREQ_MAKE_MEM_UNDEFINED: u64 = 0x4D43_0001;
+const VG_USERREQ_MAKE_MEM_DEFINED: u64 = 0x4D43_0002;
+
+/// Marks the value stored in `place` as secret, or as public again if `classify` is false, for
+/// the checkers enabled in this session.
+pub(crate) fn codegen_ct_annotation<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    classify: bool,
+    place: PlaceRef<'tcx, &'ll Value>,
+    span: Span,
+) {
+    if place.layout.is_zst() {
+        return;
+    }
+    let sess = bx.tcx.sess;
+    let ptr = place.val.llval;
+    let len = bx.const_usize(place.layout.size.bytes());
+    if sess.sanitizers().contains(SanitizerSet::MEMORY) {
+        let name = if classify { "__msan_poison" } else { "__msan_unpoison" };
+        let fn_ty = bx.type_func(&[bx.type_ptr(), bx.type_isize()], bx.type_void());
+        let f = bx.declare_cfn(name, llvm::UnnamedAddr::No, fn_ty);
+        bx.call(fn_ty, None, None, f, &[ptr, len], None, None);
+    }
+    if sess.sanitizers().contains(SanitizerSet::CONSTANT_TIME) {
+        // `dfsan_set_label(label, addr, size)`, where the secrets have the label 1.
+        let label = bx.const_u8(classify as u8);
+        let fn_ty = bx.type_func(&[bx.type_i8(), bx.type_ptr(), bx.type_isize()], bx.type_void());
+        let f = bx.declare_cfn("dfsan_set_label", llvm::UnnamedAddr::No, fn_ty);
+        bx.call(fn_ty, None, None, f, &[label, ptr, len], None, None);
+    }
+    if sess.opts.unstable_opts.valgrind_ct_annotations {
+        let request =
+            if classify { VG_USERREQ_MAKE_MEM_UNDEFINED } else { VG_USERREQ_MAKE_MEM_DEFINED };
+        let request = bx.const_usize(request);
+        let addr = bx.ptrtoint(ptr, bx.type_isize());
+        valgrind_client_request(bx, &[request, addr, len], span);
+    }
+}
+
+/// Emits a Valgrind client request: the special instruction sequence that does nothing when run
+/// natively, but that Valgrind recognizes and handles with the words in `args`.
+fn valgrind_client_request<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    args: &[&'ll Value],
+    span: Span,
+) {
+    // A request always takes six words, the request code and its five arguments.
+    const WORDS: u64 = 6;
+    let word_size = bx.tcx.data_layout.pointer_size();
+    let word_align = bx.tcx.data_layout.pointer_align().abi;
+    let array = bx.alloca(word_size * WORDS, word_align);
+    let zero = bx.const_usize(0);
+    for i in 0..WORDS {
+        let word = args.get(i as usize).copied().unwrap_or(zero);
+        let slot = bx.inbounds_gep(bx.type_isize(), array, &[bx.const_usize(i)]);
+        bx.store(word, slot, word_align);
+    }
+
+    // The preamble rotates a register by a total of 64 (or 32) bits, leaving it unchanged, and
+    // the exchange or `orr` that follows selects the request. Memcheck's requests return a value
+    // through the same register as the default result, which is not used here.
+    let (asm, constraint) = match bx.tcx.sess.target.arch {
+        Arch::X86_64 => (
+            "rolq $$3, %rdi; rolq $$13, %rdi; rolq $$61, %rdi; rolq $$51, %rdi; \
+             xchgq %rbx, %rbx",
+            "={rdx},{rax},0,~{cc},~{memory}",
+        ),
+        Arch::X86 => (
+            "roll $$3, %edi; roll $$13, %edi; roll $$29, %edi; roll $$19, %edi; \
+             xchgl %ebx, %ebx",
+            "={edx},{eax},0,~{cc},~{memory}",
+        ),
+        Arch::AArch64 => (
+            "ror x12, x12, #3; ror x12, x12, #13; ror x12, x12, #51; ror x12, x12, #61; \
+             orr x10, x10, x10",
+            "={x3},{x4},0,~{cc},~{memory}",
+        ),
+        // Rejected by `-Zvalgrind-ct-annotations` on other targets.
+        _ => bug!("Valgrind client requests are not supported on this target"),
+    };
+    let output = bx.type_isize();
+    crate::asm::inline_asm_call(
+        bx,
+        asm,
+        constraint,
+        &[array, zero],
+        output,
+        &[],
+        true,
+        false,
+        llvm::AsmDialect::Att,
+        &[span],
+        false,
+        None,
+        None,
+    )
+    .unwrap_or_else(|| bug!("failed to generate the Valgrind client request"));
+}
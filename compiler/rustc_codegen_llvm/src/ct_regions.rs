@@ -0,0 +1,81 @@
+//! The sections that `-Zct-regions` and `-Zct-metadata` record constant-time code in, for the
+//! profilers, fuzzers and binary analyzers that map what they find back to the guarantees of the
+//! compiler, and for the verifiers that check them.
+//!
+//! `.rust_ct_functions` describes the `#[constant_time]` functions and the functions with secret
+//! parameters, under either flag. Each function has a record made of:
+//!
+//! - its symbol name, NUL-terminated, through which its code is found in the symbol table, and
+//!   the code of its inlined copies in the `DW_TAG_inlined_subroutine`s of the debuginfo;
+//! - a byte of flags, of which [`CT_FUNCTION_CONSTANT_TIME`] is set for a `#[constant_time]`
+//!   function;
+//! - the number of its secret parameters, as a ULEB128;
+//! - the index of each secret parameter, counting `self`, as a ULEB128.
+//!
+//! `.debug_rust_ct_selects` holds a pair of pointer-sized addresses for each `ct_select`, the
+//! start and the end of its code, under `-Zct-regions`.
+//!
+//! Both sections are not allocated, like the debuginfo, so that they are not loaded, and so that
+//! recording a `ct_select` does not keep its function alive. GNU ld removes the sections that are
+//! not allocated but have relocations under `--gc-sections`, unless their name starts with
+//! `.debug`. The addresses of the code that the linker removed are resolved like those of the
+//! debuginfo, usually to zero.
+
+use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
+use rustc_session::Session;
+
+use crate::context::CodegenCx;
+use crate::llvm;
+
+/// The section holding the records of the functions.
+pub(crate) const CT_FUNCTIONS_SECTION: &str = ".rust_ct_functions";
+
+/// The flag of the record of a `#[constant_time]` function.
+const CT_FUNCTION_CONSTANT_TIME: u8 = 1;
+
+/// The section holding the address ranges of the `ct_select`s.
+pub(crate) const CT_SELECTS_SECTION: &str = ".debug_rust_ct_selects";
+
+/// The local labels at the start and the end of each `ct_select`. Numeric labels can be defined
+/// any number of times, and a reference to one names its closest definition.
+const BEGIN_LABEL: u32 = 7710;
+const END_LABEL: u32 = 7711;
+
+/// Records the function named `symbol_name` if it is `#[constant_time]` or has secret
+/// parameters.
+pub(crate) fn record_function(cx: &CodegenCx<'_, '_>, symbol_name: &str, attrs: &CodegenFnAttrs) {
+    let constant_time = attrs.flags.contains(CodegenFnAttrFlags::CONSTANT_TIME);
+    if !constant_time && attrs.secret_params.is_empty() {
+        return;
+    }
+    let flags = if constant_time { CT_FUNCTION_CONSTANT_TIME } else { 0 };
+    let mut asm = format!(
+        ".pushsection {CT_FUNCTIONS_SECTION},\"\",%progbits\n\
+         .asciz \"{symbol_name}\"\n\
+         .byte {flags}\n\
+         .uleb128 {}\n",
+        attrs.secret_params.len()
+    );
+    for param in &attrs.secret_params {
+        asm.push_str(&format!(".uleb128 {param}\n"));
+    }
+    asm.push_str(".popsection");
+    llvm::append_module_inline_asm(cx.llmod, asm.as_bytes());
+}
+
+/// Returns the assembly that starts the code of a `ct_select`.
+pub(crate) fn begin_asm() -> String {
+    format!("{BEGIN_LABEL}:")
+}
+
+/// Returns the assembly that ends the code of a `ct_select`, and records its address range.
+pub(crate) fn end_asm(sess: &Session) -> String {
+    let word = if sess.target.pointer_width == 64 { ".quad" } else { ".long" };
+    format!(
+        "{END_LABEL}:\n\
+         .pushsection {CT_SELECTS_SECTION},\"\",%progbits\n\
+         {word} {BEGIN_LABEL}b\n\
+         {word} {END_LABEL}b\n\
+         .popsection"
+    )
+}
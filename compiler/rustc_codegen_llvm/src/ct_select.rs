@@ -0,0 +1,918 @@
+//! Lowering of the `ct_select`, `ct_select_spec` and `ct_black_box` intrinsics.
+//!
+//! Unlike `select_unpredictable`, these must never be lowered to a branch, at any optimization
+//! level: the condition is secret, and a branch would leak it through timing and the branch
+//! predictor. LLVM treats a `select` as something it is free to turn into control flow when that
+//! looks profitable, so the lowering here picks, for each target and optimization level, a form
+//! that no pass will turn back into a branch.
+
+use rustc_abi::Size;
+use rustc_codegen_ssa::common::{IntPredicate, TypeKind};
+use rustc_codegen_ssa::mir::place::PlaceValue;
+use rustc_codegen_ssa::traits::*;
+use rustc_hir::attrs::CtSelectLoweringAttr;
+use rustc_middle::bug;
+use rustc_session::Session;
+use rustc_session::config::{CtSelectStrategy, OptLevel, WasmCtSelect};
+use rustc_span::Span;
+use rustc_target::spec::{Arch, CtSelectSupport};
+
+use crate::builder::Builder;
+use crate::ct_regions;
+use crate::llvm::{self, Type, Value};
+
+/// The comments placed before and after each `ct_select` under `-Zverify-ct-select`, which
+/// `back::write::verify_ct_select` looks for in the generated assembly.
+pub(crate) const CT_SELECT_BEGIN_MARKER: &str = "ct_select.begin";
+pub(crate) const CT_SELECT_END_MARKER: &str = "ct_select.end";
+
+/// Lowers `ct_select` for a scalar or a SIMD vector, or for one half of a pair of scalars.
+///
+/// The value is first legalized into those that every lowering can select between, integers of
+/// at most 64 bits and pointers: wider integers are selected 64 bits at a time, and
+/// floating-point numbers and vectors are selected as integers of the same width.
+pub(crate) fn codegen_ct_select<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let ty = bx.val_ty(true_val);
+    match bx.type_kind(ty) {
+        TypeKind::Pointer => select_legal(bx, cond, true_val, false_val, span),
+        TypeKind::Integer if bx.int_width(ty) <= 64 => {
+            select_legal(bx, cond, true_val, false_val, span)
+        }
+        TypeKind::Integer => {
+            let bits = bx.int_width(ty);
+            let chunk_ty = bx.type_i64();
+            let mut result = bx.const_uint(ty, 0);
+            for i in 0..bits.div_ceil(64) {
+                let shift = bx.const_uint(ty, i * 64);
+                let true_chunk = bx.lshr(true_val, shift);
+                let true_chunk = bx.trunc(true_chunk, chunk_ty);
+                let false_chunk = bx.lshr(false_val, shift);
+                let false_chunk = bx.trunc(false_chunk, chunk_ty);
+                let chunk = select_legal(bx, cond, true_chunk, false_chunk, span);
+                let chunk = bx.zext(chunk, ty);
+                let chunk = bx.shl(chunk, shift);
+                result = bx.or(result, chunk);
+            }
+            result
+        }
+        TypeKind::Half
+        | TypeKind::BFloat
+        | TypeKind::Float
+        | TypeKind::Double
+        | TypeKind::X86_FP80
+        | TypeKind::FP128
+        | TypeKind::PPC_FP128 => {
+            let int_ty = bx.type_ix(bx.float_width(ty) as u64);
+            let true_val = bx.bitcast(true_val, int_ty);
+            let false_val = bx.bitcast(false_val, int_ty);
+            let result = codegen_ct_select(bx, cond, true_val, false_val, span);
+            bx.bitcast(result, ty)
+        }
+        TypeKind::Vector => {
+            let len = bx.vector_length(ty) as u64;
+            // Vectors of pointers cannot be bitcast to an integer, so they are converted to
+            // vectors of pointer-sized integers first.
+            let is_ptr = bx.type_kind(bx.element_type(ty)) == TypeKind::Pointer;
+            let (int_vector_ty, true_val, false_val) = if is_ptr {
+                let int_vector_ty = bx.type_vector(bx.type_isize(), len);
+                (
+                    int_vector_ty,
+                    bx.ptrtoint(true_val, int_vector_ty),
+                    bx.ptrtoint(false_val, int_vector_ty),
+                )
+            } else {
+                (ty, true_val, false_val)
+            };
+            let element_ty = bx.element_type(int_vector_ty);
+            let element_bits = if bx.type_kind(element_ty) == TypeKind::Integer {
+                bx.int_width(element_ty)
+            } else {
+                bx.float_width(element_ty) as u64
+            };
+            let int_ty = bx.type_ix(element_bits * len);
+            let true_val = bx.bitcast(true_val, int_ty);
+            let false_val = bx.bitcast(false_val, int_ty);
+            let result = codegen_ct_select(bx, cond, true_val, false_val, span);
+            let result = bx.bitcast(result, int_vector_ty);
+            if is_ptr { bx.inttoptr(result, ty) } else { result }
+        }
+        kind => bug!("unexpected {kind:?} value for `ct_select`"),
+    }
+}
+
+/// Lowers `ct_select` for values of `size` bytes passed in memory, by selecting them into
+/// `result` one integer of at most 64 bits at a time.
+///
+/// Only `select_unpredictable` under `-Cforce-ct-select` takes values in memory. It would
+/// otherwise select between their addresses, which leaves the condition in the address of the
+/// copy that follows.
+pub(crate) fn codegen_ct_select_in_memory<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: PlaceValue<&'ll Value>,
+    false_val: PlaceValue<&'ll Value>,
+    result: PlaceValue<&'ll Value>,
+    size: Size,
+    span: Span,
+) {
+    let align = true_val.align.min(false_val.align).min(result.align);
+    let mut offset = Size::ZERO;
+    while offset < size {
+        let rest = (size - offset).bytes();
+        let chunk = [8, 4, 2, 1].into_iter().find(|&bytes| bytes <= rest).unwrap();
+        let ty = bx.type_ix(chunk * 8);
+        let chunk_align = align.restrict_for_offset(offset);
+        let lloffset = bx.const_usize(offset.bytes());
+        let true_ptr = bx.inbounds_ptradd(true_val.llval, lloffset);
+        let false_ptr = bx.inbounds_ptradd(false_val.llval, lloffset);
+        let result_ptr = bx.inbounds_ptradd(result.llval, lloffset);
+        let true_chunk = bx.load(ty, true_ptr, chunk_align);
+        let false_chunk = bx.load(ty, false_ptr, chunk_align);
+        let chunk_val = select_legal(bx, cond, true_chunk, false_chunk, span);
+        bx.store(chunk_val, result_ptr, chunk_align);
+        offset += Size::from_bytes(chunk);
+    }
+}
+
+/// Lowers `ct_select_spec` for a scalar, or for both halves of a pair of scalars at once.
+///
+/// The values are selected as by `ct_select`, then passed together through the speculation
+/// barrier of the target, which they are tied to in registers. A load through a selected pointer
+/// depends on the barrier, so it cannot execute before the condition has been resolved, even
+/// when the processor predicts the outcome of the selection instead of waiting for it.
+pub(crate) fn codegen_ct_select_spec<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_vals: &[&'ll Value],
+    false_vals: &[&'ll Value],
+    span: Span,
+) -> Vec<&'ll Value> {
+    let Some(barrier) = bx.sess().target.speculation_barrier() else {
+        bug!("`ct_select_spec` on a target without a speculation barrier");
+    };
+    let reg_ty = bx.type_isize();
+    // Integers narrower than a register are widened to one, like by `ct_black_box`.
+    let selected: Vec<_> = std::iter::zip(true_vals, false_vals)
+        .map(|(&true_val, &false_val)| {
+            let val = codegen_ct_select(bx, cond, true_val, false_val, span);
+            let ty = bx.val_ty(val);
+            if bx.type_kind(ty) == TypeKind::Integer && ty != reg_ty {
+                bx.zext(val, reg_ty)
+            } else {
+                val
+            }
+        })
+        .collect();
+    let output_tys: Vec<_> = selected.iter().map(|&val| bx.val_ty(val)).collect();
+    let outputs = vec!["=r"; selected.len()].join(",");
+    let ties = (0..selected.len()).map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+    // The memory clobber keeps the loads that follow from being moved above the barrier.
+    let constraint = format!("{outputs},{ties},~{{memory}}");
+    let output = if let [ty] = output_tys[..] { ty } else { bx.type_struct(&output_tys, false) };
+    let fenced = inline_asm(bx, barrier, &constraint, &selected, output, true, span);
+    (0..)
+        .zip(true_vals)
+        .map(|(i, &true_val)| {
+            let val = if selected.len() == 1 { fenced } else { bx.extract_value(fenced, i) };
+            let ty = bx.val_ty(true_val);
+            if bx.val_ty(val) != ty { bx.trunc(val, ty) } else { val }
+        })
+        .collect()
+}
+
+/// Returns the strategy that `#[ct_select_lowering]` chooses.
+pub(crate) fn strategy_of_attr(attr: CtSelectLoweringAttr) -> CtSelectStrategy {
+    match attr {
+        CtSelectLoweringAttr::Cmov => CtSelectStrategy::Cmov,
+        CtSelectLoweringAttr::Masking => CtSelectStrategy::Masking,
+        CtSelectLoweringAttr::Asm => CtSelectStrategy::Asm,
+    }
+}
+
+/// Lowers `ct_select` for an integer of at most 64 bits or a pointer.
+fn select_legal<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let verify = bx.tcx.sess.opts.unstable_opts.verify_ct_select;
+    let regions = bx.tcx.sess.opts.unstable_opts.ct_regions;
+    let opaque = bx.tcx.sess.opts.unstable_opts.opaque_ct_select;
+    let marked = verify || regions;
+    let cond = if marked || opaque {
+        // Route the condition through the opening marker so that the select cannot be
+        // scheduled before it, or through an empty assembly statement under
+        // `-Zopaque-ct-select`. The optimizer cannot see through either, so the select is kept
+        // even if the condition is known at compile time.
+        let mut asm = Vec::new();
+        if regions {
+            // The label comes first, so that `verify_ct_select` does not take it for a block.
+            asm.push(ct_regions::begin_asm());
+        }
+        if verify {
+            asm.push(marker_asm(CT_SELECT_BEGIN_MARKER));
+        }
+        let isize_ty = bx.type_isize();
+        let wide = bx.zext(cond, isize_ty);
+        let wide = inline_asm(bx, &asm.join("\n"), "=r,0", &[wide], isize_ty, marked, span);
+        let zero = bx.const_usize(0);
+        bx.icmp(IntPredicate::IntNE, wide, zero)
+    } else {
+        cond
+    };
+
+    let strategy = match bx.cx.ct_select_strategies.borrow().get(bx.llfn()) {
+        Some(&strategy) => Some(strategy),
+        None => bx.tcx.sess.opts.unstable_opts.ct_select_lowering,
+    };
+    let lowering =
+        CtSelectLowering::new(bx.tcx.sess, strategy, |feature| bx.cx.has_llvm_feature(feature));
+    let result = match lowering {
+        CtSelectLowering::Aarch64Csel => {
+            select_by_aarch64_csel(bx, cond, true_val, false_val, span)
+        }
+        CtSelectLowering::NvptxSelp => select_by_nvptx_selp(bx, cond, true_val, false_val, span),
+        CtSelectLowering::AmdGpuCndmask => {
+            select_by_amdgpu_cndmask(bx, cond, true_val, false_val, span)
+        }
+        CtSelectLowering::WordMasking(word_bits) => {
+            select_by_word_masking(bx, cond, true_val, false_val, word_bits, span)
+        }
+        CtSelectLowering::Masking { opaque_mask } => {
+            select_by_masking(bx, cond, true_val, false_val, opaque_mask.then_some(span))
+        }
+        CtSelectLowering::X86Cmov => select_by_x86_cmov(bx, cond, true_val, false_val, span),
+        CtSelectLowering::PowerPcIsel => {
+            select_by_powerpc_isel(bx, cond, true_val, false_val, span)
+        }
+        CtSelectLowering::RiscVZicond => {
+            select_by_riscv_zicond(bx, cond, true_val, false_val, span)
+        }
+        CtSelectLowering::S390xLocgr => select_by_s390x_locgr(bx, cond, true_val, false_val, span),
+        CtSelectLowering::Select { .. } => {
+            // A plain `select` may be turned back into a branch by SimplifyCFG, SelectOptimize
+            // or CodeGenPrepare. All of them leave a `select` marked `!unpredictable` alone, so
+            // the condition never reaches the branch predictor.
+            let result = bx.select(cond, true_val, false_val);
+            bx.set_unpredictable(&result);
+            result
+        }
+    };
+
+    if marked {
+        // Likewise, the closing marker uses the result so that it cannot come before the select.
+        let mut asm = Vec::new();
+        if verify {
+            asm.push(marker_asm(CT_SELECT_END_MARKER));
+        }
+        if regions {
+            asm.push(ct_regions::end_asm(bx.tcx.sess));
+        }
+        let used =
+            if bx.val_ty(result) == bx.type_i1() { bx.zext(result, bx.type_i8()) } else { result };
+        let void = bx.type_void();
+        inline_asm(bx, &asm.join("\n"), "X", &[used], void, true, span);
+    }
+    result
+}
+
+/// The way the `ct_select` intrinsic is lowered, which depends on the target, its features
+/// and the optimization level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CtSelectLowering {
+    Aarch64Csel,
+    NvptxSelp,
+    AmdGpuCndmask,
+    /// Masking one register of the given width at a time.
+    WordMasking(u64),
+    /// Masking of the whole value, with the mask hidden from the optimizers if `opaque_mask`.
+    Masking {
+        opaque_mask: bool,
+    },
+    X86Cmov,
+    PowerPcIsel,
+    RiscVZicond,
+    S390xLocgr,
+    /// A `select` marked `!unpredictable`, which is branch-free only if the target can select
+    /// without branching, in which case `reliable` is set.
+    Select {
+        reliable: bool,
+    },
+}
+
+impl CtSelectLowering {
+    /// Chooses the lowering for `sess` under `strategy`, which is chosen with
+    /// `-Zct-select-lowering` or `#[ct_select_lowering]`, where `has_feature` tells whether an
+    /// LLVM feature is enabled.
+    pub(crate) fn new(
+        sess: &Session,
+        strategy: Option<CtSelectStrategy>,
+        has_feature: impl Fn(&'static str) -> bool,
+    ) -> Self {
+        let for_target = Self::for_target(sess, &has_feature);
+        // Targets whose registers are narrower than the values they select keep masking one
+        // register at a time.
+        let masking = |opaque_mask| match for_target {
+            CtSelectLowering::WordMasking(_) => for_target,
+            _ => CtSelectLowering::Masking { opaque_mask },
+        };
+        match strategy {
+            None => for_target,
+            // The `select` is only as reliable as the instruction LLVM lowers it to, and FastISel
+            // lowers some to branches without optimizations.
+            Some(CtSelectStrategy::Cmov) => CtSelectLowering::Select {
+                reliable: for_target.has_conditional_move() && sess.opts.optimize != OptLevel::No,
+            },
+            Some(CtSelectStrategy::Masking) => masking(sess.opts.optimize != OptLevel::No),
+            Some(CtSelectStrategy::Asm) => {
+                Self::asm_for_arch(sess, has_feature).unwrap_or_else(|| masking(true))
+            }
+        }
+    }
+
+    /// Chooses the lowering for `sess` when no strategy is chosen.
+    fn for_target(sess: &Session, has_feature: impl Fn(&'static str) -> bool) -> Self {
+        let for_arch = Self::for_arch(sess, has_feature);
+        match sess.target.ct_select {
+            None => for_arch,
+            // The target promises that LLVM lowers a `select` to a conditional move.
+            Some(CtSelectSupport::Cmov) => match for_arch {
+                CtSelectLowering::Select { .. } => CtSelectLowering::Select { reliable: true },
+                lowering => lowering,
+            },
+            Some(CtSelectSupport::Masking) => match for_arch {
+                lowering
+                @ (CtSelectLowering::WordMasking(_) | CtSelectLowering::Masking { .. }) => lowering,
+                _ => CtSelectLowering::Masking { opaque_mask: sess.opts.optimize != OptLevel::No },
+            },
+            // Whatever the lowering, nothing about it can be relied on.
+            Some(CtSelectSupport::Unsupported) => CtSelectLowering::Select { reliable: false },
+        }
+    }
+
+    /// Chooses the lowering from the architecture alone, for targets that do not declare their
+    /// support for constant-time selections.
+    fn for_arch(sess: &Session, has_feature: impl Fn(&'static str) -> bool) -> Self {
+        match sess.target.arch {
+            // The inline assembly is no harder to optimize around than masking, so unlike on
+            // other targets it is used at every optimization level.
+            Arch::AArch64 | Arch::Arm64EC => CtSelectLowering::Aarch64Csel,
+            Arch::Nvptx64 => CtSelectLowering::NvptxSelp,
+            Arch::AmdGpu => CtSelectLowering::AmdGpuCndmask,
+            // Masking a whole value at once would need its mask sign-extended across several
+            // registers, which, like the `select` itself, LLVM may lower to a branch or a loop.
+            Arch::Avr => CtSelectLowering::WordMasking(8),
+            Arch::Msp430 => CtSelectLowering::WordMasking(16),
+            // Without optimizations, instruction selection is done by FastISel, which lowers
+            // some `select`s to branches regardless of metadata (those of bytes on x86, for
+            // instance). Nothing runs that could fold masking back into a `select`, so use that
+            // instead.
+            _ if sess.opts.optimize == OptLevel::No => {
+                CtSelectLowering::Masking { opaque_mask: false }
+            }
+            Arch::X86 | Arch::X86_64 => {
+                if has_feature("cmov") {
+                    CtSelectLowering::X86Cmov
+                } else {
+                    // i586-class CPUs, and kernels built with `cmov` disabled, have no
+                    // conditional move, so LLVM would lower a `select` to a branch.
+                    CtSelectLowering::Masking { opaque_mask: true }
+                }
+            }
+            Arch::Arm if has_feature("thumb-mode") && !has_feature("thumb2") => {
+                // Thumb-1, all that ARMv6-M and ARMv8-M Baseline have, has neither conditional
+                // execution nor a conditional select, so LLVM lowers a `select` to a branch.
+                CtSelectLowering::Masking { opaque_mask: true }
+            }
+            Arch::PowerPC | Arch::PowerPC64 if has_feature("isel") => CtSelectLowering::PowerPcIsel,
+            Arch::RiscV32 | Arch::RiscV64 => {
+                if has_feature("zicond") {
+                    CtSelectLowering::RiscVZicond
+                } else {
+                    // Without Zicond, RISC-V has no conditional move or conditional zeroing,
+                    // and LLVM lowers a `select` to a branch.
+                    CtSelectLowering::Masking { opaque_mask: true }
+                }
+            }
+            Arch::S390x => {
+                if has_feature("load-store-on-cond") {
+                    CtSelectLowering::S390xLocgr
+                } else {
+                    // Load on condition only arrived with z196, and before it LLVM lowers a
+                    // `select` to a compare and branch.
+                    CtSelectLowering::Masking { opaque_mask: true }
+                }
+            }
+            Arch::Wasm32 | Arch::Wasm64
+                if sess.opts.unstable_opts.wasm_ct_select == WasmCtSelect::Mask =>
+            {
+                // Engines may compile the `select` instruction to a branch, which nothing in the
+                // module can prevent.
+                CtSelectLowering::Masking { opaque_mask: true }
+            }
+            // These have conditional execution or a conditional move or select, that LLVM uses
+            // for every `select` it does not turn into a branch itself.
+            Arch::Arm | Arch::Hexagon | Arch::LoongArch32 | Arch::LoongArch64 => {
+                CtSelectLowering::Select { reliable: true }
+            }
+            Arch::Mips | Arch::Mips64 => {
+                // `movn` and `movz` arrived with MIPS IV and MIPS32.
+                CtSelectLowering::Select { reliable: has_feature("mips4") || has_feature("mips32") }
+            }
+            Arch::Mips32r6 | Arch::Mips64r6 => CtSelectLowering::Select { reliable: true },
+            Arch::Sparc | Arch::Sparc64 => CtSelectLowering::Select { reliable: has_feature("v9") },
+            // Everything else, including PowerPC without `isel` and WebAssembly, whose engines
+            // may compile a `select` to a branch.
+            _ => CtSelectLowering::Select { reliable: false },
+        }
+    }
+
+    /// Returns the lowering to the conditional move or select instruction of the target written
+    /// as inline assembly, if the target has one.
+    fn asm_for_arch(sess: &Session, has_feature: impl Fn(&'static str) -> bool) -> Option<Self> {
+        Some(match sess.target.arch {
+            Arch::AArch64 | Arch::Arm64EC => CtSelectLowering::Aarch64Csel,
+            Arch::Nvptx64 => CtSelectLowering::NvptxSelp,
+            Arch::AmdGpu => CtSelectLowering::AmdGpuCndmask,
+            Arch::X86 | Arch::X86_64 if has_feature("cmov") => CtSelectLowering::X86Cmov,
+            Arch::PowerPC | Arch::PowerPC64 if has_feature("isel") => CtSelectLowering::PowerPcIsel,
+            Arch::RiscV32 | Arch::RiscV64 if has_feature("zicond") => CtSelectLowering::RiscVZicond,
+            Arch::S390x if has_feature("load-store-on-cond") => CtSelectLowering::S390xLocgr,
+            _ => return None,
+        })
+    }
+
+    /// Returns whether the target has a conditional move or select instruction, which LLVM
+    /// lowers a `select` to when it does not turn it into a branch.
+    fn has_conditional_move(self) -> bool {
+        match self {
+            CtSelectLowering::WordMasking(_) | CtSelectLowering::Masking { .. } => false,
+            CtSelectLowering::Select { reliable } => reliable,
+            _ => true,
+        }
+    }
+
+    /// Returns whether the lowering is guaranteed not to branch on the condition or call into a
+    /// library.
+    pub(crate) fn is_reliable(self) -> bool {
+        match self {
+            CtSelectLowering::Select { reliable } => reliable,
+            _ => true,
+        }
+    }
+
+    /// Returns the name of the lowering, as reported by `-Zdump-ct-lowering`.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            CtSelectLowering::Aarch64Csel => "aarch64-csel",
+            CtSelectLowering::NvptxSelp => "nvptx-selp",
+            CtSelectLowering::AmdGpuCndmask => "amdgpu-cndmask",
+            CtSelectLowering::WordMasking(_) => "word-masking",
+            CtSelectLowering::Masking { opaque_mask: true } => "opaque-masking",
+            CtSelectLowering::Masking { opaque_mask: false } => "masking",
+            CtSelectLowering::X86Cmov => "x86-cmov",
+            CtSelectLowering::PowerPcIsel => "powerpc-isel",
+            CtSelectLowering::RiscVZicond => "riscv-zicond",
+            CtSelectLowering::S390xLocgr => "s390x-locgr",
+            CtSelectLowering::Select { .. } => "select",
+        }
+    }
+
+    /// Returns the instructions that each register of a selection is lowered to. Masking and
+    /// `select` are reported as the LLVM IR operations, since the instructions are chosen by the
+    /// target's instruction selection.
+    pub(crate) fn instructions(self) -> &'static [&'static str] {
+        match self {
+            CtSelectLowering::Aarch64Csel => &["cmp", "csel"],
+            CtSelectLowering::NvptxSelp => &["setp.ne", "selp"],
+            CtSelectLowering::AmdGpuCndmask => &["v_cmp_ne_u32", "v_cndmask_b32"],
+            CtSelectLowering::WordMasking(_) | CtSelectLowering::Masking { .. } => {
+                &["sext", "xor", "and", "xor"]
+            }
+            CtSelectLowering::X86Cmov => &["test", "cmovne"],
+            CtSelectLowering::PowerPcIsel => &["cmplwi", "isel"],
+            CtSelectLowering::RiscVZicond => &["czero.eqz", "czero.nez", "or"],
+            CtSelectLowering::S390xLocgr => &["chi", "locgrne"],
+            CtSelectLowering::Select { .. } => &["select"],
+        }
+    }
+}
+
+/// Selects between `true_val` and `false_val` with bitwise operations only, as
+/// `false_val ^ (mask & (true_val ^ false_val))` where `mask` is `cond` sign-extended.
+///
+/// Pointers are masked as integers of the same width.
+///
+/// With optimizations, LLVM recognizes this pattern and turns it back into a `select`. To keep it
+/// from doing so, `opaque_mask_span` should be given, in which case the mask goes through an
+/// empty inline assembly statement at that span so that it is no longer known to come from a
+/// boolean.
+fn select_by_masking<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    opaque_mask_span: Option<Span>,
+) -> &'ll Value {
+    let ty = bx.val_ty(true_val);
+    let is_ptr = bx.type_kind(ty) == TypeKind::Pointer;
+    let (int_ty, true_val, false_val) = if is_ptr {
+        let int_ty = bx.type_isize();
+        (int_ty, bx.ptrtoint(true_val, int_ty), bx.ptrtoint(false_val, int_ty))
+    } else {
+        (ty, true_val, false_val)
+    };
+    let mask = if let Some(span) = opaque_mask_span {
+        // The mask is made opaque at the width of a register and then resized, which works
+        // for values that take more than one register.
+        let reg_ty = bx.type_isize();
+        let mask = bx.sext(cond, reg_ty);
+        let mask = inline_asm(bx, "", "=r,0", &[mask], reg_ty, false, span);
+        bx.intcast(mask, int_ty, true)
+    } else if int_ty == bx.type_i1() {
+        cond
+    } else {
+        bx.sext(cond, int_ty)
+    };
+    let diff = bx.xor(true_val, false_val);
+    let masked = bx.and(mask, diff);
+    let result = bx.xor(false_val, masked);
+    if is_ptr { bx.inttoptr(result, ty) } else { result }
+}
+
+/// Selects between `true_val` and `false_val` with a `cmov` written as inline assembly.
+///
+/// X86CmovConversion turns a `cmov` that it considers unprofitable, typically one waiting on a
+/// load, back into a branch. It leaves alone those whose `select` still carried `!unpredictable`
+/// at instruction selection, but the mid-end does not reliably keep that metadata when it
+/// rewrites a `select`. No pass looks into inline assembly.
+fn select_by_x86_cmov<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let cond = bx.zext(cond, bx.type_i32());
+    let reg_bits = bx.tcx.data_layout.pointer_size().bits();
+    select_in_registers(bx, true_val, false_val, reg_bits, |bx, reg_ty, true_val, false_val| {
+        inline_asm(
+            bx,
+            "test $1, $1\n\tcmovne $2, $0",
+            "=r,r,r,0,~{flags}",
+            &[cond, true_val, false_val],
+            reg_ty,
+            false,
+            span,
+        )
+    })
+}
+
+/// Selects between `true_val` and `false_val` by masking them one `word_bits`-wide register at a
+/// time, for targets whose registers are narrower than the values they select.
+///
+/// The mask is made a single register wide and passed through an empty inline assembly
+/// statement, as in `select_by_masking`. Each word of the values is then masked on its own with
+/// the same mask and the words are reassembled, so no operation is ever wider than a register.
+fn select_by_word_masking<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    word_bits: u64,
+    span: Span,
+) -> &'ll Value {
+    let ty = bx.val_ty(true_val);
+    let is_ptr = bx.type_kind(ty) == TypeKind::Pointer;
+    let bits = if is_ptr { bx.tcx.data_layout.pointer_size().bits() } else { bx.int_width(ty) };
+    let int_ty = bx.type_ix(bits);
+    let (true_val, false_val) = if is_ptr {
+        (bx.ptrtoint(true_val, int_ty), bx.ptrtoint(false_val, int_ty))
+    } else {
+        (true_val, false_val)
+    };
+    let word_ty = bx.type_ix(word_bits);
+    let mask = bx.sext(cond, word_ty);
+    let mask = inline_asm(bx, "", "=r,0", &[mask], word_ty, false, span);
+    let result = if bits <= word_bits {
+        let true_val = bx.zext(true_val, word_ty);
+        let false_val = bx.zext(false_val, word_ty);
+        let diff = bx.xor(true_val, false_val);
+        let diff = bx.and(mask, diff);
+        let result = bx.xor(false_val, diff);
+        bx.trunc(result, int_ty)
+    } else {
+        assert_eq!(bits % word_bits, 0, "unexpected width for `ct_select`");
+        let mut result = bx.const_uint(int_ty, 0);
+        for i in 0..bits / word_bits {
+            let shift = bx.const_uint(int_ty, i * word_bits);
+            let true_word = bx.lshr(true_val, shift);
+            let true_word = bx.trunc(true_word, word_ty);
+            let false_word = bx.lshr(false_val, shift);
+            let false_word = bx.trunc(false_word, word_ty);
+            let diff = bx.xor(true_word, false_word);
+            let diff = bx.and(mask, diff);
+            let word = bx.xor(false_word, diff);
+            let word = bx.zext(word, int_ty);
+            let word = bx.shl(word, shift);
+            result = bx.or(result, word);
+        }
+        result
+    };
+    if is_ptr { bx.inttoptr(result, ty) } else { result }
+}
+
+/// Selects between `true_val` and `false_val` with a `csel` written as inline assembly.
+///
+/// A `select` usually becomes a `csel` on AArch64, but nothing guarantees it: FastISel and the
+/// SelectionDAG may both use a conditional branch instead.
+fn select_by_aarch64_csel<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let cond = bx.zext(cond, bx.type_isize());
+    let reg_bits = bx.tcx.data_layout.pointer_size().bits();
+    select_in_registers(bx, true_val, false_val, reg_bits, |bx, reg_ty, true_val, false_val| {
+        inline_asm(
+            bx,
+            "cmp $1, #0\n\tcsel $0, $2, $3, ne",
+            "=r,r,r,r,~{cc}",
+            &[cond, true_val, false_val],
+            reg_ty,
+            false,
+            span,
+        )
+    })
+}
+
+/// Selects between `true_val` and `false_val` with an `isel` written as inline assembly.
+///
+/// The `isel` picks its first operand, `false_val`, when the comparison of `cond` with zero sets
+/// the EQ bit of CR0 (bit 2). It reads register 0 as zero in that operand, so `false_val` is
+/// constrained to the other general-purpose registers.
+fn select_by_powerpc_isel<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let cond = bx.zext(cond, bx.type_i32());
+    let reg_bits = bx.tcx.data_layout.pointer_size().bits();
+    select_in_registers(bx, true_val, false_val, reg_bits, |bx, reg_ty, true_val, false_val| {
+        inline_asm(
+            bx,
+            "cmplwi $1, 0\n\tisel $0, $3, $2, 2",
+            "=r,r,r,b,~{cr0}",
+            &[cond, true_val, false_val],
+            reg_ty,
+            false,
+            span,
+        )
+    })
+}
+
+/// Selects between `true_val` and `false_val` with a `locgr` written as inline assembly, which
+/// overwrites `false_val`, tied to the output, with `true_val` if `cond` is nonzero.
+fn select_by_s390x_locgr<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let cond = bx.zext(cond, bx.type_i32());
+    let reg_bits = bx.tcx.data_layout.pointer_size().bits();
+    select_in_registers(bx, true_val, false_val, reg_bits, |bx, reg_ty, true_val, false_val| {
+        inline_asm(
+            bx,
+            "chi $1, 0\n\tlocgrne $0, $2",
+            "=r,r,r,0,~{cc}",
+            &[cond, true_val, false_val],
+            reg_ty,
+            false,
+            span,
+        )
+    })
+}
+
+/// Selects between `true_val` and `false_val` with a `selp` written as inline assembly.
+///
+/// `selp` is a predicated selection, so all the threads of a warp execute it together whatever
+/// their conditions. A `select` may instead become a branch, and so divergence.
+fn select_by_nvptx_selp<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let ty = bx.val_ty(true_val);
+    let is_ptr = bx.type_kind(ty) == TypeKind::Pointer;
+    let bits = if is_ptr { bx.tcx.data_layout.pointer_size().bits() } else { bx.int_width(ty) };
+    let int_ty = bx.type_ix(bits);
+    let (true_val, false_val) = if is_ptr {
+        (bx.ptrtoint(true_val, int_ty), bx.ptrtoint(false_val, int_ty))
+    } else {
+        (true_val, false_val)
+    };
+    // PTX has 32-bit (`r`) and 64-bit (`l`) registers, and predicates cannot be operands.
+    let (reg_ty, width, reg) =
+        if bits <= 32 { (bx.type_i32(), 32, "r") } else { (bx.type_i64(), 64, "l") };
+    let cond = bx.zext(cond, bx.type_i32());
+    let true_val = bx.zext(true_val, reg_ty);
+    let false_val = bx.zext(false_val, reg_ty);
+    let result = inline_asm(
+        bx,
+        &format!("{{ .reg .pred %p; setp.ne.b32 %p, $1, 0; selp.b{width} $0, $2, $3, %p; }}"),
+        &format!("={reg},r,{reg},{reg}"),
+        &[cond, true_val, false_val],
+        reg_ty,
+        false,
+        span,
+    );
+    let result = bx.trunc(result, int_ty);
+    if is_ptr { bx.inttoptr(result, ty) } else { result }
+}
+
+/// Selects between `true_val` and `false_val` with a `v_cndmask_b32` written as inline assembly.
+///
+/// Like `selp` on NVPTX, `v_cndmask_b32` selects in each lane of a wavefront according to a lane
+/// mask, here computed into VCC, so that the lanes never diverge. Values are selected in 32-bit
+/// vector registers, one half at a time for 64-bit values.
+fn select_by_amdgpu_cndmask<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    // In wave32 mode, the lane mask is only the low half of VCC.
+    let vcc = if bx.cx.has_llvm_feature("wavefrontsize32") { "vcc_lo" } else { "vcc" };
+    let cond = bx.zext(cond, bx.type_i32());
+    select_in_registers(bx, true_val, false_val, 32, |bx, reg_ty, true_val, false_val| {
+        inline_asm(
+            bx,
+            &format!("v_cmp_ne_u32_e32 {vcc}, 0, $1\n\tv_cndmask_b32_e32 $0, $3, $2, {vcc}"),
+            "=v,v,v,v,~{vcc}",
+            &[cond, true_val, false_val],
+            reg_ty,
+            false,
+            span,
+        )
+    })
+}
+
+/// Selects between `true_val` and `false_val` with the conditional-zero instructions of the
+/// RISC-V Zicond extension, written as inline assembly, as
+/// `czero.eqz(true_val, cond) | czero.nez(false_val, cond)`.
+///
+/// LLVM itself uses these instructions for a `select` when it finds them profitable, and a branch
+/// otherwise.
+fn select_by_riscv_zicond<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    cond: &'ll Value,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    span: Span,
+) -> &'ll Value {
+    let cond = bx.zext(cond, bx.type_isize());
+    let reg_bits = bx.tcx.data_layout.pointer_size().bits();
+    select_in_registers(bx, true_val, false_val, reg_bits, |bx, reg_ty, true_val, false_val| {
+        let czero = |bx: &mut Builder<'_, 'll, 'tcx>, insn: &str, val| {
+            inline_asm(
+                bx,
+                &format!("{insn} $0, $1, $2"),
+                "=r,r,r",
+                &[val, cond],
+                reg_ty,
+                false,
+                span,
+            )
+        };
+        let true_val = czero(bx, "czero.eqz", true_val);
+        let false_val = czero(bx, "czero.nez", false_val);
+        bx.or(true_val, false_val)
+    })
+}
+
+/// Selects between `true_val` and `false_val` with `select_regs`, which selects between two
+/// integers of the register type it is given, `reg_bits` wide.
+///
+/// Pointers are selected as integers, values narrower than a register are zero-extended, and
+/// values that take two registers, such as 64-bit integers on 32-bit targets, are selected one
+/// half at a time.
+fn select_in_registers<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    true_val: &'ll Value,
+    false_val: &'ll Value,
+    reg_bits: u64,
+    mut select_regs: impl FnMut(
+        &mut Builder<'_, 'll, 'tcx>,
+        &'ll Type,
+        &'ll Value,
+        &'ll Value,
+    ) -> &'ll Value,
+) -> &'ll Value {
+    let ty = bx.val_ty(true_val);
+    let is_ptr = bx.type_kind(ty) == TypeKind::Pointer;
+    let reg_ty = bx.type_ix(reg_bits);
+    let bits = if is_ptr { bx.tcx.data_layout.pointer_size().bits() } else { bx.int_width(ty) };
+    let int_ty = bx.type_ix(bits);
+    let (true_val, false_val) = if is_ptr {
+        (bx.ptrtoint(true_val, int_ty), bx.ptrtoint(false_val, int_ty))
+    } else {
+        (true_val, false_val)
+    };
+    let result = if bits < reg_bits {
+        let true_val = bx.zext(true_val, reg_ty);
+        let false_val = bx.zext(false_val, reg_ty);
+        let result = select_regs(bx, reg_ty, true_val, false_val);
+        bx.trunc(result, int_ty)
+    } else if bits == reg_bits {
+        select_regs(bx, reg_ty, true_val, false_val)
+    } else {
+        assert_eq!(bits, 2 * reg_bits, "unexpected width for `ct_select`");
+        let shift = bx.const_uint(int_ty, reg_bits);
+        let true_hi = bx.lshr(true_val, shift);
+        let true_hi = bx.trunc(true_hi, reg_ty);
+        let true_lo = bx.trunc(true_val, reg_ty);
+        let false_hi = bx.lshr(false_val, shift);
+        let false_hi = bx.trunc(false_hi, reg_ty);
+        let false_lo = bx.trunc(false_val, reg_ty);
+        let lo = select_regs(bx, reg_ty, true_lo, false_lo);
+        let hi = select_regs(bx, reg_ty, true_hi, false_hi);
+        let lo = bx.zext(lo, int_ty);
+        let hi = bx.zext(hi, int_ty);
+        let hi = bx.shl(hi, shift);
+        bx.or(hi, lo)
+    };
+    if is_ptr { bx.inttoptr(result, ty) } else { result }
+}
+
+/// Lowers `ct_black_box` for a value that fits in a general-purpose register, by passing it
+/// through an empty assembly statement that the optimizer can neither see through nor merge with
+/// another. Returns `None` for other values, which the caller hides in memory.
+pub(crate) fn codegen_ct_black_box<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    value: &'ll Value,
+    span: Span,
+) -> Option<&'ll Value> {
+    let ty = bx.val_ty(value);
+    let reg_bits = bx.tcx.data_layout.pointer_size().bits();
+    match bx.type_kind(ty) {
+        TypeKind::Pointer => Some(inline_asm(bx, "", "=r,0", &[value], ty, true, span)),
+        TypeKind::Integer if bx.int_width(ty) <= reg_bits => {
+            // Narrower values are widened to a register, which works for booleans as well.
+            let reg_ty = bx.type_ix(reg_bits);
+            let wide = if ty == reg_ty { value } else { bx.zext(value, reg_ty) };
+            let wide = inline_asm(bx, "", "=r,0", &[wide], reg_ty, true, span);
+            Some(if ty == reg_ty { wide } else { bx.trunc(wide, ty) })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the assembly for a comment reading `marker`.
+fn marker_asm(marker: &str) -> String {
+    format!("${{:comment}} {marker}")
+}
+
+/// Emits an inline assembly statement, which is kept even if its output is unused if `volatile`.
+fn inline_asm<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    asm: &str,
+    constraint: &str,
+    inputs: &[&'ll Value],
+    output: &'ll Type,
+    volatile: bool,
+    span: Span,
+) -> &'ll Value {
+    crate::asm::inline_asm_call(
+        bx,
+        asm,
+        constraint,
+        inputs,
+        output,
+        &[],
+        volatile,
+        false,
+        llvm::AsmDialect::Att,
+        &[span],
+        false,
+        None,
+        None,
+    )
+    .unwrap_or_else(|| bug!("failed to generate inline asm call for `ct_select`"))
+}
@@ -172,6 +172,32 @@ pub(crate) struct WriteBytecode<'a> {
     pub err: std::io::Error,
 }
 
+#[derive(Diagnostic)]
+#[diag("`ct_select` was lowered to a branch in `{$function}`")]
+#[note("the generated code leaks the condition through timing and the branch predictor")]
+pub(crate) struct CtSelectBranch {
+    pub function: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(
+    "{$filename}:{$line}:{$column} registers were spilled to the stack in `{$function}`, which holds `Secret` values: {$message}"
+)]
+pub(crate) struct CtSecretsSpilled<'a> {
+    pub filename: &'a str,
+    pub line: std::ffi::c_uint,
+    pub column: std::ffi::c_uint,
+    pub function: String,
+    pub message: &'a str,
+}
+
+#[derive(Diagnostic)]
+#[diag("failed to read assembly from {$path} to verify `ct_select`: {$err}")]
+pub(crate) struct VerifyCtSelectRead<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag("failed to copy bitcode to object file: {$err}")]
 pub(crate) struct CopyBitcode {
@@ -41,6 +41,7 @@
 use crate::llvm::{self, Metadata, Type, Value};
 use crate::type_of::LayoutLlvmExt;
 use crate::va_arg::emit_va_arg;
+use crate::{ct_annotations, ct_select};
 
 fn call_simple_intrinsic<'ll, 'tcx>(
     bx: &mut Builder<'_, 'll, 'tcx>,
@@ -231,7 +232,7 @@ fn codegen_intrinsic_call(
                     self.const_bool(false)
                 }
             }
-            sym::select_unpredictable => {
+            sym::select_unpredictable if !self.sess().opts.cg.force_ct_select => {
                 let cond = args[0].immediate();
                 assert_eq!(args[1].layout, args[2].layout);
                 let select = |bx: &mut Self, true_val, false_val| {
@@ -260,6 +261,82 @@ fn codegen_intrinsic_call(
                     _ => span_bug!(span, "Incompatible OperandValue for select_unpredictable"),
                 }
             }
+            // The per-width variants are deprecated aliases of `ct_select`.
+            sym::ct_select
+            | sym::ct_select_i8
+            | sym::ct_select_i16
+            | sym::ct_select_i32
+            | sym::ct_select_i64
+            | sym::ct_select_bool
+            | sym::ct_select_ptr
+            // `-Cforce-ct-select` lowers `select_unpredictable` like `ct_select` as well.
+            | sym::select_unpredictable => {
+                let cond = args[0].immediate();
+                // A `MaybeUninit` operand, such as the payload of a `None` selected by
+                // `Option::ct_unwrap_or`, may be undef or poison. The lowering masks both
+                // operands, so they are frozen first, lest the value that is not selected
+                // make the result poison as well.
+                let maybe_uninit = match args[1].layout.backend_repr {
+                    BackendRepr::Scalar(a) => a.is_uninit_valid(),
+                    BackendRepr::ScalarPair(a, b) => a.is_uninit_valid() || b.is_uninit_valid(),
+                    BackendRepr::SimdVector { element, .. } => element.is_uninit_valid(),
+                    _ => false,
+                };
+                let mut freeze =
+                    |val: &'ll Value| if maybe_uninit { self.freeze(val) } else { val };
+                match (args[1].val, args[2].val) {
+                    (OperandValue::Immediate(true_val), OperandValue::Immediate(false_val)) => {
+                        let (true_val, false_val) = (freeze(true_val), freeze(false_val));
+                        ct_select::codegen_ct_select(self, cond, true_val, false_val, span)
+                    }
+                    (OperandValue::Pair(true_a, true_b), OperandValue::Pair(false_a, false_b)) => {
+                        let (true_a, true_b) = (freeze(true_a), freeze(true_b));
+                        let (false_a, false_b) = (freeze(false_a), freeze(false_b));
+                        // Both halves are selected on the same condition, so the result is
+                        // always one of the two values, never a mix of them.
+                        let a = ct_select::codegen_ct_select(self, cond, true_a, false_a, span);
+                        let b = ct_select::codegen_ct_select(self, cond, true_b, false_b, span);
+                        OperandValue::Pair(a, b).store(self, result);
+                        return Ok(());
+                    }
+                    (OperandValue::Ref(true_val), OperandValue::Ref(false_val)) => {
+                        let size = result.layout.size;
+                        ct_select::codegen_ct_select_in_memory(
+                            self, cond, true_val, false_val, result.val, size, span,
+                        );
+                        return Ok(());
+                    }
+                    (OperandValue::ZeroSized, OperandValue::ZeroSized) => return Ok(()),
+                    _ => span_bug!(span, "unexpected operands for `{name}`"),
+                }
+            }
+            sym::ct_select_spec => {
+                let cond = args[0].immediate();
+                match (args[1].val, args[2].val) {
+                    (OperandValue::Immediate(true_val), OperandValue::Immediate(false_val)) => {
+                        let selected = ct_select::codegen_ct_select_spec(
+                            self,
+                            cond,
+                            &[true_val],
+                            &[false_val],
+                            span,
+                        );
+                        selected[0]
+                    }
+                    (OperandValue::Pair(true_a, true_b), OperandValue::Pair(false_a, false_b)) => {
+                        let selected = ct_select::codegen_ct_select_spec(
+                            self,
+                            cond,
+                            &[true_a, true_b],
+                            &[false_a, false_b],
+                            span,
+                        );
+                        OperandValue::Pair(selected[0], selected[1]).store(self, result);
+                        return Ok(());
+                    }
+                    _ => span_bug!(span, "unexpected operands for `{name}`"),
+                }
+            }
             sym::catch_unwind => {
                 catch_unwind_intrinsic(
                     self,
@@ -518,42 +595,32 @@ fn codegen_intrinsic_call(
 
             sym::black_box => {
                 args[0].val.store(self, result);
-                let result_val_span = [result.val.llval];
-                // We need to "use" the argument in some way LLVM can't introspect, and on
-                // targets that support it we can typically leverage inline assembly to do
-                // this. LLVM's interpretation of inline assembly is that it's, well, a black
-                // box. This isn't the greatest implementation since it probably deoptimizes
-                // more than we want, but it's so far good enough.
-                //
-                // For zero-sized types, the location pointed to by the result may be
-                // uninitialized. Do not "use" the result in this case; instead just clobber
-                // the memory.
-                let (constraint, inputs): (&str, &[_]) = if result.layout.is_zst() {
-                    ("~{memory}", &[])
-                } else {
-                    ("r,~{memory}", &result_val_span)
-                };
-                crate::asm::inline_asm_call(
-                    self,
-                    "",
-                    constraint,
-                    inputs,
-                    self.type_void(),
-                    &[],
-                    true,
-                    false,
-                    llvm::AsmDialect::Att,
-                    &[span],
-                    false,
-                    None,
-                    None,
-                )
-                .unwrap_or_else(|| bug!("failed to generate inline asm call for `black_box`"));
+                black_box_in_memory(self, result, span);
 
                 // We have copied the value to `result` already.
                 return Ok(());
             }
 
+            sym::ct_black_box => {
+                if let OperandValue::Immediate(value) = args[0].val
+                    && let Some(value) = ct_select::codegen_ct_black_box(self, value, span)
+                {
+                    value
+                } else {
+                    // Values that do not fit in a register are hidden in memory instead.
+                    args[0].val.store(self, result);
+                    black_box_in_memory(self, result, span);
+                    return Ok(());
+                }
+            }
+
+            sym::ct_classify | sym::ct_declassify => {
+                args[0].val.store(self, result);
+                let classify = name == sym::ct_classify;
+                ct_annotations::codegen_ct_annotation(self, classify, result, span);
+                return Ok(());
+            }
+
             sym::amdgpu_dispatch_ptr => {
                 let val = self.call_intrinsic("llvm.amdgcn.dispatch.ptr", &[], &[]);
                 // Relying on `LLVMBuildPointerCast` to produce an addrspacecast
@@ -799,6 +866,42 @@ fn va_end(&mut self, va_list: &'ll Value) -> &'ll Value {
     }
 }
 
+/// Hides the value stored in `result` from the optimizer, for `black_box` and `ct_black_box`.
+fn black_box_in_memory<'ll, 'tcx>(
+    bx: &mut Builder<'_, 'll, 'tcx>,
+    result: PlaceRef<'tcx, &'ll Value>,
+    span: Span,
+) {
+    let result_val_span = [result.val.llval];
+    // We need to "use" the argument in some way LLVM can't introspect, and on
+    // targets that support it we can typically leverage inline assembly to do
+    // this. LLVM's interpretation of inline assembly is that it's, well, a black
+    // box. This isn't the greatest implementation since it probably deoptimizes
+    // more than we want, but it's so far good enough.
+    //
+    // For zero-sized types, the location pointed to by the result may be
+    // uninitialized. Do not "use" the result in this case; instead just clobber
+    // the memory.
+    let (constraint, inputs): (&str, &[_]) =
+        if result.layout.is_zst() { ("~{memory}", &[]) } else { ("r,~{memory}", &result_val_span) };
+    crate::asm::inline_asm_call(
+        bx,
+        "",
+        constraint,
+        inputs,
+        bx.type_void(),
+        &[],
+        true,
+        false,
+        llvm::AsmDialect::Att,
+        &[span],
+        false,
+        None,
+        None,
+    )
+    .unwrap_or_else(|| bug!("failed to generate inline asm call for `black_box`"));
+}
+
 fn catch_unwind_intrinsic<'ll, 'tcx>(
     bx: &mut Builder<'_, 'll, 'tcx>,
     try_func: &'ll Value,
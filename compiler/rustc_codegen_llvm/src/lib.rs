@@ -60,6 +60,9 @@
 mod consts;
 mod context;
 mod coverageinfo;
+mod ct_annotations;
+mod ct_regions;
+mod ct_select;
 mod debuginfo;
 mod declare;
 mod errors;
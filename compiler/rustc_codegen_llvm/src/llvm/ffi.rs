@@ -293,6 +293,7 @@ pub(crate) enum AttributeKind {
     CapturesNone = 46,
     SanitizeRealtimeNonblocking = 47,
     SanitizeRealtimeBlocking = 48,
+    SpeculativeLoadHardening = 49,
 }
 
 /// LLVMIntPredicate
@@ -451,6 +452,7 @@ pub(crate) struct SanitizerOptions {
     pub sanitize_address: bool,
     pub sanitize_address_recover: bool,
     pub sanitize_cfi: bool,
+    pub sanitize_constant_time: bool,
     pub sanitize_dataflow: bool,
     pub sanitize_dataflow_abilist: *const *const c_char,
     pub sanitize_dataflow_abilist_len: size_t,
@@ -1513,6 +1515,11 @@ pub(crate) fn LLVMBuildSelect<'a>(
         Else: &'a Value,
         Name: *const c_char,
     ) -> &'a Value;
+    pub(crate) fn LLVMBuildFreeze<'a>(
+        B: &Builder<'a>,
+        Val: &'a Value,
+        Name: *const c_char,
+    ) -> &'a Value;
     pub(crate) fn LLVMBuildVAArg<'a>(
         B: &Builder<'a>,
         list: &'a Value,
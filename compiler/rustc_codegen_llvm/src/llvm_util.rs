@@ -14,13 +14,14 @@
 use rustc_fs_util::path_to_c_string;
 use rustc_middle::bug;
 use rustc_session::Session;
-use rustc_session::config::{PrintKind, PrintRequest};
+use rustc_session::config::{CtSelectStrategy, PrintKind, PrintRequest};
 use rustc_target::spec::{
     Abi, Arch, Env, MergeFunctions, Os, PanicStrategy, SmallDataThresholdSupport,
 };
 use smallvec::{SmallVec, smallvec};
 
 use crate::back::write::create_informational_target_machine;
+use crate::ct_select::CtSelectLowering;
 use crate::{errors, llvm};
 
 static INIT: Once = Once::new();
@@ -355,9 +356,30 @@ pub(crate) fn target_config(sess: &Session) -> TargetConfig {
         has_reliable_f16_math: true,
         has_reliable_f128: true,
         has_reliable_f128_math: true,
+        has_reliable_ct_select: true,
+        has_reliable_ct_select_cmov: true,
+        ct_select_lowering: "unknown",
+        ct_select_instructions: &[],
     };
 
     update_target_reliable_float_cfg(sess, &mut cfg);
+
+    // Unlike the features above, those that decide how selections are lowered include the
+    // `-Ctarget-feature`s, as codegen does.
+    let full_target_machine = create_informational_target_machine(sess, false);
+    let has_feature = |feature| {
+        let cstr = SmallCStr::new(feature);
+        unsafe { llvm::LLVMRustHasFeature(full_target_machine.raw(), cstr.as_ptr()) }
+    };
+    let strategy = sess.opts.unstable_opts.ct_select_lowering;
+    let ct_select_lowering = CtSelectLowering::new(sess, strategy, has_feature);
+    cfg.has_reliable_ct_select = ct_select_lowering.is_reliable();
+    // `#[ct_select_lowering(cmov)]` chooses this lowering for single functions, so the
+    // monomorphization checks report their selections if it is not reliable.
+    cfg.has_reliable_ct_select_cmov =
+        CtSelectLowering::new(sess, Some(CtSelectStrategy::Cmov), has_feature).is_reliable();
+    cfg.ct_select_lowering = ct_select_lowering.name();
+    cfg.ct_select_instructions = ct_select_lowering.instructions();
     cfg
 }
 
@@ -487,6 +509,16 @@ fn llvm_target_features(tm: &llvm::TargetMachine) -> Vec<(&str, &str)> {
     ret
 }
 
+/// Returns whether LLVM considers `feature` enabled for the target CPU, taking the
+/// `-Ctarget-feature`s into account.
+///
+/// Unlike the `cfg(target_feature)`s, this works for LLVM features that Rust does not know about.
+pub(crate) fn has_llvm_feature(sess: &Session, feature: &str) -> bool {
+    let tm = create_informational_target_machine(sess, false);
+    let cstr = SmallCStr::new(feature);
+    unsafe { llvm::LLVMRustHasFeature(tm.raw(), cstr.as_ptr()) }
+}
+
 pub(crate) fn print(req: &PrintRequest, out: &mut String, sess: &Session) {
     require_inited();
     let tm = create_informational_target_machine(sess, false);
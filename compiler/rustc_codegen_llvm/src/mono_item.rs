@@ -6,6 +6,7 @@
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::bug;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::mono::Visibility;
 use rustc_middle::ty::layout::{FnAbiOf, HasTypingEnv, LayoutOf};
 use rustc_middle::ty::{self, Instance, TypeVisitableExt};
@@ -16,7 +17,7 @@
 use crate::context::CodegenCx;
 use crate::errors::SymbolAlreadyDefined;
 use crate::type_of::LayoutLlvmExt;
-use crate::{base, llvm};
+use crate::{attributes, base, ct_regions, ct_select, llvm};
 
 impl<'tcx> PreDefineCodegenMethods<'tcx> for CodegenCx<'_, 'tcx> {
     fn predefine_static(
@@ -86,6 +87,29 @@ fn predefine_fn(
 
         self.add_aliases(lldecl, &attrs.foreign_item_symbol_aliases);
 
+        let opts = &self.tcx.sess.opts.unstable_opts;
+        if opts.ct_regions || opts.ct_metadata {
+            ct_regions::record_function(self, symbol_name, &attrs);
+        }
+        // Under `-Zct-scrub-stack`, the function zeroes the frame of its body from a landing pad
+        // of its own when the body unwinds, which needs a personality function.
+        if opts.ct_scrub_stack
+            && attrs.flags.contains(CodegenFnAttrFlags::CONSTANT_TIME)
+            && self.tcx.sess.panic_strategy().unwinds()
+        {
+            unsafe { llvm::LLVMSetPersonalityFn(lldecl, self.eh_personality()) };
+        }
+        if let Some(attr) = attributes::secret_conditions_attr(self, self.tcx, instance, &attrs) {
+            attributes::apply_to_llfn(lldecl, llvm::AttributePlace::Function, &[attr]);
+        }
+        if let Some(attr) = attributes::pin_secrets_attr(self, self.tcx, instance) {
+            attributes::apply_to_llfn(lldecl, llvm::AttributePlace::Function, &[attr]);
+        }
+        if let Some(lowering) = attrs.ct_select_lowering {
+            let strategy = ct_select::strategy_of_attr(lowering);
+            self.ct_select_strategies.borrow_mut().insert(lldecl, strategy);
+        }
+
         self.instances.borrow_mut().insert(instance, lldecl);
     }
 }
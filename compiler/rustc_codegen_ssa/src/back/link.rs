@@ -1231,7 +1231,8 @@ fn add_sanitizer_libraries(
     if sanitizer.contains(SanitizerSet::ADDRESS) {
         link_sanitizer_runtime(sess, flavor, linker, "asan");
     }
-    if sanitizer.contains(SanitizerSet::DATAFLOW) {
+    // The constant-time sanitizer uses the runtime of DataFlowSanitizer.
+    if sanitizer.intersects(SanitizerSet::DATAFLOW | SanitizerSet::CONSTANT_TIME) {
         link_sanitizer_runtime(sess, flavor, linker, "dfsan");
     }
     if sanitizer.contains(SanitizerSet::LEAK)
@@ -107,6 +107,8 @@ pub struct ModuleConfig {
     // Miscellaneous flags. These are mostly copied from command-line
     // options.
     pub verify_llvm_ir: bool,
+    pub verify_ct_select: bool,
+    pub ct_pin_secrets: bool,
     pub lint_llvm_ir: bool,
     pub no_prepopulate_passes: bool,
     pub no_builtins: bool,
@@ -221,6 +223,8 @@ macro_rules! if_regular {
             ),
 
             verify_llvm_ir: sess.verify_llvm_ir(),
+            verify_ct_select: if_regular!(sess.opts.unstable_opts.verify_ct_select, false),
+            ct_pin_secrets: if_regular!(sess.opts.unstable_opts.ct_pin_secrets, false),
             lint_llvm_ir: sess.opts.unstable_opts.lint_llvm_ir,
             no_prepopulate_passes: sess.opts.cg.no_prepopulate_passes,
             no_builtins: no_builtins || sess.target.no_builtins,
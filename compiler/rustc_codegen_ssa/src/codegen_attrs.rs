@@ -8,6 +8,7 @@
 };
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE, LocalDefId};
+use rustc_hir::definitions::DefPathData;
 use rustc_hir::{self as hir, Attribute, LangItem, find_attr, lang_items};
 use rustc_middle::middle::codegen_fn_attrs::{
     CodegenFnAttrFlags, CodegenFnAttrs, PatchableFunctionEntry, SanitizerFnAttrs,
@@ -74,6 +75,15 @@ fn process_builtin_attrs(
     for attr in parsed_attrs {
         match attr {
             AttributeKind::Cold(_) => codegen_fn_attrs.flags |= CodegenFnAttrFlags::COLD,
+            AttributeKind::ConstantTime { speculative_load_hardening, .. } => {
+                codegen_fn_attrs.flags |= CodegenFnAttrFlags::CONSTANT_TIME;
+                if *speculative_load_hardening {
+                    codegen_fn_attrs.flags |= CodegenFnAttrFlags::SPECULATIVE_LOAD_HARDENING;
+                }
+            }
+            AttributeKind::CtSelectLowering(lowering, _) => {
+                codegen_fn_attrs.ct_select_lowering = Some(*lowering)
+            }
             AttributeKind::ExportName { name, .. } => codegen_fn_attrs.symbol_name = Some(*name),
             AttributeKind::Inline(inline, span) => {
                 codegen_fn_attrs.inline = *inline;
@@ -288,6 +298,7 @@ fn process_builtin_attrs(
             AttributeKind::RustcAllocatorZeroed => {
                 codegen_fn_attrs.flags |= CodegenFnAttrFlags::ALLOCATOR_ZEROED
             }
+            AttributeKind::RustcNoBranch => codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_BRANCH,
             AttributeKind::RustcNounwind => {
                 codegen_fn_attrs.flags |= CodegenFnAttrFlags::NEVER_UNWIND
             }
@@ -358,6 +369,14 @@ fn apply_overrides(tcx: TyCtxt<'_>, did: LocalDefId, codegen_fn_attrs: &mut Code
         codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_BUILTINS;
     }
 
+    // `memcmp` and `bcmp` return at the first difference, so the comparison loops of
+    // constant-time code must stay as written.
+    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::CONSTANT_TIME)
+        || is_in_core_select(tcx, did)
+    {
+        codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_BUILTIN_CMP;
+    }
+
     // inherit track-caller properly
     if tcx.should_inherit_track_caller(did) {
         codegen_fn_attrs.flags |= CodegenFnAttrFlags::TRACK_CALLER;
@@ -393,6 +412,16 @@ fn apply_overrides(tcx: TyCtxt<'_>, did: LocalDefId, codegen_fn_attrs: &mut Code
     }
 }
 
+/// Returns whether `did` is part of the constant-time code of `core::select`.
+fn is_in_core_select(tcx: TyCtxt<'_>, did: LocalDefId) -> bool {
+    tcx.crate_name(LOCAL_CRATE) == sym::core
+        && tcx
+            .def_path(did.to_def_id())
+            .data
+            .first()
+            .is_some_and(|root| root.data == DefPathData::TypeNs(sym::select))
+}
+
 fn check_result(
     tcx: TyCtxt<'_>,
     did: LocalDefId,
@@ -565,6 +594,7 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
     let attrs = tcx.hir_attrs(tcx.local_def_id_to_hir_id(did));
 
     let interesting_spans = process_builtin_attrs(tcx, did, attrs, &mut codegen_fn_attrs);
+    codegen_fn_attrs.secret_params = secret_params(tcx, did);
     handle_lang_items(tcx, did, &interesting_spans, attrs, &mut codegen_fn_attrs);
     apply_overrides(tcx, did, &mut codegen_fn_attrs);
     check_result(tcx, did, interesting_spans, &codegen_fn_attrs);
@@ -572,6 +602,31 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: LocalDefId) -> CodegenFnAttrs {
     codegen_fn_attrs
 }
 
+/// Returns the indices of the parameters of `did` that hold secrets, for the verifiers of
+/// constant-time code that `-Zct-metadata` describes the functions to.
+fn secret_params(tcx: TyCtxt<'_>, did: LocalDefId) -> Vec<u32> {
+    // Closures take their parameters in a different shape than they are declared.
+    if !tcx.features().constant_time()
+        || !matches!(tcx.def_kind(did), DefKind::Fn | DefKind::AssocFn)
+    {
+        return Vec::new();
+    }
+    let Some(body) = tcx.hir_maybe_body_owned_by(did) else {
+        return Vec::new();
+    };
+    let inputs = tcx.fn_sig(did).instantiate_identity().skip_binder().inputs();
+    body.params
+        .iter()
+        .zip(inputs)
+        .enumerate()
+        .filter(|(_, (param, ty))| {
+            find_attr!(tcx.hir_attrs(param.hir_id), AttributeKind::Secret(..))
+                || ty.is_ct_secret(tcx)
+        })
+        .map(|(i, _)| i as u32)
+        .collect()
+}
+
 fn sanitizer_settings_for(tcx: TyCtxt<'_>, did: LocalDefId) -> SanitizerFnAttrs {
     // Backtrack to the crate root.
     let mut settings = match tcx.opt_local_parent(did) {
@@ -695,6 +695,15 @@ pub(crate) struct MultipleMainFunctions {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag("`ct_select_spec` is not supported on `{$target_triple}`")]
+#[note("the target has no speculation barrier")]
+pub(crate) struct CtSelectSpecUnsupported<'a> {
+    #[primary_span]
+    pub span: Span,
+    pub target_triple: &'a str,
+}
+
 #[derive(Diagnostic)]
 #[diag("could not evaluate shuffle_indices at compile time")]
 pub(crate) struct ShuffleIndicesEvaluation {
@@ -728,6 +737,22 @@ pub enum InvalidMonomorphization<'tcx> {
         ty: Ty<'tcx>,
     },
 
+    #[diag("invalid monomorphization of `{$name}` intrinsic: expected a type passed as one or two scalars or as a SIMD vector, found `{$ty}`", code = E0511)]
+    CtSelectType {
+        #[primary_span]
+        span: Span,
+        name: Symbol,
+        ty: Ty<'tcx>,
+    },
+
+    #[diag("invalid monomorphization of `{$name}` intrinsic: expected a type passed as one or two integers or pointers, found `{$ty}`", code = E0511)]
+    CtSelectSpecType {
+        #[primary_span]
+        span: Span,
+        name: Symbol,
+        ty: Ty<'tcx>,
+    },
+
     #[diag("invalid monomorphization of `float_to_int_unchecked` intrinsic: expected basic float type, found `{$ty}`", code = E0511)]
     FloatToIntUnchecked {
         #[primary_span]
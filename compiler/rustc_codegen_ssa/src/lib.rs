@@ -248,6 +248,17 @@ pub struct TargetConfig {
     pub has_reliable_f128: bool,
     /// Option for `cfg(target_has_reliable_f128_math)`, true if `f128` math calls work.
     pub has_reliable_f128_math: bool,
+    /// Option for `cfg(target_has_reliable_ct_select)`, true if the `ct_select` intrinsic is
+    /// lowered without branches or library calls.
+    pub has_reliable_ct_select: bool,
+    /// True if the `ct_select` intrinsic is lowered without branches or library calls in the
+    /// functions marked `#[ct_select_lowering(cmov)]`, which depends on the optimization level.
+    pub has_reliable_ct_select_cmov: bool,
+    /// The name of the lowering of the `ct_select` intrinsic, for `-Zdump-ct-lowering`.
+    pub ct_select_lowering: &'static str,
+    /// The instructions that the `ct_select` intrinsic is lowered to, for each register of
+    /// the selected values.
+    pub ct_select_instructions: &'static [&'static str],
 }
 
 #[derive(Encodable, Decodable)]
@@ -408,6 +408,14 @@ pub(crate) fn debug_introduce_local(&self, bx: &mut Bx, local: mir::Local) {
                     return;
                 }
 
+                // Under `-Zct-pin-secrets`, don't give the scalar values of `Secret` a stack slot
+                // of their own, which would keep a copy of the secret in memory.
+                if bx.sess().opts.unstable_opts.ct_pin_secrets
+                    && operand.layout.ty.is_ct_secret_scalar(bx.tcx())
+                {
+                    return;
+                }
+
                 // Don't spill `<vscale x N x i1>` for `N != 16`:
                 //
                 // SVE predicates are only one bit for each byte in an SVE vector (which makes
@@ -1,4 +1,4 @@
-use rustc_abi::WrappingRange;
+use rustc_abi::{BackendRepr, Primitive, Scalar, WrappingRange};
 use rustc_middle::mir::SourceInfo;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_middle::{bug, span_bug};
@@ -10,7 +10,7 @@
 use super::operand::OperandRef;
 use super::place::PlaceRef;
 use crate::common::{AtomicRmwBinOp, SynchronizationScope};
-use crate::errors::InvalidMonomorphization;
+use crate::errors::{self, InvalidMonomorphization};
 use crate::traits::*;
 use crate::{MemFlags, meth, size_of_val};
 
@@ -582,6 +582,59 @@ pub fn codegen_intrinsic_call(
                 return Ok(());
             }
 
+            sym::ct_select => {
+                let layout = args[1].layout;
+                match layout.backend_repr {
+                    _ if layout.is_zst() => {}
+                    // The backends legalize these into the values they can select between.
+                    BackendRepr::Scalar(_)
+                    | BackendRepr::ScalarPair(..)
+                    | BackendRepr::SimdVector { .. } => {
+                        return bx.codegen_intrinsic_call(instance, args, result, span);
+                    }
+                    _ => {
+                        bx.tcx().dcx().emit_err(InvalidMonomorphization::CtSelectType {
+                            span,
+                            name,
+                            ty: layout.ty,
+                        });
+                    }
+                }
+                return Ok(());
+            }
+
+            sym::ct_select_spec => {
+                let layout = args[1].layout;
+                // The barrier holds the selected value in registers, so it must be made of
+                // integers and pointers that each fit in one.
+                let pointer_size = bx.data_layout().pointer_size();
+                let fits_register = |scalar: Scalar| match scalar.primitive() {
+                    Primitive::Int(int, _) => int.size() <= pointer_size,
+                    Primitive::Pointer(_) => true,
+                    Primitive::Float(_) => false,
+                };
+                let supported = match layout.backend_repr {
+                    BackendRepr::Scalar(a) => fits_register(a),
+                    BackendRepr::ScalarPair(a, b) => fits_register(a) && fits_register(b),
+                    _ => false,
+                };
+                if !supported {
+                    bx.tcx().dcx().emit_err(InvalidMonomorphization::CtSelectSpecType {
+                        span,
+                        name,
+                        ty: layout.ty,
+                    });
+                } else if bx.sess().target.speculation_barrier().is_none() {
+                    bx.tcx().dcx().emit_err(errors::CtSelectSpecUnsupported {
+                        span,
+                        target_triple: bx.sess().opts.target_triple.tuple(),
+                    });
+                } else {
+                    return bx.codegen_intrinsic_call(instance, args, result, span);
+                }
+                return Ok(());
+            }
+
             _ => {
                 // Need to use backend-specific things in the implementation.
                 return bx.codegen_intrinsic_call(instance, args, result, span);
@@ -55,6 +55,10 @@ fn target_config(&self, _sess: &Session) -> TargetConfig {
             has_reliable_f16_math: true,
             has_reliable_f128: true,
             has_reliable_f128_math: true,
+            has_reliable_ct_select: true,
+            has_reliable_ct_select_cmov: true,
+            ct_select_lowering: "unknown",
+            ct_select_instructions: &[],
         }
     }
 
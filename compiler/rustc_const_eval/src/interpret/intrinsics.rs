@@ -556,10 +556,22 @@ pub fn eval_intrinsic(
                 self.exact_div(&val, &size, dest)?;
             }
 
-            sym::black_box => {
+            sym::black_box | sym::ct_black_box => {
                 // These just return their argument
                 self.copy_op(&args[0], dest)?;
             }
+            sym::ct_select
+            | sym::ct_select_bool
+            | sym::ct_select_i8
+            | sym::ct_select_i16
+            | sym::ct_select_i32
+            | sym::ct_select_i64
+            | sym::ct_select_ptr
+            | sym::ct_select_spec => {
+                // Timing is not observable here, so this is an ordinary selection.
+                let b = self.read_scalar(&args[0])?.to_bool()?;
+                self.copy_op(if b { &args[1] } else { &args[2] }, dest)?;
+            }
             sym::raw_eq => {
                 let result = self.raw_eq_intrinsic(&args[0], &args[1])?;
                 self.write_scalar(result, dest)?;
@@ -292,6 +292,12 @@ fn equal_float_min_max<F: Float>(_ecx: &InterpCx<'tcx, Self>, a: F, _b: F) -> F
     /// Determines whether the `fmuladd` intrinsics fuse the multiply-add or use separate operations.
     fn float_fuse_mul_add(_ecx: &InterpCx<'tcx, Self>) -> bool;
 
+    /// Called before a statement is executed.
+    #[inline]
+    fn before_statement(_ecx: &mut InterpCx<'tcx, Self>) -> InterpResult<'tcx> {
+        interp_ok(())
+    }
+
     /// Called before a basic block terminator is executed.
     #[inline]
     fn before_terminator(_ecx: &mut InterpCx<'tcx, Self>) -> InterpResult<'tcx> {
@@ -54,6 +54,7 @@ pub fn step(&mut self) -> InterpResult<'tcx, bool> {
 
         if let Some(stmt) = basic_block.statements.get(loc.statement_index) {
             let old_frames = self.frame_idx();
+            M::before_statement(self)?;
             self.eval_statement(stmt)?;
             // Make sure we are not updating `statement_index` of the wrong frame.
             assert_eq!(old_frames, self.frame_idx());
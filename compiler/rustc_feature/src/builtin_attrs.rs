@@ -42,6 +42,11 @@
     // this is consistent with naming of the compiler flag it's for
     (sym::fmt_debug, sym::fmt_debug, Features::fmt_debug),
     (sym::emscripten_wasm_eh, sym::cfg_emscripten_wasm_eh, Features::cfg_emscripten_wasm_eh),
+    (
+        sym::target_has_reliable_ct_select,
+        sym::cfg_target_has_reliable_ct_select,
+        Features::cfg_target_has_reliable_ct_select,
+    ),
     (
         sym::target_has_reliable_f16,
         sym::cfg_target_has_reliable_f16_f128,
@@ -910,6 +915,32 @@ pub struct BuiltinAttribute {
         EncodeCrossCrate::Yes, pin_ergonomics, experimental!(pin_v2),
     ),
 
+    // `#[constant_time]`, which rejects branching and indexing on secrets in a function body.
+    gated!(
+        constant_time, Normal, template!(Word, List: &["speculative_load_hardening"]),
+        WarnFollowing, EncodeCrossCrate::No, experimental!(constant_time)
+    ),
+    // `#[secret]`, which marks a function parameter as holding a secret for those checks.
+    gated!(
+        secret, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, constant_time, experimental!(secret)
+    ),
+    // `#[declassify = "..."]`, which exempts an expression from those checks, with a justification.
+    gated!(
+        declassify, Normal, template!(NameValueStr: "justification"), ErrorFollowing,
+        EncodeCrossCrate::No, constant_time, experimental!(declassify)
+    ),
+    // `#[branchless]`, which turns simple `if`/`else` on a `Choice` into selections.
+    gated!(
+        branchless, Normal, template!(Word), WarnFollowing,
+        EncodeCrossCrate::No, constant_time, experimental!(branchless)
+    ),
+    // `#[ct_select_lowering(..)]`, which overrides `-Zct-select-lowering` for a function.
+    gated!(
+        ct_select_lowering, Normal, template!(List: &["cmov", "masking", "asm"]), ErrorPreceding,
+        EncodeCrossCrate::No, constant_time, experimental!(ct_select_lowering)
+    ),
+
     // ==========================================================================
     // Internal attributes: Stability, deprecation, and unsafe:
     // ==========================================================================
@@ -1407,6 +1438,10 @@ pub struct BuiltinAttribute {
         rustc_intrinsic, Normal, template!(Word), ErrorFollowing, EncodeCrossCrate::Yes, intrinsics,
         "the `#[rustc_intrinsic]` attribute is used to declare intrinsics as function items",
     ),
+    rustc_attr!(
+        rustc_no_branch, Normal, template!(Word), WarnFollowing, EncodeCrossCrate::No,
+        "`#[rustc_no_branch]` forbids the optimizer from introducing conditional branches into a function"
+    ),
     rustc_attr!(
         rustc_no_mir_inline, Normal, template!(Word), WarnFollowing, EncodeCrossCrate::Yes,
         "`#[rustc_no_mir_inline]` prevents the MIR inliner from inlining a function while not affecting codegen"
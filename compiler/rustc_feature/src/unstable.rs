@@ -402,6 +402,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, cfg_target_has_atomic, "1.60.0", Some(94039)),
     /// Allows `cfg(target_has_atomic_equal_alignment = "...")`.
     (unstable, cfg_target_has_atomic_equal_alignment, "1.60.0", Some(93822)),
+    /// Allows `cfg(target_has_reliable_ct_select)`.
+    (unstable, cfg_target_has_reliable_ct_select, "CURRENT_RUSTC_VERSION", None),
     /// Allows `cfg(target_thread_local)`.
     (unstable, cfg_target_thread_local, "1.7.0", Some(29594)),
     /// Allows the use of `#[cfg(ub_checks)` to check if UB checks are enabled.
@@ -432,6 +434,9 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, const_trait_impl, "1.42.0", Some(143874)),
     /// Allows the `?` operator in const contexts.
     (unstable, const_try, "1.56.0", Some(74935)),
+    /// Allows `#[constant_time]` on functions, which forbids branching and indexing on secret
+    /// values in their bodies.
+    (unstable, constant_time, "CURRENT_RUSTC_VERSION", None),
     /// Allows use of contracts attributes.
     (incomplete, contracts, "1.86.0", Some(128044)),
     /// Allows access to internal machinery used to implement contracts.
@@ -160,6 +160,18 @@ pub fn do_not_optimize(&self) -> bool {
     }
 }
 
+/// The lowering of the `ct_select`s of a function, chosen with `#[ct_select_lowering(..)]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PrintAttribute)]
+#[derive(Encodable, Decodable, HashStable_Generic)]
+pub enum CtSelectLoweringAttr {
+    /// `#[ct_select_lowering(cmov)]`
+    Cmov,
+    /// `#[ct_select_lowering(masking)]`
+    Masking,
+    /// `#[ct_select_lowering(asm)]`
+    Asm,
+}
+
 #[derive(PartialEq, Debug, Encodable, Decodable, Copy, Clone, HashStable_Generic, PrintAttribute)]
 pub enum ReprAttr {
     ReprInt(IntType),
@@ -857,6 +869,9 @@ pub enum AttributeKind {
     /// Represents `#[automatically_derived]`
     AutomaticallyDerived(Span),
 
+    /// Represents `#[branchless]`.
+    Branchless(Span),
+
     /// Represents the trace attribute of `#[cfg_attr]`
     CfgAttrTrace,
 
@@ -878,6 +893,9 @@ pub enum AttributeKind {
     /// Represents `#[const_continue]`.
     ConstContinue(Span),
 
+    /// Represents `#[constant_time]` and `#[constant_time(speculative_load_hardening)]`.
+    ConstantTime { span: Span, speculative_load_hardening: bool },
+
     /// Represents `#[coroutine]`.
     Coroutine(Span),
 
@@ -890,12 +908,18 @@ pub enum AttributeKind {
     /// Represents `#![crate_type = ...]`
     CrateType(ThinVec<CrateType>),
 
+    /// Represents `#[ct_select_lowering(..)]`.
+    CtSelectLowering(CtSelectLoweringAttr, Span),
+
     /// Represents `#[custom_mir]`.
     CustomMir(Option<(MirDialect, Span)>, Option<(MirPhase, Span)>, Span),
 
     /// Represents `#[debugger_visualizer]`.
     DebuggerVisualizer(ThinVec<DebugVisualizer>),
 
+    /// Represents `#[declassify = "justification"]` on an expression.
+    Declassify { justification: Symbol, span: Span },
+
     /// Represents [`#[deprecated]`](https://doc.rust-lang.org/stable/reference/attributes/diagnostics.html#the-deprecated-attribute).
     Deprecation { deprecation: Deprecation, span: Span },
 
@@ -1246,6 +1270,9 @@ pub enum AttributeKind {
         diverging_block_default: Option<DivergingBlockBehavior>,
     },
 
+    /// Represents `#[rustc_no_branch]`
+    RustcNoBranch,
+
     /// Represents `#[rustc_no_implicit_autorefs]`
     RustcNoImplicitAutorefs,
 
@@ -1356,6 +1383,9 @@ pub enum AttributeKind {
         span: Span,
     },
 
+    /// Represents `#[secret]` on a function parameter.
+    Secret(Span),
+
     /// Represents `#[should_panic]`
     ShouldPanic { reason: Option<Symbol>, span: Span },
 
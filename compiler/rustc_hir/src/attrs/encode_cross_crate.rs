@@ -22,6 +22,7 @@ pub fn encode_cross_crate(&self) -> EncodeCrossCrate {
             AllowInternalUnsafe(..) => Yes,
             AllowInternalUnstable(..) => Yes,
             AutomaticallyDerived(..) => Yes,
+            Branchless(..) => No,
             CfgAttrTrace => Yes,
             CfgTrace(..) => Yes,
             CfiEncoding { .. } => Yes,
@@ -29,12 +30,15 @@ pub fn encode_cross_crate(&self) -> EncodeCrossCrate {
             CollapseDebugInfo(..) => Yes,
             CompilerBuiltins => No,
             ConstContinue(..) => No,
+            ConstantTime { .. } => No,
             Coroutine(..) => No,
             Coverage(..) => No,
             CrateName { .. } => No,
             CrateType(_) => No,
+            CtSelectLowering(..) => No,
             CustomMir(_, _, _) => Yes,
             DebuggerVisualizer(..) => No,
+            Declassify { .. } => No,
             Deprecation { .. } => Yes,
             DoNotRecommend { .. } => Yes,
             Doc(_) => Yes,
@@ -142,6 +146,7 @@ pub fn encode_cross_crate(&self) -> EncodeCrossCrate {
             RustcMustImplementOneOf { .. } => No,
             RustcNeverReturnsNullPointer => Yes,
             RustcNeverTypeOptions { .. } => No,
+            RustcNoBranch => No,
             RustcNoImplicitAutorefs => Yes,
             RustcNoImplicitBounds => No,
             RustcNoMirInline => Yes,
@@ -174,6 +179,7 @@ pub fn encode_cross_crate(&self) -> EncodeCrossCrate {
             RustcVariance => No,
             RustcVarianceOfOpaques => No,
             Sanitize { .. } => No,
+            Secret(..) => No,
             ShouldPanic { .. } => No,
             Stability { .. } => Yes,
             TargetFeature { .. } => No,
@@ -99,6 +99,17 @@ fn intrinsic_operation_unsafety(tcx: TyCtxt<'_>, intrinsic_id: LocalDefId) -> hi
         | sym::cosf32
         | sym::cosf64
         | sym::cosf128
+        | sym::ct_black_box
+        | sym::ct_classify
+        | sym::ct_declassify
+        | sym::ct_select
+        | sym::ct_select_bool
+        | sym::ct_select_i8
+        | sym::ct_select_i16
+        | sym::ct_select_i32
+        | sym::ct_select_i64
+        | sym::ct_select_ptr
+        | sym::ct_select_spec
         | sym::ctlz
         | sym::ctpop
         | sym::cttz
@@ -376,6 +387,9 @@ pub(crate) fn check_intrinsic_type(
             vec![Ty::new_mut_ptr(tcx, param(0)), tcx.types.u8, tcx.types.usize],
             tcx.types.unit,
         ),
+        sym::secure_zeroize => {
+            (0, 0, vec![Ty::new_mut_ptr(tcx, tcx.types.u8), tcx.types.usize], tcx.types.unit)
+        }
 
         sym::sqrtf16 => (0, 0, vec![tcx.types.f16], tcx.types.f16),
         sym::sqrtf32 => (0, 0, vec![tcx.types.f32], tcx.types.f32),
@@ -576,6 +590,28 @@ pub(crate) fn check_intrinsic_type(
 
         sym::assume => (0, 0, vec![tcx.types.bool], tcx.types.unit),
         sym::select_unpredictable => (1, 0, vec![tcx.types.bool, param(0), param(0)], param(0)),
+        sym::ct_select => (1, 0, vec![tcx.types.bool, param(0), param(0)], param(0)),
+        sym::ct_select_spec => (1, 0, vec![tcx.types.bool, param(0), param(0)], param(0)),
+        // The per-width variants are deprecated aliases of `ct_select`.
+        sym::ct_select_i8 => (0, 0, vec![tcx.types.bool, tcx.types.i8, tcx.types.i8], tcx.types.i8),
+        sym::ct_select_i16 => {
+            (0, 0, vec![tcx.types.bool, tcx.types.i16, tcx.types.i16], tcx.types.i16)
+        }
+        sym::ct_select_i32 => {
+            (0, 0, vec![tcx.types.bool, tcx.types.i32, tcx.types.i32], tcx.types.i32)
+        }
+        sym::ct_select_i64 => {
+            (0, 0, vec![tcx.types.bool, tcx.types.i64, tcx.types.i64], tcx.types.i64)
+        }
+        sym::ct_select_bool => {
+            (0, 0, vec![tcx.types.bool, tcx.types.bool, tcx.types.bool], tcx.types.bool)
+        }
+        sym::ct_select_ptr => (
+            1,
+            0,
+            vec![tcx.types.bool, Ty::new_imm_ptr(tcx, param(0)), Ty::new_imm_ptr(tcx, param(0))],
+            Ty::new_imm_ptr(tcx, param(0)),
+        ),
         sym::cold_path => (0, 0, vec![], tcx.types.unit),
 
         sym::read_via_copy => (1, 0, vec![Ty::new_imm_ptr(tcx, param(0))], param(0)),
@@ -657,6 +693,8 @@ pub(crate) fn check_intrinsic_type(
         }
 
         sym::black_box => (1, 0, vec![param(0)], param(0)),
+        sym::ct_black_box => (1, 0, vec![param(0)], param(0)),
+        sym::ct_classify | sym::ct_declassify => (1, 0, vec![param(0)], param(0)),
 
         sym::is_val_statically_known => (1, 0, vec![param(0)], tcx.types.bool),
 
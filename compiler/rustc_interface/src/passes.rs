@@ -1128,6 +1128,8 @@ fn run_required_analyses(tcx: TyCtxt<'_>) {
         });
     });
 
+    sess.time("check_secret_params", || tcx.ensure_ok().check_secret_params(()));
+
     sess.time("layout_testing", || layout_test::test_layout(tcx));
     sess.time("abi_testing", || abi_test::test_abi(tcx));
 }
@@ -1196,6 +1198,10 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) {
         let _ = tcx.all_diagnostic_items(());
     });
 
+    if tcx.sess.opts.unstable_opts.report_declassify {
+        rustc_passes::declassify::print_declassify_sites(tcx);
+    }
+
     // If `-Zvalidate-mir` is set, we also want to compute the final MIR for each item
     // (either its `mir_for_ctfe` or `optimized_mir`) since that helps uncover any bugs
     // in MIR optimizations that may only be reachable through codegen, or other codepaths
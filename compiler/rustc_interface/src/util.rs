@@ -49,6 +49,9 @@ pub(crate) fn add_configuration(
 
     sess.unstable_target_features.extend(tf_cfg.unstable_target_features.iter().copied());
     sess.target_features.extend(tf_cfg.target_features.iter().copied());
+    sess.has_reliable_ct_select = tf_cfg.has_reliable_ct_select;
+    sess.has_reliable_ct_select_cmov = tf_cfg.has_reliable_ct_select_cmov;
+    sess.ct_select_lowering = (tf_cfg.ct_select_lowering, tf_cfg.ct_select_instructions);
 
     cfg.extend(tf_cfg.target_features.into_iter().map(|feat| (tf, Some(feat))));
 
@@ -64,6 +67,9 @@ pub(crate) fn add_configuration(
     if tf_cfg.has_reliable_f128_math {
         cfg.insert((sym::target_has_reliable_f128_math, None));
     }
+    if tf_cfg.has_reliable_ct_select {
+        cfg.insert((sym::target_has_reliable_ct_select, None));
+    }
 
     if sess.crt_static(None) {
         cfg.insert((tf, Some(sym::crt_dash_static)));
@@ -389,6 +395,10 @@ fn target_config(&self, sess: &Session) -> TargetConfig {
             has_reliable_f16_math: true,
             has_reliable_f128: true,
             has_reliable_f128_math: true,
+            has_reliable_ct_select: true,
+            has_reliable_ct_select_cmov: true,
+            ct_select_lowering: "unknown",
+            ct_select_instructions: &[],
         }
     }
 
@@ -100,6 +100,7 @@
         RUST_2024_GUARDED_STRING_INCOMPATIBLE_SYNTAX,
         RUST_2024_INCOMPATIBLE_PAT,
         RUST_2024_PRELUDE_COLLISIONS,
+        SECRET_DEPENDENT_BRANCH,
         SELF_CONSTRUCTOR_FROM_OUTER_ITEM,
         SEMICOLON_IN_EXPRESSIONS_FROM_MACROS,
         SHADOWING_SUPERTRAIT_ITEMS,
@@ -127,6 +128,7 @@
         UNREACHABLE_CFG_SELECT_PREDICATES,
         UNREACHABLE_CODE,
         UNREACHABLE_PATTERNS,
+        UNRELIABLE_CT_SELECT,
         UNSAFE_ATTR_OUTSIDE_UNSAFE,
         UNSAFE_OP_IN_UNSAFE_FN,
         UNSTABLE_NAME_COLLISIONS,
@@ -5556,3 +5558,83 @@
         report_in_deps: false,
     };
 }
+
+declare_lint! {
+    /// The `secret_dependent_branch` lint detects branches on values derived from the
+    /// constant-time types of `core::select`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// #![feature(constant_time, ct_select)]
+    /// #![warn(secret_dependent_branch)]
+    /// use std::select::Secret;
+    ///
+    /// pub fn is_extreme(key: &Secret<u32>) -> bool {
+    ///     *key.expose() == 0 || *key.expose() == u32::MAX
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Values of type `Choice`, `Secret`, `CtOption` or `CtResult`, and anything computed from
+    /// them, are meant to stay secret. An `if`, a `match`, a `&&`, a `||` or a `?` on such a
+    /// value takes a different path depending on the secret, and the time it takes can be
+    /// measured. Use `ct_select` to compute both outcomes and pick one without branching, or, if
+    /// the value is meant to be made public, make that explicit with `Choice::declassify`,
+    /// `CtOption::into_option` or `CtResult::into_result`. A branch that is meant to depend on
+    /// the secret can be marked with `#[declassify = "justification"]` instead.
+    ///
+    /// Functions marked `#[constant_time]` reject such branches with an error instead.
+    pub SECRET_DEPENDENT_BRANCH,
+    Allow,
+    "branches on a value derived from a constant-time secret",
+    @feature_gate = constant_time;
+}
+
+declare_lint! {
+    /// The `unreliable_ct_select` lint detects constant-time selections instantiated for a
+    /// target on which the backend cannot lower them without branching.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs a target without a conditional select, such as m68k)
+    /// #![feature(ct_select)]
+    /// use std::select::{Choice, ct_select};
+    ///
+    /// pub fn pick(c: Choice, a: u32, b: u32) -> u32 {
+    ///     ct_select(c, a, b)
+    /// }
+    /// ```
+    ///
+    /// produces, when built for `m68k-unknown-linux-gnu`:
+    ///
+    /// ```text
+    /// warning: this selection of `i32` may be compiled to a branch on target `m68k-unknown-linux-gnu`
+    ///  --> src/lib.rs:5:5
+    ///   |
+    /// 5 |     ct_select(c, a, b)
+    ///   |     ^^^^^^^^^^^^^^^^^^ not guaranteed to be branch-free
+    ///   |
+    ///   = note: the backend cannot lower constant-time selections on this target without branching, so the condition may leak through timing
+    ///   = note: `#[warn(unreliable_ct_select)]` on by default
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// `ct_select` and the types built on it promise to pick a value without branching on the
+    /// condition. Most targets have a conditional move or select instruction, or the backend
+    /// falls back to masking, but on some, such as m68k, BPF or WebAssembly, the selection is
+    /// emitted as a plain `select` that may still be compiled to a branch, by the backend or by
+    /// the engine running the code. This lint is reported after monomorphization, for every
+    /// selection that is actually compiled, so that code meant to run in constant time does not
+    /// silently lose that property when built for such a target.
+    ///
+    /// Deny this lint to make such builds fail.
+    pub UNRELIABLE_CT_SELECT,
+    Warn,
+    "constant-time selections that may be compiled to branches on the current target",
+    @feature_gate = ct_select;
+}
@@ -5,6 +5,8 @@
 #include "llvm/ADT/DenseSet.h"
 #include "llvm/ADT/SmallVector.h"
 #include "llvm/Analysis/Lint.h"
+#include "llvm/Analysis/LoopAccessAnalysis.h"
+#include "llvm/Analysis/LoopInfo.h"
 #include "llvm/Analysis/TargetLibraryInfo.h"
 #if LLVM_VERSION_GE(22, 0)
 #include "llvm/Analysis/RuntimeLibcallInfo.h"
@@ -12,8 +14,14 @@
 #include "llvm/Bitcode/BitcodeWriter.h"
 #include "llvm/Bitcode/BitcodeWriterPass.h"
 #include "llvm/CodeGen/CommandFlags.h"
+#include "llvm/Demangle/Demangle.h"
 #include "llvm/IR/AssemblyAnnotationWriter.h"
 #include "llvm/IR/AutoUpgrade.h"
+#include "llvm/IR/DebugInfoMetadata.h"
+#include "llvm/IR/EHPersonalities.h"
+#include "llvm/IR/IRBuilder.h"
+#include "llvm/IR/InstIterator.h"
+#include "llvm/IR/IntrinsicInst.h"
 #include "llvm/IR/LegacyPassManager.h"
 #include "llvm/IR/PassManager.h"
 #include "llvm/IR/Verifier.h"
@@ -50,6 +58,7 @@
 #include "llvm/Transforms/Scalar/AnnotationRemarks.h"
 #include "llvm/Transforms/Utils/CanonicalizeAliases.h"
 #include "llvm/Transforms/Utils/FunctionImportUtils.h"
+#include "llvm/Transforms/Utils/LoopUtils.h"
 #include "llvm/Transforms/Utils/NameAnonGlobals.h"
 #include <set>
 #include <string>
@@ -540,10 +549,426 @@ enum class LLVMRustOptStage {
   FatLTO,
 };
 
+namespace {
+
+// The constant-time sanitizer (`-Zsanitizer=constant-time`).
+//
+// `ct_classify` labels secrets with DataFlowSanitizer, which then propagates
+// the label to every value computed from them. This pass runs just before
+// DataFlowSanitizer, and checks the label of the condition of every branch and
+// of the address of every memory access: the program is aborted with a report
+// as soon as a secret reaches one of them.
+class ConstantTimeSanitizer {
+public:
+  explicit ConstantTimeSanitizer(Module &M)
+      : M(M), C(M.getContext()), Int8Ty(Type::getInt8Ty(C)),
+        Int32Ty(Type::getInt32Ty(C)), Int64Ty(Type::getInt64Ty(C)),
+        PtrTy(PointerType::getUnqual(C)) {}
+
+  bool run() {
+    bool Changed = false;
+    for (Function &F : M) {
+      if (F.isDeclaration() || F.getName() == CheckName)
+        continue;
+      Changed |= instrumentFunction(F);
+    }
+    return Changed;
+  }
+
+private:
+  static constexpr StringLiteral CheckName = "__rust_ctsan_check";
+
+  Module &M;
+  LLVMContext &C;
+  Type *Int8Ty;
+  Type *Int32Ty;
+  Type *Int64Ty;
+  Type *PtrTy;
+  Function *Check = nullptr;
+
+  // Returns `__rust_ctsan_check(value, report, report_len)`, which aborts with
+  // the report if `value` is labeled. It is instrumented by DataFlowSanitizer
+  // like the code that calls it, so that `dfsan_get_label` receives the label
+  // of its argument.
+  Function *getCheck() {
+    if (Check)
+      return Check;
+    auto *CheckTy = FunctionType::get(Type::getVoidTy(C),
+                                      {Int64Ty, PtrTy, Int64Ty}, false);
+    Check = Function::Create(CheckTy, GlobalValue::LinkOnceODRLinkage,
+                             CheckName, M);
+    Check->setVisibility(GlobalValue::HiddenVisibility);
+    Check->addFnAttr(Attribute::NoInline);
+    Check->addFnAttr(Attribute::Cold);
+
+    auto *Entry = BasicBlock::Create(C, "entry", Check);
+    auto *Report = BasicBlock::Create(C, "report", Check);
+    auto *Ok = BasicBlock::Create(C, "ok", Check);
+    IRBuilder<> B(Entry);
+    FunctionCallee GetLabel = M.getOrInsertFunction(
+        "dfsan_get_label", FunctionType::get(Int8Ty, {Int64Ty}, false));
+    Value *Label = B.CreateCall(GetLabel, {Check->getArg(0)});
+    B.CreateCondBr(B.CreateIsNotNull(Label), Report, Ok);
+
+    B.SetInsertPoint(Report);
+    FunctionCallee Write = M.getOrInsertFunction(
+        "write", FunctionType::get(Int64Ty, {Int32Ty, PtrTy, Int64Ty}, false));
+    B.CreateCall(Write, {ConstantInt::get(Int32Ty, 2), Check->getArg(1),
+                         Check->getArg(2)});
+    FunctionCallee PrintStack = M.getOrInsertFunction(
+        "__sanitizer_print_stack_trace",
+        FunctionType::get(Type::getVoidTy(C), false));
+    B.CreateCall(PrintStack);
+    B.CreateIntrinsic(Intrinsic::trap, {}, {});
+    B.CreateUnreachable();
+
+    B.SetInsertPoint(Ok);
+    B.CreateRetVoid();
+    return Check;
+  }
+
+  void insertCheck(Instruction *I, Value *V, StringRef What) {
+    std::string Report;
+    raw_string_ostream OS(Report);
+    OS << "==ERROR: ConstantTimeSanitizer: " << What
+       << " depends on a secret in `" << demangle(I->getFunction()->getName())
+       << "`";
+    if (const DebugLoc &Loc = I->getDebugLoc())
+      OS << " at " << Loc->getFilename() << ":" << Loc.getLine() << ":"
+         << Loc.getCol();
+    OS << "\n";
+
+    IRBuilder<> B(I);
+    Value *Int = V->getType()->isPointerTy() ? B.CreatePtrToInt(V, Int64Ty)
+                                             : B.CreateZExtOrTrunc(V, Int64Ty);
+    Value *Message = B.CreateGlobalString(Report, "ctsan.report");
+    B.CreateCall(getCheck(), {Int, Message,
+                              ConstantInt::get(Int64Ty, Report.size())});
+  }
+
+  // The address of a stack slot or a global is never secret.
+  static bool isConstantAddress(Value *Ptr) {
+    Value *Base = Ptr->stripPointerCasts();
+    return isa<AllocaInst>(Base) || isa<GlobalValue>(Base);
+  }
+
+  void checkAddress(Instruction *I, Value *Ptr) {
+    if (!isConstantAddress(Ptr))
+      insertCheck(I, Ptr, "the address of a memory access");
+  }
+
+  bool instrumentFunction(Function &F) {
+    // The checks are inserted once all the instructions have been collected,
+    // so that they are not instrumented themselves.
+    SmallVector<Instruction *, 32> Sites;
+    for (Instruction &I : instructions(F)) {
+      if (auto *Br = dyn_cast<BranchInst>(&I)) {
+        if (Br->isConditional())
+          Sites.push_back(&I);
+      } else if (isa<SwitchInst>(I) || isa<IndirectBrInst>(I) ||
+                 isa<LoadInst>(I) || isa<StoreInst>(I) ||
+                 isa<AtomicRMWInst>(I) || isa<AtomicCmpXchgInst>(I) ||
+                 isa<MemIntrinsic>(I)) {
+        Sites.push_back(&I);
+      } else if (auto *CB = dyn_cast<CallBase>(&I)) {
+        if (CB->isIndirectCall())
+          Sites.push_back(&I);
+      }
+    }
+
+    for (Instruction *I : Sites) {
+      if (auto *Br = dyn_cast<BranchInst>(I)) {
+        insertCheck(I, Br->getCondition(), "a branch");
+      } else if (auto *Switch = dyn_cast<SwitchInst>(I)) {
+        insertCheck(I, Switch->getCondition(), "a branch");
+      } else if (auto *IndirectBr = dyn_cast<IndirectBrInst>(I)) {
+        insertCheck(I, IndirectBr->getAddress(), "a branch");
+      } else if (auto *Load = dyn_cast<LoadInst>(I)) {
+        checkAddress(I, Load->getPointerOperand());
+      } else if (auto *Store = dyn_cast<StoreInst>(I)) {
+        checkAddress(I, Store->getPointerOperand());
+      } else if (auto *RMW = dyn_cast<AtomicRMWInst>(I)) {
+        checkAddress(I, RMW->getPointerOperand());
+      } else if (auto *CmpXchg = dyn_cast<AtomicCmpXchgInst>(I)) {
+        checkAddress(I, CmpXchg->getPointerOperand());
+      } else if (auto *Mem = dyn_cast<MemIntrinsic>(I)) {
+        checkAddress(I, Mem->getRawDest());
+        if (auto *Transfer = dyn_cast<MemTransferInst>(Mem))
+          checkAddress(I, Transfer->getRawSource());
+        // The time taken by a copy depends on its length.
+        insertCheck(I, Mem->getLength(), "the length of a memory access");
+      } else {
+        insertCheck(I, cast<CallBase>(I)->getCalledOperand(),
+                    "an indirect call");
+      }
+    }
+    return !Sites.empty();
+  }
+};
+
+class ConstantTimeSanitizerPass
+    : public PassInfoMixin<ConstantTimeSanitizerPass> {
+public:
+  PreservedAnalyses run(Module &M, ModuleAnalysisManager &) {
+    return ConstantTimeSanitizer(M).run() ? PreservedAnalyses::none()
+                                          : PreservedAnalyses::all();
+  }
+};
+
+// `#[rustc_no_branch]` functions carry this attribute: the optimizer must not
+// introduce conditional branches into them.
+constexpr StringLiteral NoBranchAttr = "rust-no-branch";
+
+// The functions that compute conditions from secrets carry this attribute:
+// the optimizer must not turn these conditions into branches, which it cannot
+// tell apart from the others.
+constexpr StringLiteral SecretConditionsAttr = "rust-secret-conditions";
+
+// The passes that turn selects into branches, or duplicate code on a
+// condition, and are skipped for the functions that must not branch, and for
+// those with secret conditions.
+constexpr StringLiteral BranchingPasses[] = {
+    "JumpThreadingPass",
+    "DFAJumpThreadingPass",
+    "SimpleLoopUnswitchPass",
+};
+
+bool mustNotBranch(const Function &F) {
+  return F.hasFnAttribute(NoBranchAttr) ||
+         F.hasFnAttribute(SecretConditionsAttr);
+}
+
+bool mayIntroduceBranches(Any IR) {
+  const Function *F = nullptr;
+  if (const auto *Cast = any_cast<const Function *>(&IR))
+    F = *Cast;
+  else if (const auto *Cast = any_cast<const Loop *>(&IR))
+    F = (*Cast)->getHeader()->getParent();
+  return !F || !mustNotBranch(*F);
+}
+
+// Keeps the loop vectorizer from vectorizing the loops of the functions that
+// must not branch when it would guard the vector loop with runtime checks,
+// which depend on the addresses that the loop accesses or on the values of its
+// strides, or when the loop has several exits, which the vector loop would
+// take depending on the data it loads. The other loops are vectorized as
+// usual, and their vector loops are only guarded by their trip count, like the
+// loops they come from.
+class CtLoopVectorizePass : public PassInfoMixin<CtLoopVectorizePass> {
+public:
+  PreservedAnalyses run(Function &F, FunctionAnalysisManager &FAM) {
+    if (!mustNotBranch(F))
+      return PreservedAnalyses::all();
+    auto &LI = FAM.getResult<LoopAnalysis>(F);
+    auto &LAIs = FAM.getResult<LoopAccessAnalysis>(F);
+    for (Loop *L : LI.getLoopsInPreorder()) {
+      // Only innermost loops are vectorized.
+      if (!L->isInnermost())
+        continue;
+      if (L->getExitingBlock() && !needsRuntimeChecks(LAIs.getInfo(*L)))
+        continue;
+      addStringMetadataToLoop(L, "llvm.loop.vectorize.enable", 0);
+    }
+    // Only metadata changed, which no analysis depends on.
+    return PreservedAnalyses::all();
+  }
+
+private:
+  static bool needsRuntimeChecks(const LoopAccessInfo &LAI) {
+    const RuntimePointerChecking *Checks = LAI.getRuntimePointerChecking();
+    return (Checks && Checks->Need) ||
+           !LAI.getPSE().getPredicate().isAlwaysTrue();
+  }
+};
+
+// Marks the selects of the functions that must not branch as unpredictable,
+// so that neither CodeGenPrepare, SelectOptimize nor the conversion of
+// conditional moves turn them into branches during code generation.
+class NoBranchPass : public PassInfoMixin<NoBranchPass> {
+public:
+  PreservedAnalyses run(Function &F, FunctionAnalysisManager &) {
+    if (!F.hasFnAttribute(NoBranchAttr))
+      return PreservedAnalyses::all();
+    MDNode *Unpredictable = MDNode::get(F.getContext(), {});
+    for (Instruction &I : instructions(F))
+      if (isa<SelectInst>(I))
+        I.setMetadata(LLVMContext::MD_unpredictable, Unpredictable);
+    // Only metadata changed, which no analysis depends on.
+    return PreservedAnalyses::all();
+  }
+
+  static bool isRequired() { return true; }
+};
+
+// `-Zct-scrub-stack` marks the `#[constant_time]` functions with this
+// attribute: their stack frame is zeroed when they return, so that the secrets
+// they spilled do not outlive them.
+constexpr StringLiteral ScrubStackAttr = "rust-scrub-stack";
+
+// A function cannot zero its own frame, which is in use until it returns. The
+// body of each function to scrub is moved to an internal function, which also
+// reports its stack pointer once its frame is allocated, and the function is
+// left calling it and zeroing the stack between the two stack pointers with
+// volatile stores, also when the body unwinds. The frames of the functions the
+// body calls are not zeroed, unless they are scrubbed themselves.
+class StackScrubber {
+public:
+  explicit StackScrubber(Module &M)
+      : M(M), C(M.getContext()),
+        PtrTy(PointerType::get(C, M.getDataLayout().getAllocaAddrSpace())),
+        IntPtrTy(M.getDataLayout().getIntPtrType(PtrTy)) {}
+
+  bool run() {
+    SmallVector<Function *, 8> Functions;
+    for (Function &F : M)
+      if (!F.isDeclaration() && F.hasFnAttribute(ScrubStackAttr))
+        Functions.push_back(&F);
+    for (Function *F : Functions) {
+      F->removeFnAttr(ScrubStackAttr);
+      if (canScrub(*F))
+        scrub(*F);
+    }
+    return !Functions.empty();
+  }
+
+private:
+  Module &M;
+  LLVMContext &C;
+  PointerType *PtrTy;
+  Type *IntPtrTy;
+
+  // The arguments of the function must be forwarded to its body as they are,
+  // and the tail calls of its body must keep its signature.
+  static bool canScrub(Function &F) {
+    if (F.isVarArg())
+      return false;
+    for (Argument &A : F.args())
+      if (A.hasInAllocaAttr() || A.hasPreallocatedAttr())
+        return false;
+    for (Instruction &I : instructions(F))
+      if (auto *Call = dyn_cast<CallInst>(&I); Call && Call->isMustTailCall())
+        return false;
+    return true;
+  }
+
+  // Zeroes the stack from `Bottom` up to `Top`, both aligned to a word.
+  void emitScrub(IRBuilder<> &B, Value *Bottom, Value *Top) {
+    Function *F = B.GetInsertBlock()->getParent();
+    BasicBlock *Pre = B.GetInsertBlock();
+    BasicBlock *Loop = BasicBlock::Create(C, "scrub", F);
+    BasicBlock *Done = BasicBlock::Create(C, "scrubbed", F);
+    B.CreateCondBr(B.CreateICmpULT(Bottom, Top), Loop, Done);
+
+    B.SetInsertPoint(Loop);
+    PHINode *Word = B.CreatePHI(PtrTy, 2);
+    Word->addIncoming(Bottom, Pre);
+    unsigned WordSize = M.getDataLayout().getTypeStoreSize(IntPtrTy);
+    B.CreateAlignedStore(ConstantInt::get(IntPtrTy, 0), Word, Align(WordSize),
+                         /*isVolatile=*/true);
+    Value *Next = B.CreateConstGEP1_64(B.getInt8Ty(), Word, WordSize);
+    Word->addIncoming(Next, Loop);
+    B.CreateCondBr(B.CreateICmpULT(Next, Top), Loop, Done);
+
+    B.SetInsertPoint(Done);
+  }
+
+  void scrub(Function &F) {
+    SmallVector<Type *, 8> Params(F.getFunctionType()->params());
+    Params.push_back(PtrTy);
+    auto *BodyTy = FunctionType::get(F.getReturnType(), Params, false);
+    Function *Body =
+        Function::Create(BodyTy, GlobalValue::InternalLinkage,
+                         F.getAddressSpace(), F.getName() + ".ct_scrubbed", &M);
+    Body->copyAttributesFrom(&F);
+    Body->setLinkage(GlobalValue::InternalLinkage);
+    Body->setVisibility(GlobalValue::DefaultVisibility);
+    Body->setDLLStorageClass(GlobalValue::DefaultStorageClass);
+    Body->removeFnAttr(Attribute::AlwaysInline);
+    Body->removeFnAttr(Attribute::InlineHint);
+    Body->addFnAttr(Attribute::NoInline);
+    // A leaf function could keep its frame below its stack pointer.
+    Body->addFnAttr(Attribute::NoRedZone);
+    // The body writes its stack pointer through its last argument, and the
+    // function zeroes memory that is not its own.
+    Body->removeFnAttr(Attribute::Memory);
+    F.removeFnAttr(Attribute::Memory);
+    F.addFnAttr(Attribute::NoRedZone);
+
+    Body->splice(Body->begin(), &F);
+    for (auto [Old, New] : zip(F.args(), Body->args())) {
+      New.setName(Old.getName());
+      Old.replaceAllUsesWith(&New);
+    }
+    Argument *SPArg = Body->getArg(Body->arg_size() - 1);
+    SPArg->setName("body_sp");
+    Body->setSubprogram(F.getSubprogram());
+    F.setSubprogram(nullptr);
+
+    // The static allocas are part of the frame, whatever their position in
+    // the entry block.
+    IRBuilder<> B(&*Body->getEntryBlock().getFirstInsertionPt());
+    B.CreateStore(B.CreateStackSave("sp"), SPArg);
+
+    B.SetInsertPoint(BasicBlock::Create(C, "start", &F));
+    AllocaInst *BodySP = B.CreateAlloca(PtrTy, nullptr, "body_sp");
+    Value *Top = B.CreateStackSave();
+    SmallVector<Value *, 8> Args(make_pointer_range(F.args()));
+    Args.push_back(BodySP);
+    SmallVector<AttributeSet, 8> ArgAttrs;
+    for (unsigned I = 0; I < F.arg_size(); ++I)
+      ArgAttrs.push_back(F.getAttributes().getParamAttrs(I));
+    ArgAttrs.push_back(AttributeSet());
+    AttributeList CallAttrs = AttributeList::get(
+        C, AttributeSet(), F.getAttributes().getRetAttrs(), ArgAttrs);
+
+    CallBase *Call;
+    bool Unwinds = !F.doesNotThrow() && F.hasPersonalityFn() &&
+                   !isScopedEHPersonality(
+                       classifyEHPersonality(F.getPersonalityFn()));
+    if (Unwinds) {
+      BasicBlock *Returned = BasicBlock::Create(C, "returned", &F);
+      BasicBlock *Unwound = BasicBlock::Create(C, "unwound", &F);
+      Call = B.CreateInvoke(Body, Returned, Unwound, Args);
+
+      B.SetInsertPoint(Unwound);
+      LandingPadInst *Pad = B.CreateLandingPad(
+          StructType::get(PointerType::getUnqual(C), B.getInt32Ty()), 0);
+      Pad->setCleanup(true);
+      emitScrub(B, B.CreateLoad(PtrTy, BodySP), Top);
+      B.CreateResume(Pad);
+
+      B.SetInsertPoint(Returned);
+    } else {
+      Call = B.CreateCall(Body, Args);
+    }
+    Call->setCallingConv(F.getCallingConv());
+    Call->setAttributes(CallAttrs);
+
+    emitScrub(B, B.CreateLoad(PtrTy, BodySP), Top);
+    if (F.getReturnType()->isVoidTy())
+      B.CreateRetVoid();
+    else
+      B.CreateRet(Call);
+  }
+};
+
+class StackScrubPass : public PassInfoMixin<StackScrubPass> {
+public:
+  PreservedAnalyses run(Module &M, ModuleAnalysisManager &) {
+    return StackScrubber(M).run() ? PreservedAnalyses::none()
+                                  : PreservedAnalyses::all();
+  }
+
+  static bool isRequired() { return true; }
+};
+
+} // namespace
+
 struct LLVMRustSanitizerOptions {
   bool SanitizeAddress;
   bool SanitizeAddressRecover;
   bool SanitizeCFI;
+  bool SanitizeConstantTime;
   bool SanitizeDataFlow;
   char **SanitizeDataFlowABIList;
   size_t SanitizeDataFlowABIListLen;
@@ -648,6 +1073,14 @@ extern "C" LLVMRustResult LLVMRustOptimize(
                               /*DebugLogging=*/false);
   SI.registerCallbacks(PIC, &MAM);
 
+  PIC.registerShouldRunOptionalPassCallback([](StringRef Pass, Any IR) {
+    return !is_contained(BranchingPasses, Pass) || mayIntroduceBranches(IR);
+  });
+  PB.registerVectorizerStartEPCallback(
+      [](FunctionPassManager &FPM, OptimizationLevel Level) {
+        FPM.addPass(CtLoopVectorizePass());
+      });
+
   if (LLVMPluginsLen) {
     auto PluginsStr = StringRef(LLVMPlugins, LLVMPluginsLen);
     SmallVector<StringRef> Plugins;
@@ -731,14 +1164,20 @@ extern "C" LLVMRustResult LLVMRustOptimize(
   }
 
   if (SanitizerOptions) {
-    if (SanitizerOptions->SanitizeDataFlow) {
+    if (SanitizerOptions->SanitizeDataFlow ||
+        SanitizerOptions->SanitizeConstantTime) {
       std::vector<std::string> ABIListFiles(
           SanitizerOptions->SanitizeDataFlowABIList,
           SanitizerOptions->SanitizeDataFlowABIList +
               SanitizerOptions->SanitizeDataFlowABIListLen);
+      bool ConstantTime = SanitizerOptions->SanitizeConstantTime;
       OptimizerLastEPCallbacks.push_back(
-          [ABIListFiles](ModulePassManager &MPM, OptimizationLevel Level,
-                         ThinOrFullLTOPhase phase) {
+          [ABIListFiles, ConstantTime](ModulePassManager &MPM,
+                                       OptimizationLevel Level,
+                                       ThinOrFullLTOPhase phase) {
+            // The checks must be instrumented by DataFlowSanitizer.
+            if (ConstantTime)
+              MPM.addPass(ConstantTimeSanitizerPass());
             MPM.addPass(DataFlowSanitizerPass(ABIListFiles));
           });
     }
@@ -807,6 +1246,14 @@ extern "C" LLVMRustResult LLVMRustOptimize(
     }
   }
 
+  // Last, so that the selects formed by the whole pipeline are marked.
+  OptimizerLastEPCallbacks.push_back([](ModulePassManager &MPM,
+                                        OptimizationLevel Level,
+                                        ThinOrFullLTOPhase phase) {
+    MPM.addPass(createModuleToFunctionPassAdaptor(NoBranchPass()));
+    MPM.addPass(StackScrubPass());
+  });
+
   ModulePassManager MPM;
   bool NeedThinLTOBufferPasses = EmitThinLTO;
   auto ThinLTOBuffer = std::make_unique<LLVMRustThinLTOBuffer>();
@@ -1185,6 +1185,10 @@ fn get_debugger_visualizers(self, tcx: TyCtxt<'_>) -> Vec<DebuggerVisualizerFile
         self.root.debugger_visualizers.decode((self, tcx)).collect::<Vec<_>>()
     }
 
+    fn get_declassify_sites(self, tcx: TyCtxt<'_>) -> impl Iterator<Item = DeclassifySite> {
+        self.root.declassify_sites.decode((self, tcx))
+    }
+
     /// Iterates over all the stability attributes in the given crate.
     fn get_lib_features(self, tcx: TyCtxt<'_>) -> LibFeatures {
         LibFeatures {
@@ -402,6 +402,7 @@ fn into_args(self) -> (DefId, SimplifiedType) {
 
     used_crate_source => { Arc::clone(&cdata.source) }
     debugger_visualizers => { cdata.get_debugger_visualizers(tcx) }
+    declassify_sites => { tcx.arena.alloc_from_iter(cdata.get_declassify_sites(tcx)) }
 
     exportable_items => { tcx.arena.alloc_from_iter(cdata.get_exportable_items(tcx)) }
     stable_order_of_exportable_impls => { tcx.arena.alloc(cdata.get_stable_order_of_exportable_impls(tcx).collect()) }
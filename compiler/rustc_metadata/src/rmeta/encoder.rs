@@ -687,6 +687,8 @@ macro_rules! stat {
         let debugger_visualizers =
             stat!("debugger-visualizers", || self.encode_debugger_visualizers());
 
+        let declassify_sites = stat!("declassify-sites", || self.encode_declassify_sites());
+
         let exportable_items = stat!("exportable-items", || self.encode_exportable_items());
 
         let stable_order_of_exportable_impls =
@@ -741,6 +743,7 @@ macro_rules! stat {
                 externally_implementable_items,
                 proc_macro_data,
                 debugger_visualizers,
+                declassify_sites,
                 compiler_builtins: find_attr!(attrs, AttributeKind::CompilerBuiltins),
                 needs_allocator: find_attr!(attrs, AttributeKind::NeedsAllocator),
                 needs_panic_runtime: find_attr!(attrs, AttributeKind::NeedsPanicRuntime),
@@ -2063,6 +2066,11 @@ fn encode_debugger_visualizers(&mut self) -> LazyArray<DebuggerVisualizerFile> {
         )
     }
 
+    fn encode_declassify_sites(&mut self) -> LazyArray<DeclassifySite> {
+        empty_proc_macro!(self);
+        self.lazy_array(self.tcx.declassify_sites(LOCAL_CRATE))
+    }
+
     fn encode_crate_deps(&mut self) -> LazyArray<CrateDep> {
         empty_proc_macro!(self);
 
@@ -1,7 +1,9 @@
 use std::borrow::Cow;
 
 use rustc_abi::Align;
-use rustc_hir::attrs::{InlineAttr, InstructionSetAttr, Linkage, OptimizeAttr, RtsanSetting};
+use rustc_hir::attrs::{
+    CtSelectLoweringAttr, InlineAttr, InstructionSetAttr, Linkage, OptimizeAttr, RtsanSetting,
+};
 use rustc_hir::def_id::DefId;
 use rustc_macros::{HashStable, TyDecodable, TyEncodable};
 use rustc_span::Symbol;
@@ -109,6 +111,12 @@ pub struct CodegenFnAttrs {
     pub objc_class: Option<Symbol>,
     /// The `#[rustc_objc_selector = "..."]` attribute.
     pub objc_selector: Option<Symbol>,
+    /// The indices of the parameters that hold secrets: those marked `#[secret]`, and those whose
+    /// type is secret by construction, such as `Choice`.
+    pub secret_params: Vec<u32>,
+    /// The `#[ct_select_lowering(..)]` attribute, which overrides `-Zct-select-lowering` for the
+    /// `ct_select`s of the function.
+    pub ct_select_lowering: Option<CtSelectLoweringAttr>,
 }
 
 #[derive(Copy, Clone, Debug, TyEncodable, TyDecodable, HashStable, PartialEq, Eq)]
@@ -210,6 +218,18 @@ impl CodegenFnAttrFlags: u32 {
         /// As such, we must make sure these symbols really do exist in the final binary/library.
         /// This flag is put on both the implementations of EIIs and the foreign item they implement.
         const EXTERNALLY_IMPLEMENTABLE_ITEM = 1 << 18;
+        /// `#[constant_time]`: the function must not branch or index on secret values, and is
+        /// compiled so as not to introduce such branches or indexing itself.
+        const CONSTANT_TIME = 1 << 19;
+        /// `#[constant_time(speculative_load_hardening)]`: loads in the function are hardened
+        /// against speculative execution.
+        const SPECULATIVE_LOAD_HARDENING = 1 << 20;
+        /// `#[rustc_no_branch]`: the optimizer must not introduce conditional branches into the
+        /// function.
+        const NO_BRANCH = 1 << 21;
+        /// The comparisons of the function must not be turned into calls to `memcmp` or `bcmp`.
+        /// Set for the `#[constant_time]` functions and those of `core::select`.
+        const NO_BUILTIN_CMP = 1 << 22;
     }
 }
 rustc_data_structures::external_bitflags_debug! { CodegenFnAttrFlags }
@@ -236,6 +256,8 @@ pub const fn new() -> CodegenFnAttrs {
             patchable_function_entry: None,
             objc_class: None,
             objc_selector: None,
+            secret_params: vec![],
+            ct_select_lowering: None,
         }
     }
 
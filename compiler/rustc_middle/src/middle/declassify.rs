@@ -0,0 +1,22 @@
+use rustc_hir::def_id::DefId;
+use rustc_macros::{Decodable, Encodable, HashStable};
+use rustc_span::{Span, Symbol};
+
+/// A place where a secret may become public, as listed by `-Zreport-declassify`.
+#[derive(HashStable)]
+#[derive(Copy, Clone, Debug, Encodable, Decodable)]
+pub struct DeclassifySite {
+    /// The body in which the secret is declassified.
+    pub caller: DefId,
+    pub kind: DeclassifyKind,
+    pub span: Span,
+}
+
+#[derive(HashStable)]
+#[derive(Copy, Clone, Debug, Encodable, Decodable)]
+pub enum DeclassifyKind {
+    /// A call to one of the functions that make a secret public, such as `Choice::declassify`.
+    Call(DefId),
+    /// An expression marked `#[declassify]`, with the justification it gives.
+    Attribute(Symbol),
+}
@@ -1,5 +1,6 @@
 pub mod codegen_fn_attrs;
 pub mod debugger_visualizer;
+pub mod declassify;
 pub mod deduced_param_attrs;
 pub mod dependency_format;
 pub mod exported_symbols;
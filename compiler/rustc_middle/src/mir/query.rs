@@ -4,9 +4,11 @@
 
 use rustc_abi::{FieldIdx, VariantIdx};
 use rustc_errors::ErrorGuaranteed;
+use rustc_hir::HirId;
 use rustc_index::IndexVec;
 use rustc_index::bit_set::BitMatrix;
 use rustc_macros::{HashStable, TyDecodable, TyEncodable, TypeFoldable, TypeVisitable};
+use rustc_span::def_id::LocalDefId;
 use rustc_span::{Span, Symbol};
 
 use super::{ConstValue, SourceInfo};
@@ -94,6 +96,50 @@ pub struct ConstQualifs {
     pub needs_non_const_drop: bool,
     pub tainted_by_errors: Option<ErrorGuaranteed>,
 }
+
+/// The result of the `mir_secret_flow` query: how secrets reach calls, branches and address
+/// computations in the body of a function, which lets the `#[constant_time]` checks follow secrets from callers
+/// into callees.
+///
+/// Parameters are numbered from zero, in the order of the function signature.
+#[derive(Clone, Debug, Default, TyEncodable, TyDecodable, HashStable)]
+pub struct SecretFlow {
+    /// The arguments derived from a secret in calls to other functions of the crate.
+    pub calls: Vec<SecretCallArg>,
+    /// The branches and address computations on a value derived from a parameter that is not
+    /// known to be secret within the body, and so are only checked once the callers are known.
+    pub uses: Vec<SecretUse>,
+}
+
+/// An argument derived from a secret in a call, see [`SecretFlow`].
+#[derive(Clone, Copy, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct SecretCallArg {
+    pub callee: LocalDefId,
+    /// The parameter of `callee` the argument is passed to.
+    pub param: u32,
+    /// The parameter of the caller the argument is derived from, or `None` if it is secret
+    /// whatever the callers pass.
+    pub source: Option<u32>,
+}
+
+/// A branch or an address computation on a value derived from a parameter, see [`SecretFlow`].
+#[derive(Clone, Copy, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct SecretUse {
+    pub kind: SecretUseKind,
+    pub span: Span,
+    pub lint_root: HirId,
+    /// The parameter the value is derived from.
+    pub source: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, TyEncodable, TyDecodable, HashStable)]
+pub enum SecretUseKind {
+    Branch,
+    /// Indexing, built-in or through the `Index` traits, or `get_unchecked`.
+    Index,
+    /// Pointer arithmetic, such as `ptr::add`.
+    Offset,
+}
 /// Outlives-constraints can be categorized to determine whether and why they
 /// are interesting (for error reporting). Order of variants indicates sort
 /// order of the category, thereby influencing diagnostic output.
@@ -66,8 +66,10 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use rustc_abi as abi;
 use rustc_abi::Align;
 use rustc_arena::TypedArena;
+use rustc_ast as ast;
 use rustc_ast::expand::allocator::AllocatorKind;
 use rustc_ast::tokenstream::TokenStream;
 use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
@@ -76,6 +78,7 @@
 use rustc_data_structures::svh::Svh;
 use rustc_data_structures::unord::{UnordMap, UnordSet};
 use rustc_errors::ErrorGuaranteed;
+use rustc_hir as hir;
 use rustc_hir::attrs::{EiiDecl, EiiImpl, StrippedCfgItem};
 use rustc_hir::def::{DefKind, DocLinkResMap};
 use rustc_hir::def_id::{
@@ -96,13 +99,13 @@
 use rustc_span::source_map::Spanned;
 use rustc_span::{DUMMY_SP, LocalExpnId, Span, Symbol};
 use rustc_target::spec::PanicStrategy;
-use {rustc_abi as abi, rustc_ast as ast, rustc_hir as hir};
 
 use crate::infer::canonical::{self, Canonical};
 use crate::lint::LintExpectation;
 use crate::metadata::ModChild;
 use crate::middle::codegen_fn_attrs::{CodegenFnAttrs, SanitizerFnAttrs};
 use crate::middle::debugger_visualizer::DebuggerVisualizerFile;
+use crate::middle::declassify::DeclassifySite;
 use crate::middle::deduced_param_attrs::DeducedParamAttrs;
 use crate::middle::exported_symbols::{ExportedSymbol, SymbolExportInfo};
 use crate::middle::lib_features::LibFeatures;
@@ -680,6 +683,15 @@
         desc { |tcx| "promoting constants in MIR for `{}`", tcx.def_path_str(key) }
     }
 
+    /// Summarizes how secrets reach calls, branches and address computations in the MIR of
+    /// `key`, for the `#[constant_time]` checks across function boundaries. Computed from the
+    /// built MIR, so it must be forced before `mir_promoted` steals it.
+    query mir_secret_flow(key: LocalDefId) -> &'tcx mir::SecretFlow {
+        arena_cache
+        desc { |tcx| "tracking secrets through `{}`", tcx.def_path_str(key) }
+        cache_on_disk_if { true }
+    }
+
     query closure_typeinfo(key: LocalDefId) -> ty::ClosureTypeInfo<'tcx> {
         desc {
             |tcx| "finding symbols for captures of closure `{}`",
@@ -1208,6 +1220,12 @@
         desc { |tcx| "checking privacy in {}", describe_as_module(key.to_local_def_id(), tcx) }
     }
 
+    /// Checks the branches and address computations on values derived from the parameters to
+    /// which a caller passes a secret, in every function of the crate.
+    query check_secret_params(_: ()) {
+        desc { "checking secrets passed to function parameters" }
+    }
+
     query check_liveness(key: LocalDefId) -> &'tcx rustc_index::bit_set::DenseBitSet<abi::FieldIdx> {
         arena_cache
         desc { |tcx| "checking liveness of variables in `{}`", tcx.def_path_str(key.to_def_id()) }
@@ -2351,6 +2369,14 @@
         separate_provide_extern
     }
 
+    /// The calls to the functions that declassify secrets in a crate, and its expressions marked
+    /// `#[declassify]`, collected for `-Zreport-declassify`. Empty if the crate was compiled
+    /// without the flag.
+    query declassify_sites(_: CrateNum) -> &'tcx [DeclassifySite] {
+        desc { "collecting the declassification points of a crate" }
+        separate_provide_extern
+    }
+
     query trait_impls_in_crate(_: CrateNum) -> &'tcx [DefId] {
         desc { "fetching all trait impls in a crate" }
         separate_provide_extern
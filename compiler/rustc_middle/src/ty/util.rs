@@ -1397,6 +1397,32 @@ pub fn has_significant_drop(self, tcx: TyCtxt<'tcx>, typing_env: ty::TypingEnv<'
         }
     }
 
+    /// Returns whether values of this type are secret by construction: the constant-time types
+    /// of `core::select`, such as `Choice` or `Secret`, possibly behind references or pointers.
+    pub fn is_ct_secret(self, tcx: TyCtxt<'tcx>) -> bool {
+        let mut ty = self;
+        while let ty::Ref(_, inner, _) | ty::RawPtr(inner, _) = *ty.kind() {
+            ty = inner;
+        }
+        let ty::Adt(adt, _) = *ty.kind() else {
+            return false;
+        };
+        matches!(
+            tcx.get_diagnostic_name(adt.did()),
+            Some(sym::Choice | sym::Secret | sym::CtOption | sym::CtResult)
+        )
+    }
+
+    /// Returns whether this type is a `Secret` of a scalar, such as `Secret<u64>`.
+    pub fn is_ct_secret_scalar(self, tcx: TyCtxt<'tcx>) -> bool {
+        match *self.kind() {
+            ty::Adt(adt, args) => {
+                tcx.is_diagnostic_item(sym::Secret, adt.did()) && args.type_at(0).is_scalar()
+            }
+            _ => false,
+        }
+    }
+
     /// Returns `true` if equality for this type is both reflexive and structural.
     ///
     /// Reflexive equality for a type is indicated by an `Eq` impl for that type.
@@ -0,0 +1,174 @@
+use rustc_index::bit_set::DenseBitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::sym;
+
+use crate::Analysis;
+
+/// A dataflow analysis that tracks which locals may hold a value derived from a secret.
+///
+/// Locals whose type is one of the constant-time types of `core::select`, such as `Choice` or
+/// `Secret`, possibly behind references, are secret throughout the body. Any other local becomes
+/// secret when it is assigned a value computed from a secret local, whether by a statement, as
+/// the result of a call with a secret argument, or as an output of inline assembly with a secret
+/// input, and public again when it is overwritten as a whole by a public value or goes out of
+/// storage. The result of a declassification function such as `Choice::declassify` is always
+/// public. Arguments can also be marked as secret on entry, as for `#[secret]` parameters.
+///
+/// Secrets are not followed through memory: storing a secret through a pointer makes the pointer
+/// secret, not the place it points to.
+pub struct MaybeSecretLocals<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The locals that are secret because of their type, and so can never become public.
+    secret_by_type: DenseBitSet<Local>,
+    /// The locals that are secret on entry to the body.
+    secret_on_entry: DenseBitSet<Local>,
+}
+
+impl<'tcx> MaybeSecretLocals<'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Self {
+        let mut secret_by_type = DenseBitSet::new_empty(body.local_decls.len());
+        for (local, decl) in body.local_decls.iter_enumerated() {
+            if decl.ty.is_ct_secret(tcx) {
+                secret_by_type.insert(local);
+            }
+        }
+        let secret_on_entry = secret_by_type.clone();
+        MaybeSecretLocals { tcx, secret_by_type, secret_on_entry }
+    }
+
+    /// Creates an analysis in which only `args` are secret on entry, ignoring the types of the
+    /// locals, to find which values depend on these arguments.
+    pub fn for_args(
+        tcx: TyCtxt<'tcx>,
+        body: &Body<'tcx>,
+        args: impl IntoIterator<Item = Local>,
+    ) -> Self {
+        let none = DenseBitSet::new_empty(body.local_decls.len());
+        MaybeSecretLocals { tcx, secret_by_type: none.clone(), secret_on_entry: none }
+            .with_secret_args(args)
+    }
+
+    /// Marks `args` as secret on entry as well.
+    pub fn with_secret_args(mut self, args: impl IntoIterator<Item = Local>) -> Self {
+        for arg in args {
+            self.secret_on_entry.insert(arg);
+        }
+        self
+    }
+
+    /// Returns whether any local of the body is secret on entry. If none is, no local can ever
+    /// become secret, and there is no need to run the analysis.
+    pub fn has_secrets(&self) -> bool {
+        !self.secret_on_entry.is_empty()
+    }
+
+    /// Returns whether `func` is one of the functions whose result is public even if their
+    /// arguments are secret.
+    fn is_declassify(&self, func: &Operand<'tcx>) -> bool {
+        func.const_fn_def().is_some_and(|(def_id, _)| {
+            matches!(
+                self.tcx.get_diagnostic_name(def_id),
+                Some(
+                    sym::choice_declassify
+                        | sym::ct_option_into_option
+                        | sym::ct_result_into_result
+                )
+            )
+        })
+    }
+
+    /// Updates `state` for an assignment of a value, secret or not, to `place`.
+    fn assign(&self, state: &mut DenseBitSet<Local>, place: Place<'tcx>, secret: bool) {
+        if secret {
+            state.insert(place.local);
+        } else if let Some(local) = place.as_local()
+            && !self.secret_by_type.contains(local)
+        {
+            state.remove(local);
+        }
+    }
+}
+
+impl<'tcx> Analysis<'tcx> for MaybeSecretLocals<'tcx> {
+    type Domain = DenseBitSet<Local>;
+    const NAME: &'static str = "maybe_secret_locals";
+
+    fn bottom_value(&self, body: &Body<'tcx>) -> Self::Domain {
+        // bottom = public
+        DenseBitSet::new_empty(body.local_decls.len())
+    }
+
+    fn initialize_start_block(&self, _: &Body<'tcx>, state: &mut Self::Domain) {
+        state.union(&self.secret_on_entry);
+    }
+
+    fn apply_primary_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement<'tcx>,
+        location: Location,
+    ) {
+        match &statement.kind {
+            StatementKind::Assign(box (place, rvalue)) => {
+                let mut uses = UsesSecret { state, found: false };
+                uses.visit_rvalue(rvalue, location);
+                let secret = uses.found;
+                self.assign(state, *place, secret);
+            }
+            StatementKind::StorageDead(local) => self.assign(state, Place::from(*local), false),
+            _ => {}
+        }
+    }
+
+    fn apply_primary_terminator_effect<'mir>(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &'mir Terminator<'tcx>,
+        location: Location,
+    ) -> TerminatorEdges<'mir, 'tcx> {
+        match &terminator.kind {
+            TerminatorKind::Call { func, args, destination, .. } => {
+                let mut uses = UsesSecret { state, found: false };
+                for arg in args.iter() {
+                    uses.visit_operand(&arg.node, location);
+                }
+                let secret = uses.found && !self.is_declassify(func);
+                self.assign(state, *destination, secret);
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                let mut uses = UsesSecret { state, found: false };
+                for op in operands.iter() {
+                    if let InlineAsmOperand::In { value, .. }
+                    | InlineAsmOperand::InOut { in_value: value, .. } = op
+                    {
+                        uses.visit_operand(value, location);
+                    }
+                }
+                let secret = uses.found;
+                for op in operands.iter() {
+                    if let InlineAsmOperand::Out { place: Some(place), .. }
+                    | InlineAsmOperand::InOut { out_place: Some(place), .. } = op
+                    {
+                        self.assign(state, *place, secret);
+                    }
+                }
+            }
+            _ => {}
+        }
+        terminator.edges()
+    }
+}
+
+/// A `Visitor` that finds whether any of the locals it visits is secret in `state`.
+struct UsesSecret<'a> {
+    state: &'a DenseBitSet<Local>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for UsesSecret<'_> {
+    fn visit_local(&mut self, local: Local, _context: PlaceContext, _location: Location) {
+        self.found |= self.state.contains(local);
+    }
+}
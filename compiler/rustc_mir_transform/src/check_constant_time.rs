@@ -0,0 +1,440 @@
+//! Checks that functions marked `#[constant_time]` neither branch on secret values nor compute
+//! addresses from them, by indexing or pointer arithmetic, and lints branches on secret values
+//! in other functions.
+//!
+//! Which locals hold secrets at each point of the body is computed by [`MaybeSecretLocals`]. Each
+//! body is first checked on its own, from the values that are secret whatever the callers pass:
+//! locals of secret type and parameters marked `#[secret]`. The values that are secret because a
+//! caller passes a secret to one of the parameters are checked once the whole crate is known,
+//! from the [`SecretFlow`] summary of each body, by following the calls from secret arguments to
+//! the parameters they are passed to. Calls are resolved where possible, and otherwise followed
+//! into the generic callee, so a secret reaches every instantiation of a generic function.
+//!
+//! The uses within an expression marked `#[declassify = "justification"]` are not reported.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, Visitor as _};
+use rustc_hir::{self as hir, HirId, LangItem, find_attr};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_mir_dataflow::impls::MaybeSecretLocals;
+use rustc_mir_dataflow::{Analysis, ResultsCursor};
+use rustc_session::lint::builtin::SECRET_DEPENDENT_BRANCH;
+use rustc_span::{Span, sym};
+
+use crate::errors;
+use crate::pass_manager::MirLint;
+
+pub(super) struct CheckConstantTime;
+
+impl<'tcx> MirLint<'tcx> for CheckConstantTime {
+    fn run_lint(&self, tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
+        if body.source.promoted.is_some() {
+            return;
+        }
+        let def_id = body.source.def_id().expect_local();
+        let analysis =
+            MaybeSecretLocals::new(tcx, body).with_secret_args(declared_secret_args(tcx, def_id));
+        if !analysis.has_secrets() {
+            return;
+        }
+        let attr_span = constant_time_attr(tcx, def_id);
+        let declassified = declassified_spans(tcx, def_id);
+
+        let mut secret = analysis.iterate_to_fixpoint(tcx, body, None).into_results_cursor(body);
+        // A single expression can give rise to several uses, so only report each span once.
+        let mut reported = FxHashSet::default();
+        for site in secret_sites(tcx, body) {
+            let SiteKind::Use(kind) = site.kind else {
+                continue;
+            };
+            let source_info = body.source_info(site.location);
+            if is_checked(kind, attr_span)
+                && site.is_secret(&mut secret)
+                && !is_declassified(&declassified, source_info.span)
+                && reported.insert(source_info.span)
+            {
+                let lint_root = lint_root(body, source_info);
+                report(tcx, kind, source_info.span, lint_root, attr_span, false);
+            }
+        }
+    }
+}
+
+/// Implementation of the `mir_secret_flow` query.
+pub(crate) fn mir_secret_flow(tcx: TyCtxt<'_>, def_id: LocalDefId) -> SecretFlow {
+    let mut flow = SecretFlow::default();
+    if !tcx.features().constant_time() || !tcx.def_kind(def_id).is_fn_like() {
+        return flow;
+    }
+    // N.B., this `borrow()` is guaranteed to be valid, because `mir_promoted()`, which steals
+    // from `mir_built()`, forces this query to execute before performing the steal.
+    let body = &*tcx.mir_built(def_id).borrow();
+    if body.tainted_by_errors.is_some() {
+        return flow;
+    }
+    let sites = secret_sites(tcx, body);
+    if sites.is_empty() {
+        return flow;
+    }
+
+    // What is secret whatever the callers pass has been checked with the body, and only matters
+    // here for the arguments it is passed as.
+    let declared = declared_secret_args(tcx, def_id);
+    let mut secret = MaybeSecretLocals::new(tcx, body)
+        .with_secret_args(declared.iter().copied())
+        .iterate_to_fixpoint(tcx, body, None)
+        .into_results_cursor(body);
+    let always: Vec<bool> = sites.iter().map(|site| site.is_secret(&mut secret)).collect();
+    for (site, _) in sites.iter().zip(&always).filter(|&(_, &always)| always) {
+        if let SiteKind::Arg { callee, param } = site.kind {
+            flow.calls.push(SecretCallArg { callee, param, source: None });
+        }
+    }
+
+    // Closures and functions with a spread argument receive their parameters in a different
+    // shape than the callers pass them, so they are not followed into.
+    if tcx.is_closure_like(def_id.to_def_id()) || body.spread_arg.is_some() {
+        return flow;
+    }
+    for (source, arg) in body.args_iter().enumerate() {
+        if declared.contains(&arg) {
+            continue;
+        }
+        let source = source as u32;
+        let mut secret = MaybeSecretLocals::for_args(tcx, body, [arg])
+            .iterate_to_fixpoint(tcx, body, None)
+            .into_results_cursor(body);
+        for (site, _) in sites.iter().zip(&always).filter(|&(_, &always)| !always) {
+            if !site.is_secret(&mut secret) {
+                continue;
+            }
+            match site.kind {
+                SiteKind::Arg { callee, param } => {
+                    flow.calls.push(SecretCallArg { callee, param, source: Some(source) });
+                }
+                SiteKind::Use(kind) => {
+                    let source_info = body.source_info(site.location);
+                    let lint_root = lint_root(body, source_info);
+                    flow.uses.push(SecretUse { kind, span: source_info.span, lint_root, source });
+                }
+            }
+        }
+    }
+    flow
+}
+
+/// Implementation of the `check_secret_params` query.
+pub(crate) fn check_secret_params(tcx: TyCtxt<'_>, (): ()) {
+    if !tcx.features().constant_time() {
+        return;
+    }
+    let bodies: Vec<_> =
+        tcx.hir_body_owners().filter(|&def_id| tcx.def_kind(def_id).is_fn_like()).collect();
+
+    // Follow the calls from the arguments that are secret whatever the callers pass.
+    let mut worklist: Vec<_> = bodies
+        .iter()
+        .flat_map(|&def_id| &tcx.mir_secret_flow(def_id).calls)
+        .filter(|call| call.source.is_none())
+        .map(|call| (call.callee, call.param))
+        .collect();
+    let mut secret = FxHashSet::default();
+    while let Some((def_id, param)) = worklist.pop() {
+        if secret.insert((def_id, param)) {
+            worklist.extend(
+                tcx.mir_secret_flow(def_id)
+                    .calls
+                    .iter()
+                    .filter(|call| call.source == Some(param))
+                    .map(|call| (call.callee, call.param)),
+            );
+        }
+    }
+    if secret.is_empty() {
+        return;
+    }
+
+    for def_id in bodies {
+        let uses = &tcx.mir_secret_flow(def_id).uses;
+        if uses.is_empty() {
+            continue;
+        }
+        let attr_span = constant_time_attr(tcx, def_id);
+        let declassified = declassified_spans(tcx, def_id);
+        let mut reported = FxHashSet::default();
+        for secret_use in uses {
+            if is_checked(secret_use.kind, attr_span)
+                && secret.contains(&(def_id, secret_use.source))
+                && !is_declassified(&declassified, secret_use.span)
+                && reported.insert(secret_use.span)
+            {
+                let SecretUse { kind, span, lint_root, .. } = *secret_use;
+                report(tcx, kind, span, lint_root, attr_span, true);
+            }
+        }
+    }
+}
+
+/// Returns the span of the `#[constant_time]` attribute of `def_id`, if it has one.
+fn constant_time_attr(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Span> {
+    find_attr!(tcx.get_all_attrs(def_id), AttributeKind::ConstantTime { span, .. } => *span)
+}
+
+/// Returns the locals of the parameters of `def_id` that are marked `#[secret]`.
+fn declared_secret_args(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Vec<Local> {
+    let Some(body) = tcx.hir_maybe_body_owned_by(def_id) else {
+        return Vec::new();
+    };
+    // Closures take their environment as their first argument.
+    let first = if tcx.is_closure_like(def_id.to_def_id()) { 2 } else { 1 };
+    body.params
+        .iter()
+        .enumerate()
+        .filter(|(_, param)| find_attr!(tcx.hir_attrs(param.hir_id), AttributeKind::Secret(..)))
+        .map(|(i, _)| Local::new(first + i))
+        .collect()
+}
+
+/// Returns the spans of the expressions marked `#[declassify]` in the body of `def_id`, including
+/// those of the body that encloses it if it is a closure.
+fn declassified_spans(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Vec<Span> {
+    let root = tcx.typeck_root_def_id(def_id.to_def_id()).expect_local();
+    let Some(body) = tcx.hir_maybe_body_owned_by(root) else {
+        return Vec::new();
+    };
+    let mut collector = DeclassifiedCollector { tcx, spans: Vec::new() };
+    collector.visit_body(body);
+    collector.spans
+}
+
+/// Returns whether `span`, or the macro call it comes from, is within one of the `declassified`
+/// expressions.
+fn is_declassified(declassified: &[Span], span: Span) -> bool {
+    declassified.iter().any(|&outer| span.find_ancestor_inside(outer).is_some())
+}
+
+struct DeclassifiedCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    spans: Vec<Span>,
+}
+
+impl<'tcx> intravisit::Visitor<'tcx> for DeclassifiedCollector<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.tcx
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if find_attr!(self.tcx.hir_attrs(expr.hir_id), AttributeKind::Declassify { .. }) {
+            // The expressions within are already covered.
+            self.spans.push(expr.span);
+            return;
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Returns whether a secret use of the given kind is reported, given the span of the
+/// `#[constant_time]` attribute of the function, if any. Outside of `#[constant_time]` functions,
+/// only branches are, by the `secret_dependent_branch` lint.
+fn is_checked(kind: SecretUseKind, attr_span: Option<Span>) -> bool {
+    kind == SecretUseKind::Branch || attr_span.is_some()
+}
+
+fn report(
+    tcx: TyCtxt<'_>,
+    kind: SecretUseKind,
+    span: Span,
+    lint_root: HirId,
+    attr_span: Option<Span>,
+    from_caller: bool,
+) {
+    match (kind, attr_span) {
+        (SecretUseKind::Branch, Some(attr_span)) => {
+            tcx.dcx().emit_err(errors::ConstantTimeBranch { span, attr_span, from_caller });
+        }
+        (SecretUseKind::Index, Some(attr_span)) => {
+            tcx.dcx().emit_err(errors::ConstantTimeIndex { span, attr_span, from_caller });
+        }
+        (SecretUseKind::Offset, Some(attr_span)) => {
+            tcx.dcx().emit_err(errors::ConstantTimeOffset { span, attr_span, from_caller });
+        }
+        (SecretUseKind::Branch, None) => {
+            tcx.emit_node_span_lint(
+                SECRET_DEPENDENT_BRANCH,
+                lint_root,
+                span,
+                errors::SecretDependentBranch { span, from_caller },
+            );
+        }
+        (SecretUseKind::Index | SecretUseKind::Offset, None) => {}
+    }
+}
+
+fn lint_root(body: &Body<'_>, source_info: SourceInfo) -> HirId {
+    body.source_scopes[source_info.scope].local_data.as_ref().unwrap_crate_local().lint_root
+}
+
+/// A place in a body where the value of a local matters if it is secret.
+struct Site {
+    location: Location,
+    local: Local,
+    kind: SiteKind,
+}
+
+enum SiteKind {
+    /// The local is branched on, or used to compute an address.
+    Use(SecretUseKind),
+    /// The local is passed to the parameter `param` of `callee`.
+    Arg { callee: LocalDefId, param: u32 },
+}
+
+impl Site {
+    /// Returns whether the local may hold a secret at the site.
+    fn is_secret<'tcx>(
+        &self,
+        secret: &mut ResultsCursor<'_, 'tcx, MaybeSecretLocals<'tcx>>,
+    ) -> bool {
+        secret.seek_before_primary_effect(self.location);
+        secret.get().contains(self.local)
+    }
+}
+
+/// Returns the sites of `body`, in order.
+fn secret_sites<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> Vec<Site> {
+    let mut collector = SiteCollector { tcx, body, sites: Vec::new() };
+    collector.visit_body(body);
+    collector.sites
+}
+
+struct SiteCollector<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    sites: Vec<Site>,
+}
+
+impl<'tcx> SiteCollector<'_, 'tcx> {
+    /// Returns the function of the crate that `func` calls, if its parameters can be followed.
+    fn local_callee(&self, func: &Operand<'tcx>) -> Option<LocalDefId> {
+        let ty::FnDef(callee, args) = *func.ty(self.body, self.tcx).kind() else {
+            return None;
+        };
+        let typing_env = self.body.typing_env(self.tcx);
+        let callee = if let Ok(args) = self.tcx.try_normalize_erasing_regions(typing_env, args)
+            && let Ok(Some(instance)) = Instance::try_resolve(self.tcx, typing_env, callee, args)
+        {
+            let ty::InstanceKind::Item(callee) = instance.def else {
+                return None;
+            };
+            callee
+        } else {
+            callee
+        };
+        let callee = callee.as_local()?;
+        (!self.tcx.is_closure_like(callee.to_def_id())
+            && self.tcx.hir_maybe_body_owned_by(callee).is_some())
+        .then_some(callee)
+    }
+
+    /// Returns how a call to `func` computes an address from its second argument, if it does.
+    fn address_computation(&self, func: &Operand<'tcx>) -> Option<SecretUseKind> {
+        let tcx = self.tcx;
+        let (def_id, _) = func.const_fn_def()?;
+        if let Some(intrinsic) = tcx.intrinsic(def_id) {
+            return matches!(intrinsic.name, sym::offset | sym::arith_offset)
+                .then_some(SecretUseKind::Offset);
+        }
+        if let Some(trait_id) = tcx.trait_of_assoc(def_id) {
+            return (tcx.is_lang_item(trait_id, LangItem::Index)
+                || tcx.is_lang_item(trait_id, LangItem::IndexMut))
+            .then_some(SecretUseKind::Index);
+        }
+        let impl_id = tcx.inherent_impl_of_assoc(def_id)?;
+        let is_pointer = match *tcx.type_of(impl_id).instantiate_identity().kind() {
+            ty::RawPtr(..) => true,
+            ty::Adt(adt, _) => tcx.is_diagnostic_item(sym::NonNull, adt.did()),
+            ty::Slice(_) | ty::Str => false,
+            _ => return None,
+        };
+        match tcx.item_name(def_id) {
+            sym::get_unchecked | sym::get_unchecked_mut if !is_pointer => {
+                Some(SecretUseKind::Index)
+            }
+            sym::add
+            | sym::sub
+            | sym::offset
+            | sym::wrapping_add
+            | sym::wrapping_sub
+            | sym::wrapping_offset
+            | sym::byte_add
+            | sym::byte_sub
+            | sym::byte_offset
+                if is_pointer =>
+            {
+                Some(SecretUseKind::Offset)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for SiteCollector<'_, 'tcx> {
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        match &terminator.kind {
+            TerminatorKind::SwitchInt { discr, .. } => {
+                if let Some(place) = discr.place() {
+                    let kind = SiteKind::Use(SecretUseKind::Branch);
+                    self.sites.push(Site { location, local: place.local, kind });
+                }
+            }
+            TerminatorKind::Call { func, args, .. } => {
+                if let Some(kind) = self.address_computation(func)
+                    && let Some(offset) = args.get(1)
+                    && let Some(place) = offset.node.place()
+                {
+                    self.sites.push(Site {
+                        location,
+                        local: place.local,
+                        kind: SiteKind::Use(kind),
+                    });
+                }
+                if let Some(callee) = self.local_callee(func) {
+                    for (param, arg) in args.iter().enumerate() {
+                        if let Some(place) = arg.node.place() {
+                            let kind = SiteKind::Arg { callee, param: param as u32 };
+                            self.sites.push(Site { location, local: place.local, kind });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        for elem in place.projection.iter() {
+            if let ProjectionElem::Index(index) = elem {
+                let kind = SiteKind::Use(SecretUseKind::Index);
+                self.sites.push(Site { location, local: index, kind });
+            }
+        }
+        self.super_place(place, context, location);
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let Rvalue::BinaryOp(BinOp::Offset, box (_, offset)) = rvalue
+            && let Some(place) = offset.place()
+        {
+            let kind = SiteKind::Use(SecretUseKind::Offset);
+            self.sites.push(Site { location, local: place.local, kind });
+        }
+        self.super_rvalue(rvalue, location);
+    }
+}
@@ -72,6 +72,59 @@ pub(crate) struct InvalidForceInline {
     pub reason: &'static str,
 }
 
+#[derive(Diagnostic)]
+#[diag("branch on a secret value in a `#[constant_time]` function")]
+#[note("which way the branch goes, and so how long the function takes, would depend on the secret")]
+#[help("use `ct_select` to compute both outcomes and pick one without branching")]
+pub(crate) struct ConstantTimeBranch {
+    #[primary_span]
+    #[label("this branches on a value derived from a secret")]
+    pub span: Span,
+    #[label("the function is marked `#[constant_time]` here")]
+    pub attr_span: Span,
+    #[note("the value is derived from a parameter to which a caller passes a secret")]
+    pub from_caller: bool,
+}
+
+#[derive(Diagnostic)]
+#[diag("indexing with a secret value in a `#[constant_time]` function")]
+#[note("the address accessed, and so the state of the cache, would depend on the secret")]
+#[help("use `ct_lookup` to read every element and select the one at the index")]
+pub(crate) struct ConstantTimeIndex {
+    #[primary_span]
+    #[label("this indexes with a value derived from a secret")]
+    pub span: Span,
+    #[label("the function is marked `#[constant_time]` here")]
+    pub attr_span: Span,
+    #[note("the value is derived from a parameter to which a caller passes a secret")]
+    pub from_caller: bool,
+}
+
+#[derive(Diagnostic)]
+#[diag("pointer offset by a secret value in a `#[constant_time]` function")]
+#[note("the address accessed, and so the state of the cache, would depend on the secret")]
+#[help("use `ct_lookup` to read every element and select the one at the offset")]
+pub(crate) struct ConstantTimeOffset {
+    #[primary_span]
+    #[label("this offsets a pointer by a value derived from a secret")]
+    pub span: Span,
+    #[label("the function is marked `#[constant_time]` here")]
+    pub attr_span: Span,
+    #[note("the value is derived from a parameter to which a caller passes a secret")]
+    pub from_caller: bool,
+}
+
+#[derive(LintDiagnostic)]
+#[diag("branch on a value derived from a secret")]
+#[note("which way the branch goes, and so how long the code takes, would depend on the secret")]
+#[help("use `ct_select` to compute both outcomes and pick one without branching")]
+pub(crate) struct SecretDependentBranch {
+    #[label("this branches on a value derived from a secret")]
+    pub span: Span,
+    #[note("the value is derived from a parameter to which a caller passes a secret")]
+    pub from_caller: bool,
+}
+
 #[derive(LintDiagnostic)]
 pub(crate) enum ConstMutate {
     #[diag("attempting to modify a `const` item")]
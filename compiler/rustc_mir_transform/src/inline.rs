@@ -11,7 +11,7 @@
 use rustc_index::Idx;
 use rustc_index::bit_set::DenseBitSet;
 use rustc_middle::bug;
-use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
+use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::mir::visit::*;
 use rustc_middle::mir::*;
 use rustc_middle::ty::{self, Instance, InstanceKind, Ty, TyCtxt, TypeFlags, TypeVisitableExt};
@@ -825,6 +825,13 @@ fn check_codegen_attributes<'tcx, I: Inliner<'tcx>>(
         return Err("incompatible sanitizer set");
     }
 
+    // Speculative load hardening only applies to the loads of the functions it is enabled for.
+    if callee_attrs.flags.contains(CodegenFnAttrFlags::SPECULATIVE_LOAD_HARDENING)
+        && !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::SPECULATIVE_LOAD_HARDENING)
+    {
+        return Err("incompatible speculative load hardening");
+    }
+
     // Two functions are compatible if the callee has no attribute (meaning
     // that it's codegen agnostic), or sets an attribute that is identical
     // to this function's attribute.
@@ -132,6 +132,7 @@ macro_rules! pass_names {
     mod check_alignment : CheckAlignment;
     mod check_enums : CheckEnums;
     mod check_const_item_mutation : CheckConstItemMutation;
+    mod check_constant_time : CheckConstantTime;
     mod check_null : CheckNull;
     mod check_packed_ref : CheckPackedRef;
     // This pass is public to allow external drivers to perform MIR cleanup
@@ -181,6 +182,7 @@ mod dead_store_elimination : DeadStoreElimination {
     mod required_consts : RequiredConstsVisitor;
     mod post_analysis_normalize : PostAnalysisNormalize;
     mod sanity_check : SanityCheck;
+    mod select_branches : SelectBranches;
     // This pass is public to allow external drivers to perform MIR cleanup
     pub mod simplify :
         SimplifyCfg {
@@ -228,6 +230,8 @@ pub fn provide(providers: &mut Providers) {
         mir_coroutine_witnesses: coroutine::mir_coroutine_witnesses,
         optimized_mir,
         check_liveness: liveness::check_liveness,
+        mir_secret_flow: check_constant_time::mir_secret_flow,
+        check_secret_params: check_constant_time::check_secret_params,
         is_mir_available,
         mir_callgraph_cyclic: inline::cycle::mir_callgraph_cyclic,
         mir_inliner_callees: inline::cycle::mir_inliner_callees,
@@ -415,6 +419,7 @@ fn mir_built(tcx: TyCtxt<'_>, def: LocalDefId) -> &Steal<Body<'_>> {
             &Lint(check_inline_always_target_features::CheckInlineAlwaysTargetFeature),
             &Lint(check_packed_ref::CheckPackedRef),
             &Lint(check_const_item_mutation::CheckConstItemMutation),
+            &Lint(check_constant_time::CheckConstantTime),
             &Lint(function_item_references::FunctionItemReferences),
             // What we need to do constant evaluation.
             &simplify::SimplifyCfg::Initial,
@@ -462,6 +467,9 @@ fn mir_promoted(
     // the `trivial_const` query uses mir_built, so make sure it is run.
     tcx.ensure_done().trivial_const(def);
 
+    // the `mir_secret_flow` query uses the raw mir, so make sure it is run.
+    tcx.ensure_done().mir_secret_flow(def);
+
     let mut body = tcx.mir_built(def).steal();
     if let Some(error_reported) = const_qualifs.tainted_by_errors {
         body.tainted_by_errors = Some(error_reported);
@@ -706,6 +714,9 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &check_alignment::CheckAlignment,
             &check_null::CheckNull,
             &check_enums::CheckEnums,
+            // Has to be done before inlining, which hides the `Choice::declassify` calls it looks
+            // for.
+            &select_branches::SelectBranches,
             // Before inlining: trim down MIR with passes to reduce inlining work.
 
             // Has to be done before inlining, otherwise actual call will be almost always inlined.
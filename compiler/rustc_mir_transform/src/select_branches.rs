@@ -0,0 +1,473 @@
+//! Rewrites the `if`/`else` on a `Choice` of functions marked `#[branchless]` into calls to
+//! `core::select::ct_select`.
+//!
+//! A branch is rewritten when its condition comes straight from `Choice::declassify`, and both
+//! arms are a single block of assignments that cannot fail, trap, or be observed, and that
+//! assign the same locals before joining again. Both arms are then executed one after the other,
+//! each assigning fresh temporaries instead of the locals they have in common, and each of these
+//! locals is assigned the value selected from the two temporaries by the `Choice`:
+//!
+//! ```ignore (MIR)
+//! bb0: {
+//!     _4 = copy _1;
+//!     _3 = Choice::declassify(move _4) -> [return: bb1, unwind continue];
+//! }
+//!
+//! bb1: {
+//!     switchInt(move _3) -> [0: bb3, otherwise: bb2];
+//! }
+//!
+//! bb2: {
+//!     _0 = copy _2;
+//!     goto -> bb4;
+//! }
+//!
+//! bb3: {
+//!     _0 = const 0_u32;
+//!     goto -> bb4;
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore (MIR)
+//! bb0: {
+//!     _4 = copy _1;
+//!     _5 = copy _4;
+//!     _3 = Choice::declassify(move _4) -> [return: bb1, unwind continue];
+//! }
+//!
+//! bb1: {
+//!     _6 = copy _2;
+//!     _7 = const 0_u32;
+//!     _0 = ct_select::<u32>(copy _5, move _6, move _7) -> [return: bb4, unwind continue];
+//! }
+//! ```
+//!
+//! This has to run before inlining, which would hide the call to `Choice::declassify`. It runs
+//! at every optimization level, since the point of the attribute is the absence of the branch.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{LangItem, find_attr};
+use rustc_index::IndexVec;
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_infer::traits::{Obligation, ObligationCause};
+use rustc_middle::mir::visit::{MutVisitor, MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::source_map::Spanned;
+use rustc_span::sym;
+use rustc_trait_selection::traits::ObligationCtxt;
+
+use crate::simplify::simplify_cfg;
+
+pub(super) struct SelectBranches;
+
+impl<'tcx> crate::MirPass<'tcx> for SelectBranches {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let def_id = body.source.def_id();
+        if body.source.promoted.is_some()
+            || !def_id.is_local()
+            || !find_attr!(tcx.get_all_attrs(def_id), AttributeKind::Branchless(..))
+        {
+            return;
+        }
+        let Some(ct_select) = tcx.lang_items().get(LangItem::CtSelect) else {
+            return;
+        };
+
+        let typing_env = body.typing_env(tcx);
+        let selections: Vec<_> = body
+            .basic_blocks
+            .indices()
+            .filter_map(|bb| Selection::find(tcx, typing_env, ct_select, body, bb))
+            .collect();
+        if selections.is_empty() {
+            return;
+        }
+        for selection in selections {
+            selection.apply(tcx, ct_select, body);
+        }
+        // The arms are now unreachable.
+        simplify_cfg(tcx, body);
+    }
+
+    fn is_required(&self) -> bool {
+        true
+    }
+}
+
+/// A branch on a declassified `Choice` that can be turned into selections.
+struct Selection<'tcx> {
+    /// The block ending with the call to `Choice::declassify`.
+    declassify: BasicBlock,
+    /// The `Choice` passed to `Choice::declassify`.
+    choice: Operand<'tcx>,
+    /// The block ending with the branch on the result.
+    switch: BasicBlock,
+    then_arm: BasicBlock,
+    else_arm: BasicBlock,
+    /// The block both arms jump to.
+    join: BasicBlock,
+    /// The locals of non-zero size assigned by both arms, whose values are selected.
+    outputs: Vec<Local>,
+    unwind: UnwindAction,
+}
+
+impl<'tcx> Selection<'tcx> {
+    fn find(
+        tcx: TyCtxt<'tcx>,
+        typing_env: ty::TypingEnv<'tcx>,
+        ct_select: DefId,
+        body: &Body<'tcx>,
+        switch: BasicBlock,
+    ) -> Option<Self> {
+        let bbs = &body.basic_blocks;
+        let TerminatorKind::SwitchInt { discr, targets } = &bbs[switch].terminator().kind else {
+            return None;
+        };
+        let cond = discr.place()?.as_local()?;
+        let (0, else_arm, then_arm) = targets.as_static_if()? else {
+            return None;
+        };
+
+        // The condition must be the result of `Choice::declassify`, called right before.
+        let &[declassify] = &bbs.predecessors()[switch][..] else {
+            return None;
+        };
+        let TerminatorKind::Call { func, args, destination, target, unwind, .. } =
+            &bbs[declassify].terminator().kind
+        else {
+            return None;
+        };
+        let (callee, _) = func.const_fn_def()?;
+        if !tcx.is_diagnostic_item(sym::choice_declassify, callee)
+            || destination.as_local() != Some(cond)
+            || *target != Some(switch)
+            || bbs[declassify].is_cleanup
+        {
+            return None;
+        }
+        let [choice] = &args[..] else {
+            return None;
+        };
+        let choice = match &choice.node {
+            Operand::Copy(place) | Operand::Move(place) => Operand::Copy(*place),
+            constant @ Operand::Constant(_) => constant.clone(),
+            Operand::RuntimeChecks(_) => return None,
+        };
+
+        // Both arms must be distinct blocks, only reachable through the branch, that join again.
+        if then_arm == else_arm || then_arm == switch || else_arm == switch {
+            return None;
+        }
+        let join_of = |arm: BasicBlock| match bbs[arm].terminator().kind {
+            TerminatorKind::Goto { target }
+                if bbs.predecessors()[arm].len() == 1 && !bbs[arm].is_cleanup =>
+            {
+                Some(target)
+            }
+            _ => None,
+        };
+        let join = join_of(then_arm).filter(|&target| join_of(else_arm) == Some(target))?;
+
+        let then_writes = arm_writes(tcx, body, then_arm)?;
+        let else_writes = arm_writes(tcx, body, else_arm)?;
+        let mut shared: Vec<_> =
+            then_writes.assigned.intersection(&else_writes.assigned).copied().collect();
+        shared.sort();
+
+        let mut outputs = Vec::with_capacity(shared.len());
+        for &local in &shared {
+            if then_writes.storage.contains(&local) || else_writes.storage.contains(&local) {
+                return None;
+            }
+            // Zero-sized values, such as the `()` of an `if` used as a statement, are the same
+            // whichever arm assigns them.
+            let ty = body.local_decls[local].ty;
+            if tcx.layout_of(typing_env.as_query_input(ty)).is_ok_and(|layout| layout.is_zst()) {
+                continue;
+            }
+            if !can_select(tcx, typing_env, ct_select, ty) {
+                return None;
+            }
+            outputs.push(local);
+        }
+        // The locals only assigned in one arm are now assigned whichever arm is taken, so their
+        // values must not be observable anywhere else, including in the other arm.
+        for (arm, writes) in [(then_arm, &then_writes), (else_arm, &else_writes)] {
+            let arm_only = writes
+                .assigned
+                .iter()
+                .chain(&writes.storage)
+                .filter(|&local| !shared.contains(local))
+                .copied()
+                .collect::<FxHashSet<_>>();
+            let mut outside = UsedOutside { arm_only: &arm_only, found: false };
+            for (bb, data) in bbs.iter_enumerated() {
+                if bb != arm {
+                    outside.visit_basic_block_data(bb, data);
+                }
+            }
+            if outside.found {
+                return None;
+            }
+        }
+
+        Some(Selection {
+            declassify,
+            choice,
+            switch,
+            then_arm,
+            else_arm,
+            join,
+            outputs,
+            unwind: *unwind,
+        })
+    }
+
+    fn apply(self, tcx: TyCtxt<'tcx>, ct_select: DefId, body: &mut Body<'tcx>) {
+        let source_info = body.basic_blocks[self.switch].terminator().source_info;
+        let span = source_info.span;
+
+        // Keep a copy of the `Choice`, whose operand is usually moved into the call.
+        let choice_ty = self.choice.ty(&body.local_decls, tcx);
+        let choice = body.local_decls.push(LocalDecl::new(choice_ty, span));
+        body.basic_blocks.as_mut_preserves_cfg()[self.declassify].statements.push(Statement::new(
+            source_info,
+            StatementKind::Assign(Box::new((Place::from(choice), Rvalue::Use(self.choice)))),
+        ));
+
+        let mut selected = Vec::with_capacity(2);
+        let mut arms = Vec::with_capacity(2);
+        for arm in [self.then_arm, self.else_arm] {
+            let mut renamer = RenameOutputs {
+                tcx,
+                temps: IndexVec::from_elem(None, &body.local_decls),
+                assigned: FxHashSet::default(),
+            };
+            for &local in &self.outputs {
+                let ty = body.local_decls[local].ty;
+                renamer.temps[local] = Some(body.local_decls.push(LocalDecl::new(ty, span)));
+            }
+            let mut data = std::mem::take(&mut body.basic_blocks.as_mut()[arm].statements);
+            for (statement_index, statement) in data.iter_mut().enumerate() {
+                renamer.visit_statement(statement, Location { block: arm, statement_index });
+            }
+            arms.push(data);
+            selected.push(renamer.temps);
+        }
+
+        let basic_blocks = body.basic_blocks.as_mut();
+        for arm in arms {
+            basic_blocks[self.switch].statements.extend(arm);
+        }
+        // Select each output in its own block, the last one jumping to the join.
+        let mut next = self.join;
+        for &local in self.outputs.iter().rev() {
+            let ty = body.local_decls[local].ty;
+            let value = |map: &IndexVec<Local, Option<Local>>| Spanned {
+                node: Operand::Move(Place::from(map[local].unwrap())),
+                span,
+            };
+            let kind = TerminatorKind::Call {
+                func: Operand::function_handle(tcx, ct_select, [ty.into()], span),
+                args: [
+                    Spanned { node: Operand::Copy(Place::from(choice)), span },
+                    value(&selected[0]),
+                    value(&selected[1]),
+                ]
+                .into(),
+                destination: Place::from(local),
+                target: Some(next),
+                unwind: self.unwind,
+                call_source: CallSource::Misc,
+                fn_span: span,
+            };
+            let terminator = Terminator { source_info, kind };
+            next = basic_blocks.push(BasicBlockData::new(Some(terminator), false));
+        }
+        basic_blocks[self.switch].terminator_mut().kind = TerminatorKind::Goto { target: next };
+    }
+}
+
+/// The locals written by an arm.
+struct ArmWrites {
+    /// The locals assigned a value.
+    assigned: FxHashSet<Local>,
+    /// The locals whose storage starts or ends in the arm.
+    storage: FxHashSet<Local>,
+}
+
+/// Returns the locals written by the statements of `arm`, if these can be executed whether or not
+/// the arm is taken: they only assign locals, from values that can always be computed.
+fn arm_writes<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>, arm: BasicBlock) -> Option<ArmWrites> {
+    let statements = &body.basic_blocks[arm].statements;
+    let mut writes = ArmWrites { assigned: FxHashSet::default(), storage: FxHashSet::default() };
+    for statement in statements {
+        match &statement.kind {
+            StatementKind::Assign(box (place, _)) => {
+                writes.assigned.insert(place.as_local()?);
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                writes.storage.insert(*local);
+            }
+            StatementKind::Nop => {}
+            _ => return None,
+        }
+    }
+
+    let mut checker = ArmChecker { tcx, body, assigned: FxHashSet::default(), pure: true };
+    for (statement_index, statement) in statements.iter().enumerate() {
+        if let StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+            if !is_pure_rvalue(rvalue) {
+                return None;
+            }
+            checker.visit_rvalue(rvalue, Location { block: arm, statement_index });
+            checker.assigned.insert(place.local);
+        }
+    }
+    checker.pure.then_some(writes)
+}
+
+/// Returns whether evaluating `rvalue` can neither cause undefined behavior on operands that
+/// were only meant to be used when the other arm is taken, nor have any other effect.
+fn is_pure_rvalue(rvalue: &Rvalue<'_>) -> bool {
+    match rvalue {
+        Rvalue::Use(_)
+        | Rvalue::Repeat(..)
+        | Rvalue::Ref(_, BorrowKind::Shared, _)
+        | Rvalue::RawPtr(RawPtrKind::Const, _)
+        | Rvalue::UnaryOp(..)
+        | Rvalue::Discriminant(_)
+        | Rvalue::Aggregate(..)
+        | Rvalue::CopyForDeref(_)
+        | Rvalue::WrapUnsafeBinder(..) => true,
+        Rvalue::Cast(kind, ..) => *kind != CastKind::Transmute,
+        Rvalue::BinaryOp(op, _) => !matches!(
+            op,
+            BinOp::AddUnchecked
+                | BinOp::SubUnchecked
+                | BinOp::MulUnchecked
+                | BinOp::ShlUnchecked
+                | BinOp::ShrUnchecked
+                | BinOp::Div
+                | BinOp::Rem
+                | BinOp::Offset
+        ),
+        Rvalue::Ref(..) | Rvalue::RawPtr(..) | Rvalue::ThreadLocalRef(_) => false,
+        Rvalue::ShallowInitBox(..) => false,
+    }
+}
+
+/// Returns whether `ct_select::<ty>` is well-formed, that is, whether `ty` implements
+/// `ConstantTimeSelect`.
+fn can_select<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typing_env: ty::TypingEnv<'tcx>,
+    ct_select: DefId,
+    ty: Ty<'tcx>,
+) -> bool {
+    let (infcx, param_env) = tcx.infer_ctxt().build_with_typing_env(typing_env);
+    let ocx = ObligationCtxt::new(&infcx);
+    let predicates = tcx.predicates_of(ct_select).instantiate(tcx, tcx.mk_args(&[ty.into()]));
+    ocx.register_obligations(
+        predicates
+            .predicates
+            .into_iter()
+            .map(|pred| Obligation::new(tcx, ObligationCause::dummy(), param_env, pred)),
+    );
+    ocx.evaluate_obligations_error_on_ambiguity().is_empty()
+}
+
+/// A `Visitor` that checks the values read by the assignments of an arm.
+struct ArmChecker<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    /// The locals assigned by the statements visited so far.
+    assigned: FxHashSet<Local>,
+    pure: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ArmChecker<'_, 'tcx> {
+    fn visit_operand(&mut self, operand: &Operand<'tcx>, location: Location) {
+        // Moving out of a value the arm did not compute could invalidate it for the other arm.
+        if let Operand::Move(place) = operand
+            && !self.assigned.contains(&place.local)
+        {
+            self.pure = false;
+        }
+        self.super_operand(operand, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Dereferencing a pointer, indexing or reading the wrong variant or union field is only
+        // defined when the arm is taken.
+        for (base, elem) in place.iter_projections() {
+            match elem {
+                ProjectionElem::Field(..) => {
+                    self.pure &= !base.ty(self.body, self.tcx).ty.is_union();
+                }
+                ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. }
+                | ProjectionElem::OpaqueCast(_)
+                | ProjectionElem::UnwrapUnsafeBinder(_) => {}
+                ProjectionElem::Deref | ProjectionElem::Index(_) | ProjectionElem::Downcast(..) => {
+                    self.pure = false;
+                }
+            }
+        }
+        self.super_place(place, context, location);
+    }
+}
+
+/// A `Visitor` that finds whether any of `arm_only` is mentioned.
+struct UsedOutside<'a> {
+    arm_only: &'a FxHashSet<Local>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for UsedOutside<'_> {
+    fn visit_local(&mut self, local: Local, _context: PlaceContext, _location: Location) {
+        self.found |= self.arm_only.contains(&local);
+    }
+}
+
+/// A `MutVisitor` that makes the statements of an arm assign temporaries instead of the outputs.
+///
+/// The statements that come before the assignment of an output still read the output itself,
+/// which neither arm assigns any more.
+struct RenameOutputs<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The temporary of each output.
+    temps: IndexVec<Local, Option<Local>>,
+    /// The outputs assigned by the statements visited so far.
+    assigned: FxHashSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for RenameOutputs<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_assign(
+        &mut self,
+        place: &mut Place<'tcx>,
+        rvalue: &mut Rvalue<'tcx>,
+        location: Location,
+    ) {
+        self.visit_rvalue(rvalue, location);
+        if self.temps.get(place.local).is_some_and(Option::is_some) {
+            self.assigned.insert(place.local);
+        }
+        self.visit_place(place, PlaceContext::MutatingUse(MutatingUseContext::Store), location);
+    }
+
+    fn visit_local(&mut self, local: &mut Local, _context: PlaceContext, _location: Location) {
+        if self.assigned.contains(local) {
+            *local = self.temps[*local].unwrap();
+        }
+    }
+}
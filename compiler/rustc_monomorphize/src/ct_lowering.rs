@@ -0,0 +1,290 @@
+//! Reports, for `-Zdump-ct-lowering`, every constant-time selection, swap and comparison
+//! instantiated in this crate, and the instructions the backend lowers its selections to.
+//!
+//! The report is built from the mono items rather than by the backend, so that it also covers
+//! the codegen units that incremental compilation reuses without generating them again.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
+use rustc_hir::LangItem;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_middle::mir::{self, SourceScope, traversal};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_span::{Span, Symbol, sym};
+
+/// The constant-time APIs whose calls are reported as sites.
+fn api_name(tcx: TyCtxt<'_>, def_id: DefId) -> Option<&'static str> {
+    let api_name = |def_id| {
+        if tcx.is_lang_item(def_id, LangItem::CtSelect) {
+            return Some("ct_select");
+        }
+        match tcx.get_diagnostic_name(def_id)? {
+            sym::constant_time_select_ct_select => Some("ct_select"),
+            sym::ct_select_spec_fn => Some("ct_select_spec"),
+            sym::ct_swap_fn | sym::constant_time_select_ct_swap => Some("ct_swap"),
+            sym::ct_eq_fn | sym::constant_time_eq_ct_eq => Some("ct_eq"),
+            _ => None,
+        }
+    };
+    // Implementations of the trait methods are reported as the methods themselves.
+    api_name(def_id).or_else(|| api_name(tcx.trait_item_of(def_id)?))
+}
+
+fn is_ct_select_intrinsic(name: Symbol) -> bool {
+    matches!(
+        name,
+        sym::ct_select
+            | sym::ct_select_bool
+            | sym::ct_select_i8
+            | sym::ct_select_i16
+            | sym::ct_select_i32
+            | sym::ct_select_i64
+            | sym::ct_select_ptr
+            | sym::ct_select_spec
+    )
+}
+
+#[derive(Clone, serde::Serialize)]
+struct Selection {
+    intrinsic: String,
+    ty: String,
+}
+
+#[derive(serde::Serialize)]
+struct Site {
+    kind: String,
+    #[serde(rename = "crate")]
+    krate: String,
+    function: String,
+    span: String,
+    /// The selections that the site performs, including those in the non-inlined functions that
+    /// it calls. Comparisons, and swaps of integers, are computed arithmetically and have none.
+    selections: Vec<Selection>,
+}
+
+#[derive(serde::Serialize)]
+struct Report<'a> {
+    #[serde(rename = "crate")]
+    krate: String,
+    target: &'a str,
+    lowering: &'static str,
+    instructions: &'static [&'static str],
+    reliable: bool,
+    sites: Vec<Site>,
+}
+
+struct Collector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The selections of the non-inlined constant-time APIs, by instance.
+    callee_selections: FxHashMap<Instance<'tcx>, Vec<Selection>>,
+}
+
+/// A call in a mono item's body that is relevant to the report.
+enum Call<'tcx> {
+    Intrinsic(Selection),
+    Api(&'static str, Instance<'tcx>),
+}
+
+impl<'tcx> Collector<'tcx> {
+    fn calls(
+        &self,
+        instance: Instance<'tcx>,
+        body: &'tcx mir::Body<'tcx>,
+    ) -> Vec<(Call<'tcx>, mir::SourceInfo, Span)> {
+        let tcx = self.tcx;
+        let typing_env = ty::TypingEnv::fully_monomorphized();
+        let mut calls = Vec::new();
+        for (_, data) in traversal::mono_reachable(body, tcx, instance) {
+            let mir::TerminatorKind::Call { ref func, ref args, fn_span, .. } =
+                data.terminator().kind
+            else {
+                continue;
+            };
+            let callee_ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                typing_env,
+                ty::EarlyBinder::bind(func.ty(body, tcx)),
+            );
+            let ty::FnDef(def_id, callee_args) = *callee_ty.kind() else {
+                continue;
+            };
+            let call = if let Some(intrinsic) = tcx.intrinsic(def_id) {
+                if !is_ct_select_intrinsic(intrinsic.name) {
+                    continue;
+                }
+                let ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                    tcx,
+                    typing_env,
+                    ty::EarlyBinder::bind(args[1].node.ty(body, tcx)),
+                );
+                Call::Intrinsic(Selection {
+                    intrinsic: intrinsic.name.to_string(),
+                    ty: with_no_trimmed_paths!(ty.to_string()),
+                })
+            } else if let Ok(Some(callee)) =
+                Instance::try_resolve(tcx, typing_env, def_id, callee_args)
+                && let Some(name) = api_name(tcx, callee.def_id())
+            {
+                Call::Api(name, callee)
+            } else {
+                continue;
+            };
+            calls.push((call, data.terminator().source_info, fn_span));
+        }
+        calls
+    }
+
+    /// Returns the selections performed by a call to a constant-time API that was not inlined.
+    fn selections_of(&mut self, instance: Instance<'tcx>) -> Vec<Selection> {
+        if let Some(selections) = self.callee_selections.get(&instance) {
+            return selections.clone();
+        }
+        // Calls through vtables and to functions from other crates without MIR can't be followed.
+        let ty::InstanceKind::Item(def_id) = instance.def else {
+            return Vec::new();
+        };
+        if !self.tcx.is_mir_available(def_id) {
+            return Vec::new();
+        }
+        // Recursive calls add nothing to the selections of the outer one.
+        self.callee_selections.insert(instance, Vec::new());
+        let body = self.tcx.instance_mir(instance.def);
+        let mut selections = Vec::new();
+        for (call, ..) in self.calls(instance, body) {
+            match call {
+                Call::Intrinsic(selection) => selections.push(selection),
+                Call::Api(_, callee) => selections.extend(self.selections_of(callee)),
+            }
+        }
+        self.callee_selections.insert(instance, selections.clone());
+        selections
+    }
+
+    fn collect_sites(&mut self, instance: Instance<'tcx>, sites: &mut Vec<Site>) {
+        let tcx = self.tcx;
+        let body = tcx.instance_mir(instance.def);
+        let krate = tcx.crate_name(instance.def_id().krate).to_string();
+        let function = with_no_trimmed_paths!(instance.to_string());
+        let source_map = tcx.sess.source_map();
+        let new_site = |kind: &str, span: Span| Site {
+            kind: kind.to_string(),
+            krate: krate.clone(),
+            function: function.clone(),
+            span: source_map.span_to_diagnostic_string(span),
+            selections: Vec::new(),
+        };
+
+        // The outermost API inlined into this body that encloses a scope, if any.
+        let enclosing_api = |scope: SourceScope| {
+            let mut scope = Some(scope);
+            let mut api = None;
+            while let Some(current) = scope {
+                let data = &body.source_scopes[current];
+                if let Some((callee, call_site)) = data.inlined
+                    && let Some(name) = api_name(tcx, callee.def_id())
+                {
+                    api = Some((current, name, call_site));
+                }
+                scope = data.parent_scope;
+            }
+            api
+        };
+
+        // Each API that was inlined into this body is a site of its own, which lists the
+        // selections of all the calls inlined along with it.
+        let mut inlined_sites = FxHashMap::default();
+        for scope in body.source_scopes.indices() {
+            if let Some((site_scope, name, call_site)) = enclosing_api(scope)
+                && site_scope == scope
+            {
+                inlined_sites.insert(scope, sites.len());
+                sites.push(new_site(name, call_site));
+            }
+        }
+
+        for (call, source_info, fn_span) in self.calls(instance, body) {
+            let site = match enclosing_api(source_info.scope) {
+                Some((site_scope, ..)) => inlined_sites[&site_scope],
+                None => {
+                    // A call that was inlined from elsewhere is reported at the call site.
+                    let span = body.source_scopes[source_info.scope]
+                        .inlined
+                        .map(|(_, call_site)| call_site)
+                        .unwrap_or(fn_span);
+                    let kind = match &call {
+                        Call::Intrinsic(selection) => selection.intrinsic.as_str(),
+                        Call::Api(name, _) => *name,
+                    };
+                    sites.push(new_site(kind, span));
+                    sites.len() - 1
+                }
+            };
+            match call {
+                Call::Intrinsic(selection) => sites[site].selections.push(selection),
+                Call::Api(_, callee) => {
+                    let selections = self.selections_of(callee);
+                    sites[site].selections.extend(selections);
+                }
+            }
+        }
+    }
+}
+
+/// Writes the report of how the constant-time selections, swaps and comparisons of this crate are
+/// lowered to a file in the given output directory.
+pub(crate) fn dump_ct_lowering<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &[CodegenUnit<'tcx>],
+    output_directory: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output_directory = if let Some(directory) = output_directory {
+        fs::create_dir_all(directory)?;
+        directory
+    } else {
+        Path::new(".")
+    };
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    let filename = format!("{crate_name}.ct_lowering.json");
+    let output_path = output_directory.join(&filename);
+    let mut file = File::create_buffered(&output_path)?;
+
+    // A mono item may be placed in several codegen units, but is reported once.
+    let mut instances = FxIndexSet::default();
+    for cgu in codegen_units {
+        for mono_item in cgu.items_in_deterministic_order(tcx) {
+            if let (MonoItem::Fn(instance), _) = mono_item
+                && let ty::InstanceKind::Item(def_id) = instance.def
+                // The APIs themselves are reported at the sites that call them.
+                && api_name(tcx, def_id).is_none()
+            {
+                instances.insert(instance);
+            }
+        }
+    }
+
+    let mut collector = Collector { tcx, callee_selections: Default::default() };
+    let mut sites = Vec::new();
+    for instance in instances {
+        collector.collect_sites(instance, &mut sites);
+    }
+
+    let (lowering, instructions) = tcx.sess.ct_select_lowering;
+    let report = Report {
+        krate: crate_name.to_string(),
+        target: tcx.sess.opts.target_triple.tuple(),
+        lowering,
+        instructions,
+        reliable: tcx.sess.has_reliable_ct_select,
+        sites,
+    };
+    serde_json::to_writer_pretty(&mut file, &report)?;
+    writeln!(file)?;
+
+    Ok(())
+}
@@ -36,6 +36,23 @@ pub(crate) struct LargeAssignmentsLint {
     pub limit: u64,
 }
 
+#[derive(LintDiagnostic)]
+#[diag("this selection of `{$ty}` may be compiled to a branch on target `{$target}`")]
+pub(crate) struct UnreliableCtSelect<'a, 'tcx> {
+    #[label("not guaranteed to be branch-free")]
+    pub span: Span,
+    pub ty: Ty<'tcx>,
+    pub target: &'a str,
+    #[note(
+        "the backend cannot lower constant-time selections on this target without branching, so the condition may leak through timing"
+    )]
+    pub for_target: bool,
+    #[note(
+        "the function is marked `#[ct_select_lowering(cmov)]`, whose selections the backend may lower to branches on this target or at this optimization level, so the condition may leak through timing"
+    )]
+    pub cmov_attr: bool,
+}
+
 #[derive(Diagnostic)]
 #[diag("symbol `{$symbol}` is already defined")]
 pub(crate) struct SymbolAlreadyDefined {
@@ -50,6 +67,12 @@ pub(crate) struct CouldntDumpMonoStats {
     pub error: String,
 }
 
+#[derive(Diagnostic)]
+#[diag("unexpected error occurred while dumping the constant-time lowering report: {$error}")]
+pub(crate) struct CouldntDumpCtLowering {
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag("the above error was encountered while instantiating `{$kind} {$instance}`")]
 pub(crate) struct EncounteredErrorWhileInstantiating<'tcx> {
@@ -14,6 +14,7 @@
 use rustc_span::ErrorGuaranteed;
 
 mod collector;
+mod ct_lowering;
 mod errors;
 mod graph_checks;
 mod mono_checks;
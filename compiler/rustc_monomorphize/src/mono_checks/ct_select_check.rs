@@ -0,0 +1,93 @@
+//! This module ensures that if a constant-time selection is instantiated for a target on which
+//! the backend cannot lower it without branching, or in a function whose
+//! `#[ct_select_lowering(..)]` the backend cannot lower without branching, the build says so.
+
+use rustc_hir::CRATE_HIR_ID;
+use rustc_hir::attrs::CtSelectLoweringAttr;
+use rustc_middle::mir::{self, traversal};
+use rustc_middle::ty::{self, Instance, TyCtxt};
+use rustc_session::lint::builtin::UNRELIABLE_CT_SELECT;
+use rustc_span::sym;
+use rustc_target::spec::CtSelectSupport;
+
+use crate::errors::UnreliableCtSelect;
+
+pub(crate) fn check_ct_selects<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    body: &'tcx mir::Body<'tcx>,
+) {
+    let lowering = tcx.codegen_instance_attrs(instance.def).ct_select_lowering;
+    let reliable = match lowering {
+        None => tcx.sess.has_reliable_ct_select,
+        // A `select`, which is only reliable with optimizations, on some targets.
+        Some(CtSelectLoweringAttr::Cmov) => tcx.sess.has_reliable_ct_select_cmov,
+        // Masking, or an instruction that no optimization can rewrite, unless the target does
+        // not trust any selection.
+        Some(CtSelectLoweringAttr::Masking | CtSelectLoweringAttr::Asm) => {
+            tcx.sess.target.ct_select != Some(CtSelectSupport::Unsupported)
+        }
+    };
+    if reliable {
+        return;
+    }
+
+    for (_, data) in traversal::mono_reachable(body, tcx, instance) {
+        let mir::TerminatorKind::Call { ref func, ref args, fn_span, .. } = data.terminator().kind
+        else {
+            continue;
+        };
+        let callee_ty = instance.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            ty::TypingEnv::fully_monomorphized(),
+            ty::EarlyBinder::bind(func.ty(body, tcx)),
+        );
+        let ty::FnDef(def_id, _) = *callee_ty.kind() else {
+            continue;
+        };
+        let Some(intrinsic) = tcx.intrinsic(def_id) else {
+            continue;
+        };
+        if !matches!(
+            intrinsic.name,
+            sym::ct_select
+                | sym::ct_select_bool
+                | sym::ct_select_i8
+                | sym::ct_select_i16
+                | sym::ct_select_i32
+                | sym::ct_select_i64
+                | sym::ct_select_ptr
+                | sym::ct_select_spec
+        ) {
+            continue;
+        }
+
+        let ty = instance.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            ty::TypingEnv::fully_monomorphized(),
+            ty::EarlyBinder::bind(args[1].node.ty(body, tcx)),
+        );
+        let source_info = data.terminator().source_info;
+        // If the call was inlined by the MIR inliner, report the lint on the call site.
+        let span = body.source_scopes[source_info.scope]
+            .inlined
+            .map(|(_, call_site)| call_site)
+            .unwrap_or(fn_span);
+        // A selection in a function from another crate, instantiated in this one, has no
+        // `HirId` of its own, but can still be allowed or denied for the whole crate.
+        let lint_root = source_info.scope.lint_root(&body.source_scopes).unwrap_or(CRATE_HIR_ID);
+
+        tcx.emit_node_span_lint(
+            UNRELIABLE_CT_SELECT,
+            lint_root,
+            span,
+            UnreliableCtSelect {
+                span,
+                ty,
+                target: tcx.sess.opts.target_triple.tuple(),
+                for_target: lowering != Some(CtSelectLoweringAttr::Cmov),
+                cmov_attr: lowering == Some(CtSelectLoweringAttr::Cmov),
+            },
+        );
+    }
+}
@@ -6,12 +6,14 @@
 use rustc_middle::ty::{Instance, TyCtxt};
 
 mod abi_check;
+mod ct_select_check;
 mod move_check;
 
 fn check_mono_item<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
     let body = tcx.instance_mir(instance.def);
     abi_check::check_feature_dependent_abi(tcx, instance, body);
     move_check::check_moves(tcx, instance, body);
+    ct_select_check::check_ct_selects(tcx, instance, body);
 }
 
 pub(super) fn provide(providers: &mut Providers) {
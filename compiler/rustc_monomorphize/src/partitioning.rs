@@ -123,7 +123,8 @@
 use tracing::debug;
 
 use crate::collector::{self, MonoItemCollectionStrategy, UsageMap};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined};
+use crate::ct_lowering;
+use crate::errors::{CouldntDumpCtLowering, CouldntDumpMonoStats, SymbolAlreadyDefined};
 use crate::graph_checks::target_specific_checks;
 
 struct PartitioningCx<'a, 'tcx> {
@@ -1183,6 +1184,12 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> MonoItemPartitio
         tcx.dcx().emit_fatal(CouldntDumpMonoStats { error: err.to_string() });
     }
 
+    if let SwitchWithOptPath::Enabled(ref path) = tcx.sess.opts.unstable_opts.dump_ct_lowering
+        && let Err(err) = ct_lowering::dump_ct_lowering(tcx, codegen_units, path)
+    {
+        tcx.dcx().emit_fatal(CouldntDumpCtLowering { error: err.to_string() });
+    }
+
     if tcx.sess.opts.unstable_opts.print_mono_items {
         let mut item_to_cgus: UnordMap<_, Vec<_>> = Default::default();
 
@@ -235,17 +235,21 @@ fn check_attributes(
                     // tidy-alphabetical-start
                     AttributeKind::RustcAllowIncoherentImpl(..)
                     | AttributeKind::AutomaticallyDerived(..)
+                    | AttributeKind::Branchless(..)
                     | AttributeKind::CfgAttrTrace
                     | AttributeKind::CfgTrace(..)
                     | AttributeKind::CfiEncoding { .. }
                     | AttributeKind::Cold(..)
                     | AttributeKind::CollapseDebugInfo(..)
                     | AttributeKind::CompilerBuiltins
+                    | AttributeKind::ConstantTime { .. }
                     | AttributeKind::Coroutine(..)
                     | AttributeKind::Coverage (..)
                     | AttributeKind::CrateName { .. }
                     | AttributeKind::CrateType(..)
+                    | AttributeKind::CtSelectLowering(..)
                     | AttributeKind::DebuggerVisualizer(..)
+                    | AttributeKind::Declassify { .. }
                     // `#[doc]` is actually a lot more than just doc comments, so is checked below
                     | AttributeKind::DocComment {..}
                     | AttributeKind::EiiDeclaration { .. }
@@ -335,6 +339,7 @@ fn check_attributes(
                     | AttributeKind::RustcMir(_)
                     | AttributeKind::RustcNeverReturnsNullPointer
                     | AttributeKind::RustcNeverTypeOptions {..}
+                    | AttributeKind::RustcNoBranch
                     | AttributeKind::RustcNoImplicitAutorefs
                     | AttributeKind::RustcNoImplicitBounds
                     | AttributeKind::RustcNoMirInline
@@ -364,6 +369,7 @@ fn check_attributes(
                     | AttributeKind::RustcUnsafeSpecializationMarker(..)
                     | AttributeKind::RustcVariance
                     | AttributeKind::RustcVarianceOfOpaques
+                    | AttributeKind::Secret(..)
                     | AttributeKind::ShouldPanic { .. }
                     | AttributeKind::TestRunner(..)
                     | AttributeKind::ThreadLocal
@@ -0,0 +1,126 @@
+//! Collecting the declassification points of a crate for `-Zreport-declassify`.
+//!
+//! A declassification point is a call to one of the functions through which a secret of
+//! `core::select` may become public, such as `Choice::declassify`, or an expression marked
+//! `#[declassify = "justification"]`, in which secrets may be branched on. These are the places
+//! where secrets legally reach code that branches on them, and so the ones to audit.
+
+use std::fmt::Write;
+
+use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE, LocalDefId};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::{self as hir, find_attr};
+use rustc_middle::middle::declassify::{DeclassifyKind, DeclassifySite};
+use rustc_middle::query::{LocalCrate, Providers};
+use rustc_middle::ty::{TyCtxt, TypeckResults};
+use rustc_span::{Span, sym};
+
+fn is_declassify(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    matches!(
+        tcx.get_diagnostic_name(def_id),
+        Some(
+            sym::choice_declassify
+                | sym::ct_option_into_option
+                | sym::ct_result_into_result
+                | sym::secret_expose
+        )
+    )
+}
+
+struct DeclassifyCollector<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck_results: &'a TypeckResults<'tcx>,
+    caller: LocalDefId,
+    sites: &'a mut Vec<DeclassifySite>,
+}
+
+impl DeclassifyCollector<'_, '_> {
+    fn record(&mut self, kind: DeclassifyKind, span: Span) {
+        self.sites.push(DeclassifySite { caller: self.caller.to_def_id(), kind, span });
+    }
+
+    fn record_call(&mut self, callee: Option<DefId>, span: Span) {
+        if let Some(callee) = callee
+            && is_declassify(self.tcx, callee)
+        {
+            self.record(DeclassifyKind::Call(callee), span);
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for DeclassifyCollector<'_, 'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let Some(justification) = find_attr!(
+            self.tcx.hir_attrs(expr.hir_id),
+            AttributeKind::Declassify { justification, .. } => *justification
+        ) {
+            self.record(DeclassifyKind::Attribute(justification), expr.span);
+        }
+        // Walk first, so that the calls of a chain are recorded in the order they are written.
+        intravisit::walk_expr(self, expr);
+        match expr.kind {
+            hir::ExprKind::MethodCall(segment, ..) => {
+                let callee = self.typeck_results.type_dependent_def_id(expr.hir_id);
+                self.record_call(callee, segment.ident.span);
+            }
+            // Calls through a path, such as `Choice::declassify(c)`, and the functions passed
+            // as values, such as `.map(Choice::declassify)`.
+            hir::ExprKind::Path(ref qpath) => {
+                let callee = self.typeck_results.qpath_res(qpath, expr.hir_id).opt_def_id();
+                self.record_call(callee, expr.span);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn declassify_sites(tcx: TyCtxt<'_>, _: LocalCrate) -> &[DeclassifySite] {
+    if !tcx.sess.opts.unstable_opts.report_declassify {
+        return &[];
+    }
+
+    let mut sites = Vec::new();
+    for caller in tcx.hir_body_owners() {
+        let mut collector = DeclassifyCollector {
+            tcx,
+            typeck_results: tcx.typeck(caller),
+            caller,
+            sites: &mut sites,
+        };
+        collector.visit_body(tcx.hir_body_owned_by(caller));
+    }
+    tcx.arena.alloc_from_iter(sites)
+}
+
+/// Prints the declassification points of the local crate and of the crates it depends on.
+///
+/// Only the crates that were compiled with `-Zreport-declassify` have any to print.
+pub fn print_declassify_sites(tcx: TyCtxt<'_>) {
+    let source_map = tcx.sess.source_map();
+    let mut report = String::new();
+    for &krate in std::iter::once(&LOCAL_CRATE).chain(tcx.crates(())) {
+        for site in tcx.declassify_sites(krate) {
+            let location = source_map.span_to_diagnostic_string(site.span);
+            let caller = tcx.def_path_str(site.caller);
+            match site.kind {
+                DeclassifyKind::Call(callee) => {
+                    let callee = tcx.def_path_str(callee);
+                    writeln!(report, "{location}: `{callee}` in `{caller}`").unwrap();
+                }
+                DeclassifyKind::Attribute(justification) => {
+                    writeln!(
+                        report,
+                        "{location}: `#[declassify]` in `{caller}`: {justification:?}"
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+    print!("{report}");
+}
+
+pub(crate) fn provide(providers: &mut Providers) {
+    providers.declassify_sites = declassify_sites;
+}
@@ -16,6 +16,7 @@
 mod check_export;
 pub mod dead;
 mod debugger_visualizer;
+pub mod declassify;
 mod diagnostic_items;
 mod eii;
 pub mod entry;
@@ -34,6 +35,7 @@ pub fn provide(providers: &mut Providers) {
     check_attr::provide(providers);
     dead::provide(providers);
     debugger_visualizer::provide(providers);
+    declassify::provide(providers);
     diagnostic_items::provide(providers);
     entry::provide(providers);
     lang_items::provide(providers);
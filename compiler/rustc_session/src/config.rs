@@ -2596,6 +2596,11 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         )
     }
 
+    if !unstable_options_enabled && cg.force_ct_select {
+        early_dcx
+            .early_fatal("`-Cforce-ct-select` requires `-Zunstable-options` and a nightly compiler")
+    }
+
     if !nightly_options::is_unstable_enabled(matches) && !unstable_opts.offload.is_empty() {
         early_dcx.early_fatal(
             "`-Zoffload=Enable` also requires `-Zunstable-options` \
@@ -3032,6 +3037,28 @@ pub enum WasiExecModel {
     Reactor,
 }
 
+/// The lowering of `ct_select` on WebAssembly, chosen with `-Zwasm-ct-select`.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum WasmCtSelect {
+    /// The `select` instruction, which is compact but which engines may compile to a branch.
+    Select,
+    /// Bitwise masking, which has no instruction that an engine could compile to a branch.
+    Mask,
+}
+
+/// The lowering of every `ct_select`, chosen with `-Zct-select-lowering`.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum CtSelectStrategy {
+    /// A `select` that the backend lowers to the conditional move or select instruction of the
+    /// target, if it has one.
+    Cmov,
+    /// Bitwise masking, with the mask hidden from the optimizers.
+    Masking,
+    /// The conditional move or select instruction of the target written as inline assembly, which
+    /// no optimization can see into, or masking on targets without one.
+    Asm,
+}
+
 /// Command-line arguments passed to the compiler have to be incorporated with
 /// the dependency tracking system for incremental compilation. This module
 /// provides some utilities to make this more convenient.
@@ -3073,11 +3100,12 @@ pub(crate) mod dep_tracking {
 
     use super::{
         AnnotateMoves, AutoDiff, BranchProtection, CFGuard, CFProtection, CoverageOptions,
-        CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FmtDebug, FunctionReturn,
-        InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, MirStripDebugInfo, NextSolverConfig, Offload, OptLevel, OutFileName, OutputType,
-        OutputTypes, PatchableFunctionEntry, Polonius, ResolveDocLinks, SourceFileHashAlgorithm,
-        SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion, WasiExecModel,
+        CrateType, CtSelectStrategy, DebugInfo, DebugInfoCompression, ErrorOutputType, FmtDebug,
+        FunctionReturn, InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto,
+        LocationDetail, LtoCli, MirStripDebugInfo, NextSolverConfig, Offload, OptLevel,
+        OutFileName, OutputType, OutputTypes, PatchableFunctionEntry, Polonius, ResolveDocLinks,
+        SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion,
+        WasiExecModel, WasmCtSelect,
     };
     use crate::lint;
     use crate::utils::NativeLib;
@@ -3132,6 +3160,8 @@ fn hash(
         PathBuf,
         lint::Level,
         WasiExecModel,
+        WasmCtSelect,
+        CtSelectStrategy,
         u32,
         FramePointer,
         RelocModel,
@@ -143,6 +143,7 @@ pub(crate) fn disallow_cfgs(sess: &Session, user_cfgs: &Cfg) {
             | (sym::target_has_atomic, Some(_))
             | (sym::target_has_atomic_equal_alignment, Some(_))
             | (sym::target_has_atomic_load_store, Some(_))
+            | (sym::target_has_reliable_ct_select, None | Some(_))
             | (sym::target_has_reliable_f16, None | Some(_))
             | (sym::target_has_reliable_f16_math, None | Some(_))
             | (sym::target_has_reliable_f128, None | Some(_))
@@ -477,6 +478,8 @@ macro_rules! ins {
             ins!(sym, no_values).extend(atomic_values);
         }
 
+        ins!(sym::target_has_reliable_ct_select, no_values);
+
         ins!(sym::target_thread_local, no_values);
 
         ins!(sym::ub_checks, no_values);
@@ -161,6 +161,11 @@ pub(crate) struct CannotMixAndMatchSanitizers {
 #[diag("`-Zsanitizer-cfi-canonical-jump-tables` requires `-Zsanitizer=cfi`")]
 pub(crate) struct SanitizerCfiCanonicalJumpTablesRequiresCfi;
 
+#[derive(Diagnostic)]
+#[diag("`-Zsanitizer=constant-time` requires `-Zsanitizer-dataflow-abilist`")]
+#[note("DataFlowSanitizer's ABI list is `share/dfsan_abilist.txt` in Clang's resource directory")]
+pub(crate) struct SanitizerConstantTimeRequiresAbiList;
+
 #[derive(Diagnostic)]
 #[diag("`-Zsanitizer-cfi-generalize-pointers` requires `-Zsanitizer=cfi` or `-Zsanitizer=kcfi`")]
 pub(crate) struct SanitizerCfiGeneralizePointersRequiresCfi;
@@ -185,6 +190,21 @@ pub(crate) struct CannotMixAndMatchSanitizers {
 #[diag("`-Zvirtual-function-elimination` requires `-Clto`")]
 pub(crate) struct UnstableVirtualFunctionElimination;
 
+#[derive(Diagnostic)]
+#[diag("`-Zverify-ct-select` cannot check the code generated by the linker")]
+#[note(
+    "with `-Clinker-plugin-lto`, only the code that rustc generates before the link is checked, and the linker may still lower a `ct_select` to a branch"
+)]
+pub(crate) struct VerifyCtSelectLinkerPluginLto;
+
+#[derive(Diagnostic)]
+#[diag("`-Z{$flag}` is not supported on `{$target_triple}`")]
+#[note("the code is described in ELF sections, which this target does not use")]
+pub(crate) struct CtSectionsRequireElf<'a> {
+    pub(crate) flag: &'static str,
+    pub(crate) target_triple: &'a TargetTuple,
+}
+
 #[derive(Diagnostic)]
 #[diag("requested DWARF version {$dwarf_version} is not supported")]
 #[help("supported DWARF versions are 2, 3, 4 and 5")]
@@ -511,6 +531,10 @@ pub(crate) struct UnsupportedRegparm {
 #[diag("`-Zreg-struct-return` is only supported on x86")]
 pub(crate) struct UnsupportedRegStructReturnArch;
 
+#[derive(Diagnostic)]
+#[diag("`-Zvalgrind-ct-annotations` is only supported on x86, x86_64 and AArch64")]
+pub(crate) struct ValgrindCtAnnotationsUnsupportedArch;
+
 #[derive(Diagnostic)]
 #[diag("failed to create profiler: {$err}")]
 pub(crate) struct FailedToCreateProfiler {
@@ -107,7 +107,8 @@ pub(super) fn sanitizer(l: &TargetModifier, r: Option<&TargetModifier>) -> bool
             | SanitizerSet::KCFI
             | SanitizerSet::KERNELADDRESS
             | SanitizerSet::SAFESTACK
-            | SanitizerSet::DATAFLOW;
+            | SanitizerSet::DATAFLOW
+            | SanitizerSet::CONSTANT_TIME;
 
         lparsed & tmod_sanitizers == rparsed & tmod_sanitizers
     }
@@ -811,7 +812,7 @@ mod desc {
     pub(crate) const parse_patchable_function_entry: &str = "either two comma separated integers (total_nops,prefix_nops), with prefix_nops <= total_nops, or one integer (total_nops)";
     pub(crate) const parse_opt_panic_strategy: &str = parse_panic_strategy;
     pub(crate) const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
-    pub(crate) const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `dataflow`, `hwaddress`, `kcfi`, `kernel-address`, `leak`, `memory`, `memtag`, `safestack`, `shadow-call-stack`, `thread`, or 'realtime'";
+    pub(crate) const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `constant-time`, `dataflow`, `hwaddress`, `kcfi`, `kernel-address`, `leak`, `memory`, `memtag`, `safestack`, `shadow-call-stack`, `thread`, or 'realtime'";
     pub(crate) const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
     pub(crate) const parse_cfguard: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
@@ -858,6 +859,8 @@ mod desc {
     pub(crate) const parse_terminal_url: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), or `auto`";
     pub(crate) const parse_wasi_exec_model: &str = "either `command` or `reactor`";
+    pub(crate) const parse_wasm_ct_select: &str = "either `select` or `mask`";
+    pub(crate) const parse_ct_select_lowering: &str = "one of `cmov`, `masking`, or `asm`";
     pub(crate) const parse_split_debuginfo: &str =
         "one of supported split-debuginfo modes (`off`, `packed`, or `unpacked`)";
     pub(crate) const parse_split_dwarf_kind: &str =
@@ -1261,6 +1264,7 @@ pub(crate) fn parse_sanitizers(slot: &mut SanitizerSet, v: Option<&str>) -> bool
                 *slot |= match s {
                     "address" => SanitizerSet::ADDRESS,
                     "cfi" => SanitizerSet::CFI,
+                    "constant-time" => SanitizerSet::CONSTANT_TIME,
                     "dataflow" => SanitizerSet::DATAFLOW,
                     "kcfi" => SanitizerSet::KCFI,
                     "kernel-address" => SanitizerSet::KERNELADDRESS,
@@ -1876,6 +1880,28 @@ pub(crate) fn parse_wasi_exec_model(slot: &mut Option<WasiExecModel>, v: Option<
         true
     }
 
+    pub(crate) fn parse_wasm_ct_select(slot: &mut WasmCtSelect, v: Option<&str>) -> bool {
+        match v {
+            Some("select") => *slot = WasmCtSelect::Select,
+            Some("mask") => *slot = WasmCtSelect::Mask,
+            _ => return false,
+        }
+        true
+    }
+
+    pub(crate) fn parse_ct_select_lowering(
+        slot: &mut Option<CtSelectStrategy>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("cmov") => Some(CtSelectStrategy::Cmov),
+            Some("masking") => Some(CtSelectStrategy::Masking),
+            Some("asm") => Some(CtSelectStrategy::Asm),
+            _ => return false,
+        };
+        true
+    }
+
     pub(crate) fn parse_split_debuginfo(
         slot: &mut Option<SplitDebuginfo>,
         v: Option<&str>,
@@ -2098,6 +2124,8 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "emit bitcode in rlibs (default: yes)"),
     extra_filename: String = (String::new(), parse_string, [UNTRACKED],
         "extra data to put in each output filename"),
+    force_ct_select: bool = (false, parse_bool, [TRACKED],
+        "lower `select_unpredictable` like `ct_select`, which is never a branch (default: no)"),
     force_frame_pointers: FramePointer = (FramePointer::MayOmit, parse_frame_pointer, [TRACKED],
         "force use of the frame pointers"),
     #[rustc_lint_opt_deny_field_access("use `Session::must_emit_unwind_tables` instead of this field")]
@@ -2276,6 +2304,20 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "inject the given attribute in the crate"),
     cross_crate_inline_threshold: InliningThreshold = (InliningThreshold::Sometimes(100), parse_inlining_threshold, [TRACKED],
         "threshold to allow cross crate inlining of functions"),
+    ct_metadata: bool = (false, parse_bool, [TRACKED],
+        "describe the `#[constant_time]` functions and the functions with secret parameters in a \
+        section of the object files (default: no)"),
+    ct_pin_secrets: bool = (false, parse_bool, [TRACKED],
+        "keep the scalar values of `Secret` out of stack slots where possible, and report the \
+        functions that spill them (default: no)"),
+    ct_regions: bool = (false, parse_bool, [TRACKED],
+        "record the code of `#[constant_time]` functions and `ct_select`s in sections of the \
+        object files (default: no)"),
+    ct_scrub_stack: bool = (false, parse_bool, [TRACKED],
+        "zero the stack frame of each `#[constant_time]` function when it returns (default: no)"),
+    ct_select_lowering: Option<CtSelectStrategy> = (None, parse_ct_select_lowering, [TRACKED],
+        "lower every `ct_select` to a `select` (`cmov`), bitwise masking (`masking`), or the \
+        select instruction as inline assembly (`asm`) (default: chosen for the target)"),
     debug_info_for_profiling: bool = (false, parse_bool, [TRACKED],
         "emit discriminators and other data necessary for AutoFDO"),
     debug_info_type_line_numbers: bool = (false, parse_bool, [TRACKED],
@@ -2293,6 +2335,10 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "Direct or use GOT indirect to reference external data symbols"),
     dual_proc_macros: bool = (false, parse_bool, [TRACKED],
         "load proc macros for both target and host, but only link to the target (default: no)"),
+    dump_ct_lowering: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "output a JSON report of how each constant-time selection, swap and comparison is \
+        lowered by the backend"),
     dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
         "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
         (default: no)"),
@@ -2532,6 +2578,9 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         Currently the only option available"),
     on_broken_pipe: OnBrokenPipe = (OnBrokenPipe::Default, parse_on_broken_pipe, [TRACKED],
         "behavior of std::io::ErrorKind::BrokenPipe (SIGPIPE)"),
+    opaque_ct_select: bool = (false, parse_bool, [TRACKED],
+        "hide the condition of `ct_select` from the optimizer, so that the selection is kept even \
+        if the condition is known at compile time (default: no)"),
     osx_rpath_install_name: bool = (false, parse_bool, [TRACKED],
         "pass `-install_name @rpath/...` to the macOS linker (default: no)"),
     packed_bundled_libs: bool = (false, parse_bool, [TRACKED],
@@ -2598,6 +2647,10 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
     remark_dir: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
         "directory into which to write optimization remarks (if not specified, they will be \
 written to standard error output)"),
+    report_declassify: bool = (false, parse_bool, [TRACKED],
+        "print the calls to the functions that declassify secrets, such as `Choice::declassify`, \
+        and the expressions marked `#[declassify]`, in this crate and in the crates it depends on \
+        that were compiled with this flag (default: no)"),
     retpoline: bool = (false, parse_bool, [TRACKED TARGET_MODIFIER],
         "enables retpoline-indirect-branches and retpoline-indirect-calls target features (default: no)"),
     retpoline_external_thunk: bool = (false, parse_bool, [TRACKED TARGET_MODIFIER],
@@ -2772,6 +2825,9 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "use legacy .ctors section for initializers rather than .init_array"),
     use_sync_unwind: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "Generate sync unwind tables instead of async unwind tables (default: no)"),
+    valgrind_ct_annotations: bool = (false, parse_bool, [TRACKED],
+        "emit Valgrind client requests that mark the values passed to `ct_classify` as undefined \
+        for Memcheck, and those passed to `ct_declassify` as defined again (default: no)"),
     validate_mir: bool = (false, parse_bool, [UNTRACKED],
         "validate MIR after each transformation"),
     verbose_asm: bool = (false, parse_bool, [TRACKED],
@@ -2779,6 +2835,8 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
     #[rustc_lint_opt_deny_field_access("use `Session::verbose_internals` instead of this field")]
     verbose_internals: bool = (false, parse_bool, [TRACKED_NO_CRATE_HASH],
         "in general, enable more debug printouts (default: no)"),
+    verify_ct_select: bool = (false, parse_bool, [TRACKED],
+        "check the generated assembly for branches emitted for `ct_select` (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::verify_llvm_ir` instead of this field")]
     verify_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "verify LLVM IR (default: no)"),
@@ -2792,6 +2850,9 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
     // FIXME remove this after a couple releases
     wasm_c_abi: () = ((), parse_wasm_c_abi, [TRACKED],
         "use spec-compliant C ABI for `wasm32-unknown-unknown` (deprecated, always enabled)"),
+    wasm_ct_select: WasmCtSelect = (WasmCtSelect::Select, parse_wasm_ct_select, [TRACKED],
+        "lower `ct_select` on WebAssembly to the `select` instruction or to bitwise masking \
+        (default: select)"),
     write_long_types_to_disk: bool = (true, parse_bool, [UNTRACKED],
         "whether long type names should be written to files instead of being printed in errors"),
     // tidy-alphabetical-end
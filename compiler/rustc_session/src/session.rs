@@ -29,9 +29,9 @@
 use rustc_span::{RealFileName, Span, Symbol};
 use rustc_target::asm::InlineAsmArch;
 use rustc_target::spec::{
-    Arch, CodeModel, DebuginfoKind, Os, PanicStrategy, RelocModel, RelroLevel, SanitizerSet,
-    SmallDataThresholdSupport, SplitDebuginfo, StackProtector, SymbolVisibility, Target,
-    TargetTuple, TlsModel, apple,
+    Arch, BinaryFormat, CodeModel, DebuginfoKind, Os, PanicStrategy, RelocModel, RelroLevel,
+    SanitizerSet, SmallDataThresholdSupport, SplitDebuginfo, StackProtector, SymbolVisibility,
+    Target, TargetTuple, TlsModel, apple,
 };
 
 use crate::code_stats::CodeStats;
@@ -135,6 +135,18 @@ pub struct Session {
     /// Set of enabled features for the current target, including unstable ones.
     pub unstable_target_features: FxIndexSet<Symbol>,
 
+    /// Whether the codegen backend lowers the `ct_select_*` intrinsics without branches or
+    /// library calls on the current target.
+    pub has_reliable_ct_select: bool,
+
+    /// Whether the codegen backend lowers the `ct_select_*` intrinsics without branches or
+    /// library calls in the functions marked `#[ct_select_lowering(cmov)]`.
+    pub has_reliable_ct_select_cmov: bool,
+
+    /// The name of the lowering of the `ct_select_*` intrinsics, and the instructions they are
+    /// lowered to, as reported by `-Zdump-ct-lowering`.
+    pub ct_select_lowering: (&'static str, &'static [&'static str]),
+
     /// The version of the rustc process, possibly including a commit hash and description.
     pub cfg_version: &'static str,
 
@@ -1082,6 +1094,9 @@ pub fn build_session(
         asm_arch,
         target_features: Default::default(),
         unstable_target_features: Default::default(),
+        has_reliable_ct_select: true,
+        has_reliable_ct_select_cmov: true,
+        ct_select_lowering: ("unknown", &[]),
         cfg_version,
         using_internal_features,
         target_filesearch,
@@ -1195,6 +1210,14 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         sess.dcx().emit_err(errors::SanitizerCfiRequiresSingleCodegenUnit);
     }
 
+    // The constant-time sanitizer reads the labels of DataFlowSanitizer, whose ABI list must be
+    // given for the calls into its runtime to be instrumented correctly.
+    if sess.sanitizers().contains(SanitizerSet::CONSTANT_TIME)
+        && sess.opts.unstable_opts.sanitizer_dataflow_abilist.is_empty()
+    {
+        sess.dcx().emit_err(errors::SanitizerConstantTimeRequiresAbiList);
+    }
+
     // Canonical jump tables requires CFI.
     if sess.is_sanitizer_cfi_canonical_jump_tables_disabled() {
         if !sess.is_sanitizer_cfi_enabled() {
@@ -1237,6 +1260,25 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // Under linker-plugin LTO, the final machine code is generated by the linker, after rustc
+    // has checked its own output.
+    if sess.opts.unstable_opts.verify_ct_select && sess.opts.cg.linker_plugin_lto.enabled() {
+        sess.dcx().emit_warn(errors::VerifyCtSelectLinkerPluginLto);
+    }
+
+    if sess.target.binary_format != BinaryFormat::Elf {
+        let flags = [
+            ("ct-regions", sess.opts.unstable_opts.ct_regions),
+            ("ct-metadata", sess.opts.unstable_opts.ct_metadata),
+        ];
+        for (flag, _) in flags.into_iter().filter(|&(_, enabled)| enabled) {
+            sess.dcx().emit_err(errors::CtSectionsRequireElf {
+                flag,
+                target_triple: &sess.opts.target_triple,
+            });
+        }
+    }
+
     if sess.opts.unstable_opts.stack_protector != StackProtector::None {
         if !sess.target.options.supports_stack_protector {
             sess.dcx().emit_warn(errors::StackProtectorNotSupportedForTarget {
@@ -1323,6 +1365,12 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    if sess.opts.unstable_opts.valgrind_ct_annotations
+        && !matches!(sess.target.arch, Arch::X86 | Arch::X86_64 | Arch::AArch64)
+    {
+        sess.dcx().emit_err(errors::ValgrindCtAnnotationsUnsupportedArch);
+    }
+
     // The code model check applies to `thunk` and `thunk-extern`, but not `thunk-inline`, so it is
     // kept as a `match` to force a change if new ones are added, even if we currently only support
     // `thunk-extern` like Clang.
@@ -205,6 +205,7 @@
         Center,
         Char,
         Child,
+        Choice,
         Cleanup,
         Clone,
         CoercePointee,
@@ -219,6 +220,8 @@
         ControlFlow,
         Copy,
         Cow,
+        CtOption,
+        CtResult,
         Debug,
         DebugStruct,
         Decodable,
@@ -362,6 +365,7 @@
         RwLockReadGuard,
         RwLockWriteGuard,
         Saturating,
+        Secret,
         SeekFrom,
         SelfTy,
         Send,
@@ -622,6 +626,7 @@
         bpf_target_feature,
         braced_empty_structs,
         branch,
+        branchless,
         breakpoint,
         bridge,
         bswap,
@@ -631,6 +636,9 @@
         built,
         builtin_syntax,
         bundle,
+        byte_add,
+        byte_offset,
+        byte_sub,
         c,
         c_dash_variadic,
         c_str,
@@ -677,6 +685,7 @@
         cfg_target_feature,
         cfg_target_has_atomic,
         cfg_target_has_atomic_equal_alignment,
+        cfg_target_has_reliable_ct_select,
         cfg_target_has_reliable_f16_f128,
         cfg_target_thread_local,
         cfg_target_vendor,
@@ -690,6 +699,7 @@
         char_to_digit,
         child_id,
         child_kill,
+        choice_declassify,
         client,
         clippy,
         clobber_abi,
@@ -701,6 +711,7 @@
         closure_lifetime_binder,
         closure_to_fn_coercion,
         closure_track_caller,
+        cmov,
         cmp,
         cmp_max,
         cmp_min,
@@ -783,6 +794,10 @@
         const_try,
         const_ty_placeholder: "<const_ty>",
         constant,
+        constant_time,
+        constant_time_eq_ct_eq,
+        constant_time_select_ct_select,
+        constant_time_select_ct_swap,
         constructor,
         contract_build_check_ensures,
         contract_check_ensures,
@@ -832,6 +847,25 @@
         cstr_type,
         cstring_as_c_str,
         cstring_type,
+        ct_black_box,
+        ct_classify,
+        ct_declassify,
+        ct_eq_fn,
+        ct_option_into_option,
+        ct_result_into_result,
+        ct_select,
+        ct_select_bool,
+        ct_select_fn,
+        ct_select_i8,
+        ct_select_i16,
+        ct_select_i32,
+        ct_select_i64,
+        ct_select_lowering,
+        ct_select_ptr,
+        ct_select_spec,
+        ct_select_spec_fn,
+        ct_swap_fn,
+        ct_test,
         ctlz,
         ctlz_nonzero,
         ctpop,
@@ -861,6 +895,7 @@
         debugger_visualizer,
         decl_macro,
         declare_lint_pass,
+        declassify,
         decode,
         decorated,
         default_alloc_error_handler,
@@ -1182,6 +1217,8 @@
         generic_pattern_types,
         generics,
         get_context,
+        get_unchecked,
+        get_unchecked_mut,
         global_alloc_ty,
         global_allocator,
         global_asm,
@@ -1424,6 +1461,7 @@
         marker,
         marker_trait_attr,
         masked,
+        masking,
         match_beginning_vert,
         match_default_bindings,
         matches_macro,
@@ -2002,6 +2040,7 @@
         rustc_must_implement_one_of,
         rustc_never_returns_null_ptr,
         rustc_never_type_options,
+        rustc_no_branch,
         rustc_no_implicit_autorefs,
         rustc_no_implicit_bounds,
         rustc_no_mir_inline,
@@ -2066,6 +2105,10 @@
         saturating_sub,
         sdylib,
         search_unbox,
+        secret,
+        secret_expose,
+        secure_zeroize,
+        select,
         select_unpredictable,
         self_in_typedefs,
         self_struct_ctor,
@@ -2189,6 +2232,7 @@
         sparc_target_feature,
         spe_acc,
         specialization,
+        speculative_load_hardening,
         speed,
         spirv,
         spotlight,
@@ -2275,6 +2319,7 @@
         target_has_atomic,
         target_has_atomic_equal_alignment,
         target_has_atomic_load_store,
+        target_has_reliable_ct_select,
         target_has_reliable_f16,
         target_has_reliable_f16_math,
         target_has_reliable_f128,
@@ -2533,6 +2578,7 @@
         wrapping_add,
         wrapping_div,
         wrapping_mul,
+        wrapping_offset,
         wrapping_rem,
         wrapping_rem_euclid,
         wrapping_sub,
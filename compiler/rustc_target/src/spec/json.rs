@@ -5,11 +5,11 @@
 
 use super::crt_objects::CrtObjects;
 use super::{
-    Abi, Arch, BinaryFormat, CodeModel, DebuginfoKind, Env, FloatAbi, FramePointer, LinkArgsCli,
-    LinkSelfContainedComponents, LinkSelfContainedDefault, LinkerFlavorCli, LldFlavor,
-    MergeFunctions, Os, PanicStrategy, RelocModel, RelroLevel, RustcAbi, SanitizerSet,
-    SmallDataThresholdSupport, SplitDebuginfo, StackProbeType, StaticCow, SymbolVisibility, Target,
-    TargetKind, TargetOptions, TargetWarnings, TlsModel,
+    Abi, Arch, BinaryFormat, CodeModel, CtSelectSupport, DebuginfoKind, Env, FloatAbi,
+    FramePointer, LinkArgsCli, LinkSelfContainedComponents, LinkSelfContainedDefault,
+    LinkerFlavorCli, LldFlavor, MergeFunctions, Os, PanicStrategy, RelocModel, RelroLevel,
+    RustcAbi, SanitizerSet, SmallDataThresholdSupport, SplitDebuginfo, StackProbeType, StaticCow,
+    SymbolVisibility, Target, TargetKind, TargetOptions, TargetWarnings, TlsModel,
 };
 use crate::json::{Json, ToJson};
 use crate::spec::AbiMap;
@@ -226,6 +226,7 @@ macro_rules! forward_opt {
         forward!(small_data_threshold_support);
         forward!(entry_name);
         forward!(supports_xray);
+        forward_opt!(ct_select);
 
         // we're going to run `update_from_cli`, but that won't change the target's AbiMap
         // FIXME: better factor the Target definition so we enforce this on a type level
@@ -410,6 +411,7 @@ macro_rules! target_option_val {
         target_option_val!(entry_name);
         target_option_val!(entry_abi);
         target_option_val!(supports_xray);
+        target_option_val!(ct_select);
 
         // Serializing `-Clink-self-contained` needs a dynamic key to support the
         // backwards-compatible variants.
@@ -631,6 +633,7 @@ struct TargetSpecJson {
     small_data_threshold_support: Option<SmallDataThresholdSupport>,
     entry_name: Option<StaticCow<str>>,
     supports_xray: Option<bool>,
+    ct_select: Option<CtSelectSupport>,
     entry_abi: Option<ExternAbiWrapper>,
 }
 
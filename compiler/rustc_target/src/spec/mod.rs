@@ -1012,6 +1012,21 @@ pub enum RustcAbi {
     parse_error_type = "rustc abi";
 }
 
+crate::target_spec_enum! {
+    /// The support of a target for constant-time selections.
+    pub enum CtSelectSupport {
+        /// The target has a conditional move or select instruction, which selections are
+        /// lowered to.
+        Cmov = "cmov",
+        /// Selections are lowered to bitwise masking, hidden from the optimizers.
+        Masking = "masking",
+        /// Selections cannot be guaranteed not to branch on this target.
+        Unsupported = "unsupported",
+    }
+
+    parse_error_type = "constant-time select support";
+}
+
 crate::target_spec_enum! {
     pub enum TlsModel {
         GeneralDynamic = "global-dynamic",
@@ -1178,6 +1193,7 @@ impl SanitizerSet: u16 {
         const SAFESTACK = 1 << 10;
         const DATAFLOW = 1 << 11;
         const REALTIME = 1 << 12;
+        const CONSTANT_TIME = 1 << 13;
     }
 }
 rustc_data_structures::external_bitflags_debug! { SanitizerSet }
@@ -1209,6 +1225,13 @@ impl SanitizerSet {
         (SanitizerSet::CFI, SanitizerSet::KCFI),
         (SanitizerSet::MEMTAG, SanitizerSet::KERNELADDRESS),
         (SanitizerSet::KERNELADDRESS, SanitizerSet::SAFESTACK),
+        // The constant-time sanitizer is built on the instrumentation of DataFlowSanitizer, which
+        // does not share its shadow memory with the other sanitizers.
+        (SanitizerSet::CONSTANT_TIME, SanitizerSet::ADDRESS),
+        (SanitizerSet::CONSTANT_TIME, SanitizerSet::DATAFLOW),
+        (SanitizerSet::CONSTANT_TIME, SanitizerSet::HWADDRESS),
+        (SanitizerSet::CONSTANT_TIME, SanitizerSet::MEMORY),
+        (SanitizerSet::CONSTANT_TIME, SanitizerSet::THREAD),
     ];
 
     /// Return sanitizer's name
@@ -1218,6 +1241,7 @@ pub fn as_str(self) -> Option<&'static str> {
         Some(match self {
             SanitizerSet::ADDRESS => "address",
             SanitizerSet::CFI => "cfi",
+            SanitizerSet::CONSTANT_TIME => "constant-time",
             SanitizerSet::DATAFLOW => "dataflow",
             SanitizerSet::KCFI => "kcfi",
             SanitizerSet::KERNELADDRESS => "kernel-address",
@@ -1263,6 +1287,7 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "address" => SanitizerSet::ADDRESS,
             "cfi" => SanitizerSet::CFI,
+            "constant-time" => SanitizerSet::CONSTANT_TIME,
             "dataflow" => SanitizerSet::DATAFLOW,
             "kcfi" => SanitizerSet::KCFI,
             "kernel-address" => SanitizerSet::KERNELADDRESS,
@@ -2600,6 +2625,12 @@ pub struct TargetOptions {
     /// Whether the target supports XRay instrumentation.
     pub supports_xray: bool,
 
+    /// How constant-time selections can be lowered on this target. Overrides what the backend
+    /// would otherwise choose for the architecture, and decides whether
+    /// `cfg(target_has_reliable_ct_select)` is set. Defaults to `None`, leaving both to the
+    /// backend.
+    pub ct_select: Option<CtSelectSupport>,
+
     /// The default address space for this target. When using LLVM as a backend, most targets simply
     /// use LLVM's default address space (0). Some other targets, such as CHERI targets, use a
     /// custom default address space (in this specific case, `200`).
@@ -2840,6 +2871,7 @@ fn default() -> TargetOptions {
             entry_name: "main".into(),
             entry_abi: CanonAbi::C,
             supports_xray: false,
+            ct_select: None,
             default_address_space: rustc_abi::AddressSpace::ZERO,
             small_data_threshold_support: SmallDataThresholdSupport::DefaultForArch,
         }
@@ -3486,6 +3518,23 @@ pub fn max_reliable_alignment(&self) -> Align {
         }
     }
 
+    /// Returns the instruction that keeps the instructions after it from executing speculatively
+    /// until the instructions before it have completed, if the target has one that every CPU of
+    /// the architecture runs. Used by `ct_select_spec`.
+    pub fn speculation_barrier(&self) -> Option<&'static str> {
+        match self.arch {
+            // `lfence` was added with SSE2, and is a barrier since the Spectre mitigations of
+            // the CPU vendors.
+            Arch::X86 | Arch::X86_64 => Some("lfence"),
+            // `csdb`, spelled as its hint so that assemblers without the Spectre extensions
+            // accept it. Older CPUs run it as a `nop`, and do not need it.
+            Arch::AArch64 | Arch::Arm64EC => Some("hint #20"),
+            // The `ori 31,31,0` barrier of the PowerPC Spectre mitigations.
+            Arch::PowerPC | Arch::PowerPC64 => Some("ori 31, 31, 0"),
+            _ => None,
+        }
+    }
+
     pub fn vendor_symbol(&self) -> Symbol {
         Symbol::intern(&self.vendor)
     }
@@ -12,6 +12,8 @@
 #[cfg(not(no_global_oom_handling))]
 pub mod linked_list;
 #[cfg(not(no_global_oom_handling))]
+pub mod oblivious_ring_buffer;
+#[cfg(not(no_global_oom_handling))]
 pub mod vec_deque;
 
 #[cfg(not(no_global_oom_handling))]
@@ -55,6 +57,11 @@ pub mod btree_set {
 #[cfg(not(test))]
 pub use linked_list::LinkedList;
 #[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "ct_select", issue = "none")]
+#[doc(no_inline)]
+#[cfg(not(test))]
+pub use oblivious_ring_buffer::ObliviousRingBuffer;
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 #[cfg(not(test))]
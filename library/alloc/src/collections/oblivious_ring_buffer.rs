@@ -0,0 +1,180 @@
+//! A fixed-capacity ring buffer that hides which slots are accessed.
+//!
+//! [`ObliviousRingBuffer`] is meant for constant-time protocol state, such as
+//! buffering records whose number or lengths are secret. Every operation
+//! reads or writes every slot, so neither its timing nor its memory access
+//! pattern reveals the positions of the elements.
+
+#![unstable(feature = "ct_select", issue = "none")]
+
+use core::fmt;
+use core::select::{
+    Choice, ConstantTimeEq, ConstantTimeOrd, ConstantTimeSelect, CtOption, ct_lookup, ct_select,
+};
+
+use crate::boxed::Box;
+
+/// A ring buffer with room for `N` elements whose operations touch every
+/// slot.
+///
+/// The number of elements and the position of the front of the buffer are
+/// treated as secret. [`push`] and [`pop`] write or read the slot at the
+/// relevant end by selecting over all `N` slots with [`ct_select`], and
+/// their conditional forms take a [`Choice`] instead of being skipped, so a
+/// sequence of operations takes the same time and touches the same memory
+/// regardless of the data. As a consequence, every operation costs time
+/// proportional to `N` rather than constant time.
+///
+/// Pushing to a full buffer and popping from an empty one are not errors:
+/// nothing changes, and this is reported through the returned [`Choice`] or
+/// [`CtOption`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::collections::ObliviousRingBuffer;
+/// use std::select::Choice;
+///
+/// let mut records = ObliviousRingBuffer::<u32, 4>::new();
+/// records.push(7);
+/// records.conditional_push(8, Choice::from(false));
+/// records.push(9);
+///
+/// assert_eq!(records.read(1).unwrap(), 9);
+/// assert_eq!(records.pop().unwrap(), 7);
+/// assert_eq!(records.pop().unwrap(), 9);
+/// assert!(records.is_empty().declassify());
+/// ```
+///
+/// [`push`]: ObliviousRingBuffer::push
+/// [`pop`]: ObliviousRingBuffer::pop
+/// [`ct_select`]: core::select::ct_select
+#[unstable(feature = "ct_select", issue = "none")]
+pub struct ObliviousRingBuffer<T, const N: usize> {
+    slots: Box<[T; N]>,
+    /// The slot holding the front element. Always less than `N`.
+    head: usize,
+    /// The number of elements. At most `N`.
+    len: usize,
+}
+
+impl<T: ConstantTimeSelect + Default, const N: usize> ObliviousRingBuffer<T, N> {
+    /// Creates an empty ring buffer, with every slot set to the default
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn new() -> Self {
+        assert!(N > 0, "ring buffer capacity must be nonzero");
+        ObliviousRingBuffer { slots: Box::new([T::default(); N]), head: 0, len: 0 }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeSelect + Default, const N: usize> Default for ObliviousRingBuffer<T, N> {
+    /// Creates an empty ring buffer.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ConstantTimeSelect, const N: usize> ObliviousRingBuffer<T, N> {
+    /// Returns the number of elements the buffer can hold.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns whether the buffer holds no elements.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn is_empty(&self) -> Choice {
+        self.len.ct_eq(&0)
+    }
+
+    /// Returns whether the buffer holds `N` elements.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn is_full(&self) -> Choice {
+        self.len.ct_eq(&N)
+    }
+
+    /// Returns the slot `offset` places after the front, where `offset` is at
+    /// most `N`.
+    #[inline]
+    fn slot(&self, offset: usize) -> usize {
+        // The sum is less than `2 * N`, so wrapping it takes at most one
+        // subtraction.
+        let pos = self.head + offset;
+        ct_select(pos.ct_lt(&N), pos, pos.wrapping_sub(N))
+    }
+
+    /// Appends `value` to the back of the buffer, returning whether it was
+    /// added.
+    ///
+    /// Nothing is added if the buffer is full.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn push(&mut self, value: T) -> Choice {
+        self.conditional_push(value, Choice::from(true))
+    }
+
+    /// Appends `value` to the back of the buffer if `choice` is set, returning
+    /// whether it was added.
+    ///
+    /// Nothing is added if the buffer is full. Every slot is written either
+    /// way.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn conditional_push(&mut self, value: T, choice: Choice) -> Choice {
+        let pushed = choice & !self.is_full();
+        let tail = self.slot(self.len);
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            *slot = ct_select(pushed & i.ct_eq(&tail), value, *slot);
+        }
+        self.len += ct_select(pushed, 1, 0);
+        pushed
+    }
+
+    /// Removes the front element of the buffer and returns it, or none if
+    /// the buffer is empty.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn pop(&mut self) -> CtOption<T> {
+        self.conditional_pop(Choice::from(true))
+    }
+
+    /// Removes the front element of the buffer if `choice` is set and returns
+    /// it, or none if `choice` is unset or the buffer is empty.
+    ///
+    /// Every slot is read either way.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn conditional_pop(&mut self, choice: Choice) -> CtOption<T> {
+        let popped = choice & !self.is_empty();
+        let value = ct_lookup(&self.slots[..], self.head);
+        self.head = ct_select(popped, self.slot(1), self.head);
+        self.len -= ct_select(popped, 1, 0);
+        CtOption::new(value, popped)
+    }
+
+    /// Returns the element `index` places after the front of the buffer, or
+    /// none if there are not that many elements.
+    ///
+    /// Every slot is read, so `index` may be secret.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn read(&self, index: usize) -> CtOption<T> {
+        let in_bounds = index.ct_lt(&self.len);
+        // Keep the offset below `N` so that it can be wrapped into a slot.
+        let offset = ct_select(in_bounds, index, 0);
+        CtOption::new(ct_lookup(&self.slots[..], self.slot(offset)), in_bounds)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T, const N: usize> fmt::Debug for ObliviousRingBuffer<T, N> {
+    /// Formats the buffer without its contents, which are secret.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObliviousRingBuffer").field("capacity", &N).finish_non_exhaustive()
+    }
+}
@@ -6,6 +6,7 @@
 #![feature(wtf8_internals)]
 #![feature(cow_is_borrowed)]
 #![feature(core_intrinsics)]
+#![feature(ct_select)]
 #![feature(downcast_unchecked)]
 #![feature(exact_size_is_empty)]
 #![feature(hashmap_internals)]
@@ -68,6 +69,7 @@
 mod linked_list;
 mod misc_tests;
 mod num;
+mod oblivious_ring_buffer;
 mod rc;
 mod slice;
 mod sort;
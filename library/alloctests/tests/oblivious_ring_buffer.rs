@@ -0,0 +1,48 @@
+use std::collections::{ObliviousRingBuffer, VecDeque};
+use std::select::Choice;
+
+#[test]
+fn test_push_pop_wraps_around() {
+    let mut buf = ObliviousRingBuffer::<u64, 3>::new();
+    let mut model = VecDeque::new();
+    for i in 0..20u64 {
+        // Push twice as often as popping for a while, then drain.
+        let push = i < 12 || i % 3 == 0;
+        let pushed = buf.conditional_push(i, Choice::from(push));
+        if push && model.len() < 3 {
+            model.push_back(i);
+            assert!(pushed.declassify());
+        } else {
+            assert!(!pushed.declassify());
+        }
+        if i % 2 == 1 {
+            assert_eq!(buf.pop().into_option(), model.pop_front());
+        }
+        for j in 0..4 {
+            assert_eq!(buf.read(j).into_option(), model.get(j).copied());
+        }
+        assert_eq!(buf.is_empty().declassify(), model.is_empty());
+        assert_eq!(buf.is_full().declassify(), model.len() == 3);
+    }
+}
+
+#[test]
+fn test_empty_and_full() {
+    let mut buf = ObliviousRingBuffer::<u8, 2>::default();
+    assert_eq!(buf.capacity(), 2);
+    assert!(buf.pop().is_none().declassify());
+    assert!(buf.read(usize::MAX).is_none().declassify());
+    assert!(buf.push(1).declassify());
+    assert!(!buf.conditional_pop(Choice::from(false)).is_some().declassify());
+    assert!(buf.push(2).declassify());
+    assert!(!buf.push(3).declassify());
+    assert_eq!(buf.pop().unwrap(), 1);
+    assert_eq!(buf.pop().unwrap(), 2);
+    assert_eq!(format!("{buf:?}"), "ObliviousRingBuffer { capacity: 2, .. }");
+}
+
+#[test]
+#[should_panic]
+fn test_zero_capacity() {
+    ObliviousRingBuffer::<u8, 0>::new();
+}
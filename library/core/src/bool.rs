@@ -1,6 +1,7 @@
 //! impl bool {}
 
 use crate::marker::Destruct;
+use crate::select::{Choice, CtOption};
 
 impl bool {
     /// Returns `Some(t)` if the `bool` is [`true`](../std/keyword.true.html),
@@ -137,4 +138,28 @@ pub const fn ok_or_else<E, F: [const] FnOnce() -> E + [const] Destruct>(
     ) -> Result<(), E> {
         if self { Ok(()) } else { Err(f()) }
     }
+
+    /// Returns a [`CtOption`] holding `t`, which is present if the `bool` is
+    /// [`true`](../std/keyword.true.html).
+    ///
+    /// This is the constant-time counterpart of [`then_some`]: the result is
+    /// produced without branching on `self`, so it can be used to start a
+    /// chain of fallible computations from the outcome of a comparison.
+    ///
+    /// [`then_some`]: bool::then_some
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    ///
+    /// assert!(true.ct_then_some(0).is_some().declassify());
+    /// assert!(!false.ct_then_some(0).is_some().declassify());
+    /// assert_eq!(true.ct_then_some(7).unwrap(), 7);
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn ct_then_some<T>(self, t: T) -> CtOption<T> {
+        CtOption::new(t, Choice::from(self))
+    }
 }
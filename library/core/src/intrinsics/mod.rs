@@ -492,6 +492,202 @@ pub const fn select_unpredictable<T>(b: bool, true_val: T, false_val: T) -> T {
     }
 }
 
+/// Returns either `true_val` or `false_val` depending on condition `b`, without
+/// the choice being observable through control flow.
+///
+/// Backends lower this to a conditional move or an equivalent branch-free
+/// sequence. `T` may be an integer, floating-point, `bool`, `char` or raw pointer
+/// type, a SIMD vector, or any other type that is passed as one or two such
+/// values, such as a wide pointer. Each backend legalizes it into the values it
+/// can select between: integers wider than 64 bits are selected one 64-bit half
+/// at a time, and floating-point numbers and vectors as integers of the same
+/// width. Other types are rejected when the call is monomorphized.
+///
+/// `T` may also be a `MaybeUninit` of such a type. Backends freeze its
+/// uninitialized bytes before selecting, so that they do not make the result
+/// undefined when the other value is selected.
+///
+/// There is no fallback body: a backend that cannot select without branching
+/// must not fall back to a `select` it may compile to a branch. During const
+/// evaluation, where timing is not observable, the value is selected directly.
+///
+/// Note that, unlike most intrinsics, this is safe to call;
+/// it does not require an `unsafe` block.
+/// Therefore, implementations must not require the user to uphold
+/// any safety invariants.
+///
+/// The public form of this intrinsic is [`core::select::ct_select`].
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+pub const fn ct_select<T: Copy>(b: bool, true_val: T, false_val: T) -> T;
+
+/// The `i8` variant of [`ct_select`], kept while code moves to the generic
+/// intrinsic.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[deprecated(since = "CURRENT_RUSTC_VERSION", note = "use `ct_select::<i8>` instead")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+pub const fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8 {
+    ct_select(b, true_val, false_val)
+}
+
+/// The `i16` variant of [`ct_select`], kept while code moves to the generic
+/// intrinsic.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[deprecated(since = "CURRENT_RUSTC_VERSION", note = "use `ct_select::<i16>` instead")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+pub const fn ct_select_i16(b: bool, true_val: i16, false_val: i16) -> i16 {
+    ct_select(b, true_val, false_val)
+}
+
+/// The `i32` variant of [`ct_select`], kept while code moves to the generic
+/// intrinsic.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[deprecated(since = "CURRENT_RUSTC_VERSION", note = "use `ct_select::<i32>` instead")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+pub const fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32 {
+    ct_select(b, true_val, false_val)
+}
+
+/// The `i64` variant of [`ct_select`], kept while code moves to the generic
+/// intrinsic.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[deprecated(since = "CURRENT_RUSTC_VERSION", note = "use `ct_select::<i64>` instead")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+pub const fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64 {
+    ct_select(b, true_val, false_val)
+}
+
+/// The `bool` variant of [`ct_select`], kept while code moves to the generic
+/// intrinsic.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[deprecated(since = "CURRENT_RUSTC_VERSION", note = "use `ct_select::<bool>` instead")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+pub const fn ct_select_bool(b: bool, true_val: bool, false_val: bool) -> bool {
+    ct_select(b, true_val, false_val)
+}
+
+/// The raw pointer variant of [`ct_select`], kept while code moves to the
+/// generic intrinsic.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[deprecated(since = "CURRENT_RUSTC_VERSION", note = "use `ct_select::<*const T>` instead")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+pub const fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T {
+    ct_select(b, true_val, false_val)
+}
+
+/// Returns either `true_val` or `false_val` depending on condition `b`, like
+/// [`ct_select`], then waits for the selection to be resolved before any later
+/// instruction executes, speculatively or not.
+///
+/// A conditional move still lets the processor predict the selected value, so
+/// a load through a selected pointer can run with the wrong one, and leave it
+/// in the cache, until the misprediction is detected. This inserts the
+/// speculation barrier of the target after the selection, such as `lfence` on
+/// x86 and `csdb` on AArch64. `T` must be passed as one or two integers no
+/// wider than a pointer, or pointers. Other types, and targets without a
+/// speculation barrier, are rejected when the call is monomorphized.
+///
+/// During const evaluation, the value is selected directly.
+///
+/// Note that, unlike most intrinsics, this is safe to call;
+/// it does not require an `unsafe` block.
+/// Therefore, implementations must not require the user to uphold
+/// any safety invariants.
+///
+/// The public form of this intrinsic is [`core::select::ct_select_spec`].
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+pub const fn ct_select_spec<T: Copy>(b: bool, true_val: T, false_val: T) -> T;
+
+/// Returns `value` unchanged, without the optimizer being able to see through
+/// it or to merge two calls.
+///
+/// Unlike [`black_box`], this has no fallback body: a backend that cannot
+/// guarantee the barrier must not compile it to an identity function.
+///
+/// Note that, unlike most intrinsics, this is safe to call;
+/// it does not require an `unsafe` block.
+/// Therefore, implementations must not require the user to uphold
+/// any safety invariants.
+///
+/// The public form of this intrinsic is [`core::select::ct_black_box`].
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+pub const fn ct_black_box<T>(value: T) -> T;
+
+/// Returns `value`, marked as secret for the dynamic checkers that report
+/// branches and memory accesses that depend on secret data.
+///
+/// Under `-Zsanitizer=memory`, the value is poisoned, so that
+/// MemorySanitizer treats it as uninitialized. Under
+/// `-Zvalgrind-ct-annotations`, a client request marks it as undefined for
+/// Valgrind's Memcheck instead. Both then report the uses of the value, and
+/// of the values computed from it, that could leak it through timing.
+/// Under `-Zsanitizer=constant-time`, the value is labeled as secret, and the
+/// program aborts as soon as a labeled value reaches a branch or the address
+/// of a memory access. Otherwise, this returns `value` unchanged.
+///
+/// Note that, unlike most intrinsics, this is safe to call;
+/// it does not require an `unsafe` block.
+/// Therefore, implementations must not require the user to uphold
+/// any safety invariants.
+///
+/// [`Secret::new`](crate::select::Secret::new) and the conversions into
+/// [`Choice`](crate::select::Choice) classify their values with this.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[miri::intrinsic_fallback_is_spec]
+pub const fn ct_classify<T>(value: T) -> T {
+    value
+}
+
+/// Returns `value`, marked as public again for the dynamic checkers that
+/// [`ct_classify`] marks secret values for.
+///
+/// Note that, unlike most intrinsics, this is safe to call;
+/// it does not require an `unsafe` block.
+/// Therefore, implementations must not require the user to uphold
+/// any safety invariants.
+///
+/// [`Choice::declassify`](crate::select::Choice::declassify),
+/// [`CtOption::into_option`](crate::select::CtOption::into_option) and
+/// [`CtResult::into_result`](crate::select::CtResult::into_result)
+/// declassify their values with this.
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[miri::intrinsic_fallback_is_spec]
+pub const fn ct_declassify<T>(value: T) -> T {
+    value
+}
+
 /// A guard for unsafe functions that cannot ever be executed if `T` is uninhabited:
 /// This will statically either panic, or do nothing. It does not *guarantee* to ever panic,
 /// and should only be called if an assertion failure will imply language UB in the following code.
@@ -989,6 +1185,35 @@ pub const fn select_unpredictable<T>(b: bool, true_val: T, false_val: T) -> T {
 #[rustc_nounwind]
 pub unsafe fn volatile_set_memory<T>(dst: *mut T, val: u8, count: usize);
 
+/// Sets `count` bytes starting at `dst` to zero, in a way that is never
+/// optimized out.
+///
+/// Unlike a plain [`write_bytes`], the stores are not removed even if the
+/// memory is never read again, which makes this suitable for clearing keys
+/// before their memory is freed or reused. The fallback body writes each byte
+/// with a volatile store.
+///
+/// The public form of this intrinsic is [`core::select::secure_zeroize`].
+///
+/// # Safety
+///
+/// `dst` must be [valid] for writes of `count` bytes.
+///
+/// [`write_bytes`]: ptr::write_bytes
+/// [valid]: crate::ptr#safety
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[miri::intrinsic_fallback_is_spec]
+pub unsafe fn secure_zeroize(dst: *mut u8, count: usize) {
+    let mut i = 0;
+    while i < count {
+        // SAFETY: the caller guarantees that `dst` is valid for `count` bytes.
+        unsafe { ptr::write_volatile(dst.add(i), 0) };
+        i += 1;
+    }
+}
+
 /// Performs a volatile load from the `src` pointer.
 ///
 /// The stabilized version of this intrinsic is [`core::ptr::read_volatile`].
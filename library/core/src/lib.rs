@@ -103,6 +103,7 @@
 #![feature(cfg_target_has_reliable_f16_f128)]
 #![feature(const_carrying_mul_add)]
 #![feature(const_cmp)]
+#![feature(const_ct_select)]
 #![feature(const_destruct)]
 #![feature(const_eval_select)]
 #![feature(const_select_unpredictable)]
@@ -327,6 +328,8 @@ pub mod autodiff {
 #[stable(feature = "new_range_inclusive_api", since = "CURRENT_RUSTC_VERSION")]
 pub mod range;
 pub mod result;
+#[unstable(feature = "ct_select", issue = "none")]
+pub mod select;
 pub mod sync;
 #[unstable(feature = "unsafe_binders", issue = "130516")]
 pub mod unsafe_binder;
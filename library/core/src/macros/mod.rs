@@ -1761,6 +1761,23 @@ macro_rules! trace_macros {
         /* compiler built-in */
     }
 
+    /// Attribute macro applied to a function to turn it into a statistical test that some code
+    /// runs in constant time.
+    ///
+    /// The function takes a `&mut test::CtTester`, and passes it the code to measure and a
+    /// generator for its inputs. The test fails if the running times on inputs of the two classes
+    /// differ according to Welch's t-test.
+    #[unstable(
+        feature = "ct_test",
+        issue = "none",
+        reason = "`ct_test` is a part of custom test frameworks which are unstable"
+    )]
+    #[allow_internal_unstable(test, rustc_attrs, coverage_attribute)]
+    #[rustc_builtin_macro]
+    pub macro ct_test($item:item) {
+        /* compiler built-in */
+    }
+
     /// An implementation detail of the `#[test]` and `#[bench]` macros.
     #[unstable(
         feature = "custom_test_frameworks",
@@ -587,6 +587,7 @@
 use crate::ops::{self, ControlFlow, Deref, DerefMut, Residual, Try};
 use crate::panicking::{panic, panic_display};
 use crate::pin::Pin;
+use crate::select::{Choice, ConstantTimeSelect};
 use crate::{cmp, convert, hint, mem, slice};
 
 /// The `Option` type. See [the module level documentation](self) for more.
@@ -1045,6 +1046,44 @@ pub const fn unwrap_or(self, default: T) -> T
         }
     }
 
+    /// Returns the contained [`Some`] value or the provided default, without
+    /// branching on which one it is.
+    ///
+    /// This is the constant-time counterpart of [`unwrap_or`], for code that
+    /// receives an `Option` whose variant must not influence control flow.
+    /// The payload is read from the same place whether `self` is `Some` or
+    /// [`None`], in which case it is uninitialized or holds the niche, and is
+    /// then selected against `default` with
+    /// [`ConstantTimeSelect::ct_select_uninit`], so that neither the address
+    /// of the load nor the control flow depends on the variant.
+    ///
+    /// [`unwrap_or`]: Option::unwrap_or
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    ///
+    /// assert_eq!(Some(3u32).ct_unwrap_or(9), 3);
+    /// assert_eq!(None::<u32>.ct_unwrap_or(9), 9);
+    /// ```
+    #[inline]
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub fn ct_unwrap_or(self, default: T) -> T
+    where
+        T: ConstantTimeSelect,
+    {
+        let is_some = Choice::from(self.is_some());
+        // SAFETY: as in `as_slice`, the offset of the payload is within `self`
+        // whether it is `Some` or `None`.
+        let payload = unsafe {
+            (&raw const self).byte_add(mem::offset_of!(Self, Some.0)).cast::<mem::MaybeUninit<T>>()
+        };
+        // SAFETY: `payload` is read as a `MaybeUninit`, and it is initialized
+        // if `self` is `Some`, which is when it is selected.
+        unsafe { T::ct_select_uninit(is_some, payload.read(), default) }
+    }
+
     /// Returns the contained [`Some`] value or computes it from a closure.
     ///
     /// # Examples
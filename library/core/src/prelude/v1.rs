@@ -126,6 +126,13 @@ mod panic {}
 #[unstable(feature = "derive_const", issue = "118304")]
 pub use crate::macros::builtin::derive_const;
 
+#[unstable(
+    feature = "ct_test",
+    issue = "none",
+    reason = "`ct_test` is a part of custom test frameworks which are unstable"
+)]
+pub use crate::macros::builtin::ct_test;
+
 #[unstable(
     feature = "cfg_accessible",
     issue = "64797",
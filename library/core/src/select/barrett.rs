@@ -0,0 +1,142 @@
+use super::{Choice, ConstantTimeLimb, ct_select};
+
+/// A precomputed reciprocal for reducing secret values modulo a public
+/// modulus.
+///
+/// Reduction multiplies by the reciprocal instead of dividing, and applies
+/// its two correction steps with [`ct_select`], so the time taken does not
+/// depend on the value being reduced. The modulus itself is treated as public:
+/// [`Barrett::new`] divides by it and branches on its bit length.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::Barrett;
+///
+/// const P: Barrett<u64> = Barrett::<u64>::new(0xffff_ffff_0000_0001);
+///
+/// assert_eq!(P.reduce(u64::MAX), 0xffff_fffe);
+/// assert_eq!(P.mul_mod(1 << 32, 1 << 32), 0xffff_ffff);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone, Debug)]
+pub struct Barrett<T> {
+    modulus: T,
+    /// The number of leading zeros of `modulus`.
+    shift: u32,
+    /// `floor((2^(2 * BITS) - 1) / d) - 2^BITS`, where `d` is `modulus`
+    /// shifted left by `shift`.
+    reciprocal: T,
+}
+
+/// Computes the reciprocal of the normalized `d` as `floor((n1 * 2^128 + n0) /
+/// d)` with `n1 = !d` and `n0 = u128::MAX`, one bit at a time.
+///
+/// This only runs on the public modulus, so it may branch.
+const fn reciprocal_u128(d: u128) -> u128 {
+    let mut remainder = !d;
+    let mut quotient = 0;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        let top = remainder >> 127;
+        // The low half of the dividend is all ones.
+        remainder = (remainder << 1) | 1;
+        if top == 1 || remainder >= d {
+            remainder = remainder.wrapping_sub(d);
+            quotient |= 1 << i;
+        }
+    }
+    quotient
+}
+
+macro_rules! impl_barrett {
+    ($($t:ty => |$d:ident| $reciprocal:expr;)*) => {$(
+        impl Barrett<$t> {
+            /// Precomputes the reciprocal of `modulus`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `modulus` is zero.
+            #[unstable(feature = "ct_select", issue = "none")]
+            pub const fn new(modulus: $t) -> Barrett<$t> {
+                assert!(modulus != 0, "Barrett modulus must be nonzero");
+                let shift = modulus.leading_zeros();
+                let $d = modulus << shift;
+                Barrett { modulus, shift, reciprocal: $reciprocal }
+            }
+
+            /// Returns the modulus.
+            #[unstable(feature = "ct_select", issue = "none")]
+            #[inline]
+            pub const fn modulus(&self) -> $t {
+                self.modulus
+            }
+
+            /// Reduces `x` modulo the modulus.
+            #[unstable(feature = "ct_select", issue = "none")]
+            #[inline]
+            pub fn reduce(&self, x: $t) -> $t {
+                self.reduce_wide(x, 0)
+            }
+
+            /// Reduces the double-width value `hi * 2^BITS + lo` modulo the
+            /// modulus.
+            ///
+            /// This is the remainder of a product of two reduced values, as
+            /// returned by [`ct_widening_mul`](super::ct_widening_mul).
+            ///
+            /// # Panics
+            ///
+            /// Panics if `hi` is not less than the modulus. The check always
+            /// passes for valid inputs, so it reveals nothing about them.
+            #[unstable(feature = "ct_select", issue = "none")]
+            #[inline]
+            pub fn reduce_wide(&self, lo: $t, hi: $t) -> $t {
+                assert!(hi < self.modulus, "high half must be reduced");
+                let d = self.modulus << self.shift;
+                // Normalize the dividend along with the modulus. The double
+                // shift of `lo` keeps the shift amount in range when `shift`
+                // is zero.
+                let u1 = (hi << self.shift) | ((lo >> 1) >> (<$t>::BITS - 1 - self.shift));
+                let u0 = lo << self.shift;
+
+                // Estimate the quotient from the reciprocal. The estimate may
+                // be off by one in either direction.
+                let (q0, q1) = self.reciprocal.ct_widening_mul(u1);
+                let (q0, carry) = q0.ct_adc(u0, 0);
+                let (q1, _) = q1.ct_adc(u1, carry);
+                let q1 = q1.wrapping_add(1);
+                let r = u0.wrapping_sub(q1.wrapping_mul(d));
+
+                // If the remainder wrapped around, the estimate was one too
+                // large.
+                let (_, too_large) = q0.ct_sbb(r, 0);
+                let r = ct_select(Choice::from(too_large as u8), r.wrapping_add(d), r);
+                // It may also have been one too small.
+                let (diff, too_small) = r.ct_sbb(d, 0);
+                let r = ct_select(Choice::from(too_small as u8), r, diff);
+                r >> self.shift
+            }
+
+            /// Computes `a * b` modulo the modulus.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the high half of the product is not less than the
+            /// modulus, which cannot happen when `a` and `b` are reduced.
+            #[unstable(feature = "ct_select", issue = "none")]
+            #[inline]
+            pub fn mul_mod(&self, a: $t, b: $t) -> $t {
+                let (lo, hi) = a.ct_widening_mul(b);
+                self.reduce_wide(lo, hi)
+            }
+        }
+    )*};
+}
+
+impl_barrett! {
+    u64 => |d| ((((!d as u128) << 64) | u64::MAX as u128) / d as u128) as u64;
+    u128 => |d| reciprocal_u128(d);
+}
@@ -0,0 +1,134 @@
+use super::{Choice, CtOption};
+
+/// Converts a 6-bit value to its character in the standard base64 alphabet.
+#[inline]
+fn encode_sextet(v: u8) -> u8 {
+    let v = v as i32;
+    // Start from `'A'..='Z'` and adjust the offset once `v` is past the end of
+    // each range of the alphabet; `(n - v) >> 8` is all ones when `v > n`.
+    let mut c = v + b'A' as i32;
+    c += ((25 - v) >> 8) & 6;
+    c -= ((51 - v) >> 8) & 75;
+    c -= ((61 - v) >> 8) & 15;
+    c += ((62 - v) >> 8) & 3;
+    c as u8
+}
+
+/// Returns all ones if `lo <= c <= hi`, and zero otherwise.
+#[inline]
+fn range_mask(c: i32, lo: u8, hi: u8) -> i32 {
+    // Both differences are negative exactly when `c` is in range.
+    ((lo as i32 - 1 - c) & (c - hi as i32 - 1)) >> 8
+}
+
+/// Converts a character of the standard base64 alphabet to its value, along
+/// with a flag byte that is `1` if `c` is in the alphabet.
+#[inline]
+fn decode_char(c: u8) -> (u8, u8) {
+    let c = c as i32;
+    let upper = range_mask(c, b'A', b'Z');
+    let lower = range_mask(c, b'a', b'z');
+    let digit = range_mask(c, b'0', b'9');
+    let plus = range_mask(c, b'+', b'+');
+    let slash = range_mask(c, b'/', b'/');
+    let value = (upper & (c - b'A' as i32))
+        | (lower & (c - b'a' as i32 + 26))
+        | (digit & (c - b'0' as i32 + 52))
+        | (plus & 62)
+        | (slash & 63);
+    (value as u8, ((upper | lower | digit | plus | slash) & 1) as u8)
+}
+
+/// Encodes `src` as padded base64 with the standard alphabet into the start of
+/// `dst`, returning the encoded string.
+///
+/// Every character is computed with arithmetic only, without the lookup
+/// table of a conventional encoder, so the time taken depends only on the
+/// length of `src`.
+///
+/// # Panics
+///
+/// Panics if `dst` is shorter than the encoded length, `4 * src.len().div_ceil(3)`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_base64_encode;
+///
+/// let mut buf = [0u8; 8];
+/// assert_eq!(ct_base64_encode(b"key", &mut buf), "a2V5");
+/// assert_eq!(ct_base64_encode(b"keys", &mut buf), "a2V5cw==");
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_base64_encode<'a>(src: &[u8], dst: &'a mut [u8]) -> &'a str {
+    let dst = &mut dst[..src.len().div_ceil(3) * 4];
+    for (chunk, out) in src.chunks(3).zip(dst.chunks_exact_mut(4)) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let acc = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for (i, c) in out.iter_mut().enumerate() {
+            // Only the length of the final chunk decides the padding.
+            *c = if i <= chunk.len() {
+                encode_sextet((acc >> (18 - 6 * i)) as u8 & 0x3f)
+            } else {
+                b'='
+            };
+        }
+    }
+    // SAFETY: every byte written is an ASCII character.
+    unsafe { crate::str::from_utf8_unchecked(dst) }
+}
+
+/// Decodes the padded base64 string `src`, in the standard alphabet, into the
+/// start of `dst`, returning the number of bytes written.
+///
+/// Every character is decoded with arithmetic only, and invalid characters do
+/// not stop the decoding. The padding at the end of `src` is inspected
+/// directly, since it only determines the length of the decoded data, which
+/// is treated as public. The result is none if `src` contains a character
+/// outside of the alphabet or its length is not a multiple of four.
+///
+/// # Panics
+///
+/// Panics if `dst` is shorter than the decoded length.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_base64_decode;
+///
+/// let mut buf = [0u8; 6];
+/// assert_eq!(ct_base64_decode(b"a2V5cw==", &mut buf).unwrap(), 4);
+/// assert_eq!(&buf[..4], b"keys");
+///
+/// assert!(!ct_base64_decode(b"a2V*", &mut buf).is_some().declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_base64_decode(src: &[u8], dst: &mut [u8]) -> CtOption<usize> {
+    if src.len() % 4 != 0 {
+        return CtOption::new(0, Choice::from(false));
+    }
+    let padding = match src {
+        [.., b'=', b'='] => 2,
+        [.., b'='] => 1,
+        _ => 0,
+    };
+    let groups = src.len() / 4;
+    let dst = &mut dst[..groups * 3 - padding];
+
+    let mut valid = Choice::from(true);
+    for (i, (group, out)) in src.chunks_exact(4).zip(dst.chunks_mut(3)).enumerate() {
+        let chars = if i + 1 == groups { 4 - padding } else { 4 };
+        let mut acc = 0u32;
+        for (j, &c) in group[..chars].iter().enumerate() {
+            let (value, ok) = decode_char(c);
+            acc |= (value as u32) << (18 - 6 * j);
+            valid &= Choice::from(ok);
+        }
+        for (k, byte) in out.iter_mut().enumerate() {
+            *byte = (acc >> (16 - 8 * k)) as u8;
+        }
+    }
+    CtOption::new(dst.len(), valid)
+}
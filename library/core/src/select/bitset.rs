@@ -0,0 +1,214 @@
+use super::{Choice, ConstantTimeEq, ConstantTimeOps, ConstantTimeSelect};
+use crate::fmt;
+use crate::mem::MaybeUninit;
+use crate::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+/// A set of secret flags, packed 64 to a word into `N` words.
+///
+/// Each flag is a bit rather than a whole [`Choice`], so many flags, such as
+/// the validity of each record in a batch, can be stored compactly and
+/// combined a word at a time. The index passed to [`set`] and [`get`] may be
+/// secret: every word is read, and written by `set`, with the relevant bit
+/// picked out by masks, and the bit position is shifted with
+/// [`ct_shl`](super::ct_shl). An index past the capacity of `64 * N` flags
+/// matches no word, so `get` returns false and `set` has no effect.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, CtBitSet};
+///
+/// let mut valid = CtBitSet::<2>::new();
+/// valid.set(3, Choice::from(true));
+/// valid.set(100, Choice::from(true));
+///
+/// assert!(valid.get(100).declassify());
+/// assert!(!valid.get(4).declassify());
+/// assert_eq!(valid.count(), 2);
+/// assert!(valid.any().declassify());
+/// assert!(!valid.all().declassify());
+/// ```
+///
+/// [`set`]: CtBitSet::set
+/// [`get`]: CtBitSet::get
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone)]
+pub struct CtBitSet<const N: usize>([u64; N]);
+
+impl<const N: usize> CtBitSet<N> {
+    /// The number of flags the set holds.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub const CAPACITY: usize = 64 * N;
+
+    /// Creates a set with every flag clear.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn new() -> CtBitSet<N> {
+        CtBitSet([0; N])
+    }
+
+    /// Creates a set from its words, where flag `i` is bit `i % 64` of word
+    /// `i / 64`.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn from_words(words: [u64; N]) -> CtBitSet<N> {
+        CtBitSet(words)
+    }
+
+    /// Returns the words of the set, where flag `i` is bit `i % 64` of word
+    /// `i / 64`.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn to_words(self) -> [u64; N] {
+        self.0
+    }
+
+    /// Returns the mask of the bit for `index` within its word, along with
+    /// the index of that word.
+    #[inline]
+    fn locate(index: usize) -> (u64, usize) {
+        (1u64.ct_shl((index % 64) as u32), index / 64)
+    }
+
+    /// Sets the flag at `index` to `value`.
+    ///
+    /// Every word is written, so `index` may be secret. Indices past
+    /// [`CAPACITY`](Self::CAPACITY) are ignored.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn set(&mut self, index: usize, value: Choice) {
+        let (bit, word) = Self::locate(index);
+        let value = u64::ct_mask(value);
+        for (i, w) in self.0.iter_mut().enumerate() {
+            let mask = bit & u64::ct_mask(i.ct_eq(&word));
+            *w = (*w & !mask) | (value & mask);
+        }
+    }
+
+    /// Returns the flag at `index`.
+    ///
+    /// Every word is read, so `index` may be secret. Indices past
+    /// [`CAPACITY`](Self::CAPACITY) return false.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn get(&self, index: usize) -> Choice {
+        let (bit, word) = Self::locate(index);
+        let mut found = 0;
+        for (i, &w) in self.0.iter().enumerate() {
+            found |= w & bit & u64::ct_mask(i.ct_eq(&word));
+        }
+        !found.ct_eq(&0)
+    }
+
+    /// Returns whether any flag is set.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn any(&self) -> Choice {
+        !self.0.iter().fold(0, |acc, &w| acc | w).ct_eq(&0)
+    }
+
+    /// Returns whether every flag is set.
+    ///
+    /// This is true for an empty set with `N` of zero.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn all(&self) -> Choice {
+        self.0.iter().fold(u64::MAX, |acc, &w| acc & w).ct_eq(&u64::MAX)
+    }
+
+    /// Returns the number of flags that are set.
+    ///
+    /// The count is computed with [`ct_count_ones`](super::ct_count_ones), so
+    /// the time taken does not depend on it, but the result is as secret as
+    /// the flags themselves.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.0.iter().map(|w| w.ct_count_ones()).sum()
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const N: usize> Default for CtBitSet<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const N: usize> ConstantTimeSelect for CtBitSet<N> {
+    #[inline]
+    fn ct_select(choice: Choice, t: Self, f: Self) -> Self {
+        CtBitSet(<[u64; N]>::ct_select(choice, t.0, f.0))
+    }
+
+    #[inline]
+    fn ct_swap(choice: Choice, a: &mut Self, b: &mut Self) {
+        <[u64; N]>::ct_swap(choice, &mut a.0, &mut b.0)
+    }
+
+    #[inline]
+    unsafe fn ct_select_uninit(choice: Choice, t: MaybeUninit<Self>, f: Self) -> Self {
+        // SAFETY: the limbs are within `t`, and are initialized if it is, which the caller
+        // guarantees if it is selected.
+        unsafe {
+            let limbs = (&raw const (*t.as_ptr()).0).cast::<MaybeUninit<[u64; N]>>().read();
+            CtBitSet(<[u64; N]>::ct_select_uninit(choice, limbs, f.0))
+        }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const N: usize> ConstantTimeEq for CtBitSet<N> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+macro_rules! impl_bitset_op {
+    ($($trait:ident::$method:ident, $assign:ident::$assign_method:ident => $op:tt;)*) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl<const N: usize> $trait for CtBitSet<N> {
+            type Output = CtBitSet<N>;
+
+            #[inline]
+            fn $method(self, rhs: CtBitSet<N>) -> CtBitSet<N> {
+                CtBitSet(crate::array::from_fn(|i| self.0[i] $op rhs.0[i]))
+            }
+        }
+
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl<const N: usize> $assign for CtBitSet<N> {
+            #[inline]
+            fn $assign_method(&mut self, rhs: CtBitSet<N>) {
+                *self = *self $op rhs;
+            }
+        }
+    )*};
+}
+
+impl_bitset_op! {
+    BitAnd::bitand, BitAndAssign::bitand_assign => &;
+    BitOr::bitor, BitOrAssign::bitor_assign => |;
+    BitXor::bitxor, BitXorAssign::bitxor_assign => ^;
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const N: usize> Not for CtBitSet<N> {
+    type Output = CtBitSet<N>;
+
+    #[inline]
+    fn not(self) -> CtBitSet<N> {
+        CtBitSet(self.0.map(|w| !w))
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const N: usize> fmt::Debug for CtBitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CtBitSet").finish_non_exhaustive()
+    }
+}
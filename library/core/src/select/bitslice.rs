@@ -0,0 +1,140 @@
+/// Transposes the 8x8 bit matrix whose rows are the bytes of `x`, in little
+/// endian order.
+///
+/// Bit `c` of byte `r` of the result is bit `r` of byte `c` of `x`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::bitslice::transpose_8x8;
+///
+/// // The first row is all ones, so the first column is too.
+/// assert_eq!(transpose_8x8(0xff), 0x0101_0101_0101_0101);
+/// assert_eq!(transpose_8x8(0x0101_0101_0101_0101), 0xff);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub const fn transpose_8x8(x: u64) -> u64 {
+    // Swap the off-diagonal 1x1, then 2x2, then 4x4 blocks.
+    let mut x = x;
+    let t = (x ^ (x >> 7)) & 0x00aa_00aa_00aa_00aa;
+    x ^= t ^ (t << 7);
+    let t = (x ^ (x >> 14)) & 0x0000_cccc_0000_cccc;
+    x ^= t ^ (t << 14);
+    let t = (x ^ (x >> 28)) & 0x0000_0000_f0f0_f0f0;
+    x ^= t ^ (t << 28);
+    x
+}
+
+/// Transposes the 32x32 bit matrix whose rows are the elements of `rows` in
+/// place.
+///
+/// Afterwards, bit `c` of `rows[r]` is what bit `r` of `rows[c]` was.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::bitslice::transpose_32x32;
+///
+/// let mut rows = [0u32; 32];
+/// rows[0] = u32::MAX;
+/// transpose_32x32(&mut rows);
+/// assert_eq!(rows, [1; 32]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn transpose_32x32(rows: &mut [u32; 32]) {
+    // Swap the off-diagonal 16x16 blocks, then the 8x8 blocks within each
+    // half, and so on down to single bits.
+    let mut width = 16;
+    let mut mask = 0x0000_ffffu32;
+    while width != 0 {
+        let mut k = 0;
+        while k < 32 {
+            let t = ((rows[k] >> width) ^ rows[k + width]) & mask;
+            rows[k] ^= t << width;
+            rows[k + width] ^= t;
+            // Step to the next row whose `width` bit is clear.
+            k = (k + width + 1) & !width;
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
+
+/// Converts 32 bytes into 8 bit planes, one for each bit position.
+///
+/// Bit `j` of plane `i` is bit `i` of `bytes[j]`, so each of the 32 bytes
+/// occupies one lane across the planes and a bitsliced circuit can process
+/// all of them at once.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::bitslice::{pack_planes, unpack_planes};
+///
+/// let mut bytes = [0u8; 32];
+/// bytes[3] = 0b101;
+/// let planes = pack_planes(&bytes);
+/// assert_eq!(planes, [1 << 3, 0, 1 << 3, 0, 0, 0, 0, 0]);
+/// assert_eq!(unpack_planes(&planes), bytes);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn pack_planes(bytes: &[u8; 32]) -> [u32; 8] {
+    let mut planes = [0u32; 8];
+    for (lane, chunk) in bytes.chunks_exact(8).enumerate() {
+        let t = transpose_8x8(u64::from_le_bytes(chunk.try_into().unwrap()));
+        for (i, plane) in planes.iter_mut().enumerate() {
+            *plane |= (((t >> (8 * i)) & 0xff) as u32) << (8 * lane);
+        }
+    }
+    planes
+}
+
+/// Converts 8 bit planes back into 32 bytes.
+///
+/// This is the inverse of [`pack_planes`].
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn unpack_planes(planes: &[u32; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (lane, chunk) in bytes.chunks_exact_mut(8).enumerate() {
+        let mut t = 0u64;
+        for (i, &plane) in planes.iter().enumerate() {
+            t |= (((plane >> (8 * lane)) & 0xff) as u64) << (8 * i);
+        }
+        chunk.copy_from_slice(&transpose_8x8(t).to_le_bytes());
+    }
+    bytes
+}
+
+/// Selects between two sets of bit planes lane by lane.
+///
+/// Each lane of the result comes from `true_planes` where the corresponding
+/// bit of `mask` is set, and from `false_planes` where it is clear. This is
+/// the bitsliced form of [`ct_select`](super::ct_select), taking one condition
+/// per lane rather than one for the whole value.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::bitslice::select_planes;
+///
+/// let a = [u32::MAX; 2];
+/// let b = [0; 2];
+/// assert_eq!(select_planes(0xf0, &a, &b), [0xf0; 2]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn select_planes<const N: usize>(
+    mask: u32,
+    true_planes: &[u32; N],
+    false_planes: &[u32; N],
+) -> [u32; N] {
+    crate::array::from_fn(|i| false_planes[i] ^ (mask & (true_planes[i] ^ false_planes[i])))
+}
@@ -0,0 +1,244 @@
+use super::ConstantTimeSelect;
+use crate::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use crate::{fmt, intrinsics};
+
+/// A condition that must not influence control flow.
+///
+/// A `Choice` is the constant-time counterpart of `bool`: it is produced by the
+/// comparison functions in this module and consumed by [`ct_select`] and its
+/// relatives. Internally it always holds either `0` or `1`.
+///
+/// Choices are combined with the bitwise operators `&`, `|`, `^` and `!`,
+/// which, unlike `&&` and `||`, always evaluate both operands.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ConstantTimeEq};
+///
+/// let valid = 3u8.ct_eq(&3);
+/// let in_range = Choice::from(true);
+/// let expired = 1u8.ct_eq(&0);
+/// assert!((valid & in_range & !expired).declassify());
+/// ```
+///
+/// [`ct_select`]: super::ct_select
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone)]
+#[rustc_diagnostic_item = "Choice"]
+pub struct Choice(u8);
+
+impl Choice {
+    /// Converts the condition into a `bool` that may be branched on.
+    ///
+    /// This is the only way for a `Choice` to leave constant-time code, and
+    /// marks the point at which the condition stops being a secret. Callers
+    /// must ensure that revealing it is acceptable, for example because it is
+    /// the final verdict of a MAC check. Keeping every such conversion behind
+    /// this one method, and [`CtOption::into_option`], makes them easy to find
+    /// during an audit.
+    ///
+    /// [`CtOption::into_option`]: super::CtOption::into_option
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::ConstantTimeEq;
+    ///
+    /// let expected_tag = 0x1234u16;
+    /// let tag_ok = expected_tag.ct_eq(&0x1234);
+    /// // Whether the tag matched is public once the check is complete.
+    /// assert!(tag_ok.declassify());
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[rustc_diagnostic_item = "choice_declassify"]
+    #[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+    #[inline]
+    pub const fn declassify(self) -> bool {
+        intrinsics::ct_declassify(self.0) != 0
+    }
+
+    /// Returns the condition as a `bool`, for passing to the `ct_select`
+    /// intrinsic.
+    #[rustc_force_inline]
+    pub(crate) const fn as_bool(self) -> bool {
+        self.0 != 0
+    }
+
+    /// Returns the condition as a `0` or `1` byte.
+    #[rustc_force_inline]
+    pub(crate) const fn as_u8(self) -> u8 {
+        self.0
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const From<bool> for Choice {
+    #[inline(always)]
+    fn from(b: bool) -> Choice {
+        // Hide the origin of the value from the optimizer so that it does not
+        // fold later selections back into branches on `b`.
+        Choice(intrinsics::black_box(intrinsics::ct_classify(b as u8)))
+    }
+}
+
+/// Creates a `Choice` from a `0`/`1` flag byte.
+///
+/// Only the least significant bit of `b` is kept, so a value outside of the
+/// `0`/`1` domain cannot produce an invalid `Choice`.
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const From<u8> for Choice {
+    #[inline(always)]
+    fn from(b: u8) -> Choice {
+        Choice(intrinsics::black_box(intrinsics::ct_classify(b & 1)))
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const ConstantTimeSelect for Choice {
+    #[inline(always)]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        Choice(u8::ct_select(choice, true_val.0, false_val.0))
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const BitAnd for Choice {
+    type Output = Choice;
+
+    #[inline]
+    fn bitand(self, rhs: Choice) -> Choice {
+        Choice(self.0 & rhs.0)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const BitAndAssign for Choice {
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Choice) {
+        *self = *self & rhs;
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const BitOr for Choice {
+    type Output = Choice;
+
+    #[inline]
+    fn bitor(self, rhs: Choice) -> Choice {
+        Choice(self.0 | rhs.0)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const BitOrAssign for Choice {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Choice) {
+        *self = *self | rhs;
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const BitXor for Choice {
+    type Output = Choice;
+
+    #[inline]
+    fn bitxor(self, rhs: Choice) -> Choice {
+        Choice(self.0 ^ rhs.0)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const BitXorAssign for Choice {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Choice) {
+        *self = *self ^ rhs;
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const Not for Choice {
+    type Output = Choice;
+
+    #[inline]
+    fn not(self) -> Choice {
+        // Only the low bit is ever set, so flip just that one.
+        Choice(self.0 ^ 1)
+    }
+}
+
+/// Returns a [`Choice`] that is set if both `a` and `b` are set.
+///
+/// Unlike `a && b`, both operands are evaluated before the call, so the
+/// outcome of `a` cannot decide whether `b` is computed. Either operand may be
+/// a [`Choice`] or a `bool`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ConstantTimeEq, ct_and};
+///
+/// let (user_ok, pass_ok) = (3u8.ct_eq(&3), false);
+/// assert!(!ct_and(user_ok, pass_ok).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_and<A: Into<Choice>, B: Into<Choice>>(a: A, b: B) -> Choice {
+    a.into() & b.into()
+}
+
+/// Returns a [`Choice`] that is set if `a` or `b` is set.
+///
+/// Unlike `a || b`, both operands are always evaluated.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_or};
+///
+/// assert!(ct_or(Choice::from(false), true).declassify());
+/// assert!(!ct_or(false, false).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_or<A: Into<Choice>, B: Into<Choice>>(a: A, b: B) -> Choice {
+    a.into() | b.into()
+}
+
+/// Returns a [`Choice`] that is set if exactly one of `a` and `b` is set.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_xor;
+///
+/// assert!(ct_xor(true, false).declassify());
+/// assert!(!ct_xor(true, true).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_xor<A: Into<Choice>, B: Into<Choice>>(a: A, b: B) -> Choice {
+    a.into() ^ b.into()
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl fmt::Debug for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Choice").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,119 @@
+use super::{Choice, ConstantTimeSelect, ct_select};
+use crate::cell::UnsafeCell;
+use crate::{fmt, ptr};
+
+/// A mutable memory location for secret values whose conditional updates
+/// always write.
+///
+/// Like [`Cell`](crate::cell::Cell), `CtCell` allows its value to be replaced
+/// through a shared reference. In addition, [`conditional_store`] stores
+/// either the new value or the old one depending on a [`Choice`]. The store
+/// is a volatile write of the selected value, so it takes place, and dirties
+/// the same cache line, whether or not the value changes: shared state can
+/// be updated from constant-time code without a store/no-store difference
+/// revealing the condition.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ConstantTimeOrd, CtCell};
+///
+/// let best = CtCell::new(10u32);
+/// for candidate in [12, 7, 9] {
+///     best.conditional_store(candidate.ct_lt(&best.get()), candidate);
+/// }
+/// assert_eq!(best.get(), 7);
+/// ```
+///
+/// [`conditional_store`]: CtCell::conditional_store
+#[unstable(feature = "ct_select", issue = "none")]
+#[repr(transparent)]
+pub struct CtCell<T: ?Sized> {
+    value: UnsafeCell<T>,
+}
+
+impl<T> CtCell<T> {
+    /// Creates a new `CtCell` containing `value`.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn new(value: T) -> CtCell<T> {
+        CtCell { value: UnsafeCell::new(value) }
+    }
+
+    /// Unwraps the value.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ConstantTimeSelect> CtCell<T> {
+    /// Returns a copy of the contained value.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn get(&self) -> T {
+        // SAFETY: `CtCell` is not `Sync`, and no reference to the contents is
+        // ever handed out through a shared reference, so nothing can be
+        // writing to them concurrently.
+        unsafe { *self.value.get() }
+    }
+
+    /// Sets the contained value.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn set(&self, value: T) {
+        // SAFETY: as in `get`, there is no other access to the contents.
+        unsafe { *self.value.get() = value }
+    }
+
+    /// Stores `value` if `choice` is set, and the current value otherwise.
+    ///
+    /// Either way, exactly one volatile write of the selected value is made.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn conditional_store(&self, choice: Choice, value: T) {
+        let selected = ct_select(choice, value, self.get());
+        // SAFETY: the pointer comes from a live `UnsafeCell`, so it is valid
+        // and aligned, and as in `get`, there is no other access to the
+        // contents.
+        unsafe { ptr::write_volatile(self.value.get(), selected) }
+    }
+}
+
+impl<T: ?Sized> CtCell<T> {
+    /// Returns a mutable reference to the contained value.
+    ///
+    /// This call borrows `CtCell` mutably, which guarantees that no other
+    /// references to it exist.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeSelect> Clone for CtCell<T> {
+    #[inline]
+    fn clone(&self) -> CtCell<T> {
+        CtCell::new(self.get())
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: Default> Default for CtCell<T> {
+    /// Creates a `CtCell<T>` with the `Default` value for `T`.
+    #[inline]
+    fn default() -> CtCell<T> {
+        CtCell::new(T::default())
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ?Sized> fmt::Debug for CtCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CtCell").finish_non_exhaustive()
+    }
+}
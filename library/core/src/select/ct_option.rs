@@ -0,0 +1,240 @@
+use super::{Choice, ConstantTimeSelect, ct_select};
+use crate::mem::MaybeUninit;
+use crate::{fmt, intrinsics};
+
+/// An optional value whose presence is a secret.
+///
+/// Unlike [`Option`], a `CtOption` always holds a value of type `T`; whether
+/// that value is meaningful is tracked separately as a [`Choice`]. This allows
+/// fallible computations to run to completion without revealing, through
+/// their control flow, whether they failed.
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone)]
+#[rustc_diagnostic_item = "CtOption"]
+pub struct CtOption<T> {
+    value: T,
+    is_some: Choice,
+}
+
+impl<T> CtOption<T> {
+    /// Creates a `CtOption` holding `value`, which is considered present if
+    /// `is_some` is set.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn new(value: T, is_some: Choice) -> CtOption<T> {
+        CtOption { value, is_some }
+    }
+
+    /// Returns a [`Choice`] that is set if the value is present.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn is_some(&self) -> Choice {
+        self.is_some
+    }
+
+    /// Returns a [`Choice`] that is set if the value is not present.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn is_none(&self) -> Choice {
+        !self.is_some
+    }
+
+    /// Applies `f` to the contained value, keeping its presence.
+    ///
+    /// Unlike [`Option::map`], `f` is always called, whether or not the value
+    /// is present, so it must not panic or otherwise misbehave on the
+    /// placeholder value held by an absent `CtOption`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::{Choice, CtOption};
+    ///
+    /// let x = CtOption::new(4u32, Choice::from(true));
+    /// assert_eq!(x.map(|v| v * 2).unwrap(), 8);
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> CtOption<U> {
+        CtOption::new(f(self.value), self.is_some)
+    }
+
+    /// Chains a fallible computation on the contained value.
+    ///
+    /// The result is present only if both `self` and the result of `f` are.
+    /// As with [`map`](CtOption::map), `f` is always called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::{Choice, CtOption, ct_mod_inverse};
+    ///
+    /// let x = CtOption::new(3u32, Choice::from(true));
+    /// assert_eq!(x.and_then(|v| ct_mod_inverse(v, 7)).unwrap(), 5);
+    /// assert!(!x.and_then(|v| ct_mod_inverse(v, 9)).is_some().declassify());
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn and_then<U, F: FnOnce(T) -> CtOption<U>>(self, f: F) -> CtOption<U> {
+        let res = f(self.value);
+        CtOption::new(res.value, self.is_some & res.is_some)
+    }
+
+    /// Returns `self` if the value is present, and the result of `f`
+    /// otherwise.
+    ///
+    /// `f` is always called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::{Choice, CtOption};
+    ///
+    /// let none = CtOption::new(0u8, Choice::from(false));
+    /// assert_eq!(none.or_else(|| CtOption::new(9, Choice::from(true))).unwrap(), 9);
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn or_else<F: FnOnce() -> CtOption<T>>(self, f: F) -> CtOption<T>
+    where
+        T: ConstantTimeSelect,
+    {
+        let other = f();
+        ct_select(self.is_some, self, other)
+    }
+
+    /// Returns the contained value if it is present, and `default` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::{Choice, CtOption};
+    ///
+    /// assert_eq!(CtOption::new(4u32, Choice::from(true)).unwrap_or(7), 4);
+    /// assert_eq!(CtOption::new(4u32, Choice::from(false)).unwrap_or(7), 7);
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T
+    where
+        T: ConstantTimeSelect,
+    {
+        ct_select(self.is_some, self.value, default)
+    }
+
+    /// Returns the contained value if it is present, and the result of `f`
+    /// otherwise.
+    ///
+    /// `f` is always called.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn unwrap_or_else<F: FnOnce() -> T>(self, f: F) -> T
+    where
+        T: ConstantTimeSelect,
+    {
+        self.unwrap_or(f())
+    }
+
+    /// Converts into an [`Option`], revealing whether the value is present.
+    ///
+    /// Like [`Choice::declassify`], this is a declassification point: the
+    /// resulting `Option` may be branched on, so the presence of the value
+    /// stops being a secret. Conversions are deliberately only available
+    /// through this method, rather than through [`From`], so that every one of
+    /// them can be found during an audit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::ct_parse_decimal;
+    ///
+    /// // Whether the PIN was well-formed is public once parsing is done.
+    /// assert_eq!(ct_parse_decimal::<u32>(b"1234").into_option(), Some(1234));
+    /// assert_eq!(ct_parse_decimal::<u32>(b"12a4").into_option(), None);
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[rustc_diagnostic_item = "ct_option_into_option"]
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        if self.is_some.declassify() { Some(intrinsics::ct_declassify(self.value)) } else { None }
+    }
+
+    /// Converts a public [`Option`] into a `CtOption`.
+    ///
+    /// An absent value is represented by `T::default()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::CtOption;
+    ///
+    /// assert_eq!(CtOption::from_option(Some(3u8)).unwrap(), 3);
+    /// assert!(CtOption::<u8>::from_option(None).is_none().declassify());
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn from_option(option: Option<T>) -> CtOption<T>
+    where
+        T: Default,
+    {
+        let is_some = Choice::from(option.is_some());
+        CtOption::new(option.unwrap_or_default(), is_some)
+    }
+
+    /// Returns the contained value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not present. Since this branches on the
+    /// presence of the value, it must only be used once that fact is public.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        assert!(self.is_some.declassify(), "called `CtOption::unwrap()` on a none value");
+        self.value
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeSelect> ConstantTimeSelect for CtOption<T> {
+    #[inline]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        CtOption {
+            value: T::ct_select(choice, true_val.value, false_val.value),
+            is_some: Choice::ct_select(choice, true_val.is_some, false_val.is_some),
+        }
+    }
+
+    #[inline]
+    unsafe fn ct_select_uninit(
+        choice: Choice,
+        true_val: MaybeUninit<Self>,
+        false_val: Self,
+    ) -> Self {
+        let true_val = true_val.as_ptr();
+        // SAFETY: the fields are within `true_val`, and are initialized if it is, which the
+        // caller guarantees if it is selected.
+        unsafe {
+            let value = (&raw const (*true_val).value).cast::<MaybeUninit<T>>().read();
+            let is_some = (&raw const (*true_val).is_some).cast::<MaybeUninit<Choice>>().read();
+            CtOption {
+                value: T::ct_select_uninit(choice, value, false_val.value),
+                is_some: Choice::ct_select_uninit(choice, is_some, false_val.is_some),
+            }
+        }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T> fmt::Debug for CtOption<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CtOption").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,207 @@
+use super::{Choice, ConstantTimeSelect, CtOption, ct_select};
+use crate::mem::MaybeUninit;
+use crate::{fmt, intrinsics};
+
+/// The result of a fallible computation whose outcome, and kind of failure,
+/// are secret.
+///
+/// A `CtResult` always holds both a value of type `T` and an error of type
+/// `E`; which of the two is meaningful is tracked separately as a [`Choice`].
+/// This matters when the reason for a failure must not leak, such as a
+/// decryption routine that has to make "bad padding" and "bad MAC"
+/// indistinguishable: each check produces a `CtResult`, they are combined
+/// with [`and_then`](CtResult::and_then) without branching, and the
+/// combined result is only revealed at the end.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, CtResult};
+///
+/// const BAD_PADDING: u8 = 1;
+/// const BAD_MAC: u8 = 2;
+///
+/// let padding = CtResult::new(16usize, BAD_PADDING, Choice::from(false));
+/// let checked = padding.and_then(|len| CtResult::new(len, BAD_MAC, Choice::from(false)));
+/// // The first failure is the one reported.
+/// assert_eq!(checked.into_result(), Err(BAD_PADDING));
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone)]
+#[rustc_diagnostic_item = "CtResult"]
+pub struct CtResult<T, E> {
+    value: T,
+    error: E,
+    is_ok: Choice,
+}
+
+impl<T, E> CtResult<T, E> {
+    /// Creates a `CtResult` holding `value` and `error`, which is considered a
+    /// success if `is_ok` is set and a failure with `error` otherwise.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn new(value: T, error: E, is_ok: Choice) -> CtResult<T, E> {
+        CtResult { value, error, is_ok }
+    }
+
+    /// Returns a [`Choice`] that is set if the result is a success.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn is_ok(&self) -> Choice {
+        self.is_ok
+    }
+
+    /// Returns a [`Choice`] that is set if the result is a failure.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn is_err(&self) -> Choice {
+        !self.is_ok
+    }
+
+    /// Converts into a [`CtOption`] of the value, discarding the error.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn ok(self) -> CtOption<T> {
+        CtOption::new(self.value, self.is_ok)
+    }
+
+    /// Converts into a [`CtOption`] of the error, discarding the value.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn err(self) -> CtOption<E> {
+        CtOption::new(self.error, !self.is_ok)
+    }
+
+    /// Applies `f` to the contained value, keeping the error and the outcome.
+    ///
+    /// `f` is always called, whether or not the result is a success, so it
+    /// must not panic or otherwise misbehave on the placeholder value held by
+    /// a failed `CtResult`.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> CtResult<U, E> {
+        CtResult::new(f(self.value), self.error, self.is_ok)
+    }
+
+    /// Applies `op` to the contained error, keeping the value and the
+    /// outcome.
+    ///
+    /// As with [`map`](CtResult::map), `op` is always called.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn map_err<F, O: FnOnce(E) -> F>(self, op: O) -> CtResult<T, F> {
+        CtResult::new(self.value, op(self.error), self.is_ok)
+    }
+
+    /// Chains a fallible computation on the contained value.
+    ///
+    /// The result is a success only if both `self` and the result of `f` are.
+    /// Otherwise, its error is that of `self` if `self` failed, and that of
+    /// the result of `f` if not. `f` is always called.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn and_then<U, F: FnOnce(T) -> CtResult<U, E>>(self, f: F) -> CtResult<U, E>
+    where
+        E: ConstantTimeSelect,
+    {
+        let res = f(self.value);
+        let error = ct_select(self.is_ok, res.error, self.error);
+        CtResult::new(res.value, error, self.is_ok & res.is_ok)
+    }
+
+    /// Returns the contained value if the result is a success, and `default`
+    /// otherwise.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T
+    where
+        T: ConstantTimeSelect,
+    {
+        ct_select(self.is_ok, self.value, default)
+    }
+
+    /// Converts into a [`Result`], revealing the outcome.
+    ///
+    /// Like [`Choice::declassify`] and [`CtOption::into_option`], this is a
+    /// declassification point: both whether the computation failed and, if
+    /// it did, the error stop being secret.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[rustc_diagnostic_item = "ct_result_into_result"]
+    #[inline]
+    pub fn into_result(self) -> Result<T, E> {
+        if self.is_ok.declassify() {
+            Ok(intrinsics::ct_declassify(self.value))
+        } else {
+            Err(intrinsics::ct_declassify(self.error))
+        }
+    }
+
+    /// Returns the contained value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is a failure. Since this branches on the outcome,
+    /// it must only be used once that fact is public.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        assert!(self.is_ok.declassify(), "called `CtResult::unwrap()` on a failure");
+        self.value
+    }
+
+    /// Returns the contained error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is a success. Since this branches on the outcome,
+    /// it must only be used once that fact is public.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    #[track_caller]
+    pub fn unwrap_err(self) -> E {
+        assert!(!self.is_ok.declassify(), "called `CtResult::unwrap_err()` on a success");
+        self.error
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeSelect, E: ConstantTimeSelect> ConstantTimeSelect for CtResult<T, E> {
+    #[inline]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        CtResult {
+            value: T::ct_select(choice, true_val.value, false_val.value),
+            error: E::ct_select(choice, true_val.error, false_val.error),
+            is_ok: Choice::ct_select(choice, true_val.is_ok, false_val.is_ok),
+        }
+    }
+
+    #[inline]
+    unsafe fn ct_select_uninit(
+        choice: Choice,
+        true_val: MaybeUninit<Self>,
+        false_val: Self,
+    ) -> Self {
+        let true_val = true_val.as_ptr();
+        // SAFETY: the fields are within `true_val`, and are initialized if it is, which the
+        // caller guarantees if it is selected.
+        unsafe {
+            let value = (&raw const (*true_val).value).cast::<MaybeUninit<T>>().read();
+            let error = (&raw const (*true_val).error).cast::<MaybeUninit<E>>().read();
+            let is_ok = (&raw const (*true_val).is_ok).cast::<MaybeUninit<Choice>>().read();
+            CtResult {
+                value: T::ct_select_uninit(choice, value, false_val.value),
+                error: E::ct_select_uninit(choice, error, false_val.error),
+                is_ok: Choice::ct_select_uninit(choice, is_ok, false_val.is_ok),
+            }
+        }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T, E> fmt::Debug for CtResult<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CtResult").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::arch::asm;
+use crate::marker::PhantomData;
+
+/// The bit of `PSTATE.DIT` in the value of the `DIT` system register.
+const DIT_BIT: u64 = 1 << 24;
+
+// The `DIT` register is accessed by its encoding, `S3_3_C4_C2_5`, so that the assembler does not
+// need to know the name.
+
+/// Returns whether data-independent timing (`PSTATE.DIT`) is enabled on the current thread.
+///
+/// On AArch64 CPUs with FEAT_DIT (Armv8.4 and later), only while `PSTATE.DIT` is set are the
+/// execution times of data-processing instructions, including the conditional selects that
+/// [`ct_select`](super::ct_select) is lowered to, architecturally independent of the values they
+/// operate on.
+#[unstable(feature = "ct_select", issue = "none")]
+#[target_feature(enable = "dit")]
+#[inline]
+pub fn dit_enabled() -> bool {
+    let value: u64;
+    // SAFETY: reading `DIT` has no side effects, and the `dit` target feature guarantees that
+    // the register exists.
+    unsafe {
+        asm!("mrs {}, S3_3_C4_C2_5", out(reg) value, options(nomem, nostack, preserves_flags));
+    }
+    value & DIT_BIT != 0
+}
+
+/// Enables or disables data-independent timing (`PSTATE.DIT`) on the current thread.
+///
+/// Prefer [`DitGuard`], which restores the previous state once done.
+#[unstable(feature = "ct_select", issue = "none")]
+#[target_feature(enable = "dit")]
+#[inline]
+pub fn set_dit(enabled: bool) {
+    let value = if enabled { DIT_BIT } else { 0 };
+    // SAFETY: `PSTATE.DIT` only affects timing, and the `dit` target feature guarantees that the
+    // register exists.
+    unsafe {
+        asm!("msr S3_3_C4_C2_5, {}", in(reg) value, options(nomem, nostack, preserves_flags));
+    }
+}
+
+/// A scope in which data-independent timing (`PSTATE.DIT`) is enabled on the current thread.
+///
+/// Creating a `DitGuard` sets `PSTATE.DIT`, and dropping it restores the state it was in
+/// before, so guards can be nested. Since `PSTATE.DIT` belongs to the thread, the guard cannot
+/// be sent to another one.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// # #[cfg(target_arch = "aarch64")]
+/// # fn main() {
+/// use std::select::{ConstantTimeEq, DitGuard, dit_enabled};
+///
+/// if std::arch::is_aarch64_feature_detected!("dit") {
+///     // SAFETY: the CPU supports FEAT_DIT.
+///     let guard = unsafe { DitGuard::new() };
+///     assert!(unsafe { dit_enabled() });
+///     let equal = [1u8, 2, 3].ct_eq(&[1, 2, 3]);
+///     drop(guard);
+///     assert!(equal.declassify());
+/// }
+/// # }
+/// # #[cfg(not(target_arch = "aarch64"))]
+/// # fn main() {}
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[must_use = "data-independent timing is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct DitGuard {
+    was_enabled: bool,
+    /// `PSTATE.DIT` is per thread, so the guard must be dropped on the thread that created it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl DitGuard {
+    /// Enables data-independent timing until the returned guard is dropped.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[target_feature(enable = "dit")]
+    #[inline]
+    pub fn new() -> DitGuard {
+        let was_enabled = dit_enabled();
+        set_dit(true);
+        DitGuard { was_enabled, _not_send: PhantomData }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl Drop for DitGuard {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.was_enabled {
+            // SAFETY: a guard can only have been created where the `dit` target feature is
+            // available.
+            unsafe { set_dit(false) }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::arch::asm;
+#[cfg(target_arch = "x86")]
+use crate::arch::x86::{__cpuid_count, __get_cpuid_max};
+#[cfg(target_arch = "x86_64")]
+use crate::arch::x86_64::{__cpuid_count, __get_cpuid_max};
+use crate::marker::PhantomData;
+
+/// `IA32_ARCH_CAPABILITIES`, which reports whether DOITM is supported.
+const IA32_ARCH_CAPABILITIES: u32 = 0x10a;
+/// The bit of `IA32_ARCH_CAPABILITIES` set if DOITM is supported.
+const ARCH_CAP_DOITM: u64 = 1 << 12;
+/// `IA32_UARCH_MISC_CTL`, which holds the DOITM enable bit.
+const IA32_UARCH_MISC_CTL: u32 = 0x1b01;
+/// The bit of `IA32_UARCH_MISC_CTL` that enables DOITM.
+const UARCH_MISC_DOITM: u64 = 1 << 0;
+
+/// Reads a model-specific register.
+///
+/// # Safety
+///
+/// Must be called at privilege level 0, for a register that exists.
+#[inline]
+unsafe fn rdmsr(msr: u32) -> u64 {
+    let (lo, hi): (u32, u32);
+    // SAFETY: guaranteed by the caller.
+    unsafe {
+        asm!(
+            "rdmsr",
+            in("ecx") msr,
+            out("eax") lo,
+            out("edx") hi,
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+    ((hi as u64) << 32) | lo as u64
+}
+
+/// Writes a model-specific register.
+///
+/// # Safety
+///
+/// Must be called at privilege level 0, with a value that is valid for the register.
+#[inline]
+unsafe fn wrmsr(msr: u32, value: u64) {
+    // SAFETY: guaranteed by the caller.
+    unsafe {
+        asm!(
+            "wrmsr",
+            in("ecx") msr,
+            in("eax") value as u32,
+            in("edx") (value >> 32) as u32,
+            options(nostack, preserves_flags),
+        );
+    }
+}
+
+/// Returns whether the CPU supports Data Operand Independent Timing Mode.
+///
+/// On Intel CPUs from Ice Lake on, the execution time of the data-operand-independent timing
+/// instructions, which include the conditional moves that [`ct_select`](super::ct_select) is
+/// lowered to, is only guaranteed not to depend on their operands while DOITM is enabled.
+/// Without it, the CPU may use data-dependent optimizations even for those instructions.
+///
+/// DOITM is controlled through a model-specific register, which only privileged code can
+/// access. User-space programs depend on the operating system to enable it, while kernels,
+/// hypervisors and firmware can use [`DoitmGuard`].
+///
+/// # Safety
+///
+/// Must be called at privilege level 0.
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub unsafe fn doitm_supported() -> bool {
+    // The values returned for a leaf above the highest supported one are not defined.
+    if __get_cpuid_max(0).0 < 7 {
+        return false;
+    }
+    let leaf7 = __cpuid_count(7, 0);
+    // `IA32_ARCH_CAPABILITIES` exists if bit 29 of EDX is set.
+    if leaf7.edx & (1 << 29) == 0 {
+        return false;
+    }
+    // SAFETY: the register exists, and the caller guarantees the privilege level.
+    unsafe { rdmsr(IA32_ARCH_CAPABILITIES) & ARCH_CAP_DOITM != 0 }
+}
+
+/// Returns whether Data Operand Independent Timing Mode is enabled on the current logical
+/// processor.
+///
+/// # Safety
+///
+/// Must be called at privilege level 0, on a CPU for which [`doitm_supported`] returns true.
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub unsafe fn doitm_enabled() -> bool {
+    // SAFETY: guaranteed by the caller.
+    unsafe { rdmsr(IA32_UARCH_MISC_CTL) & UARCH_MISC_DOITM != 0 }
+}
+
+/// Enables or disables Data Operand Independent Timing Mode on the current logical processor.
+///
+/// Prefer [`DoitmGuard`], which restores the previous state once done.
+///
+/// # Safety
+///
+/// Must be called at privilege level 0, on a CPU for which [`doitm_supported`] returns true.
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub unsafe fn set_doitm(enabled: bool) {
+    // SAFETY: guaranteed by the caller. The other bits of the register are preserved.
+    unsafe {
+        let value = rdmsr(IA32_UARCH_MISC_CTL) & !UARCH_MISC_DOITM;
+        wrmsr(IA32_UARCH_MISC_CTL, value | if enabled { UARCH_MISC_DOITM } else { 0 });
+    }
+}
+
+/// A scope in which Data Operand Independent Timing Mode is enabled on the current logical
+/// processor.
+///
+/// Creating a `DoitmGuard` enables DOITM, and dropping it restores the state it was in before,
+/// so guards can be nested. This is the x86 counterpart of `DitGuard` on AArch64, for
+/// privileged code: see [`doitm_supported`].
+///
+/// DOITM belongs to the logical processor, so the code in the scope must not migrate to
+/// another one, for instance by running with preemption disabled, and the guard cannot be sent
+/// to another thread.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(ct_select)]
+/// # #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+/// # fn main() {
+/// use std::select::{ConstantTimeEq, DoitmGuard, doitm_supported};
+///
+/// // SAFETY: this runs at privilege level 0, with preemption disabled.
+/// if unsafe { doitm_supported() } {
+///     // SAFETY: as above, and the CPU supports DOITM.
+///     let guard = unsafe { DoitmGuard::new() };
+///     let equal = [1u8, 2, 3].ct_eq(&[1, 2, 3]);
+///     drop(guard);
+///     assert!(equal.declassify());
+/// }
+/// # }
+/// # #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+/// # fn main() {}
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[must_use = "DOITM is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct DoitmGuard {
+    was_enabled: bool,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl DoitmGuard {
+    /// Enables DOITM until the returned guard is dropped.
+    ///
+    /// # Safety
+    ///
+    /// Must be called at privilege level 0, on a CPU for which [`doitm_supported`] returns
+    /// true, and the guard must be dropped on the same logical processor.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub unsafe fn new() -> DoitmGuard {
+        // SAFETY: guaranteed by the caller.
+        let was_enabled = unsafe { doitm_enabled() };
+        // SAFETY: as above.
+        unsafe { set_doitm(true) };
+        DoitmGuard { was_enabled, _not_send: PhantomData }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl Drop for DoitmGuard {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.was_enabled {
+            // SAFETY: the caller of `new` guaranteed that this runs privileged, on the same
+            // logical processor, which supports DOITM.
+            unsafe { set_doitm(false) }
+        }
+    }
+}
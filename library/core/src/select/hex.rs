@@ -0,0 +1,94 @@
+use super::{Choice, CtOption};
+
+/// Converts a nibble to its lowercase hexadecimal digit.
+#[inline]
+fn encode_nibble(n: u8) -> u8 {
+    let n = n as i32;
+    // `(9 - n) >> 8` is all ones exactly when `n > 9`, and then adds the
+    // distance from `'0' + 10` to `'a'`.
+    (n + b'0' as i32 + (((9 - n) >> 8) & (b'a' as i32 - b'0' as i32 - 10))) as u8
+}
+
+/// Converts a hexadecimal digit of either case to its value, along with a flag
+/// byte that is `1` if `c` is a valid digit.
+#[inline]
+pub(super) fn decode_nibble(c: u8) -> (u8, u8) {
+    let c = c as i32;
+    // `c ^ '0'` is below 10 only for the decimal digits.
+    let num = c ^ b'0' as i32;
+    let num_ok = ((num - 10) >> 8) & 1;
+    // Fold to uppercase; `'A'..='F'` become 10 to 15.
+    let alpha = (c & !0x20) - (b'A' as i32 - 10);
+    let alpha_ok = (((alpha - 10) ^ (alpha - 16)) >> 8) & 1;
+    let value = (num & -num_ok) | (alpha & -alpha_ok);
+    (value as u8, (num_ok | alpha_ok) as u8)
+}
+
+/// Encodes `src` as lowercase hexadecimal into the start of `dst`, returning
+/// the encoded string.
+///
+/// Lookup tables and `match` statements leak the values being converted
+/// through the cache and branch predictor. This converts each nibble with
+/// arithmetic only, so the time taken depends only on the length of `src`.
+///
+/// # Panics
+///
+/// Panics if `dst` is shorter than twice the length of `src`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_hex_encode;
+///
+/// let mut buf = [0u8; 8];
+/// assert_eq!(ct_hex_encode(&[0xde, 0xad, 0xbe, 0xef], &mut buf), "deadbeef");
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_hex_encode<'a>(src: &[u8], dst: &'a mut [u8]) -> &'a str {
+    let dst = &mut dst[..src.len() * 2];
+    for (&byte, pair) in src.iter().zip(dst.chunks_exact_mut(2)) {
+        pair[0] = encode_nibble(byte >> 4);
+        pair[1] = encode_nibble(byte & 0xf);
+    }
+    // SAFETY: every byte written is an ASCII hexadecimal digit.
+    unsafe { crate::str::from_utf8_unchecked(dst) }
+}
+
+/// Decodes the hexadecimal string `src` into the start of `dst`, returning
+/// the number of bytes written.
+///
+/// Both upper and lower case digits are accepted. Every character is decoded
+/// with arithmetic only, and invalid characters do not stop the decoding, so
+/// the time taken depends only on the length of `src`. The result is none if
+/// `src` contains an invalid character or has an odd length; `dst` is
+/// overwritten regardless.
+///
+/// # Panics
+///
+/// Panics if `dst` is shorter than half the length of `src`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_hex_decode;
+///
+/// let mut key = [0u8; 4];
+/// assert_eq!(ct_hex_decode(b"DEADbeef", &mut key).unwrap(), 4);
+/// assert_eq!(key, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// assert!(!ct_hex_decode(b"0g", &mut key).is_some().declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_hex_decode(src: &[u8], dst: &mut [u8]) -> CtOption<usize> {
+    let len = src.len() / 2;
+    let mut valid = Choice::from(src.len() % 2 == 0);
+    for (pair, out) in src.chunks_exact(2).zip(&mut dst[..len]) {
+        let (hi, hi_ok) = decode_nibble(pair[0]);
+        let (lo, lo_ok) = decode_nibble(pair[1]);
+        *out = (hi << 4) | lo;
+        valid &= Choice::from(hi_ok & lo_ok);
+    }
+    CtOption::new(len, valid)
+}
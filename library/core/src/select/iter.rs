@@ -0,0 +1,75 @@
+use super::{Choice, ConstantTimeSelect, CtOption, ct_select};
+
+/// Constant-time counterparts of short-circuiting [`Iterator`] methods.
+///
+/// Methods like [`Iterator::position`] stop at the first match, so the time
+/// they take reveals where the match was. The methods of this trait always
+/// consume the entire iterator and accumulate their result with
+/// [`ct_select`]. It is implemented for every iterator.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ConstantTimeEq, ConstantTimeIterator};
+///
+/// let slots = [0u8, 0, 7, 0, 7];
+/// assert_eq!(slots.iter().ct_position(|s| s.ct_eq(&7)).unwrap(), 2);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub trait ConstantTimeIterator: Iterator + Sized {
+    /// Returns the index of the first element for which `predicate` is set.
+    ///
+    /// `predicate` is called on every element, including those after the
+    /// first match.
+    #[inline]
+    fn ct_position<P>(self, mut predicate: P) -> CtOption<usize>
+    where
+        P: FnMut(Self::Item) -> Choice,
+    {
+        let mut found = Choice::from(false);
+        let mut position = 0;
+        for (i, item) in self.enumerate() {
+            let is_match = predicate(item);
+            // Only the first match updates `position`.
+            position = ct_select(found, position, ct_select(is_match, i, position));
+            found |= is_match;
+        }
+        CtOption::new(position, found)
+    }
+
+    /// Folds every element into an accumulator, keeping only the steps whose
+    /// result is present.
+    ///
+    /// For each element, `f` computes a candidate accumulator. It replaces the
+    /// current one if it is present, and is discarded otherwise, without
+    /// branching. This expresses a filtered fold, such as summing only the
+    /// elements that pass a secret check, over the whole iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::{ConstantTimeIterator, ConstantTimeOrd, CtOption};
+    ///
+    /// // Sum the values below 10.
+    /// let values = [3u32, 12, 4, 50];
+    /// let sum = values.iter().ct_fold(0, |acc, &v| CtOption::new(acc + v, v.ct_lt(&10)));
+    /// assert_eq!(sum, 7);
+    /// ```
+    #[inline]
+    fn ct_fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        B: ConstantTimeSelect,
+        F: FnMut(B, Self::Item) -> CtOption<B>,
+    {
+        let mut acc = init;
+        for item in self {
+            acc = f(acc, item).unwrap_or(acc);
+        }
+        acc
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<I: Iterator> ConstantTimeIterator for I {}
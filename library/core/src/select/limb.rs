@@ -0,0 +1,696 @@
+use super::{
+    Choice, ConstantTimeEq, ConstantTimeOps, ConstantTimeOrd, ConstantTimeSelect, CtOption,
+    ct_select, ct_swap,
+};
+use crate::cmp::Ordering;
+
+/// Unsigned integer types used as the limbs of big integers.
+///
+/// Carries and borrows are represented as a limb holding `0` or `1`, so that
+/// they can be fed straight back into the next operation of a carry chain
+/// without ever being turned into a `bool`.
+#[unstable(feature = "ct_select", issue = "none")]
+pub trait ConstantTimeLimb: ConstantTimeSelect {
+    /// Computes `self + rhs + carry`, returning the sum and the carry out.
+    ///
+    /// Only the least significant bit of `carry` is used.
+    fn ct_adc(self, rhs: Self, carry: Self) -> (Self, Self);
+
+    /// Computes `self - rhs - borrow`, returning the difference and the
+    /// borrow out.
+    ///
+    /// Only the least significant bit of `borrow` is used.
+    fn ct_sbb(self, rhs: Self, borrow: Self) -> (Self, Self);
+
+    /// Computes the full product `self * rhs`, returning the low and high
+    /// halves.
+    fn ct_widening_mul(self, rhs: Self) -> (Self, Self);
+
+    /// Computes the quotient and remainder of `self / divisor`.
+    ///
+    /// If `divisor` is zero, the quotient is the maximum value of the type
+    /// and the remainder is `self`; no panic occurs, since checking for a zero
+    /// divisor would branch on it.
+    fn ct_div_rem(self, divisor: Self) -> (Self, Self);
+
+    /// Computes the inverse of `self` modulo `modulus`.
+    ///
+    /// The result is none if `modulus` is even or `self` is not coprime to
+    /// it.
+    fn ct_mod_inverse(self, modulus: Self) -> CtOption<Self>;
+
+    /// Computes the greatest common divisor of `self` and `other`.
+    ///
+    /// The greatest common divisor of zero and zero is zero.
+    fn ct_gcd(self, other: Self) -> Self;
+}
+
+/// Computes `a + b + carry`, returning the sum and the carry out.
+///
+/// The carry out is computed with bitwise operations on the operands and the
+/// sum rather than with a comparison, so it is branch-free at every
+/// optimization level.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_adc;
+///
+/// // Add two 128-bit numbers held as little-endian `u64` limbs.
+/// let a = [u64::MAX, 1];
+/// let b = [1, 2];
+/// let (lo, carry) = ct_adc(a[0], b[0], 0);
+/// let (hi, carry) = ct_adc(a[1], b[1], carry);
+/// assert_eq!([lo, hi, carry], [0, 4, 0]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_adc<T: ConstantTimeLimb>(a: T, b: T, carry: T) -> (T, T) {
+    a.ct_adc(b, carry)
+}
+
+/// Computes `a - b - borrow`, returning the difference and the borrow out.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_sbb;
+///
+/// // Subtract two 64-bit numbers held as little-endian `u32` limbs.
+/// let (lo, borrow) = ct_sbb(0u32, 1, 0);
+/// let (hi, borrow) = ct_sbb(5u32, 0, borrow);
+/// assert_eq!([lo, hi, borrow], [u32::MAX, 4, 0]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_sbb<T: ConstantTimeLimb>(a: T, b: T, borrow: T) -> (T, T) {
+    a.ct_sbb(b, borrow)
+}
+
+/// Computes the full product `a * b` as `(low, high)`.
+///
+/// The time taken does not depend on the operands on any target. Most targets
+/// have a constant-time multiplier, and the product is computed with a single
+/// widening multiplication there. On cores whose long multiply instruction
+/// terminates early for small operands, the product is assembled from
+/// half-width partial products instead, none of which uses that instruction.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_widening_mul;
+///
+/// assert_eq!(ct_widening_mul(u32::MAX, 2), (u32::MAX - 1, 1));
+/// assert_eq!(ct_widening_mul(1u128 << 64, 1 << 64), (0, 1));
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_widening_mul<T: ConstantTimeLimb>(a: T, b: T) -> (T, T) {
+    a.ct_widening_mul(b)
+}
+
+/// Computes the quotient and remainder of `dividend / divisor`.
+///
+/// Hardware division takes a data-dependent number of cycles on nearly every
+/// target. This instead performs restoring binary long division, one bit of
+/// the quotient per step, deciding each conditional subtraction with
+/// [`ct_select`]. It is much slower than the `/` and `%` operators and should
+/// only be used where the operands are secret.
+///
+/// If `divisor` is zero, the quotient is the maximum value of the type and the
+/// remainder is `dividend`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_div_rem;
+///
+/// assert_eq!(ct_div_rem(100u32, 7), (14, 2));
+/// assert_eq!(ct_div_rem(u128::MAX, 1 << 100), (0xfff_ffff, (1 << 100) - 1));
+/// assert_eq!(ct_div_rem(5u64, 0), (u64::MAX, 5));
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_div_rem<T: ConstantTimeLimb>(dividend: T, divisor: T) -> (T, T) {
+    dividend.ct_div_rem(divisor)
+}
+
+/// Computes the inverse of `x` modulo the odd `modulus`.
+///
+/// Textbook implementations of the binary extended Euclidean algorithm branch
+/// on the operands at every step and run for a data-dependent number of
+/// iterations, which has repeatedly been exploited to recover secret keys.
+/// Like the safegcd algorithm of Bernstein and Yang, this instead runs a fixed
+/// number of steps, `2 * BITS - 1`, each of which conditionally swaps,
+/// subtracts and halves the operands with [`ct_swap`] and [`ct_select`]. The
+/// time taken depends on neither `x` nor `modulus`.
+///
+/// `x` does not need to be reduced. The result is none if `modulus` is even or
+/// `x` is not coprime to it.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_mod_inverse;
+///
+/// assert_eq!(ct_mod_inverse(3u32, 7).unwrap(), 5);
+/// assert_eq!(ct_mod_inverse(2u64, u64::MAX).unwrap(), 1 << 63);
+/// assert!(!ct_mod_inverse(6u128, 9).is_some().declassify());
+/// ```
+///
+/// [`ct_swap`]: super::ct_swap
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_mod_inverse<T: ConstantTimeLimb>(x: T, modulus: T) -> CtOption<T> {
+    x.ct_mod_inverse(modulus)
+}
+
+/// Computes the greatest common divisor of `a` and `b`.
+///
+/// This is the binary GCD algorithm run for a fixed number of steps, with
+/// every step applied through [`ct_swap`] and [`ct_select`], so the time taken
+/// depends on neither operand. The greatest common divisor of zero and zero is
+/// zero.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_gcd;
+///
+/// assert_eq!(ct_gcd(48u64, 180), 12);
+/// assert_eq!(ct_gcd(0u128, 7), 7);
+/// assert_eq!(ct_gcd(0u32, 0), 0);
+/// ```
+///
+/// [`ct_swap`]: super::ct_swap
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_gcd<T: ConstantTimeLimb>(a: T, b: T) -> T {
+    a.ct_gcd(b)
+}
+
+/// Computes the greatest common divisor of two big integers held as
+/// little-endian `u64` limbs.
+///
+/// This is the multi-limb counterpart of [`ct_gcd`], for operands such as RSA
+/// moduli and exponents that do not fit in a primitive integer.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_gcd_limbs;
+///
+/// // gcd(6 * 2^64, 4 * 2^64 + 2) = 6
+/// assert_eq!(ct_gcd_limbs([0, 6], [2, 4]), [6, 0]);
+/// assert_eq!(ct_gcd_limbs([0, 6], [0, 4]), [0, 2]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_gcd_limbs<const N: usize>(a: [u64; N], b: [u64; N]) -> [u64; N] {
+    if N == 0 {
+        return a;
+    }
+    let bits = N as u32 * u64::BITS;
+    let shr1 = |x: [u64; N]| {
+        crate::array::from_fn(|i| (x[i] >> 1) | x.get(i + 1).map_or(0, |next| next << 63))
+    };
+    let shl1 = |x: [u64; N]| {
+        crate::array::from_fn(|i| (x[i] << 1) | if i == 0 { 0 } else { x[i - 1] >> 63 })
+    };
+    let sub = |x: [u64; N], y: [u64; N]| {
+        let mut borrow = 0;
+        let diff = crate::array::from_fn(|i| {
+            let (d, b) = x[i].ct_sbb(y[i], borrow);
+            borrow = b;
+            d
+        });
+        (diff, borrow)
+    };
+
+    let (mut a, mut b) = (a, b);
+    // Strip the common factors of two, counting them in `shift`.
+    let mut shift = 0u32;
+    for _ in 0..bits - 1 {
+        let both_even = Choice::from((!(a[0] | b[0]) & 1) as u8);
+        shift += both_even.as_u8() as u32;
+        a = ct_select(both_even, shr1(a), a);
+        b = ct_select(both_even, shr1(b), b);
+    }
+    ct_swap(Choice::from((!b[0] & 1) as u8), &mut a, &mut b);
+
+    // Now `b` is odd unless both operands are zero.
+    for _ in 0..2 * bits - 1 {
+        let a_odd = Choice::from((a[0] & 1) as u8);
+        let (_, a_lt_b) = sub(a, b);
+        ct_swap(a_odd & Choice::from(a_lt_b as u8), &mut a, &mut b);
+        let (diff, _) = sub(a, b);
+        a = shr1(ct_select(a_odd, diff, a));
+    }
+
+    // Restore the common factors of two without shifting by a secret amount.
+    for i in 0..bits - 1 {
+        b = ct_select(Choice::from((i.wrapping_sub(shift) >> 31) as u8), shl1(b), b);
+    }
+    b
+}
+
+/// Computes the inverse of `x` modulo the odd `modulus`, both big integers
+/// held as little-endian `u64` limbs.
+///
+/// This is the multi-limb counterpart of [`ct_mod_inverse`], for moduli such
+/// as those of RSA and of elliptic curves over large prime fields. It runs the
+/// same fixed number of steps, `2 * 64 * N - 1`, each applied through
+/// [`ct_swap`] and [`ct_select`], so the time taken depends on neither `x`
+/// nor `modulus`.
+///
+/// `x` does not need to be reduced. The result is none if `modulus` is even or
+/// `x` is not coprime to it.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_mod_inverse_limbs;
+///
+/// // 2^127 - 1 is prime, and the inverse of 2 modulo it is 2^126.
+/// let p = [u64::MAX, u64::MAX >> 1];
+/// assert_eq!(ct_mod_inverse_limbs([2, 0], p).unwrap(), [0, 1 << 62]);
+/// assert!(!ct_mod_inverse_limbs([6, 0], [9, 0]).is_some().declassify());
+/// ```
+///
+/// [`ct_swap`]: super::ct_swap
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_mod_inverse_limbs<const N: usize>(x: [u64; N], modulus: [u64; N]) -> CtOption<[u64; N]> {
+    if N == 0 {
+        return CtOption::new(x, Choice::from(false));
+    }
+    let bits = N as u32 * u64::BITS;
+    // Shifts `x` right by one, shifting `top` in at the most significant bit.
+    let shr1 = |x: [u64; N], top: u64| {
+        crate::array::from_fn(|i| (x[i] >> 1) | (x.get(i + 1).map_or(top, |next| *next) << 63))
+    };
+    let add = |x: [u64; N], y: [u64; N]| {
+        let mut carry = 0;
+        let sum = crate::array::from_fn(|i| {
+            let (s, c) = x[i].ct_adc(y[i], carry);
+            carry = c;
+            s
+        });
+        (sum, carry)
+    };
+    let sub = |x: [u64; N], y: [u64; N]| {
+        let mut borrow = 0;
+        let diff = crate::array::from_fn(|i| {
+            let (d, b) = x[i].ct_sbb(y[i], borrow);
+            borrow = b;
+            d
+        });
+        (diff, borrow)
+    };
+
+    let m = modulus;
+    let one: [u64; N] = crate::array::from_fn(|i| (i == 0) as u64);
+    // Maintain `a = u * x` and `b = v * x` modulo `m`, with `b` odd, as in
+    // `ct_mod_inverse`. After `2 * bits - 1` steps `a` is zero and `b` is
+    // `gcd(x, m)`.
+    let (mut a, mut b) = (x, m);
+    // `u` starts as `1 mod m`, which is `0` in the degenerate case `m == 1`.
+    let (mut u, mut v) = (ct_select(m.ct_eq(&one), [0; N], one), [0; N]);
+    for _ in 0..2 * bits - 1 {
+        let a_odd = Choice::from((a[0] & 1) as u8);
+        let (_, a_lt_b) = sub(a, b);
+        let swap = a_odd & Choice::from(a_lt_b as u8);
+        ct_swap(swap, &mut a, &mut b);
+        ct_swap(swap, &mut u, &mut v);
+
+        // Now `a >= b` if `a` is odd, and `a - b` is even.
+        let (diff, _) = sub(a, b);
+        a = ct_select(a_odd, diff, a);
+        let (diff, borrow) = sub(u, v);
+        let diff = ct_select(Choice::from(borrow as u8), add(diff, m).0, diff);
+        u = ct_select(a_odd, diff, u);
+
+        // Halve `a`, and `u` modulo `m`, keeping the carry out of `u + m`.
+        a = shr1(a, 0);
+        let (sum, carry) = add(u, m);
+        let u_odd = Choice::from((u[0] & 1) as u8);
+        u = ct_select(u_odd, shr1(sum, carry), shr1(u, 0));
+    }
+    let is_some = b.ct_eq(&one) & Choice::from((m[0] & 1) as u8);
+    CtOption::new(v, is_some)
+}
+
+/// Compares two big integers held as little-endian `u64` limbs, returning
+/// whether `a < b` and whether `a > b`.
+#[inline]
+#[track_caller]
+fn ct_lt_gt_limbs(a: &[u64], b: &[u64]) -> (Choice, Choice) {
+    assert!(a.len() == b.len(), "limb slices must have the same length");
+    let mut lt = Choice::from(false);
+    let mut gt = Choice::from(false);
+    // Scan from the most significant limb down. Once a limb differs, the
+    // outcome is fixed, but the remaining limbs are still compared.
+    for (x, y) in a.iter().zip(b).rev() {
+        let undecided = !(lt | gt);
+        lt |= undecided & x.ct_lt(y);
+        gt |= undecided & y.ct_lt(x);
+    }
+    (lt, gt)
+}
+
+/// Compares two big integers held as little-endian `u64` limbs.
+///
+/// Every limb is compared, from the most significant to the least, without
+/// stopping at the first difference, so the time taken depends only on the
+/// number of limbs. This suits range checks on secret values, such as
+/// whether a scalar is less than the group order.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` do not have the same number of limbs.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::cmp::Ordering;
+/// use std::select::ct_cmp_limbs;
+///
+/// assert_eq!(ct_cmp_limbs(&[u64::MAX, 1], &[0, 2]), Ordering::Less);
+/// assert_eq!(ct_cmp_limbs(&[5, 2], &[4, 2]), Ordering::Greater);
+/// assert_eq!(ct_cmp_limbs(&[], &[]), Ordering::Equal);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_cmp_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    let (lt, gt) = ct_lt_gt_limbs(a, b);
+    ct_select(lt, Ordering::Less, ct_select(gt, Ordering::Greater, Ordering::Equal))
+}
+
+/// Returns whether the big integer `a` is less than `b`, both held as
+/// little-endian `u64` limbs.
+///
+/// See [`ct_cmp_limbs`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` do not have the same number of limbs.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_lt_limbs;
+///
+/// // The order of the Ed25519 base point, 2^252 + 27742317777372353535851937790883648493.
+/// const L: [u64; 4] =
+///     [0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0x0000000000000000, 0x1000000000000000];
+///
+/// assert!(ct_lt_limbs(&[1, 0, 0, 0], &L).declassify());
+/// assert!(!ct_lt_limbs(&L, &L).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_lt_limbs(a: &[u64], b: &[u64]) -> Choice {
+    ct_lt_gt_limbs(a, b).0
+}
+
+/// Returns whether the big integer `a` is greater than `b`, both held as
+/// little-endian `u64` limbs.
+///
+/// See [`ct_cmp_limbs`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` do not have the same number of limbs.
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_gt_limbs(a: &[u64], b: &[u64]) -> Choice {
+    ct_lt_gt_limbs(a, b).1
+}
+
+/// Returns whether the big integers `a` and `b`, held as little-endian `u64`
+/// limbs, are equal.
+///
+/// See [`ct_cmp_limbs`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` do not have the same number of limbs.
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_eq_limbs(a: &[u64], b: &[u64]) -> Choice {
+    assert!(a.len() == b.len(), "limb slices must have the same length");
+    a.ct_eq(b)
+}
+
+/// Adds `modulus` to the big integer `x` in place if `choice` is set,
+/// returning the carry out of the most significant limb.
+///
+/// Both are held as little-endian `u64` limbs. This is the correction step
+/// after a modular subtraction: subtract first, then add the modulus back if
+/// the subtraction borrowed. The modulus is masked with a single mask derived
+/// from `choice` and added in one pass, so the addition is carried out either
+/// way.
+///
+/// # Panics
+///
+/// Panics if `x` and `modulus` do not have the same number of limbs.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_cond_add_mod, ct_sbb};
+///
+/// // Compute 3 - 5 modulo 7.
+/// let (diff, borrow) = ct_sbb(3u64, 5, 0);
+/// let mut x = [diff];
+/// ct_cond_add_mod(&mut x, &[7], Choice::from(borrow as u8));
+/// assert_eq!(x, [5]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_cond_add_mod(x: &mut [u64], modulus: &[u64], choice: Choice) -> Choice {
+    assert!(x.len() == modulus.len(), "limb slices must have the same length");
+    let mask = u64::ct_mask(choice);
+    let mut carry = 0;
+    for (x, &n) in x.iter_mut().zip(modulus) {
+        (*x, carry) = x.ct_adc(n & mask, carry);
+    }
+    Choice::from(carry as u8)
+}
+
+/// Subtracts `modulus` from the big integer `x` in place if `choice` is set,
+/// returning the borrow out of the most significant limb.
+///
+/// Both are held as little-endian `u64` limbs. This is the correction step
+/// after a modular addition or multiplication, where the result is reduced
+/// by subtracting the modulus if it is too large. As with
+/// [`ct_cond_add_mod`], the modulus is masked once and subtracted in one
+/// pass.
+///
+/// # Panics
+///
+/// Panics if `x` and `modulus` do not have the same number of limbs.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ct_cond_sub_mod, ct_lt_limbs};
+///
+/// // Compute 4 + 5 modulo 7.
+/// let mut x = [4 + 5];
+/// let too_large = !ct_lt_limbs(&x, &[7]);
+/// ct_cond_sub_mod(&mut x, &[7], too_large);
+/// assert_eq!(x, [2]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_cond_sub_mod(x: &mut [u64], modulus: &[u64], choice: Choice) -> Choice {
+    assert!(x.len() == modulus.len(), "limb slices must have the same length");
+    let mask = u64::ct_mask(choice);
+    let mut borrow = 0;
+    for (x, &n) in x.iter_mut().zip(modulus) {
+        (*x, borrow) = x.ct_sbb(n & mask, borrow);
+    }
+    Choice::from(borrow as u8)
+}
+
+/// Computes `acc + x * y + carry`, returning the low and high limbs.
+///
+/// The result always fits in two limbs.
+#[inline]
+pub(super) fn mac(acc: u64, x: u64, y: u64, carry: u64) -> (u64, u64) {
+    let (lo, hi) = x.ct_widening_mul(y);
+    let (lo, c1) = lo.ct_adc(acc, 0);
+    let (lo, c2) = lo.ct_adc(carry, 0);
+    (lo, hi + c1 + c2)
+}
+
+/// Whether the long multiply instructions of the target terminate early
+/// depending on the magnitude of their operands.
+///
+/// This is the case for ARMv7-M cores without the DSP extension, such as the
+/// Cortex-M3, whose `UMULL` takes fewer cycles for small operands. The
+/// regular 32-bit `MUL` is single-cycle on those cores.
+const VARIABLE_TIME_LONG_MUL: bool =
+    cfg!(all(target_arch = "arm", target_feature = "mclass", not(target_feature = "dsp")));
+
+/// Computes the full product of `$a` and `$b` of type `$t` by schoolbook
+/// multiplication of their halves, where `$half_mul` multiplies two values
+/// that fit in the lower half of `$t` into a full `$t`.
+macro_rules! schoolbook_widening_mul {
+    ($a:expr, $b:expr, $t:ty, $half_mul:expr) => {{
+        const H: u32 = <$t>::BITS / 2;
+        const M: $t = <$t>::MAX >> H;
+        let (a, b): ($t, $t) = ($a, $b);
+        let half_mul: fn($t, $t) -> $t = $half_mul;
+        let (a0, a1, b0, b1) = (a & M, a >> H, b & M, b >> H);
+        let p00 = half_mul(a0, b0);
+        let p01 = half_mul(a0, b1);
+        let p10 = half_mul(a1, b0);
+        let p11 = half_mul(a1, b1);
+        // At most three half-width values, so this cannot overflow.
+        let mid = (p00 >> H) + (p01 & M) + (p10 & M);
+        ((p00 & M) | (mid << H), p11 + (p01 >> H) + (p10 >> H) + (mid >> H))
+    }};
+}
+
+macro_rules! impl_ct_limb {
+    ($($t:ty => |$a:ident, $b:ident| $widening_mul:expr;)*) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl ConstantTimeLimb for $t {
+            #[inline]
+            fn ct_adc(self, rhs: Self, carry: Self) -> (Self, Self) {
+                let sum = self.wrapping_add(rhs).wrapping_add(carry & 1);
+                // The carry out of the top bit is the majority of the top bits
+                // of `self`, `rhs` and the carry into that bit.
+                let carry = ((self & rhs) | ((self | rhs) & !sum)) >> (<$t>::BITS - 1);
+                (sum, carry)
+            }
+
+            #[inline]
+            fn ct_sbb(self, rhs: Self, borrow: Self) -> (Self, Self) {
+                let diff = self.wrapping_sub(rhs).wrapping_sub(borrow & 1);
+                let borrow = ((!self & rhs) | (!(self ^ rhs) & diff)) >> (<$t>::BITS - 1);
+                (diff, borrow)
+            }
+
+            #[inline]
+            fn ct_widening_mul(self, rhs: Self) -> (Self, Self) {
+                let ($a, $b) = (self, rhs);
+                $widening_mul
+            }
+
+            fn ct_div_rem(self, divisor: Self) -> (Self, Self) {
+                let mut quotient: Self = 0;
+                let mut remainder: Self = 0;
+                for i in (0..<$t>::BITS).rev() {
+                    // Shift the next bit of the dividend into the remainder,
+                    // remembering the bit shifted out at the top: if it is set,
+                    // the true remainder exceeds any divisor.
+                    let top = remainder >> (<$t>::BITS - 1);
+                    remainder = (remainder << 1) | ((self >> i) & 1);
+                    let (diff, borrow) = remainder.ct_sbb(divisor, 0);
+                    let ge = top | (borrow ^ 1);
+                    remainder = ct_select(Choice::from(ge as u8), diff, remainder);
+                    quotient |= ge << i;
+                }
+                (quotient, remainder)
+            }
+
+            fn ct_mod_inverse(self, modulus: Self) -> CtOption<Self> {
+                let m = modulus;
+                // Maintain `a = u * x` and `b = v * x` modulo `m`, with `b`
+                // odd. Every step at least halves `a` or `b`, so after
+                // `2 * BITS - 1` steps `a` is zero and `b` is `gcd(x, m)`.
+                let (mut a, mut b) = (self, m);
+                // `u` starts as `1 mod m`, which is `0` in the degenerate case
+                // `m == 1`.
+                let (mut u, mut v): (Self, Self) = ((m ^ 1).ct_signum(), 0);
+                for _ in 0..2 * <$t>::BITS - 1 {
+                    let a_odd = Choice::from((a & 1) as u8);
+                    let (_, a_lt_b) = a.ct_sbb(b, 0);
+                    let swap = a_odd & Choice::from(a_lt_b as u8);
+                    ct_swap(swap, &mut a, &mut b);
+                    ct_swap(swap, &mut u, &mut v);
+
+                    // Now `a >= b` if `a` is odd, and `a - b` is even.
+                    a = ct_select(a_odd, a.wrapping_sub(b), a);
+                    let (diff, borrow) = u.ct_sbb(v, 0);
+                    let diff = ct_select(Choice::from(borrow as u8), diff.wrapping_add(m), diff);
+                    u = ct_select(a_odd, diff, u);
+
+                    // Halve `a`, and `u` modulo `m`, keeping the carry out of
+                    // `u + m`.
+                    a >>= 1;
+                    let (sum, carry) = u.ct_adc(m, 0);
+                    let u_odd = Choice::from((u & 1) as u8);
+                    u = ct_select(u_odd, (sum >> 1) | (carry << (<$t>::BITS - 1)), u >> 1);
+                }
+                let is_some = b.ct_eq(&1) & Choice::from((m & 1) as u8);
+                CtOption::new(v, is_some)
+            }
+
+            fn ct_gcd(self, other: Self) -> Self {
+                let (mut a, mut b) = (self, other);
+                // Strip the common factors of two, counting them in `shift`.
+                let mut shift = 0u32;
+                for _ in 0..<$t>::BITS - 1 {
+                    let both_even = Choice::from((!(a | b) & 1) as u8);
+                    shift += both_even.as_u8() as u32;
+                    a = ct_select(both_even, a >> 1, a);
+                    b = ct_select(both_even, b >> 1, b);
+                }
+                ct_swap(Choice::from((!b & 1) as u8), &mut a, &mut b);
+
+                // Now `b` is odd unless both operands are zero, and the
+                // steps below are those of `ct_mod_inverse`.
+                for _ in 0..2 * <$t>::BITS - 1 {
+                    let a_odd = Choice::from((a & 1) as u8);
+                    let (_, a_lt_b) = a.ct_sbb(b, 0);
+                    ct_swap(a_odd & Choice::from(a_lt_b as u8), &mut a, &mut b);
+                    a = ct_select(a_odd, a.wrapping_sub(b), a) >> 1;
+                }
+
+                // Restore the common factors of two without shifting by a
+                // secret amount.
+                for i in 0..<$t>::BITS - 1 {
+                    b = ct_select(Choice::from((i.wrapping_sub(shift) >> 31) as u8), b << 1, b);
+                }
+                b
+            }
+        }
+    )*};
+}
+
+impl_ct_limb! {
+    u32 => |a, b| if VARIABLE_TIME_LONG_MUL {
+        schoolbook_widening_mul!(a, b, u32, |x, y| x * y)
+    } else {
+        let wide = a as u64 * b as u64;
+        (wide as u32, (wide >> 32) as u32)
+    };
+    u64 => |a, b| if VARIABLE_TIME_LONG_MUL {
+        schoolbook_widening_mul!(a, b, u64, |x, y| {
+            let (lo, hi) = (x as u32).ct_widening_mul(y as u32);
+            ((hi as u64) << 32) | lo as u64
+        })
+    } else {
+        let wide = a as u128 * b as u128;
+        (wide as u64, (wide >> 64) as u64)
+    };
+    // There is no wider type to multiply into, so always split into halves.
+    u128 => |a, b| schoolbook_widening_mul!(a, b, u128, |x, y| {
+        let (lo, hi) = (x as u64).ct_widening_mul(y as u64);
+        ((hi as u128) << 64) | lo as u128
+    });
+}
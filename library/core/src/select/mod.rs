@@ -0,0 +1,725 @@
+//! Constant-time selection and comparison.
+//!
+//! The items in this module are building blocks for code that must not leak
+//! secret values through its control flow, such as cryptographic routines.
+//! Conditions are carried as a [`Choice`] rather than a `bool`, and values are
+//! chosen with [`ct_select`] instead of `if`/`else`, so that the path taken
+//! through the program does not depend on the condition.
+//!
+//! Selection is backed by the `ct_select` intrinsic, which code generation
+//! backends lower to conditional moves or masking sequences.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(ct_select)]
+//! use std::select::{ConstantTimeEq, ct_select};
+//!
+//! let secret = 0x5au8;
+//! let guess = 0x5au8;
+//! let reward = ct_select(secret.ct_eq(&guess), 100u32, 0u32);
+//! assert_eq!(reward, 100);
+//! ```
+
+use crate::cmp::Ordering;
+use crate::intrinsics;
+use crate::mem::MaybeUninit;
+
+mod barrett;
+mod base64;
+mod bitset;
+/// Utilities for bitsliced implementations, which process many values at once
+/// by storing each bit position of them in a separate word.
+#[unstable(feature = "ct_select", issue = "none")]
+pub mod bitslice;
+mod choice;
+mod ct_cell;
+mod ct_option;
+mod ct_result;
+#[cfg(target_arch = "aarch64")]
+mod dit;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod doitm;
+mod hex;
+mod iter;
+mod limb;
+mod montgomery;
+mod ops;
+mod parse;
+mod pow;
+mod secret;
+mod slice;
+mod uint;
+mod zeroize;
+
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::barrett::Barrett;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::base64::{ct_base64_decode, ct_base64_encode};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::bitset::CtBitSet;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::choice::{Choice, ct_and, ct_or, ct_xor};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::ct_cell::CtCell;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::ct_option::CtOption;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::ct_result::CtResult;
+#[cfg(target_arch = "aarch64")]
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::dit::{DitGuard, dit_enabled, set_dit};
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::doitm::{DoitmGuard, doitm_enabled, doitm_supported, set_doitm};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::hex::{ct_hex_decode, ct_hex_encode};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::iter::ConstantTimeIterator;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::limb::{
+    ConstantTimeLimb, ct_adc, ct_cmp_limbs, ct_cond_add_mod, ct_cond_sub_mod, ct_div_rem,
+    ct_eq_limbs, ct_gcd, ct_gcd_limbs, ct_gt_limbs, ct_lt_limbs, ct_mod_inverse,
+    ct_mod_inverse_limbs, ct_sbb, ct_widening_mul,
+};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::montgomery::{ct_mont_mul, ct_mont_reduce, mont_n_prime};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::ops::{
+    ConstantTimeOps, ct_count_ones, ct_leading_zeros, ct_max, ct_min, ct_neg_if, ct_rotate_left,
+    ct_rotate_right, ct_shl, ct_shr,
+};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::parse::{ct_parse_decimal, ct_parse_hex};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::pow::ct_pow;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::secret::Secret;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::slice::{
+    ct_all, ct_any, ct_contains, ct_lookup, ct_position, ct_slice_argmax, ct_slice_argmin,
+    ct_slice_max, ct_slice_min, verify_tag,
+};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::uint::CtUint;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use self::zeroize::{secure_zeroize, secure_zeroize_value};
+
+/// Types whose values can be selected between without branching.
+///
+/// Implementations must compute the result without any control flow that
+/// depends on the [`Choice`], and without memory accesses whose address
+/// depends on it.
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+pub const trait ConstantTimeSelect: Copy {
+    /// Returns `true_val` if `choice` is set, and `false_val` otherwise.
+    #[rustc_diagnostic_item = "constant_time_select_ct_select"]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self;
+
+    /// Swaps the values of `a` and `b` if `choice` is set.
+    ///
+    /// The default implementation performs two selections. Integer types
+    /// override it with a single XOR-masked exchange.
+    #[inline]
+    #[rustc_diagnostic_item = "constant_time_select_ct_swap"]
+    fn ct_swap(choice: Choice, a: &mut Self, b: &mut Self) {
+        let (old_a, old_b) = (*a, *b);
+        *a = Self::ct_select(choice, old_b, old_a);
+        *b = Self::ct_select(choice, old_a, old_b);
+    }
+
+    /// Returns a value with every bit set if `choice` is set, and zero
+    /// otherwise.
+    ///
+    /// Integer types override this by sign-extending the condition, and
+    /// arrays by masking each element. Other types have no all-ones value,
+    /// and the default implementation panics.
+    #[inline]
+    fn ct_mask(choice: Choice) -> Self {
+        let _ = choice;
+        panic!("`ct_mask` is only implemented for integers and arrays of them")
+    }
+
+    /// Sets each element of `out` to the corresponding element of
+    /// `true_vals` if `choice` is set, and of `false_vals` otherwise.
+    ///
+    /// The default implementation selects each element separately. Integer
+    /// types override it to derive a mask from `choice` once and apply it to
+    /// every element.
+    ///
+    /// The slices must have the same length; [`ct_select_many`] checks this
+    /// before calling this method.
+    #[inline]
+    fn ct_select_slice(choice: Choice, true_vals: &[Self], false_vals: &[Self], out: &mut [Self]) {
+        let mut i = 0;
+        while i < out.len() {
+            out[i] = Self::ct_select(choice, true_vals[i], false_vals[i]);
+            i += 1;
+        }
+    }
+
+    /// Returns `true_val` if `choice` is set, and `false_val` otherwise,
+    /// where `true_val` only has to be initialized if it is selected.
+    ///
+    /// This is how [`Option::ct_unwrap_or`] selects the payload of an
+    /// `Option` that may be `None`, without reading either value through an
+    /// address that depends on `choice`. The default implementation selects
+    /// the values with the `ct_select` intrinsic, whose lowering freezes the
+    /// uninitialized bytes of `true_val` first, so that they cannot make the
+    /// result undefined when `false_val` is selected. The intrinsic only
+    /// takes values that are passed as one or two scalars or as a SIMD
+    /// vector, so the other types, such as arrays, override this to select
+    /// their fields one by one.
+    ///
+    /// # Safety
+    ///
+    /// If `choice` is set, `true_val` must be initialized.
+    #[inline]
+    unsafe fn ct_select_uninit(
+        choice: Choice,
+        true_val: MaybeUninit<Self>,
+        false_val: Self,
+    ) -> Self {
+        let selected =
+            intrinsics::ct_select(choice.as_bool(), true_val, MaybeUninit::new(false_val));
+        // SAFETY: `false_val` is initialized, and the caller guarantees that
+        // `true_val` is if it is selected.
+        unsafe { selected.assume_init() }
+    }
+}
+
+/// Types that can be compared for equality without branching.
+///
+/// Implementations must not use [`PartialEq`], whose comparisons may compile to
+/// branches or stop at the first difference. The integer implementations
+/// instead XOR the operands and reduce the difference to a single bit.
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+pub const trait ConstantTimeEq {
+    /// Returns a [`Choice`] that is set if `self` and `other` are equal.
+    #[rustc_diagnostic_item = "constant_time_eq_ct_eq"]
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+/// Types that can be ordered without branching.
+///
+/// Like [`ConstantTimeEq`], this must be computed without the comparison
+/// operators of [`PartialOrd`], which may compile to branches. It is
+/// implemented for the primitive integers, whose comparisons are derived from
+/// the borrow out of a subtraction.
+#[unstable(feature = "ct_select", issue = "none")]
+pub trait ConstantTimeOrd: ConstantTimeEq {
+    /// Returns a [`Choice`] that is set if `self` is less than `other`.
+    fn ct_lt(&self, other: &Self) -> Choice;
+
+    /// Returns a [`Choice`] that is set if `self` is greater than `other`.
+    #[inline]
+    fn ct_gt(&self, other: &Self) -> Choice {
+        other.ct_lt(self)
+    }
+
+    /// Returns a [`Choice`] that is set if `self` is less than or equal to
+    /// `other`.
+    #[inline]
+    fn ct_le(&self, other: &Self) -> Choice {
+        !other.ct_lt(self)
+    }
+
+    /// Returns a [`Choice`] that is set if `self` is greater than or equal to
+    /// `other`.
+    #[inline]
+    fn ct_ge(&self, other: &Self) -> Choice {
+        !self.ct_lt(other)
+    }
+
+    /// Returns the [`Ordering`] between `self` and `other`.
+    ///
+    /// The result is selected between the three `Ordering` values with
+    /// [`ct_select`], so it can be combined further, for example into a
+    /// lexicographic comparison, before it is ever branched on.
+    #[inline]
+    fn ct_cmp(&self, other: &Self) -> Ordering {
+        let less = self.ct_lt(other);
+        let greater = other.ct_lt(self);
+        ct_select(less, Ordering::Less, ct_select(greater, Ordering::Greater, Ordering::Equal))
+    }
+}
+
+/// Returns `true_val` if `choice` is set, and `false_val` otherwise.
+///
+/// Unlike `if choice { true_val } else { false_val }`, the selection does not
+/// introduce a branch on `choice`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_select};
+///
+/// assert_eq!(ct_select(Choice::from(true), 1u64, 2), 1);
+/// assert_eq!(ct_select(Choice::from(false), 1u64, 2), 2);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[lang = "ct_select_fn"]
+#[rustc_force_inline = "the selection must be inlined into its caller at every optimization level"]
+pub const fn ct_select<T: [const] ConstantTimeSelect>(
+    choice: Choice,
+    true_val: T,
+    false_val: T,
+) -> T {
+    T::ct_select(choice, true_val, false_val)
+}
+
+/// Returns `true_val` if `choice` is set, and `false_val` otherwise, and
+/// keeps the processor from speculating past the selection.
+///
+/// [`ct_select`] does not branch on `choice`, but the processor may still
+/// predict the value it selects. When that value is a pointer or an index that
+/// is dereferenced right away, the load may then run speculatively with the
+/// wrong one, and leave a trace in the cache that depends on `choice`. This
+/// variant is followed by the speculation barrier of the target, such as
+/// `lfence` on x86 and `csdb` on AArch64, which makes the loads that follow
+/// wait for the selection.
+///
+/// The barrier is much slower than the selection, so this is meant for the
+/// selections whose result is immediately used as an address. `T` must be an
+/// integer no wider than a pointer, a pointer, a reference, or a type made of
+/// two of these, such as a slice. Other types, and targets without a
+/// speculation barrier, are rejected at compile time.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_select_spec};
+///
+/// # #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))] {
+/// let (public, secret) = ([1u8; 4], [2u8; 4]);
+/// let table = ct_select_spec(Choice::from(true), &secret, &public);
+/// assert_eq!(table[0], 2);
+/// # }
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_force_inline = "the selection must be inlined into its caller at every optimization level"]
+#[rustc_diagnostic_item = "ct_select_spec_fn"]
+pub const fn ct_select_spec<T: Copy>(choice: Choice, true_val: T, false_val: T) -> T {
+    intrinsics::ct_select_spec(choice.as_bool(), true_val, false_val)
+}
+
+/// Swaps the values of `a` and `b` if `choice` is set.
+///
+/// Both values are rewritten either way, so the memory accesses do not reveal
+/// whether the swap took place.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_swap};
+///
+/// let (mut a, mut b) = (1u32, 2u32);
+/// ct_swap(Choice::from(true), &mut a, &mut b);
+/// assert_eq!((a, b), (2, 1));
+/// ct_swap(Choice::from(false), &mut a, &mut b);
+/// assert_eq!((a, b), (2, 1));
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_force_inline = "the selection must be inlined into its caller at every optimization level"]
+#[rustc_diagnostic_item = "ct_swap_fn"]
+pub const fn ct_swap<T: [const] ConstantTimeSelect>(choice: Choice, a: &mut T, b: &mut T) {
+    T::ct_swap(choice, a, b)
+}
+
+/// Sets each element of `out` to the corresponding element of `true_vals` if
+/// `choice` is set, and of `false_vals` otherwise.
+///
+/// This is a batch form of [`ct_select`] for selecting whole big integers or
+/// buffers under a single condition. For integer elements, the mask is derived
+/// from `choice` once and shared by every element, rather than rematerialized
+/// for each selection.
+///
+/// # Panics
+///
+/// Panics if the three slices do not all have the same length.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_select_many};
+///
+/// let reduced = [1u64, 2, 3, 4];
+/// let unreduced = [5u64, 6, 7, 8];
+/// let mut out = [0u64; 4];
+/// ct_select_many(Choice::from(false), &reduced, &unreduced, &mut out);
+/// assert_eq!(out, unreduced);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_select_many<T: ConstantTimeSelect>(
+    choice: Choice,
+    true_vals: &[T],
+    false_vals: &[T],
+    out: &mut [T],
+) {
+    assert!(
+        true_vals.len() == out.len() && false_vals.len() == out.len(),
+        "slices passed to `ct_select_many` must have the same length"
+    );
+    T::ct_select_slice(choice, true_vals, false_vals, out)
+}
+
+/// Swaps the contents of `a` and `b` element by element if `choice` is set.
+///
+/// This is the conditional swap of the Montgomery ladder and of constant-time
+/// sorting networks, applied to whole big integers or buffers at once.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_swap_slices};
+///
+/// let mut x = [1u64, 2, 3];
+/// let mut y = [4u64, 5, 6];
+/// ct_swap_slices(Choice::from(true), &mut x, &mut y);
+/// assert_eq!((x, y), ([4, 5, 6], [1, 2, 3]));
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_swap_slices<T: ConstantTimeSelect>(choice: Choice, a: &mut [T], b: &mut [T]) {
+    assert_eq!(a.len(), b.len(), "slices passed to `ct_swap_slices` must have the same length");
+    for (a, b) in a.iter_mut().zip(b) {
+        T::ct_swap(choice, a, b);
+    }
+}
+
+/// Chooses between values based on [`Choice`]s, without branching.
+///
+/// `ct_if!` reads like an `if`/`else if`/`else` chain, and expands to nested
+/// calls of [`ct_select`]:
+///
+/// ```ignore (illustrative)
+/// ct_if! { a => x, b => y, else => z }
+/// // is equivalent to
+/// ct_select(a, x, ct_select(b, y, z))
+/// ```
+///
+/// As with [`ct_select`], every value in the chain is evaluated, regardless of
+/// which condition is set. The value of the first set condition is returned.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ConstantTimeEq, ct_if};
+///
+/// let opcode = 2u8;
+/// let cost = ct_if! {
+///     opcode.ct_eq(&0) => 1u32,
+///     opcode.ct_eq(&1) => 5,
+///     opcode.ct_eq(&2) => 20,
+///     else => 0,
+/// };
+/// assert_eq!(cost, 20);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[allow_internal_unstable(ct_select)]
+#[rustc_macro_transparency = "semiopaque"]
+pub macro ct_if {
+    (else => $else_val:expr $(,)?) => {
+        $else_val
+    },
+    ($choice:expr => $val:expr, $($rest:tt)+) => {
+        $crate::select::ct_select($choice, $val, $crate::select::ct_if! { $($rest)+ })
+    },
+}
+
+/// Compares `a` and `b` for equality without branching.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_eq;
+///
+/// assert!(ct_eq(&7u32, &7).declassify());
+/// assert!(!ct_eq(&7u32, &8).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[inline]
+#[rustc_diagnostic_item = "ct_eq_fn"]
+pub const fn ct_eq<T: [const] ConstantTimeEq + ?Sized>(a: &T, b: &T) -> Choice {
+    a.ct_eq(b)
+}
+
+/// Compares `a` and `b` without branching.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::cmp::Ordering;
+/// use std::select::ct_cmp;
+///
+/// assert_eq!(ct_cmp(&3u32, &7), Ordering::Less);
+/// assert_eq!(ct_cmp(&-3i8, &-3), Ordering::Equal);
+/// assert_eq!(ct_cmp(&u128::MAX, &0), Ordering::Greater);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_cmp<T: ConstantTimeOrd + ?Sized>(a: &T, b: &T) -> Ordering {
+    a.ct_cmp(b)
+}
+
+/// Returns a value of type `T` with every bit set if `choice` is set, and zero
+/// otherwise.
+///
+/// The mask is obtained by sign-extending the condition, without comparing
+/// or branching on it, and is typically combined with other values using
+/// bitwise operations.
+///
+/// # Panics
+///
+/// Panics if `T` is neither an integer type nor an array of them. See
+/// [`ConstantTimeSelect::ct_mask`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_mask};
+///
+/// assert_eq!(ct_mask::<u32>(Choice::from(true)), u32::MAX);
+/// assert_eq!(ct_mask::<i64>(Choice::from(true)), -1);
+/// assert_eq!(ct_mask::<u8>(Choice::from(false)), 0);
+///
+/// let secret = 0xabcdu16;
+/// assert_eq!(secret & ct_mask::<u16>(Choice::from(false)), 0);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[inline]
+pub const fn ct_mask<T: [const] ConstantTimeSelect>(choice: Choice) -> T {
+    T::ct_mask(choice)
+}
+
+/// Returns `value` unchanged, in a way that the optimizer cannot see through.
+///
+/// Unlike [`hint::black_box`], which is only a hint, this is guaranteed by
+/// every code generation backend: the compiler assumes nothing about the
+/// returned value, and does not merge two calls, even on the same value. This
+/// makes it a building block for masking schemes, whose shares must not be
+/// recombined by the optimizer, and keeps a selection between two operands
+/// that the compiler can prove equal from being simplified away.
+///
+/// Values that fit in a register are kept there; others are passed through
+/// memory.
+///
+/// [`hint::black_box`]: crate::hint::black_box
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_black_box, ct_select};
+///
+/// let key = 0x1234u32;
+/// // Without the barrier, the compiler could fold this into `key`.
+/// let value = ct_select(Choice::from(true), ct_black_box(key), key);
+/// assert_eq!(value, key);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+#[rustc_force_inline = "the barrier must be inlined into its caller at every optimization level"]
+pub const fn ct_black_box<T>(value: T) -> T {
+    intrinsics::ct_black_box(value)
+}
+
+macro_rules! impl_ct_select {
+    ($($t:ty),* $(,)?) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        #[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+        impl const ConstantTimeSelect for $t {
+            #[inline(always)]
+            fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+                intrinsics::ct_select(choice.as_bool(), true_val, false_val)
+            }
+
+            #[inline(always)]
+            fn ct_mask(choice: Choice) -> Self {
+                // `0` stays `0`, and `1` becomes all ones.
+                (choice.as_u8() as $t).wrapping_neg()
+            }
+
+            #[inline(always)]
+            fn ct_swap(choice: Choice, a: &mut Self, b: &mut Self) {
+                // `t` is either zero or `a ^ b`, and applying it to both sides
+                // either leaves them alone or exchanges them.
+                let t = (*a ^ *b) & <$t>::ct_mask(choice);
+                *a ^= t;
+                *b ^= t;
+            }
+
+            #[inline]
+            fn ct_select_slice(
+                choice: Choice,
+                true_vals: &[Self],
+                false_vals: &[Self],
+                out: &mut [Self],
+            ) {
+                let mask = <$t>::ct_mask(choice);
+                let mut i = 0;
+                while i < out.len() {
+                    let (t, f) = (true_vals[i], false_vals[i]);
+                    out[i] = f ^ (mask & (t ^ f));
+                    i += 1;
+                }
+            }
+        }
+    )*};
+}
+
+impl_ct_select!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+// These are selected by the intrinsic as a whole, and swapped with the default `ct_swap`.
+macro_rules! impl_ct_select_scalar {
+    ($($t:ty),* $(,)?) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        #[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+        impl const ConstantTimeSelect for $t {
+            #[inline(always)]
+            fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+                intrinsics::ct_select(choice.as_bool(), true_val, false_val)
+            }
+        }
+    )*};
+}
+
+impl_ct_select_scalar!(bool, char, f32, f64, Ordering);
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl<T> const ConstantTimeSelect for *const T {
+    #[inline(always)]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        intrinsics::ct_select(choice.as_bool(), true_val, false_val)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl<T> const ConstantTimeSelect for *mut T {
+    #[inline(always)]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        intrinsics::ct_select(choice.as_bool(), true_val, false_val)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeSelect, const N: usize> ConstantTimeSelect for [T; N] {
+    #[inline(always)]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        crate::array::from_fn(|i| T::ct_select(choice, true_val[i], false_val[i]))
+    }
+
+    #[inline(always)]
+    fn ct_swap(choice: Choice, a: &mut Self, b: &mut Self) {
+        ct_swap_slices(choice, a, b)
+    }
+
+    #[inline(always)]
+    fn ct_mask(choice: Choice) -> Self {
+        crate::array::from_fn(|_| T::ct_mask(choice))
+    }
+
+    #[inline(always)]
+    unsafe fn ct_select_uninit(
+        choice: Choice,
+        true_val: MaybeUninit<Self>,
+        false_val: Self,
+    ) -> Self {
+        let true_vals = true_val.as_ptr().cast::<MaybeUninit<T>>();
+        crate::array::from_fn(|i| {
+            // SAFETY: the element is within `true_val`, and is initialized if
+            // `true_val` is, which the caller guarantees if it is selected.
+            unsafe { T::ct_select_uninit(choice, true_vals.add(i).read(), false_val[i]) }
+        })
+    }
+}
+
+macro_rules! impl_ct_eq {
+    ($($t:ty => $u:ty),*) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        #[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+        impl const ConstantTimeEq for $t {
+            #[inline]
+            fn ct_eq(&self, other: &Self) -> Choice {
+                let diff = (*self ^ *other) as $u;
+                // The top bit of `x | -x` is set exactly when `x` is nonzero.
+                let ne = (diff | diff.wrapping_neg()) >> (<$u>::BITS - 1);
+                !Choice::from(ne as u8)
+            }
+        }
+    )*};
+}
+
+impl_ct_eq! {
+    i8 => u8, u8 => u8, i16 => u16, u16 => u16, i32 => u32, u32 => u32, i64 => u64,
+    u64 => u64, i128 => u128, u128 => u128, isize => usize, usize => usize
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const ConstantTimeEq for bool {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        !Choice::from((*self ^ *other) as u8)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+#[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+impl const ConstantTimeEq for Choice {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        !(*self ^ *other)
+    }
+}
+
+/// Compares slices element by element.
+///
+/// Every element is compared, even after a difference has been found. The
+/// lengths of the slices are treated as public: slices of different lengths
+/// compare as unequal without examining their elements.
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeEq> ConstantTimeEq for [T] {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        if self.len() != other.len() {
+            return Choice::from(false);
+        }
+        let mut eq = Choice::from(true);
+        for (a, b) in self.iter().zip(other) {
+            eq &= a.ct_eq(b);
+        }
+        eq
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeEq, const N: usize> ConstantTimeEq for [T; N] {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self[..].ct_eq(&other[..])
+    }
+}
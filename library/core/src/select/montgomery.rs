@@ -0,0 +1,174 @@
+use super::limb::mac;
+use super::{Choice, ConstantTimeLimb, ct_cond_sub_mod};
+
+/// Computes `-modulus^-1 mod 2^64` from the least significant limb of an odd
+/// modulus, as used by [`ct_mont_mul`] and [`ct_mont_reduce`].
+///
+/// The modulus is public, so this is usually evaluated once in a constant.
+///
+/// # Panics
+///
+/// Panics if `n0` is even, since an even modulus has no such inverse.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::mont_n_prime;
+///
+/// const N_PRIME: u64 = mont_n_prime(0xffff_ffff_0000_0001);
+/// assert_eq!(0xffff_ffff_0000_0001u64.wrapping_mul(N_PRIME), u64::MAX);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub const fn mont_n_prime(n0: u64) -> u64 {
+    assert!(n0 & 1 == 1, "Montgomery modulus must be odd");
+    // An odd `n0` is its own inverse modulo 2^3, and each Newton step doubles
+    // the number of correct bits.
+    let mut inv = n0;
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(n0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// Subtracts `modulus` from the value `hi * 2^(64 * len) + out` if that value
+/// is at least `modulus`, where `hi` is `0` or `1`.
+///
+/// This is the final step of a Montgomery reduction, whose intermediate result
+/// is less than twice the modulus.
+#[inline]
+fn final_sub(out: &mut [u64], hi: u64, modulus: &[u64]) {
+    // Compute the borrow of the full subtraction first, so that the result
+    // can then be adjusted in place.
+    let mut borrow = 0;
+    for (&x, &n) in out.iter().zip(modulus) {
+        (_, borrow) = x.ct_sbb(n, borrow);
+    }
+    let (_, borrow) = hi.ct_sbb(0, borrow);
+    ct_cond_sub_mod(out, modulus, !Choice::from(borrow as u8));
+}
+
+/// Checks the limb counts shared by the Montgomery functions.
+#[inline]
+#[track_caller]
+fn check_lengths(modulus: &[u64], out: &[u64]) {
+    assert!(!modulus.is_empty(), "Montgomery modulus must have at least one limb");
+    assert!(out.len() == modulus.len(), "output must have as many limbs as the modulus");
+}
+
+/// Computes the Montgomery product `a * b * 2^(-64 * len) mod modulus` of two
+/// big integers held as little-endian `u64` limbs, writing it to `out`.
+///
+/// `n_prime` is [`mont_n_prime`] of the least significant limb of `modulus`.
+/// `a` and `b` must be less than `modulus`, which must be odd; the result is
+/// then fully reduced. Multiplication and reduction are interleaved limb by
+/// limb, and the final conditional subtraction of the modulus is applied with
+/// a mask rather than a branch, so the time taken depends only on the number
+/// of limbs.
+///
+/// # Panics
+///
+/// Panics if `modulus` is empty, or if `a`, `b` and `out` do not all have as
+/// many limbs as `modulus`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ct_mont_mul, mont_n_prime};
+///
+/// let p = [0xffff_ffff_0000_0001];
+/// let n_prime = mont_n_prime(p[0]);
+/// // 2^128 mod p, for converting into Montgomery form.
+/// let r2 = [0xffff_fffe_0000_0001];
+///
+/// let mut x = [0];
+/// ct_mont_mul(&[7], &r2, &p, n_prime, &mut x);
+/// let mut y = [0];
+/// ct_mont_mul(&x, &x, &p, n_prime, &mut y);
+/// let mut z = [0];
+/// ct_mont_mul(&y, &[1], &p, n_prime, &mut z);
+/// assert_eq!(z, [49]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_mont_mul(a: &[u64], b: &[u64], modulus: &[u64], n_prime: u64, out: &mut [u64]) {
+    check_lengths(modulus, out);
+    assert!(
+        a.len() == out.len() && b.len() == out.len(),
+        "operands must have as many limbs as the modulus"
+    );
+    let last = out.len() - 1;
+    out.fill(0);
+    // `out` holds the low limbs of the accumulator, and `hi` the limb above.
+    let mut hi = 0;
+    for &bi in b {
+        let mut carry = 0;
+        for (t, &aj) in out.iter_mut().zip(a) {
+            (*t, carry) = mac(*t, aj, bi, carry);
+        }
+        let (top, top_carry) = hi.ct_adc(carry, 0);
+
+        // Add the multiple of the modulus that clears the lowest limb, and
+        // shift the accumulator down by one limb.
+        let m = out[0].wrapping_mul(n_prime);
+        let (_, mut carry) = mac(out[0], m, modulus[0], 0);
+        for j in 1..out.len() {
+            (out[j - 1], carry) = mac(out[j], m, modulus[j], carry);
+        }
+        let (t, c) = top.ct_adc(carry, 0);
+        out[last] = t;
+        hi = top_carry + c;
+    }
+    final_sub(out, hi, modulus);
+}
+
+/// Computes the Montgomery reduction `t * 2^(-64 * len) mod modulus` of the
+/// double-width big integer `t`, writing it to `out`.
+///
+/// `t` holds twice as many little-endian `u64` limbs as `modulus`, and must be
+/// less than `modulus * 2^(64 * len)`, as the product of two reduced values
+/// is. `n_prime` is [`mont_n_prime`] of the least significant limb of
+/// `modulus`. As with [`ct_mont_mul`], the time taken depends only on the
+/// number of limbs.
+///
+/// # Panics
+///
+/// Panics if `modulus` is empty, if `out` does not have as many limbs as
+/// `modulus`, or if `t` does not have twice as many.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ct_mont_reduce, mont_n_prime};
+///
+/// let p = [0xffff_ffff_0000_0001];
+/// let mut x = [0];
+/// // Reducing `a * 2^64` gives back `a`.
+/// ct_mont_reduce(&[0, 5], &p, mont_n_prime(p[0]), &mut x);
+/// assert_eq!(x, [5]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_mont_reduce(t: &[u64], modulus: &[u64], n_prime: u64, out: &mut [u64]) {
+    check_lengths(modulus, out);
+    assert!(t.len() == 2 * out.len(), "input must have twice as many limbs as the modulus");
+    let (lo, high_limbs) = t.split_at(out.len());
+    let last = out.len() - 1;
+    out.copy_from_slice(lo);
+    // `out` is a window over the limbs of `t` that moves up by one limb each
+    // step, and `hi` is the carry into the limb above it.
+    let mut hi = 0;
+    for &next in high_limbs {
+        let m = out[0].wrapping_mul(n_prime);
+        let (_, mut carry) = mac(out[0], m, modulus[0], 0);
+        for j in 1..out.len() {
+            (out[j - 1], carry) = mac(out[j], m, modulus[j], carry);
+        }
+        (out[last], hi) = next.ct_adc(carry, hi);
+    }
+    final_sub(out, hi, modulus);
+}
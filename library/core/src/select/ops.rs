@@ -0,0 +1,519 @@
+use super::{Choice, ConstantTimeOrd, ConstantTimeSelect, ct_mask, ct_select};
+
+/// Branch-free arithmetic helpers for primitive integers.
+///
+/// Every method is computed with bitwise arithmetic and [`ct_select`], so the
+/// time taken does not depend on the values involved.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ConstantTimeOps;
+///
+/// assert_eq!((-5i32).ct_abs(), 5);
+/// assert_eq!((-5i32).ct_signum(), -1);
+/// assert_eq!(3u8.ct_min(9), 3);
+/// assert_eq!(3u8.ct_max(9), 9);
+/// assert_eq!(300i64.ct_clamp(0, 255), 255);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub trait ConstantTimeOps: Sized {
+    /// Computes the absolute value of `self`.
+    ///
+    /// Like [`i32::wrapping_abs`], the absolute value of the minimum value of
+    /// a signed type is that minimum value itself. For unsigned types this
+    /// returns `self`.
+    fn ct_abs(self) -> Self;
+
+    /// Returns a number representing the sign of `self`.
+    ///
+    /// The result is `0` if `self` is zero, `1` if it is positive, and `-1` if
+    /// it is negative.
+    fn ct_signum(self) -> Self;
+
+    /// Returns the smaller of `self` and `other`.
+    ///
+    /// See also the generic [`ct_min`] function.
+    fn ct_min(self, other: Self) -> Self;
+
+    /// Returns the larger of `self` and `other`.
+    ///
+    /// See also the generic [`ct_max`] function.
+    fn ct_max(self, other: Self) -> Self;
+
+    /// Returns `-self` if `choice` is set, and `self` otherwise.
+    ///
+    /// Negation wraps around, so negating the minimum value of a signed type
+    /// yields that value again, and unsigned values are negated modulo
+    /// `2^BITS`.
+    fn ct_neg_if(self, choice: Choice) -> Self;
+
+    /// Shifts `self` left by `amount` bits.
+    ///
+    /// Amounts of at least the bit width of the type shift every bit out,
+    /// giving zero.
+    fn ct_shl(self, amount: u32) -> Self;
+
+    /// Shifts `self` right by `amount` bits.
+    ///
+    /// Like `>>`, this is an arithmetic shift for signed types. Amounts of at
+    /// least the bit width of the type shift every bit out, giving zero, or
+    /// `-1` for negative values.
+    fn ct_shr(self, amount: u32) -> Self;
+
+    /// Rotates the bits of `self` left by `amount`, modulo the bit width of
+    /// the type.
+    fn ct_rotate_left(self, amount: u32) -> Self;
+
+    /// Rotates the bits of `self` right by `amount`, modulo the bit width of
+    /// the type.
+    fn ct_rotate_right(self, amount: u32) -> Self;
+
+    /// Returns the number of leading zeros in the binary representation of
+    /// `self`.
+    fn ct_leading_zeros(self) -> u32;
+
+    /// Returns the number of ones in the binary representation of `self`.
+    fn ct_count_ones(self) -> u32;
+
+    /// Restricts `self` to the interval `[min, max]`.
+    ///
+    /// Unlike [`Ord::clamp`], this does not panic if `min > max`; `max` is
+    /// returned in that case.
+    #[inline]
+    fn ct_clamp(self, min: Self, max: Self) -> Self {
+        self.ct_max(min).ct_min(max)
+    }
+}
+
+/// Computes `a < b` for values of the unsigned type `$u` from the borrow out
+/// of `a - b`.
+macro_rules! ct_lt_unsigned {
+    ($a:expr, $b:expr, $u:ty) => {{
+        let (a, b): ($u, $u) = ($a, $b);
+        let borrow = (!a & b) | (!(a ^ b) & a.wrapping_sub(b));
+        Choice::from((borrow >> (<$u>::BITS - 1)) as u8)
+    }};
+}
+
+/// Computes `a < b` for signed values whose unsigned counterpart is `$u`.
+///
+/// Flipping the sign bit maps the signed order onto the unsigned one.
+macro_rules! ct_lt_signed {
+    ($a:expr, $b:expr, $u:ty) => {{
+        const FLIP: $u = 1 << (<$u>::BITS - 1);
+        ct_lt_unsigned!($a as $u ^ FLIP, $b as $u ^ FLIP, $u)
+    }};
+}
+
+/// Shifts `$x` of type `$t` by the secret `$amount` with the operator `$op`,
+/// producing `$fill` for amounts of at least `BITS`.
+///
+/// The shift is composed of one conditional shift by each power of two below
+/// `BITS`, so the amount only ever selects between values.
+macro_rules! ct_shift {
+    ($x:expr, $amount:expr, $t:ty, $op:tt, $fill:expr) => {{
+        let (mut x, amount): ($t, u32) = ($x, $amount);
+        let fill: $t = $fill;
+        for i in 0..<$t>::BITS.ilog2() {
+            let bit = Choice::from(((amount >> i) & 1) as u8);
+            x = ct_select(bit, x $op (1 << i), x);
+        }
+        let high = amount >> <$t>::BITS.ilog2();
+        let too_large = Choice::from(((high | high.wrapping_neg()) >> 31) as u8);
+        ct_select(too_large, fill, x)
+    }};
+}
+
+/// Rotates `$x` of type `$t` by the secret `$amount` with the method `$rotate`,
+/// one conditional rotation by each power of two below `BITS`.
+///
+/// Rotations wrap around, so the bits of `$amount` above those are ignored.
+macro_rules! ct_rotate {
+    ($x:expr, $amount:expr, $t:ty, $rotate:ident) => {{
+        let (mut x, amount): ($t, u32) = ($x, $amount);
+        for i in 0..<$t>::BITS.ilog2() {
+            let bit = Choice::from(((amount >> i) & 1) as u8);
+            x = ct_select(bit, x.$rotate(1 << i), x);
+        }
+        x
+    }};
+}
+
+macro_rules! impl_ct_ops_unsigned {
+    ($($u:ty),*) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl ConstantTimeOrd for $u {
+            #[inline]
+            fn ct_lt(&self, other: &Self) -> Choice {
+                ct_lt_unsigned!(*self, *other, $u)
+            }
+        }
+
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl ConstantTimeOps for $u {
+            #[inline]
+            fn ct_abs(self) -> Self {
+                self
+            }
+
+            #[inline]
+            fn ct_signum(self) -> Self {
+                // The top bit of `x | -x` is set exactly when `x` is nonzero.
+                (self | self.wrapping_neg()) >> (<$u>::BITS - 1)
+            }
+
+            #[inline]
+            fn ct_min(self, other: Self) -> Self {
+                ct_select(self.ct_lt(&other), self, other)
+            }
+
+            #[inline]
+            fn ct_max(self, other: Self) -> Self {
+                ct_select(self.ct_lt(&other), other, self)
+            }
+
+            #[inline]
+            fn ct_neg_if(self, choice: Choice) -> Self {
+                // With an all-ones mask this computes `!self + 1`, i.e. `-self`;
+                // with a zero mask it leaves `self` untouched. Unlike selecting
+                // `-self`, this cannot overflow.
+                let mask = ct_mask::<Self>(choice);
+                (self ^ mask).wrapping_sub(mask)
+            }
+
+            #[inline]
+            fn ct_shl(self, amount: u32) -> Self {
+                ct_shift!(self, amount, $u, <<, 0)
+            }
+
+            #[inline]
+            fn ct_shr(self, amount: u32) -> Self {
+                ct_shift!(self, amount, $u, >>, 0)
+            }
+
+            #[inline]
+            fn ct_rotate_left(self, amount: u32) -> Self {
+                ct_rotate!(self, amount, $u, rotate_left)
+            }
+
+            #[inline]
+            fn ct_rotate_right(self, amount: u32) -> Self {
+                ct_rotate!(self, amount, $u, rotate_right)
+            }
+
+            #[inline]
+            fn ct_leading_zeros(self) -> u32 {
+                // Smear the highest set bit into every lower position, then
+                // count the ones that are not leading zeros.
+                let mut x = self;
+                let mut shift = 1;
+                while shift < <$u>::BITS {
+                    x |= x >> shift;
+                    shift *= 2;
+                }
+                <$u>::BITS - x.ct_count_ones()
+            }
+
+            #[inline]
+            fn ct_count_ones(self) -> u32 {
+                // Sum adjacent bits, then pairs, then nibbles, in parallel.
+                // The per-byte counts are added up with shifts rather than the
+                // usual multiplication, which is not constant-time for wide
+                // types on every target.
+                let x = self - ((self >> 1) & (<$u>::MAX / 3));
+                let x = (x & (<$u>::MAX / 5)) + ((x >> 2) & (<$u>::MAX / 5));
+                let mut x = (x + (x >> 4)) & (<$u>::MAX / 17);
+                let mut shift = 8;
+                while shift < <$u>::BITS {
+                    x += x >> shift;
+                    shift *= 2;
+                }
+                // The count is at most 128, so it fits in the lowest byte.
+                (x & 0xff) as u32
+            }
+        }
+    )*};
+}
+
+impl_ct_ops_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_ct_ops_signed {
+    ($($s:ty => $u:ty),*) => {$(
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl ConstantTimeOrd for $s {
+            #[inline]
+            fn ct_lt(&self, other: &Self) -> Choice {
+                ct_lt_signed!(*self, *other, $u)
+            }
+        }
+
+        #[unstable(feature = "ct_select", issue = "none")]
+        impl ConstantTimeOps for $s {
+            #[inline]
+            fn ct_abs(self) -> Self {
+                let sign = self >> (<$s>::BITS - 1);
+                (self ^ sign).wrapping_sub(sign)
+            }
+
+            #[inline]
+            fn ct_signum(self) -> Self {
+                // `-1` from the sign bit, or'd with `1` if the negation is
+                // negative, i.e. if `self` is positive (or `MIN`, which is
+                // already covered by the sign bit).
+                (self >> (<$s>::BITS - 1))
+                    | ((self.wrapping_neg() as $u) >> (<$s>::BITS - 1)) as $s
+            }
+
+            #[inline]
+            fn ct_min(self, other: Self) -> Self {
+                ct_select(self.ct_lt(&other), self, other)
+            }
+
+            #[inline]
+            fn ct_max(self, other: Self) -> Self {
+                ct_select(self.ct_lt(&other), other, self)
+            }
+
+            #[inline]
+            fn ct_neg_if(self, choice: Choice) -> Self {
+                // With an all-ones mask this computes `!self + 1`, i.e. `-self`;
+                // with a zero mask it leaves `self` untouched. Unlike selecting
+                // `-self`, this cannot overflow.
+                let mask = ct_mask::<Self>(choice);
+                (self ^ mask).wrapping_sub(mask)
+            }
+
+            #[inline]
+            fn ct_shl(self, amount: u32) -> Self {
+                ct_shift!(self, amount, $s, <<, 0)
+            }
+
+            #[inline]
+            fn ct_shr(self, amount: u32) -> Self {
+                ct_shift!(self, amount, $s, >>, self >> (<$s>::BITS - 1))
+            }
+
+            #[inline]
+            fn ct_rotate_left(self, amount: u32) -> Self {
+                ct_rotate!(self, amount, $s, rotate_left)
+            }
+
+            #[inline]
+            fn ct_rotate_right(self, amount: u32) -> Self {
+                ct_rotate!(self, amount, $s, rotate_right)
+            }
+
+            #[inline]
+            fn ct_leading_zeros(self) -> u32 {
+                (self as $u).ct_leading_zeros()
+            }
+
+            #[inline]
+            fn ct_count_ones(self) -> u32 {
+                (self as $u).ct_count_ones()
+            }
+        }
+    )*};
+}
+
+impl_ct_ops_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// Returns `-value` if `choice` is set, and `value` otherwise.
+///
+/// This is the conditional negation step of signed-digit recodings. It is
+/// computed as `(value ^ mask) - mask` for a mask derived from `choice`, and
+/// wraps around like [`i32::wrapping_neg`] rather than overflowing on the
+/// minimum value.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_neg_if};
+///
+/// assert_eq!(ct_neg_if(Choice::from(true), 5i8), -5);
+/// assert_eq!(ct_neg_if(Choice::from(false), 5i8), 5);
+/// assert_eq!(ct_neg_if(Choice::from(true), i8::MIN), i8::MIN);
+/// assert_eq!(ct_neg_if(Choice::from(true), 1u32), u32::MAX);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_neg_if<T: ConstantTimeOps>(choice: Choice, value: T) -> T {
+    value.ct_neg_if(choice)
+}
+
+/// Shifts `value` left by the secret `amount`.
+///
+/// Shift instructions are not guaranteed to take the same time for every
+/// amount, and `value << amount` panics or is undefined for amounts of at
+/// least the bit width of the type. This instead conditionally shifts by each
+/// power of two in turn, with [`ct_select`], and returns zero for overlarge
+/// amounts.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_shl;
+///
+/// assert_eq!(ct_shl(1u32, 5), 32);
+/// assert_eq!(ct_shl(1u32, 31), 1 << 31);
+/// assert_eq!(ct_shl(1u32, 32), 0);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_shl<T: ConstantTimeOps>(value: T, amount: u32) -> T {
+    value.ct_shl(amount)
+}
+
+/// Shifts `value` right by the secret `amount`.
+///
+/// Signed values are shifted arithmetically. Overlarge amounts give zero, or
+/// `-1` for negative values. See [`ct_shl`] for details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_shr;
+///
+/// assert_eq!(ct_shr(0x80u8, 7), 1);
+/// assert_eq!(ct_shr(-64i16, 3), -8);
+/// assert_eq!(ct_shr(-64i16, 100), -1);
+/// assert_eq!(ct_shr(u64::MAX, 64), 0);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_shr<T: ConstantTimeOps>(value: T, amount: u32) -> T {
+    value.ct_shr(amount)
+}
+
+/// Rotates the bits of `value` left by the secret `amount`, modulo the bit
+/// width of the type.
+///
+/// This is the rotation step of ARX ciphers with key-dependent rotations, such
+/// as RC5 and RC6. Each power of two below the bit width is conditionally
+/// rotated by in turn, with [`ct_select`], so the amount does not influence
+/// the instructions executed.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_rotate_left;
+///
+/// assert_eq!(ct_rotate_left(0x8000_0001u32, 4), 0x18);
+/// assert_eq!(ct_rotate_left(0x8000_0001u32, 36), 0x18);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_rotate_left<T: ConstantTimeOps>(value: T, amount: u32) -> T {
+    value.ct_rotate_left(amount)
+}
+
+/// Rotates the bits of `value` right by the secret `amount`, modulo the bit
+/// width of the type.
+///
+/// See [`ct_rotate_left`] for details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_rotate_right;
+///
+/// assert_eq!(ct_rotate_right(0x18u32, 4), 0x8000_0001);
+/// assert_eq!(ct_rotate_right(1u8, 9), 0x80);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_rotate_right<T: ConstantTimeOps>(value: T, amount: u32) -> T {
+    value.ct_rotate_right(amount)
+}
+
+/// Returns the number of leading zeros in the binary representation of
+/// `value`.
+///
+/// [`u32::leading_zeros`] is lowered to a loop or a branchy sequence on
+/// targets without a count-leading-zeros instruction. This is always computed
+/// with a fixed sequence of shifts, ORs and additions, as needed for the
+/// normalization steps of big integer division.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_leading_zeros;
+///
+/// assert_eq!(ct_leading_zeros(1u32), 31);
+/// assert_eq!(ct_leading_zeros(0u64), 64);
+/// assert_eq!(ct_leading_zeros(-1i8), 0);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_leading_zeros<T: ConstantTimeOps>(value: T) -> u32 {
+    value.ct_leading_zeros()
+}
+
+/// Returns the number of ones in the binary representation of `value`.
+///
+/// Like [`ct_leading_zeros`], this never depends on a population count
+/// instruction being available.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_count_ones;
+///
+/// assert_eq!(ct_count_ones(0b1011u8), 3);
+/// assert_eq!(ct_count_ones(u128::MAX), 128);
+/// assert_eq!(ct_count_ones(-1i16), 16);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_count_ones<T: ConstantTimeOps>(value: T) -> u32 {
+    value.ct_count_ones()
+}
+
+/// Returns the smaller of `a` and `b`, without branching.
+///
+/// Unlike [`cmp::min`](crate::cmp::min), this only accepts types whose
+/// ordering is itself constant-time, so no `<` comparison that might compile
+/// to a branch is involved. If `a` and `b` are equal, `b` is returned.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_min;
+///
+/// assert_eq!(ct_min(3u8, 9), 3);
+/// assert_eq!(ct_min(-3i64, -9), -9);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_min<T: ConstantTimeOrd + ConstantTimeSelect>(a: T, b: T) -> T {
+    ct_select(a.ct_lt(&b), a, b)
+}
+
+/// Returns the larger of `a` and `b`, without branching.
+///
+/// If `a` and `b` are equal, `a` is returned. See [`ct_min`] for details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_max;
+///
+/// assert_eq!(ct_max(3u8, 9), 9);
+/// assert_eq!(ct_max(-3i64, -9), -3);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn ct_max<T: ConstantTimeOrd + ConstantTimeSelect>(a: T, b: T) -> T {
+    ct_select(a.ct_lt(&b), b, a)
+}
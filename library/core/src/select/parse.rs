@@ -0,0 +1,83 @@
+use super::{Choice, ConstantTimeEq, ConstantTimeLimb, CtOption};
+
+/// Converts a decimal digit to its value, along with a flag byte that is `1`
+/// if `c` is a valid digit.
+#[inline]
+fn decode_decimal(c: u8) -> (u8, u8) {
+    let d = c as i32 - b'0' as i32;
+    // Either `d` or `9 - d` is negative when `c` is not a digit.
+    let invalid = ((d | (9 - d)) >> 8) & 1;
+    (d as u8, (invalid ^ 1) as u8)
+}
+
+/// Accumulates the digits of `src` in `radix`, decoded by `decode`.
+fn parse_digits<T>(src: &[u8], radix: u8, decode: fn(u8) -> (u8, u8)) -> CtOption<T>
+where
+    T: ConstantTimeLimb + ConstantTimeEq + From<u8>,
+{
+    let zero = T::from(0);
+    let mut value = zero;
+    let mut valid = Choice::from(!src.is_empty());
+    for &c in src {
+        let (digit, ok) = decode(c);
+        valid &= Choice::from(ok);
+        // Anything shifted into the high half, or carried out of the
+        // addition, means that the value does not fit in `T`.
+        let (lo, hi) = value.ct_widening_mul(T::from(radix));
+        let (sum, carry) = lo.ct_adc(T::from(digit), zero);
+        valid &= hi.ct_eq(&zero) & carry.ct_eq(&zero);
+        value = sum;
+    }
+    CtOption::new(value, valid)
+}
+
+/// Parses the decimal digits of `src` as an unsigned integer.
+///
+/// Unlike [`str::parse`], this does not stop at the first invalid character:
+/// every character of `src` is decoded with arithmetic only and multiplied
+/// into the result, so the time taken depends only on the length of `src`.
+/// This is intended for short secrets of a known length, such as PINs and
+/// one-time passwords.
+///
+/// The result is none if `src` is empty, contains anything but the digits
+/// `0` to `9`, or overflows `T`. Leading zeros are accepted.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_parse_decimal;
+///
+/// assert_eq!(ct_parse_decimal::<u32>(b"004271").unwrap(), 4271);
+/// assert!(!ct_parse_decimal::<u32>(b"42 1").is_some().declassify());
+/// assert!(!ct_parse_decimal::<u32>(b"4294967296").is_some().declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_parse_decimal<T>(src: &[u8]) -> CtOption<T>
+where
+    T: ConstantTimeLimb + ConstantTimeEq + From<u8>,
+{
+    parse_digits(src, 10, decode_decimal)
+}
+
+/// Parses the hexadecimal digits of `src`, of either case, as an unsigned
+/// integer.
+///
+/// See [`ct_parse_decimal`] for details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_parse_hex;
+///
+/// assert_eq!(ct_parse_hex::<u64>(b"DeadBeef").unwrap(), 0xdead_beef);
+/// assert!(!ct_parse_hex::<u64>(b"0x10").is_some().declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_parse_hex<T>(src: &[u8]) -> CtOption<T>
+where
+    T: ConstantTimeLimb + ConstantTimeEq + From<u8>,
+{
+    parse_digits(src, 16, super::hex::decode_nibble)
+}
@@ -0,0 +1,83 @@
+use super::{ConstantTimeEq, ConstantTimeSelect, ct_lookup, ct_select};
+
+/// Returns the `width` bits of the little-endian limbs `exponent` starting at
+/// bit `pos`, treating bits past the end as zero.
+///
+/// The positions are public, so this may branch on them.
+#[inline]
+fn window_at(exponent: &[u64], pos: usize, width: usize) -> usize {
+    let (limb, offset) = (pos / 64, pos % 64);
+    let mut bits = exponent[limb] >> offset;
+    if offset + width > 64 && limb + 1 < exponent.len() {
+        bits |= exponent[limb + 1] << (64 - offset);
+    }
+    (bits & ((1 << width) - 1)) as usize
+}
+
+/// Raises `base` to the power `exponent` in the group defined by `op`, using
+/// a fixed window exponentiation.
+///
+/// `op` is the group operation and `identity` its identity element; for
+/// modular exponentiation they are multiplication modulo the modulus and
+/// `1`. `exponent` is held as little-endian `u64` limbs. The window is
+/// `log2(N)` bits wide, and `N` powers of `base` are precomputed.
+///
+/// The sequence of group operations is fixed by `N` and the number of limbs
+/// in `exponent`: each window costs the same number of squarings and one
+/// multiplication, the table entry for it is read with [`ct_lookup`], and
+/// the multiplication by the entry for a zero window is discarded with
+/// [`ct_select`] rather than skipped. As long as `op` itself runs in constant
+/// time, so does the whole exponentiation.
+///
+/// # Panics
+///
+/// Panics if `N` is not a power of two between 2 and 256, or if `exponent`
+/// is empty.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Barrett, ct_pow};
+///
+/// let p = Barrett::<u64>::new(1_000_000_007);
+/// // 3^(p - 1) = 1 by Fermat's little theorem.
+/// let x = ct_pow::<_, 16>(3, 1, &[1_000_000_006], |a, b| p.mul_mod(a, b));
+/// assert_eq!(x, 1);
+/// assert_eq!(ct_pow::<_, 4>(2, 1, &[0, 1], |a, b| p.mul_mod(a, b)), 963061529);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_pow<T: ConstantTimeSelect, const N: usize>(
+    base: T,
+    identity: T,
+    exponent: &[u64],
+    mut op: impl FnMut(T, T) -> T,
+) -> T {
+    assert!(N.is_power_of_two() && (2..=256).contains(&N), "table size must be a power of two");
+    assert!(!exponent.is_empty(), "exponent must have at least one limb");
+    let width = N.trailing_zeros() as usize;
+
+    // `table[i]` is `base^i`.
+    let mut table = [identity; N];
+    table[1] = base;
+    for i in 2..N {
+        table[i] = op(table[i - 1], base);
+    }
+
+    // Start from the most significant window, whose entry is the
+    // accumulator itself.
+    let windows = (exponent.len() * 64).div_ceil(width);
+    let mut acc = ct_lookup(&table, window_at(exponent, (windows - 1) * width, width));
+    for w in (0..windows - 1).rev() {
+        for _ in 0..width {
+            acc = op(acc, acc);
+        }
+        let digit = window_at(exponent, w * width, width);
+        // Multiply by the entry for `digit` even when it is zero, and only
+        // then decide whether to keep the product.
+        let entry = ct_lookup(&table[1..], digit.wrapping_sub(1));
+        acc = ct_select(digit.ct_eq(&0), acc, op(acc, entry));
+    }
+    acc
+}
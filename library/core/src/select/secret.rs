@@ -0,0 +1,104 @@
+use super::{Choice, ConstantTimeEq, ConstantTimeSelect};
+use crate::mem::MaybeUninit;
+use crate::{fmt, intrinsics};
+
+/// A value that must not influence control flow.
+///
+/// `Secret` deliberately exposes only constant-time operations on the value it
+/// wraps: it can be compared with [`ct_eq`](ConstantTimeEq::ct_eq), selected
+/// with [`ct_select`](super::ct_select) and conditionally assigned, but does
+/// not implement [`PartialEq`], [`Ord`], [`Hash`](crate::hash::Hash) or
+/// [`Deref`](crate::ops::Deref), and its [`Debug`] output omits the value. Any
+/// other use of the value has to go through [`expose`](Secret::expose), which
+/// marks the places that need auditing.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ConstantTimeEq, Secret};
+///
+/// let mut key = Secret::new(0x1234u32);
+/// assert!(key.ct_eq(&Secret::new(0x1234)).declassify());
+/// assert_eq!(format!("{key:?}"), "Secret { .. }");
+///
+/// key.conditional_assign(&Secret::new(0x5678), Choice::from(true));
+/// assert_eq!(*key.expose(), 0x5678);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone, Default)]
+#[rustc_diagnostic_item = "Secret"]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wraps `value`.
+    ///
+    /// The value is marked as secret for MemorySanitizer, Valgrind and the
+    /// constant-time sanitizer, which then report the branches and memory
+    /// accesses that depend on it. See
+    /// [`ct_classify`](intrinsics::ct_classify).
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[rustc_const_unstable(feature = "const_ct_select", issue = "none")]
+    #[inline]
+    pub const fn new(value: T) -> Secret<T> {
+        Secret(intrinsics::ct_classify(value))
+    }
+
+    /// Returns a reference to the wrapped value.
+    ///
+    /// The value is no longer protected by the type system once exposed, so
+    /// callers must ensure that it is only used in constant-time code or that
+    /// revealing it is acceptable.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[rustc_diagnostic_item = "secret_expose"]
+    #[inline]
+    pub const fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Sets `self` to `other` if `choice` is set, and leaves it unchanged
+    /// otherwise.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn conditional_assign(&mut self, other: &Secret<T>, choice: Choice)
+    where
+        T: ConstantTimeSelect,
+    {
+        self.0 = T::ct_select(choice, other.0, self.0);
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeEq> ConstantTimeEq for Secret<T> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T: ConstantTimeSelect> ConstantTimeSelect for Secret<T> {
+    #[inline]
+    fn ct_select(choice: Choice, true_val: Self, false_val: Self) -> Self {
+        Secret(T::ct_select(choice, true_val.0, false_val.0))
+    }
+
+    #[inline]
+    unsafe fn ct_select_uninit(
+        choice: Choice,
+        true_val: MaybeUninit<Self>,
+        false_val: Self,
+    ) -> Self {
+        // SAFETY: the field is within `true_val`, and is initialized if it is.
+        let value = unsafe { (&raw const (*true_val.as_ptr()).0).cast::<MaybeUninit<T>>().read() };
+        // SAFETY: the caller guarantees that `true_val` is initialized if it is selected.
+        Secret(unsafe { T::ct_select_uninit(choice, value, false_val.0) })
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Secret").finish_non_exhaustive()
+    }
+}
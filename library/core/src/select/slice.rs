@@ -0,0 +1,261 @@
+use super::{
+    Choice, ConstantTimeEq, ConstantTimeIterator, ConstantTimeOrd, ConstantTimeSelect, CtOption,
+    ct_select,
+};
+
+/// Returns the index of the first element of `haystack` equal to `needle`.
+///
+/// Unlike [`Iterator::position`], this always compares `needle` against every
+/// element, so the time taken does not reveal where (or whether) a match was
+/// found. The result is accumulated with [`ct_select`]; see
+/// [`ConstantTimeIterator::ct_position`] for the general form.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_position;
+///
+/// let keys = [3u32, 9, 4, 9];
+/// assert_eq!(ct_position(&keys, &9).unwrap(), 1);
+/// assert!(!ct_position(&keys, &5).is_some().declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_position<T: ConstantTimeEq>(haystack: &[T], needle: &T) -> CtOption<usize> {
+    haystack.iter().ct_position(|element| element.ct_eq(needle))
+}
+
+/// Returns whether `haystack` contains an element equal to `needle`.
+///
+/// Every element is compared, regardless of whether a match has already been
+/// found.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_contains;
+///
+/// let keys = [3u32, 9, 4];
+/// assert!(ct_contains(&keys, &4).declassify());
+/// assert!(!ct_contains(&keys, &5).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_contains<T: ConstantTimeEq>(haystack: &[T], needle: &T) -> Choice {
+    let mut found = Choice::from(false);
+    for element in haystack {
+        found = ct_select(element.ct_eq(needle), Choice::from(true), found);
+    }
+    found
+}
+
+/// Returns a [`Choice`] that is set if every flag in `flags` is set.
+///
+/// Unlike [`Iterator::all`], this does not stop at the first unset flag. The
+/// flags may be given either as [`Choice`]s or as `bool`s.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_all};
+///
+/// assert!(ct_all(&[true, true]).declassify());
+/// assert!(!ct_all(&[Choice::from(true), Choice::from(false)]).declassify());
+/// assert!(ct_all::<bool>(&[]).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_all<T: Copy + Into<Choice>>(flags: &[T]) -> Choice {
+    let mut all = Choice::from(true);
+    for &flag in flags {
+        all = ct_select(flag.into(), all, Choice::from(false));
+    }
+    all
+}
+
+/// Returns a [`Choice`] that is set if any flag in `flags` is set.
+///
+/// Unlike [`Iterator::any`], this does not stop at the first set flag. The
+/// flags may be given either as [`Choice`]s or as `bool`s.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{Choice, ct_any};
+///
+/// assert!(ct_any(&[false, true]).declassify());
+/// assert!(!ct_any(&[Choice::from(false), Choice::from(false)]).declassify());
+/// assert!(!ct_any::<bool>(&[]).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_any<T: Copy + Into<Choice>>(flags: &[T]) -> Choice {
+    let mut any = Choice::from(false);
+    for &flag in flags {
+        any = ct_select(flag.into(), Choice::from(true), any);
+    }
+    any
+}
+
+/// Returns whether the authentication tag `actual` matches `expected`.
+///
+/// This is the check to use when verifying a MAC or AEAD tag. The contents
+/// are compared in constant time: every byte is examined, and the differences
+/// are accumulated without branching, so the time taken does not reveal how
+/// many leading bytes of a forged tag were correct. The lengths are treated as
+/// public and compared directly, since tag lengths are fixed by the algorithm;
+/// tags of different lengths never match, and neither do two empty tags.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::verify_tag;
+///
+/// let expected = [0x8b, 0x1f, 0x03, 0xe2];
+/// assert!(verify_tag(&expected, &[0x8b, 0x1f, 0x03, 0xe2]).declassify());
+/// assert!(!verify_tag(&expected, &[0x8b, 0x1f, 0x03, 0xe3]).declassify());
+/// assert!(!verify_tag(&expected, &[0x8b, 0x1f]).declassify());
+/// assert!(!verify_tag(&[], &[]).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn verify_tag(expected: &[u8], actual: &[u8]) -> Choice {
+    if expected.len() != actual.len() || expected.is_empty() {
+        return Choice::from(false);
+    }
+    let mut diff = 0u8;
+    for (&a, &b) in expected.iter().zip(actual) {
+        diff |= a ^ b;
+    }
+    diff.ct_eq(&0)
+}
+
+/// Returns `table[index]` without revealing `index` through memory accesses.
+///
+/// Indexing a table with a secret index leaks the index through the cache.
+/// This reads every element of `table` instead, and keeps the one at `index`
+/// with [`ct_select`], so the memory accessed depends only on the length of
+/// `table`. If `index` is out of bounds, no element is kept and the first one
+/// is returned.
+///
+/// # Panics
+///
+/// Panics if `table` is empty.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::ct_lookup;
+///
+/// let sbox = [0x63u8, 0x7c, 0x77, 0x7b];
+/// assert_eq!(ct_lookup(&sbox, 2), 0x77);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_lookup<T: ConstantTimeSelect>(table: &[T], index: usize) -> T {
+    let mut found = table[0];
+    for (i, &entry) in table.iter().enumerate().skip(1) {
+        found = ct_select(i.ct_eq(&index), entry, found);
+    }
+    found
+}
+
+/// Returns the index and value of the element of the nonempty `slice` that
+/// `replaces` prefers, scanning every element.
+///
+/// `replaces(candidate, best)` decides whether `candidate` takes the place of
+/// the best element so far; it must be strict for the first of several equal
+/// elements to be kept.
+#[inline]
+#[track_caller]
+fn ct_extreme<T: ConstantTimeSelect>(
+    slice: &[T],
+    replaces: impl Fn(&T, &T) -> Choice,
+) -> (usize, T) {
+    let mut best = slice[0];
+    let mut best_index = 0;
+    for (i, &x) in slice.iter().enumerate().skip(1) {
+        let replace = replaces(&x, &best);
+        best = ct_select(replace, x, best);
+        best_index = ct_select(replace, i, best_index);
+    }
+    (best_index, best)
+}
+
+/// Returns the smallest element of `slice`.
+///
+/// Unlike [`Iterator::min`], every element is compared and the running
+/// minimum is kept with [`ct_select`], so the time taken does not reveal
+/// where the minimum is or how often it changed during the scan. The length
+/// of `slice` is treated as public.
+///
+/// # Panics
+///
+/// Panics if `slice` is empty.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ct_slice_max, ct_slice_min};
+///
+/// let samples = [17i32, -4, 9, -4];
+/// assert_eq!(ct_slice_min(&samples), -4);
+/// assert_eq!(ct_slice_max(&samples), 17);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_slice_min<T: ConstantTimeOrd + ConstantTimeSelect>(slice: &[T]) -> T {
+    ct_extreme(slice, T::ct_lt).1
+}
+
+/// Returns the largest element of `slice`.
+///
+/// See [`ct_slice_min`].
+///
+/// # Panics
+///
+/// Panics if `slice` is empty.
+#[unstable(feature = "ct_select", issue = "none")]
+#[track_caller]
+pub fn ct_slice_max<T: ConstantTimeOrd + ConstantTimeSelect>(slice: &[T]) -> T {
+    ct_extreme(slice, T::ct_gt).1
+}
+
+/// Returns the index of the first smallest element of `slice`, or none if
+/// `slice` is empty.
+///
+/// Every element is compared, and the index is kept with [`ct_select`]
+/// along with the running minimum.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ct_slice_argmax, ct_slice_argmin};
+///
+/// let norms = [5u32, 2, 8, 2, 8];
+/// assert_eq!(ct_slice_argmin(&norms).unwrap(), 1);
+/// assert_eq!(ct_slice_argmax(&norms).unwrap(), 2);
+/// assert!(!ct_slice_argmin::<u32>(&[]).is_some().declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_slice_argmin<T: ConstantTimeOrd + ConstantTimeSelect>(slice: &[T]) -> CtOption<usize> {
+    if slice.is_empty() {
+        return CtOption::new(0, Choice::from(false));
+    }
+    CtOption::new(ct_extreme(slice, T::ct_lt).0, Choice::from(true))
+}
+
+/// Returns the index of the first largest element of `slice`, or none if
+/// `slice` is empty.
+///
+/// See [`ct_slice_argmin`].
+#[unstable(feature = "ct_select", issue = "none")]
+pub fn ct_slice_argmax<T: ConstantTimeOrd + ConstantTimeSelect>(slice: &[T]) -> CtOption<usize> {
+    if slice.is_empty() {
+        return CtOption::new(0, Choice::from(false));
+    }
+    CtOption::new(ct_extreme(slice, T::ct_gt).0, Choice::from(true))
+}
@@ -0,0 +1,257 @@
+use super::limb::mac;
+use super::{Choice, ConstantTimeEq, ConstantTimeLimb, ConstantTimeOrd, ConstantTimeSelect};
+use crate::fmt;
+use crate::mem::MaybeUninit;
+
+/// A fixed-width unsigned integer of `LIMBS` 64-bit limbs, with constant-time
+/// arithmetic.
+///
+/// The limbs are stored in little-endian order on the stack. Every operation
+/// processes all of the limbs with carry chains built from
+/// [`ConstantTimeLimb`], so the time taken depends only on `LIMBS`. Carries,
+/// borrows and comparisons are returned as [`Choice`]s, and values are
+/// selected and swapped through [`ConstantTimeSelect`].
+///
+/// `CtUint` does not implement [`PartialEq`] or [`Ord`], whose results would
+/// be returned as a `bool`; use [`ct_eq`](ConstantTimeEq::ct_eq) and
+/// [`ct_lt`](ConstantTimeOrd::ct_lt) instead.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::{ConstantTimeOrd, CtUint};
+///
+/// let a = CtUint::<2>::from_words([u64::MAX, 0]);
+/// let (sum, carry) = a.overflowing_add(&CtUint::ONE);
+/// assert_eq!(sum.to_words(), [0, 1]);
+/// assert!(!carry.declassify());
+/// assert!(a.ct_lt(&sum).declassify());
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[derive(Copy, Clone)]
+pub struct CtUint<const LIMBS: usize>([u64; LIMBS]);
+
+impl<const LIMBS: usize> CtUint<LIMBS> {
+    /// The value zero.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub const ZERO: CtUint<LIMBS> = CtUint([0; LIMBS]);
+
+    /// The value one.
+    ///
+    /// With zero limbs, this is zero.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub const ONE: CtUint<LIMBS> = CtUint::from_u64(1);
+
+    /// The largest value, with every bit set.
+    #[unstable(feature = "ct_select", issue = "none")]
+    pub const MAX: CtUint<LIMBS> = CtUint([u64::MAX; LIMBS]);
+
+    /// Creates an integer from its limbs, least significant first.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn from_words(words: [u64; LIMBS]) -> CtUint<LIMBS> {
+        CtUint(words)
+    }
+
+    /// Creates an integer from a single limb.
+    ///
+    /// With zero limbs, the result is zero.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn from_u64(x: u64) -> CtUint<LIMBS> {
+        let mut words = [0; LIMBS];
+        if LIMBS > 0 {
+            words[0] = x;
+        }
+        CtUint(words)
+    }
+
+    /// Returns the limbs, least significant first.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn to_words(self) -> [u64; LIMBS] {
+        self.0
+    }
+
+    /// Returns a reference to the limbs, least significant first.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub const fn as_words(&self) -> &[u64; LIMBS] {
+        &self.0
+    }
+
+    /// Computes `self + rhs`, returning the wrapped sum and whether it
+    /// overflowed.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn overflowing_add(&self, rhs: &Self) -> (Self, Choice) {
+        let mut carry = 0;
+        let sum = crate::array::from_fn(|i| {
+            let s;
+            (s, carry) = self.0[i].ct_adc(rhs.0[i], carry);
+            s
+        });
+        (CtUint(sum), Choice::from(carry as u8))
+    }
+
+    /// Computes `self - rhs`, returning the wrapped difference and whether it
+    /// borrowed.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn overflowing_sub(&self, rhs: &Self) -> (Self, Choice) {
+        let mut borrow = 0;
+        let diff = crate::array::from_fn(|i| {
+            let d;
+            (d, borrow) = self.0[i].ct_sbb(rhs.0[i], borrow);
+            d
+        });
+        (CtUint(diff), Choice::from(borrow as u8))
+    }
+
+    /// Computes `self + rhs`, wrapping around at the boundary of the type.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn wrapping_add(&self, rhs: &Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    /// Computes `self - rhs`, wrapping around at the boundary of the type.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Computes `-self`, wrapping around at the boundary of the type.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn wrapping_neg(&self) -> Self {
+        Self::ZERO.wrapping_sub(self)
+    }
+
+    /// Computes the full product `self * rhs`, returning the low and high
+    /// halves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ct_select)]
+    /// use std::select::CtUint;
+    ///
+    /// let (lo, hi) = CtUint::<2>::MAX.widening_mul(&CtUint::MAX);
+    /// assert_eq!(lo.to_words(), [1, 0]);
+    /// assert_eq!(hi.to_words(), [u64::MAX - 1, u64::MAX]);
+    /// ```
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn widening_mul(&self, rhs: &Self) -> (Self, Self) {
+        let mut lo = [0; LIMBS];
+        let mut hi = [0; LIMBS];
+        // Schoolbook multiplication into the double-width `hi:lo`. The limb
+        // indices are public, so choosing a half by index does not leak.
+        for (i, &b) in rhs.0.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &a) in self.0.iter().enumerate() {
+                let k = i + j;
+                let acc = if k < LIMBS { &mut lo[k] } else { &mut hi[k - LIMBS] };
+                (*acc, carry) = mac(*acc, a, b, carry);
+            }
+            hi[i] = carry;
+        }
+        (CtUint(lo), CtUint(hi))
+    }
+
+    /// Computes `self * rhs`, wrapping around at the boundary of the type.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+        let mut product = [0; LIMBS];
+        for (i, &b) in rhs.0.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &a) in self.0[..LIMBS - i].iter().enumerate() {
+                (product[i + j], carry) = mac(product[i + j], a, b, carry);
+            }
+        }
+        CtUint(product)
+    }
+
+    /// Adds `rhs` to `self` if `choice` is set, wrapping around at the
+    /// boundary of the type, and returns whether the addition overflowed.
+    ///
+    /// `rhs` is masked rather than selected afterwards, so the addition is
+    /// carried out either way.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn conditional_add(&mut self, rhs: &Self, choice: Choice) -> Choice {
+        let masked = Self::ct_select(choice, *rhs, Self::ZERO);
+        let (sum, carry) = self.overflowing_add(&masked);
+        *self = sum;
+        carry
+    }
+
+    /// Subtracts `rhs` from `self` if `choice` is set, wrapping around at the
+    /// boundary of the type, and returns whether the subtraction borrowed.
+    #[unstable(feature = "ct_select", issue = "none")]
+    #[inline]
+    pub fn conditional_sub(&mut self, rhs: &Self, choice: Choice) -> Choice {
+        let masked = Self::ct_select(choice, *rhs, Self::ZERO);
+        let (diff, borrow) = self.overflowing_sub(&masked);
+        *self = diff;
+        borrow
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const LIMBS: usize> Default for CtUint<LIMBS> {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const LIMBS: usize> ConstantTimeSelect for CtUint<LIMBS> {
+    #[inline]
+    fn ct_select(choice: Choice, t: Self, f: Self) -> Self {
+        CtUint(<[u64; LIMBS]>::ct_select(choice, t.0, f.0))
+    }
+
+    #[inline]
+    fn ct_swap(choice: Choice, a: &mut Self, b: &mut Self) {
+        <[u64; LIMBS]>::ct_swap(choice, &mut a.0, &mut b.0)
+    }
+
+    #[inline]
+    unsafe fn ct_select_uninit(choice: Choice, t: MaybeUninit<Self>, f: Self) -> Self {
+        // SAFETY: the limbs are within `t`, and are initialized if it is, which the caller
+        // guarantees if it is selected.
+        unsafe {
+            let limbs = (&raw const (*t.as_ptr()).0).cast::<MaybeUninit<[u64; LIMBS]>>().read();
+            CtUint(<[u64; LIMBS]>::ct_select_uninit(choice, limbs, f.0))
+        }
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const LIMBS: usize> ConstantTimeEq for CtUint<LIMBS> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const LIMBS: usize> ConstantTimeOrd for CtUint<LIMBS> {
+    #[inline]
+    fn ct_lt(&self, other: &Self) -> Choice {
+        self.overflowing_sub(other).1
+    }
+}
+
+#[unstable(feature = "ct_select", issue = "none")]
+impl<const LIMBS: usize> fmt::Debug for CtUint<LIMBS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CtUint").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{intrinsics, mem};
+
+/// Overwrites `bytes` with zeros, in a way that the compiler never removes.
+///
+/// Clearing a key with an ordinary loop or [`fill`] right before it goes out
+/// of scope is frequently optimized away, since the zeros are never read.
+/// This is guaranteed to perform the stores, like `explicit_bzero` in C.
+///
+/// Note that this only clears `bytes` itself. Copies that the compiler made
+/// earlier, for example when moving the value, and copies held in registers
+/// are unaffected.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::secure_zeroize;
+///
+/// let mut key = [0x42u8; 32];
+/// // ... use the key ...
+/// secure_zeroize(&mut key);
+/// assert_eq!(key, [0; 32]);
+/// ```
+///
+/// [`fill`]: slice::fill
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub fn secure_zeroize(bytes: &mut [u8]) {
+    // SAFETY: `bytes` is valid for writes of its whole length.
+    unsafe { intrinsics::secure_zeroize(bytes.as_mut_ptr(), bytes.len()) }
+}
+
+/// Overwrites the memory of `value` with zeros, in a way that the compiler
+/// never removes.
+///
+/// This is the counterpart of [`secure_zeroize`] for values of any type, such
+/// as arrays of limbs or structures of key material.
+///
+/// # Safety
+///
+/// The all-zero bit pattern must be a valid value of type `T`, as it is left
+/// behind in `value`. This holds for integers and arrays of them, but not for
+/// references, [`NonZero`](crate::num::NonZero) or most enums, for example.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ct_select)]
+/// use std::select::secure_zeroize_value;
+///
+/// let mut scalar = [u64::MAX; 4];
+/// // SAFETY: zero is a valid `u64`.
+/// unsafe { secure_zeroize_value(&mut scalar) };
+/// assert_eq!(scalar, [0; 4]);
+/// ```
+#[unstable(feature = "ct_select", issue = "none")]
+#[inline]
+pub unsafe fn secure_zeroize_value<T: ?Sized>(value: &mut T) {
+    let len = mem::size_of_val(value);
+    // SAFETY: `value` is valid for writes of its whole size, and the caller
+    // guarantees that the zeros written form a valid `T`.
+    unsafe { intrinsics::secure_zeroize((value as *mut T).cast::<u8>(), len) }
+}
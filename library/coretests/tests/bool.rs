@@ -121,3 +121,10 @@ fn test_bool_to_result() {
     assert_eq!(C, Err(0));
     assert_eq!(D, Ok(()));
 }
+
+#[test]
+fn test_bool_ct_then_some() {
+    assert!(true.ct_then_some(0).is_some().declassify());
+    assert!(!false.ct_then_some(0).is_some().declassify());
+    assert_eq!(true.ct_then_some(7u64).unwrap(), 7);
+}
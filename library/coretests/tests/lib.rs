@@ -21,6 +21,7 @@
 #![feature(const_clone)]
 #![feature(const_cmp)]
 #![feature(const_convert)]
+#![feature(const_ct_select)]
 #![feature(const_default)]
 #![feature(const_destruct)]
 #![feature(const_drop_in_place)]
@@ -42,6 +43,7 @@
 #![feature(core_private_bignum)]
 #![feature(core_private_diy_float)]
 #![feature(cstr_display)]
+#![feature(ct_select)]
 #![feature(debug_closure_helpers)]
 #![feature(dec2flt)]
 #![feature(drop_guard)]
@@ -207,6 +209,7 @@ fn $test() $block
 mod pin_macro;
 mod ptr;
 mod result;
+mod select;
 mod simd;
 mod slice;
 mod str;
@@ -582,3 +582,16 @@ fn as_slice() {
     assert_eq!(A, &[44]);
     assert_eq!(B, &[]);
 }
+
+#[test]
+fn test_ct_unwrap_or() {
+    assert_eq!(Some(3u32).ct_unwrap_or(9), 3);
+    assert_eq!(None::<u32>.ct_unwrap_or(9), 9);
+    assert_eq!(Some([1u8, 2, 3]).ct_unwrap_or([0; 3]), [1, 2, 3]);
+    assert_eq!(None.ct_unwrap_or([0u8; 3]), [0, 0, 0]);
+    let x = 5u8;
+    assert_eq!(Some(&raw const x).ct_unwrap_or(core::ptr::null()), &raw const x);
+    // `Option<bool>` stores `None` in a niche of the payload.
+    assert!(!Some(false).ct_unwrap_or(true));
+    assert!(None.ct_unwrap_or(true));
+}
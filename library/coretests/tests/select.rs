@@ -0,0 +1,1250 @@
+use core::select::{
+    Barrett, Choice, ConstantTimeEq, ConstantTimeIterator, ConstantTimeOps, ConstantTimeOrd,
+    CtBitSet, CtCell, CtOption, CtResult, CtUint, Secret, ct_adc, ct_all, ct_and, ct_any,
+    ct_base64_decode, ct_base64_encode, ct_black_box, ct_cmp, ct_cmp_limbs, ct_cond_add_mod,
+    ct_cond_sub_mod, ct_contains, ct_count_ones, ct_div_rem, ct_eq, ct_eq_limbs, ct_gcd,
+    ct_gcd_limbs, ct_gt_limbs, ct_hex_decode, ct_hex_encode, ct_if, ct_leading_zeros, ct_lookup,
+    ct_lt_limbs, ct_mask, ct_max, ct_min, ct_mod_inverse, ct_mod_inverse_limbs, ct_mont_mul,
+    ct_mont_reduce, ct_neg_if, ct_or, ct_parse_decimal, ct_parse_hex, ct_position, ct_pow,
+    ct_rotate_left, ct_rotate_right, ct_sbb, ct_select, ct_select_many, ct_shl, ct_shr,
+    ct_slice_argmax, ct_slice_argmin, ct_slice_max, ct_slice_min, ct_swap, ct_swap_slices,
+    ct_widening_mul, ct_xor, mont_n_prime, secure_zeroize, secure_zeroize_value, verify_tag,
+};
+
+#[test]
+fn test_ct_select() {
+    assert_eq!(ct_select(Choice::from(true), 1u8, 2), 1);
+    assert_eq!(ct_select(Choice::from(false), 1u8, 2), 2);
+    assert_eq!(ct_select(Choice::from(true), -1i64, i64::MIN), -1);
+    assert_eq!(ct_select(Choice::from(false), -1i64, i64::MIN), i64::MIN);
+    assert_eq!(ct_select(Choice::from(true), u128::MAX, 1), u128::MAX);
+    assert_eq!(ct_select(Choice::from(false), u128::MAX, 1 << 100), 1 << 100);
+    assert_eq!(ct_select(Choice::from(false), usize::MAX, 7), 7);
+    assert!(!ct_select(Choice::from(true), false, true));
+    assert_eq!(ct_select(Choice::from(false), [1u16, 2], [3, 4]), [3, 4]);
+
+    let (a, b) = (1u32, 2u32);
+    assert_eq!(ct_select(Choice::from(true), &raw const a, &raw const b), &raw const a);
+    assert_eq!(ct_select(Choice::from(false), &raw const a, &raw const b), &raw const b);
+}
+
+#[test]
+fn test_debug_omits_secrets() {
+    assert_eq!(format!("{:?}", Choice::from(true)), "Choice { .. }");
+    assert_eq!(format!("{:?}", CtOption::new(7u8, Choice::from(true))), "CtOption { .. }");
+    let result = CtResult::new(7u8, 1u8, Choice::from(false));
+    assert_eq!(format!("{result:?}"), "CtResult { .. }");
+    assert_eq!(format!("{:?}", CtUint::<2>::ONE), "CtUint { .. }");
+    assert_eq!(format!("{:?}", CtBitSet::<1>::new()), "CtBitSet { .. }");
+}
+
+#[test]
+fn test_ct_eq() {
+    assert!(ct_eq(&0u8, &0).declassify());
+    assert!(!ct_eq(&0u8, &1).declassify());
+    assert!(i128::MIN.ct_eq(&i128::MIN).declassify());
+    assert!(!i128::MIN.ct_eq(&i128::MAX).declassify());
+    assert!(true.ct_eq(&true).declassify());
+    assert!(!true.ct_eq(&false).declassify());
+
+    for (a, b) in [(0u64, 1u64), (1 << 63, 0), (u64::MAX, u64::MAX - 1), (1 << 32, 1)] {
+        assert!(!ct_eq(&a, &b).declassify());
+        assert!(ct_eq(&a, &a).declassify());
+        assert!(!ct_eq(&(a as i64), &(b as i64)).declassify());
+    }
+    assert!(ct_eq(&-1isize, &-1).declassify());
+    assert!(!ct_eq(&u128::MAX, &(u128::MAX >> 1)).declassify());
+
+    assert!(Choice::from(true).ct_eq(&Choice::from(true)).declassify());
+    assert!(!Choice::from(true).ct_eq(&Choice::from(false)).declassify());
+}
+
+#[test]
+fn test_ct_eq_slices() {
+    assert!(ct_eq(&[1u8, 2, 3], &[1, 2, 3]).declassify());
+    assert!(!ct_eq(&[1u8, 2, 3], &[1, 2, 4]).declassify());
+    assert!(!ct_eq(&[9u8, 2, 3], &[1, 2, 3]).declassify());
+    assert!(ct_eq::<[u32]>(&[], &[]).declassify());
+    assert!(!ct_eq::<[u32]>(&[1, 2], &[1]).declassify());
+    assert!(ct_eq(b"tag".as_slice(), b"tag").declassify());
+}
+
+#[test]
+fn test_ct_position() {
+    let haystack = [5u32, 1, 5, 9];
+    assert_eq!(ct_position(&haystack, &5).unwrap(), 0);
+    assert_eq!(ct_position(&haystack, &9).unwrap(), 3);
+    assert!(!ct_position(&haystack, &2).is_some().declassify());
+    assert!(!ct_position(&[], &2u32).is_some().declassify());
+}
+
+#[test]
+fn test_ct_contains() {
+    let haystack = [5u32, 1, 5, 9];
+    assert!(ct_contains(&haystack, &1).declassify());
+    assert!(ct_contains(&haystack, &9).declassify());
+    assert!(!ct_contains(&haystack, &2).declassify());
+    assert!(!ct_contains(&[], &2u32).declassify());
+}
+
+#[test]
+fn test_ct_all_any() {
+    let t = Choice::from(true);
+    let f = Choice::from(false);
+    assert!(ct_all(&[t, t, t]).declassify());
+    assert!(!ct_all(&[t, f, t]).declassify());
+    assert!(ct_all::<Choice>(&[]).declassify());
+    assert!(ct_any(&[f, f, t]).declassify());
+    assert!(!ct_any(&[f, f, f]).declassify());
+    assert!(!ct_any::<Choice>(&[]).declassify());
+
+    assert!(ct_all(&[true, true]).declassify());
+    assert!(!ct_all(&[false, true]).declassify());
+    assert!(ct_any(&[false, true]).declassify());
+    assert!(!ct_any(&[false, false]).declassify());
+}
+
+#[test]
+fn test_choice_ops() {
+    for a in [false, true] {
+        for b in [false, true] {
+            let (ca, cb) = (Choice::from(a), Choice::from(b));
+            assert_eq!((ca & cb).declassify(), a & b);
+            assert_eq!((ca | cb).declassify(), a | b);
+            assert_eq!((ca ^ cb).declassify(), a ^ b);
+            assert_eq!((!ca).declassify(), !a);
+
+            let mut c = ca;
+            c &= cb;
+            assert_eq!(c.declassify(), a & b);
+            let mut c = ca;
+            c |= cb;
+            assert_eq!(c.declassify(), a | b);
+            let mut c = ca;
+            c ^= cb;
+            assert_eq!(c.declassify(), a ^ b);
+        }
+    }
+    assert!((!!Choice::from(true)).declassify());
+}
+
+#[test]
+fn test_choice_from_u8() {
+    assert!(!Choice::from(0u8).declassify());
+    assert!(Choice::from(1u8).declassify());
+    // Only the low bit is significant.
+    assert!(!Choice::from(2u8).declassify());
+    assert!(Choice::from(0xffu8).declassify());
+    assert!((!Choice::from(0xfeu8)).declassify());
+}
+
+#[test]
+fn test_ct_black_box() {
+    assert!(ct_black_box(true));
+    assert_eq!(ct_black_box(0x5au8), 0x5a);
+    assert_eq!(ct_black_box(-1i64), -1);
+    assert_eq!(ct_black_box(u128::MAX), u128::MAX);
+    assert_eq!(ct_black_box([1u32, 2, 3]), [1, 2, 3]);
+    let x = 7;
+    assert_eq!(ct_black_box(&x) as *const i32, &x as *const i32);
+    let key = 0x1234u32;
+    assert_eq!(ct_select(Choice::from(true), ct_black_box(key), key), key);
+}
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+fn test_ct_select_spec() {
+    use core::select::ct_select_spec;
+
+    let t = Choice::from(true);
+    let f = Choice::from(false);
+    assert_eq!(ct_select_spec(t, 1u8, 2), 1);
+    assert_eq!(ct_select_spec(f, -1i64, 2), 2);
+    assert_eq!(ct_select_spec(f, true, false), false);
+    let (a, b) = ([1u32; 4], [2u32; 4]);
+    assert_eq!(*ct_select_spec(t, &a, &b), a);
+    assert_eq!(ct_select_spec(f, &a[..], &b[..2]), &[2, 2]);
+    assert_eq!(ct_select_spec(t, &a as *const _, &b as *const _), &a as *const _);
+    const SELECTED: u32 = ct_select_spec(Choice::from(false), 1, 2);
+    assert_eq!(SELECTED, 2);
+}
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_doitm_exports() {
+    use core::select::{DoitmGuard, doitm_enabled, doitm_supported, set_doitm};
+
+    // The MSRs can only be accessed at privilege level 0, so the functions are not called here.
+    let _: unsafe fn() -> bool = doitm_supported;
+    let _: unsafe fn() -> bool = doitm_enabled;
+    let _: unsafe fn(bool) = set_doitm;
+    let _: unsafe fn() -> DoitmGuard = DoitmGuard::new;
+}
+
+#[test]
+fn test_ct_mask() {
+    let t = Choice::from(true);
+    let f = Choice::from(false);
+    assert_eq!(ct_mask::<u8>(t), u8::MAX);
+    assert_eq!(ct_mask::<u8>(f), 0);
+    assert_eq!(ct_mask::<i16>(t), -1);
+    assert_eq!(ct_mask::<u32>(t), u32::MAX);
+    assert_eq!(ct_mask::<i64>(f), 0);
+    assert_eq!(ct_mask::<u128>(t), u128::MAX);
+    assert_eq!(ct_mask::<i128>(f), 0);
+    assert_eq!(ct_mask::<usize>(t), usize::MAX);
+    assert_eq!(ct_mask::<isize>(t), -1);
+    assert_eq!(ct_mask::<[u16; 2]>(t), [u16::MAX; 2]);
+    assert_eq!(ct_mask::<[i32; 3]>(f), [0; 3]);
+}
+
+#[test]
+fn test_ct_select_const() {
+    const fn table_entry(index: u8) -> u32 {
+        let mut entry = 0;
+        let mut i = 0;
+        while i < 4 {
+            entry = ct_select(ct_eq(&index, &i), (i as u32 + 1) * 100, entry);
+            i += 1;
+        }
+        entry
+    }
+    const TABLE: [u32; 5] =
+        [table_entry(0), table_entry(1), table_entry(2), table_entry(3), table_entry(9)];
+    assert_eq!(TABLE, [100, 200, 300, 400, 0]);
+
+    const SWAPPED: (u64, u64) = {
+        let (mut a, mut b) = (1u64, 2u64);
+        ct_swap(Choice::from(true), &mut a, &mut b);
+        (a, b)
+    };
+    assert_eq!(SWAPPED, (2, 1));
+
+    const MASK: u16 = ct_mask::<u16>(!Choice::from(false)) & ct_black_box(0xabcd);
+    assert_eq!(MASK, 0xabcd);
+    const EQ: bool = (ct_eq(&3i8, &3) & !true.ct_eq(&false)).declassify();
+    assert!(EQ);
+}
+
+#[test]
+fn test_ct_ops_unsigned() {
+    assert_eq!(7u8.ct_abs(), 7);
+    assert_eq!(0u32.ct_signum(), 0);
+    assert_eq!(9u32.ct_signum(), 1);
+    assert_eq!(u64::MAX.ct_signum(), 1);
+    assert_eq!(3u16.ct_min(9), 3);
+    assert_eq!(9u16.ct_min(3), 3);
+    assert_eq!(3u16.ct_max(9), 9);
+    assert_eq!(u128::MAX.ct_max(0), u128::MAX);
+    assert_eq!(0usize.ct_min(usize::MAX), 0);
+    assert_eq!(5u8.ct_clamp(10, 20), 10);
+    assert_eq!(15u8.ct_clamp(10, 20), 15);
+    assert_eq!(25u8.ct_clamp(10, 20), 20);
+
+    for a in [0u8, 1, 0x7f, 0x80, 0xfe, 0xff] {
+        for b in [0u8, 1, 0x7f, 0x80, 0xfe, 0xff] {
+            assert_eq!(a.ct_min(b), a.min(b));
+            assert_eq!(a.ct_max(b), a.max(b));
+        }
+    }
+}
+
+#[test]
+fn test_ct_ops_signed() {
+    assert_eq!((-5i32).ct_abs(), 5);
+    assert_eq!(5i32.ct_abs(), 5);
+    assert_eq!(i32::MIN.ct_abs(), i32::MIN);
+    assert_eq!((-5i64).ct_signum(), -1);
+    assert_eq!(0i64.ct_signum(), 0);
+    assert_eq!(5i64.ct_signum(), 1);
+    assert_eq!(i8::MIN.ct_signum(), -1);
+    assert_eq!(i8::MAX.ct_signum(), 1);
+    assert_eq!(i128::MIN.ct_min(i128::MAX), i128::MIN);
+    assert_eq!(i128::MIN.ct_max(i128::MAX), i128::MAX);
+    assert_eq!((-1isize).ct_max(0), 0);
+    assert_eq!((-300i16).ct_clamp(-100, 100), -100);
+    assert_eq!(300i16.ct_clamp(-100, 100), 100);
+    assert_eq!(30i16.ct_clamp(-100, 100), 30);
+
+    for a in [i8::MIN, -1, 0, 1, i8::MAX] {
+        for b in [i8::MIN, -1, 0, 1, i8::MAX] {
+            assert_eq!(a.ct_min(b), a.min(b));
+            assert_eq!(a.ct_max(b), a.max(b));
+        }
+        assert_eq!(a.ct_signum(), a.signum());
+        assert_eq!(a.ct_abs(), a.wrapping_abs());
+    }
+}
+
+#[test]
+fn test_ct_neg_if() {
+    let t = Choice::from(true);
+    let f = Choice::from(false);
+    assert_eq!(ct_neg_if(t, 5i32), -5);
+    assert_eq!(ct_neg_if(t, -5i32), 5);
+    assert_eq!(ct_neg_if(f, -5i32), -5);
+    assert_eq!(ct_neg_if(t, i64::MIN), i64::MIN);
+    assert_eq!(ct_neg_if(t, i128::MAX), -i128::MAX);
+    assert_eq!(ct_neg_if(t, 0isize), 0);
+    assert_eq!(ct_neg_if(t, 1u8), u8::MAX);
+    assert_eq!(ct_neg_if(f, 1u8), 1);
+    assert_eq!(ct_neg_if(t, 0u64), 0);
+    assert_eq!(7i16.ct_neg_if(t), -7);
+}
+
+#[test]
+fn test_ct_adc_sbb() {
+    assert_eq!(ct_adc(1u32, 2, 0), (3, 0));
+    assert_eq!(ct_adc(1u32, 2, 1), (4, 0));
+    assert_eq!(ct_adc(u32::MAX, 1, 0), (0, 1));
+    assert_eq!(ct_adc(u32::MAX, 0, 1), (0, 1));
+    assert_eq!(ct_adc(u64::MAX, u64::MAX, 1), (u64::MAX, 1));
+    assert_eq!(ct_adc(u128::MAX, u128::MAX, 0), (u128::MAX - 1, 1));
+    assert_eq!(ct_adc(1u128 << 127, 1 << 127, 0), (0, 1));
+
+    assert_eq!(ct_sbb(3u32, 2, 0), (1, 0));
+    assert_eq!(ct_sbb(3u32, 2, 1), (0, 0));
+    assert_eq!(ct_sbb(0u32, 1, 0), (u32::MAX, 1));
+    assert_eq!(ct_sbb(0u64, 0, 1), (u64::MAX, 1));
+    assert_eq!(ct_sbb(0u128, u128::MAX, 1), (0, 1));
+    assert_eq!(ct_sbb(u128::MAX, u128::MAX, 0), (0, 0));
+
+    for a in [0u32, 1, 0x7fff_ffff, 0x8000_0000, u32::MAX] {
+        for b in [0u32, 1, 0x7fff_ffff, 0x8000_0000, u32::MAX] {
+            for c in [0u32, 1] {
+                let wide = a as u64 + b as u64 + c as u64;
+                assert_eq!(ct_adc(a, b, c), (wide as u32, (wide >> 32) as u32));
+                let wide = (a as u64).wrapping_sub(b as u64).wrapping_sub(c as u64);
+                assert_eq!(ct_sbb(a, b, c), (wide as u32, (wide >> 63) as u32));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ct_widening_mul() {
+    assert_eq!(ct_widening_mul(0u32, u32::MAX), (0, 0));
+    assert_eq!(ct_widening_mul(u32::MAX, u32::MAX), (1, u32::MAX - 1));
+    assert_eq!(ct_widening_mul(u64::MAX, u64::MAX), (1, u64::MAX - 1));
+    assert_eq!(ct_widening_mul(u128::MAX, u128::MAX), (1, u128::MAX - 1));
+    assert_eq!(ct_widening_mul(u128::MAX, 2), (u128::MAX - 1, 1));
+    assert_eq!(
+        ct_widening_mul(0x0123_4567_89ab_cdef_u128 << 64, 0xfedc_ba98_7654_3210),
+        (0x2236_d88f_e561_8cf0 << 64, 0x0121_fa00_ad77_d742),
+    );
+
+    for a in [0u32, 1, 0xffff, 0x1_0000, 0x8000_0000, u32::MAX] {
+        for b in [0u32, 3, 0xffff, 0x1_0000, 0x8000_0001, u32::MAX] {
+            let wide = a as u64 * b as u64;
+            assert_eq!(ct_widening_mul(a, b), (wide as u32, (wide >> 32) as u32));
+        }
+    }
+}
+
+#[test]
+fn test_ct_div_rem() {
+    assert_eq!(ct_div_rem(100u32, 7), (14, 2));
+    assert_eq!(ct_div_rem(6u32, 7), (0, 6));
+    assert_eq!(ct_div_rem(u32::MAX, u32::MAX), (1, 0));
+    assert_eq!(ct_div_rem(u32::MAX, 0x8000_0001), (1, 0x7fff_fffe));
+    assert_eq!(ct_div_rem(u64::MAX, 10), (u64::MAX / 10, u64::MAX % 10));
+    assert_eq!(ct_div_rem(u128::MAX, 3), (u128::MAX / 3, 0));
+    assert_eq!(ct_div_rem(u128::MAX - 1, u128::MAX), (0, u128::MAX - 1));
+    assert_eq!(ct_div_rem(42u32, 0), (u32::MAX, 42));
+
+    for a in [0u32, 1, 7, 0xffff, 0x8000_0000, 0xdead_beef, u32::MAX] {
+        for b in [1u32, 2, 3, 0x1_0000, 0x7fff_ffff, 0x8000_0000, 0xc000_0000, u32::MAX] {
+            assert_eq!(ct_div_rem(a, b), (a / b, a % b));
+        }
+    }
+}
+
+#[test]
+fn test_barrett_u64() {
+    for m in [1u64, 2, 3, 7, 1 << 32, 0xffff_ffff_0000_0001, (1 << 63) + 1, u64::MAX] {
+        let b = Barrett::<u64>::new(m);
+        assert_eq!(b.modulus(), m);
+        for x in [0u64, 1, 2, m - 1, m.wrapping_add(1), 0xdead_beef_cafe_f00d, u64::MAX] {
+            assert_eq!(b.reduce(x), x % m);
+            let hi = x % m;
+            for lo in [0u64, 1, 0x1234_5678_9abc_def0, u64::MAX] {
+                let wide = ((hi as u128) << 64) | lo as u128;
+                assert_eq!(b.reduce_wide(lo, hi), (wide % m as u128) as u64);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_barrett_u128() {
+    let b = Barrett::<u128>::new((1 << 127) + 1);
+    assert_eq!(b.reduce(u128::MAX), (1 << 127) - 2);
+    // 2^254 = (-1)^2 mod 2^127 + 1.
+    assert_eq!(b.mul_mod(1 << 127, 1 << 127), 1);
+
+    for m in [3u128, 1 << 64, (1 << 64) + 13, u64::MAX as u128 * 3, u128::MAX] {
+        let b = Barrett::<u128>::new(m);
+        for (x, y) in [(0u128, 5u128), (1, 1), (u64::MAX as u128, u64::MAX as u128), (7, 1 << 100)]
+        {
+            let (x, y) = (x % m, y % m);
+            if let Some(p) = x.checked_mul(y) {
+                assert_eq!(b.mul_mod(x, y), p % m);
+            }
+        }
+        assert_eq!(b.reduce(u128::MAX), u128::MAX % m);
+    }
+}
+
+#[test]
+fn test_ct_swap() {
+    let (mut a, mut b) = (1u64, 2u64);
+    ct_swap(Choice::from(false), &mut a, &mut b);
+    assert_eq!((a, b), (1, 2));
+    ct_swap(Choice::from(true), &mut a, &mut b);
+    assert_eq!((a, b), (2, 1));
+
+    let (mut a, mut b) = ([1u8, 2], [3u8, 4]);
+    ct_swap(Choice::from(true), &mut a, &mut b);
+    assert_eq!((a, b), ([3, 4], [1, 2]));
+
+    let (mut a, mut b) = (u128::MAX, 1u128 << 100);
+    ct_swap(Choice::from(true), &mut a, &mut b);
+    assert_eq!((a, b), (1 << 100, u128::MAX));
+    let (mut a, mut b) = (-1i8, i8::MIN);
+    ct_swap(Choice::from(false), &mut a, &mut b);
+    assert_eq!((a, b), (-1, i8::MIN));
+    let (mut a, mut b) = (true, false);
+    ct_swap(Choice::from(true), &mut a, &mut b);
+    assert_eq!((a, b), (false, true));
+}
+
+#[test]
+fn test_ct_swap_slices() {
+    let mut a = [1u32, 2, 3];
+    let mut b = [4u32, 5, 6];
+    ct_swap_slices(Choice::from(false), &mut a, &mut b);
+    assert_eq!((a, b), ([1, 2, 3], [4, 5, 6]));
+    ct_swap_slices(Choice::from(true), &mut a[1..], &mut b[1..]);
+    assert_eq!((a, b), ([1, 5, 6], [4, 2, 3]));
+    ct_swap_slices::<u8>(Choice::from(true), &mut [], &mut []);
+}
+
+#[test]
+#[should_panic]
+fn test_ct_swap_slices_len_mismatch() {
+    ct_swap_slices(Choice::from(true), &mut [1u8, 2], &mut [3u8]);
+}
+
+#[test]
+fn test_ct_mod_inverse() {
+    assert_eq!(ct_mod_inverse(3u32, 7).unwrap(), 5);
+    assert_eq!(ct_mod_inverse(10u32, 7).unwrap(), 5);
+    assert_eq!(ct_mod_inverse(1u32, 1).unwrap(), 0);
+    assert_eq!(ct_mod_inverse(u32::MAX - 1, u32::MAX).unwrap(), u32::MAX - 1);
+    assert!(!ct_mod_inverse(0u32, 7).is_some().declassify());
+    assert!(!ct_mod_inverse(6u32, 9).is_some().declassify());
+    assert!(!ct_mod_inverse(3u32, 8).is_some().declassify());
+    assert!(!ct_mod_inverse(3u64, 0).is_some().declassify());
+
+    let p = 0xffff_ffff_0000_0001u64;
+    for x in [1u64, 2, 3, 0xdead_beef, p - 1, u64::MAX] {
+        let inv = ct_mod_inverse(x, p).unwrap();
+        assert!(inv < p);
+        assert_eq!(((x % p) as u128 * inv as u128) % p as u128, 1);
+    }
+
+    // 2^127 - 1 is prime.
+    let p = u128::MAX >> 1;
+    assert_eq!(ct_mod_inverse(2u128, p).unwrap(), 1 << 126);
+    assert_eq!(ct_mod_inverse(p - 1, p).unwrap(), p - 1);
+    assert_eq!(ct_mod_inverse(u128::MAX - 1, u128::MAX).unwrap(), u128::MAX - 1);
+}
+
+#[test]
+fn test_ct_mod_inverse_limbs() {
+    assert!(!ct_mod_inverse_limbs::<0>([], []).is_some().declassify());
+    assert_eq!(ct_mod_inverse_limbs([3], [7]).unwrap(), [5]);
+    assert_eq!(ct_mod_inverse_limbs([5, 9], [1, 0]).unwrap(), [0, 0]);
+    assert!(!ct_mod_inverse_limbs([0, 0], [7, 0]).is_some().declassify());
+    assert!(!ct_mod_inverse_limbs([6, 0], [9, 0]).is_some().declassify());
+    assert!(!ct_mod_inverse_limbs([3, 0], [8, 0]).is_some().declassify());
+
+    // The limbs agree with `ct_mod_inverse` on `u128`.
+    let limbs = |x: u128| [x as u64, (x >> 64) as u64];
+    let p = u128::MAX >> 1;
+    for x in [2, 3, (0xdead_beef << 64) | 0xcafe, p - 1, u128::MAX] {
+        assert_eq!(
+            ct_mod_inverse_limbs(limbs(x), limbs(p)).unwrap(),
+            limbs(ct_mod_inverse(x, p).unwrap())
+        );
+    }
+    assert_eq!(
+        ct_mod_inverse_limbs(limbs(u128::MAX - 1), limbs(u128::MAX)).unwrap(),
+        limbs(u128::MAX - 1)
+    );
+
+    // The inverse of 2 modulo the P-256 prime, 2^256 - 2^224 + 2^192 + 2^96 - 1, is (p + 1) / 2.
+    let p256 = [u64::MAX, 0xffff_ffff, 0, 0xffff_ffff_0000_0001];
+    let half = [0, 1 << 31, 1 << 63, 0x7fff_ffff_8000_0000];
+    assert_eq!(ct_mod_inverse_limbs([2, 0, 0, 0], p256).unwrap(), half);
+}
+
+#[test]
+fn test_ct_gcd() {
+    assert_eq!(ct_gcd(48u32, 180), 12);
+    assert_eq!(ct_gcd(180u32, 48), 12);
+    assert_eq!(ct_gcd(17u32, 5), 1);
+    assert_eq!(ct_gcd(0u32, 12), 12);
+    assert_eq!(ct_gcd(12u32, 0), 12);
+    assert_eq!(ct_gcd(0u32, 0), 0);
+    assert_eq!(ct_gcd(1u32 << 31, 1 << 31), 1 << 31);
+    assert_eq!(ct_gcd(u32::MAX, u32::MAX - 2), 1);
+    assert_eq!(ct_gcd(1u64 << 63, 3 << 40), 1 << 40);
+    assert_eq!(ct_gcd(u64::MAX, 0xffff_ffff), 0xffff_ffff);
+    assert_eq!(ct_gcd(1u128 << 127, 0), 1 << 127);
+    assert_eq!(ct_gcd(u128::MAX, u64::MAX as u128 * 7), u64::MAX as u128);
+}
+
+#[test]
+fn test_ct_gcd_limbs() {
+    assert_eq!(ct_gcd_limbs::<0>([], []), []);
+    assert_eq!(ct_gcd_limbs([48], [180]), [12]);
+    assert_eq!(ct_gcd_limbs([0, 0], [0, 0]), [0, 0]);
+    assert_eq!(ct_gcd_limbs([0, 0], [5, 1]), [5, 1]);
+    assert_eq!(ct_gcd_limbs([0, 6], [2, 4]), [6, 0]);
+    assert_eq!(ct_gcd_limbs([0, 6], [0, 4]), [0, 2]);
+    assert_eq!(ct_gcd_limbs([0, 1 << 63], [0, 1 << 62]), [0, 1 << 62]);
+    assert_eq!(ct_gcd_limbs([u64::MAX, u64::MAX, 0], [1, 0, 1]), [1, 0, 0]);
+}
+
+#[test]
+fn test_ct_shl_shr() {
+    for amount in 0..8 {
+        assert_eq!(ct_shl(0xa5u8, amount), 0xa5u8 << amount);
+        assert_eq!(ct_shr(0xa5u8, amount), 0xa5u8 >> amount);
+        assert_eq!(ct_shr(-91i8, amount), -91i8 >> amount);
+    }
+    for amount in [0, 1, 17, 63, 64, 127] {
+        let x = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+        assert_eq!(ct_shl(x, amount), x << amount);
+        assert_eq!(ct_shr(x as i128, amount), (x as i128) >> amount);
+        assert_eq!(ct_shr(!x as i128, amount), (!x as i128) >> amount);
+    }
+
+    assert_eq!(ct_shl(1u64, 64), 0);
+    assert_eq!(ct_shr(1u64, 64), 0);
+    assert_eq!(ct_shl(1u16, u32::MAX), 0);
+    assert_eq!(ct_shr(i32::MIN, 32), -1);
+    assert_eq!(ct_shr(i32::MAX, 1 << 31), 0);
+    assert_eq!(ct_shl(-1isize, usize::BITS), 0);
+}
+
+#[test]
+fn test_ct_rotate() {
+    for amount in [0, 1, 7, 8, 9, 31, 32, 33, 100, u32::MAX] {
+        assert_eq!(ct_rotate_left(0xa5u8, amount), 0xa5u8.rotate_left(amount));
+        assert_eq!(ct_rotate_right(0xa5u8, amount), 0xa5u8.rotate_right(amount));
+        assert_eq!(ct_rotate_left(-2i32, amount), (-2i32).rotate_left(amount));
+        assert_eq!(ct_rotate_right(-2i32, amount), (-2i32).rotate_right(amount));
+        let x = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+        assert_eq!(ct_rotate_left(x, amount), x.rotate_left(amount));
+        assert_eq!(ct_rotate_right(x, amount), x.rotate_right(amount));
+    }
+}
+
+#[test]
+fn test_ct_leading_zeros_count_ones() {
+    let x = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+    for shift in 0..128 {
+        let v = x >> shift;
+        assert_eq!(ct_leading_zeros(v), v.leading_zeros());
+        assert_eq!(ct_count_ones(v), v.count_ones());
+        assert_eq!(ct_leading_zeros(v as u64), (v as u64).leading_zeros());
+        assert_eq!(ct_count_ones(v as u64), (v as u64).count_ones());
+        assert_eq!(ct_leading_zeros(v as u32), (v as u32).leading_zeros());
+        assert_eq!(ct_count_ones(v as i32), (v as i32).count_ones());
+        assert_eq!(ct_leading_zeros(v as u16), (v as u16).leading_zeros());
+        assert_eq!(ct_count_ones(v as i8), (v as i8).count_ones());
+    }
+    assert_eq!(ct_leading_zeros(0u8), 8);
+    assert_eq!(ct_leading_zeros(0usize), usize::BITS);
+    assert_eq!(ct_leading_zeros(i64::MIN), 0);
+    assert_eq!(ct_count_ones(u64::MAX), 64);
+    assert_eq!(ct_count_ones(0i128), 0);
+}
+
+#[test]
+fn test_ct_hex() {
+    let mut buf = [0u8; 512];
+    let bytes: [u8; 256] = core::array::from_fn(|i| i as u8);
+    let hex = ct_hex_encode(&bytes, &mut buf);
+    assert_eq!(hex.len(), 512);
+    assert_eq!(&hex[..8], "00010203");
+    assert_eq!(&hex[504..], "fcfdfeff");
+
+    let mut decoded = [0u8; 256];
+    assert_eq!(ct_hex_decode(hex.as_bytes(), &mut decoded).unwrap(), 256);
+    assert_eq!(decoded, bytes);
+
+    let mut out = [0u8; 3];
+    assert_eq!(ct_hex_decode(b"0aFf9E", &mut out).unwrap(), 3);
+    assert_eq!(out, [0x0a, 0xff, 0x9e]);
+    assert_eq!(ct_hex_decode(b"", &mut out).unwrap(), 0);
+    assert_eq!(ct_hex_encode(&[], &mut []), "");
+
+    // Every byte that is not a hexadecimal digit is rejected.
+    for c in 0..=255u8 {
+        let valid = c.is_ascii_hexdigit();
+        let res = ct_hex_decode(&[b'0', c], &mut out);
+        assert_eq!(res.is_some().declassify(), valid, "{c:#x}");
+        if valid {
+            assert_eq!(out[0], (c as char).to_digit(16).unwrap() as u8);
+        }
+    }
+    assert!(!ct_hex_decode(b"abc", &mut out).is_some().declassify());
+}
+
+#[test]
+fn test_ct_base64() {
+    let mut buf = [0u8; 16];
+    for (plain, encoded) in [
+        (&b""[..], ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+        (&[0xfb, 0xff, 0xbf], "+/+/"),
+    ] {
+        assert_eq!(ct_base64_encode(plain, &mut buf), encoded);
+        let mut decoded = [0u8; 6];
+        let len = ct_base64_decode(encoded.as_bytes(), &mut decoded).unwrap();
+        assert_eq!(&decoded[..len], plain);
+    }
+
+    // Round trip every 6-bit value, in order.
+    let bytes: [u8; 48] = core::array::from_fn(|i| {
+        let (group, k) = (i / 3, i % 3);
+        let acc = (0..4).fold(0u32, |acc, j| (acc << 6) | (group * 4 + j) as u32);
+        (acc >> (16 - 8 * k)) as u8
+    });
+    let mut encoded = [0u8; 64];
+    let encoded = ct_base64_encode(&bytes, &mut encoded);
+    assert_eq!(encoded, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+    let mut decoded = [0u8; 48];
+    assert_eq!(ct_base64_decode(encoded.as_bytes(), &mut decoded).unwrap(), 48);
+    assert_eq!(decoded, bytes);
+
+    for c in 0..=255u8 {
+        let valid = c.is_ascii_alphanumeric() || c == b'+' || c == b'/';
+        assert_eq!(
+            ct_base64_decode(&[b'A', b'A', c, b'A'], &mut buf).is_some().declassify(),
+            valid
+        );
+    }
+    assert!(!ct_base64_decode(b"Zm9", &mut buf).is_some().declassify());
+    assert!(!ct_base64_decode(b"Zm=v", &mut buf).is_some().declassify());
+    assert!(!ct_base64_decode(b"====", &mut buf).is_some().declassify());
+}
+
+#[test]
+fn test_ct_parse_decimal() {
+    assert_eq!(ct_parse_decimal::<u32>(b"0").unwrap(), 0);
+    assert_eq!(ct_parse_decimal::<u32>(b"000123").unwrap(), 123);
+    assert_eq!(ct_parse_decimal::<u32>(b"4294967295").unwrap(), u32::MAX);
+    assert_eq!(ct_parse_decimal::<u64>(b"18446744073709551615").unwrap(), u64::MAX);
+    assert_eq!(
+        ct_parse_decimal::<u128>(b"340282366920938463463374607431768211455").unwrap(),
+        u128::MAX
+    );
+
+    for src in [&b""[..], b"-1", b"+1", b"1a", b" 1", b"1/", b"1:", b"4294967296", b"99999999999"] {
+        assert!(!ct_parse_decimal::<u32>(src).is_some().declassify());
+    }
+    assert!(!ct_parse_decimal::<u64>(b"18446744073709551616").is_some().declassify());
+}
+
+#[test]
+fn test_ct_parse_hex() {
+    assert_eq!(ct_parse_hex::<u32>(b"0").unwrap(), 0);
+    assert_eq!(ct_parse_hex::<u32>(b"aBcD").unwrap(), 0xabcd);
+    assert_eq!(ct_parse_hex::<u32>(b"00000000ffffffff").unwrap(), u32::MAX);
+    assert_eq!(ct_parse_hex::<u128>(&[b'f'; 32]).unwrap(), u128::MAX);
+
+    for src in [&b""[..], b"0x1", b"g", b"1 ", b"100000000"] {
+        assert!(!ct_parse_hex::<u32>(src).is_some().declassify());
+    }
+}
+
+#[test]
+fn test_secure_zeroize() {
+    let mut bytes = [0xffu8; 33];
+    secure_zeroize(&mut bytes[1..]);
+    assert_eq!(bytes[0], 0xff);
+    assert!(bytes[1..].iter().all(|&b| b == 0));
+    secure_zeroize(&mut []);
+
+    let mut limbs = [u64::MAX; 4];
+    unsafe { secure_zeroize_value(&mut limbs) };
+    assert_eq!(limbs, [0; 4]);
+
+    let mut words: [u32; 3] = [1, 2, 3];
+    unsafe { secure_zeroize_value(&mut words[..2]) };
+    assert_eq!(words, [0, 0, 3]);
+}
+
+#[test]
+fn test_secret() {
+    let mut a = Secret::new(7u64);
+    let b = Secret::new(9u64);
+    assert!(a.ct_eq(&Secret::new(7)).declassify());
+    assert!(!a.ct_eq(&b).declassify());
+    assert_eq!(*ct_select(Choice::from(false), a, b).expose(), 9);
+    assert_eq!(format!("{a:?}"), "Secret { .. }");
+
+    a.conditional_assign(&b, Choice::from(false));
+    assert_eq!(*a.expose(), 7);
+    a.conditional_assign(&b, Choice::from(true));
+    assert_eq!(*a.expose(), 9);
+
+    assert_eq!(*Secret::<[u8; 2]>::default().expose(), [0, 0]);
+}
+
+#[test]
+fn test_ct_if() {
+    let f = |x: u32| {
+        ct_if! {
+            x.ct_eq(&0) => b'a',
+            x.ct_eq(&1) => b'b',
+            x.ct_eq(&2) => b'c',
+            else => b'z'
+        }
+    };
+    assert_eq!([f(0), f(1), f(2), f(3)], *b"abcz");
+
+    // The first set condition wins.
+    let v = ct_if! {
+        Choice::from(false) => 1u8,
+        Choice::from(true) => 2,
+        Choice::from(true) => 3,
+        else => 4,
+    };
+    assert_eq!(v, 2);
+    assert_eq!(ct_if! { else => 5u8 }, 5);
+}
+
+#[test]
+fn test_ct_ord() {
+    let values = [i16::MIN, -300, -1, 0, 1, 255, i16::MAX];
+    for &a in &values {
+        for &b in &values {
+            assert_eq!(a.ct_lt(&b).declassify(), a < b);
+            assert_eq!(a.ct_gt(&b).declassify(), a > b);
+            assert_eq!(a.ct_le(&b).declassify(), a <= b);
+            assert_eq!(a.ct_ge(&b).declassify(), a >= b);
+            let (ua, ub) = (a as u16, b as u16);
+            assert_eq!(ua.ct_lt(&ub).declassify(), ua < ub);
+            assert_eq!(ua.ct_ge(&ub).declassify(), ua >= ub);
+            assert_eq!(ct_min(a, b), a.min(b));
+            assert_eq!(ct_max(ua, ub), ua.max(ub));
+        }
+    }
+    assert!(0u128.ct_lt(&u128::MAX).declassify());
+    assert!(i128::MIN.ct_lt(&i128::MAX).declassify());
+    assert!(!usize::MAX.ct_le(&0).declassify());
+}
+
+#[test]
+fn test_ct_option_combinators() {
+    let some = CtOption::new(4u32, Choice::from(true));
+    let none = CtOption::new(4u32, Choice::from(false));
+    assert!(none.is_none().declassify());
+    assert!(!some.is_none().declassify());
+
+    assert_eq!(some.map(|v| v + 1).unwrap(), 5);
+    assert!(none.map(|v| v + 1).is_none().declassify());
+
+    let half = |v: u32| CtOption::new(v / 2, v.ct_lt(&5) & ct_eq(&(v % 2), &0));
+    assert_eq!(some.and_then(half).unwrap(), 2);
+    assert!(none.and_then(half).is_none().declassify());
+    assert!(CtOption::new(6, Choice::from(true)).and_then(half).is_none().declassify());
+
+    let nine = || CtOption::new(9u32, Choice::from(true));
+    assert_eq!(some.or_else(nine).unwrap(), 4);
+    assert_eq!(none.or_else(nine).unwrap(), 9);
+    assert!(none.or_else(|| none).is_none().declassify());
+
+    assert_eq!(some.unwrap_or(7), 4);
+    assert_eq!(none.unwrap_or(7), 7);
+    assert_eq!(some.unwrap_or_else(|| 7), 4);
+    assert_eq!(none.unwrap_or_else(|| 7), 7);
+
+    // The closures run even when the value is absent.
+    let mut calls = 0;
+    let _ = none.map(|v| {
+        calls += 1;
+        v
+    });
+    let _ = some.unwrap_or_else(|| {
+        calls += 1;
+        0
+    });
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn test_ct_option_conversions() {
+    assert_eq!(CtOption::new(4u32, Choice::from(true)).into_option(), Some(4));
+    assert_eq!(CtOption::new(4u32, Choice::from(false)).into_option(), None);
+
+    let some = CtOption::from_option(Some([1u8, 2]));
+    assert_eq!(some.into_option(), Some([1, 2]));
+    let none = CtOption::<[u8; 2]>::from_option(None);
+    assert!(none.is_none().declassify());
+    assert_eq!(none.unwrap_or([7, 7]), [7, 7]);
+}
+
+#[test]
+fn test_ct_iterator() {
+    let values = [5u32, 1, 5, 9];
+    assert_eq!(values.iter().ct_position(|v| v.ct_eq(&5)).unwrap(), 0);
+    assert_eq!(values.into_iter().ct_position(|v| v.ct_gt(&6)).unwrap(), 3);
+    assert!(values.iter().ct_position(|v| v.ct_eq(&2)).is_none().declassify());
+    assert!(core::iter::empty::<u8>().ct_position(|_| Choice::from(true)).is_none().declassify());
+
+    // Every element is visited, even after the first match.
+    let mut visited = 0;
+    let _ = values.iter().ct_position(|v| {
+        visited += 1;
+        v.ct_eq(&5)
+    });
+    assert_eq!(visited, 4);
+
+    let max_even = values
+        .iter()
+        .ct_fold(0u32, |acc, &v| CtOption::new(v, ct_eq(&(v % 2), &0) & v.ct_gt(&acc)));
+    assert_eq!(max_even, 0);
+    let sum_odd = (1..=10u64).ct_fold(0, |acc, v| CtOption::new(acc + v, ct_eq(&(v % 2), &1)));
+    assert_eq!(sum_odd, 25);
+}
+
+#[test]
+fn test_ct_and_or_xor() {
+    for a in [false, true] {
+        for b in [false, true] {
+            assert_eq!(ct_and(a, b).declassify(), a & b);
+            assert_eq!(ct_or(a, b).declassify(), a | b);
+            assert_eq!(ct_xor(a, b).declassify(), a ^ b);
+            assert_eq!(ct_and(Choice::from(a), b).declassify(), a & b);
+            assert_eq!(ct_or(a, Choice::from(b)).declassify(), a | b);
+        }
+    }
+
+    // Both operands are evaluated even when the first decides the result.
+    let mut evaluated = false;
+    let _ = ct_and(false, {
+        evaluated = true;
+        true
+    });
+    assert!(evaluated);
+}
+
+#[test]
+fn test_ct_cmp() {
+    use core::cmp::Ordering;
+
+    let values = [i32::MIN, -7, 0, 7, i32::MAX];
+    for &a in &values {
+        for &b in &values {
+            assert_eq!(ct_cmp(&a, &b), a.cmp(&b));
+            assert_eq!(ct_cmp(&(a as u32), &(b as u32)), (a as u32).cmp(&(b as u32)));
+        }
+    }
+    assert_eq!(ct_cmp(&0u128, &u128::MAX), Ordering::Less);
+
+    for (a, b) in [(Ordering::Less, Ordering::Greater), (Ordering::Equal, Ordering::Less)] {
+        assert_eq!(ct_select(Choice::from(true), a, b), a);
+        assert_eq!(ct_select(Choice::from(false), a, b), b);
+    }
+}
+
+#[test]
+fn test_ct_select_many() {
+    let a = [1u32, 2, 3];
+    let b = [4u32, 5, 6];
+    let mut out = [0u32; 3];
+    ct_select_many(Choice::from(true), &a, &b, &mut out);
+    assert_eq!(out, a);
+    ct_select_many(Choice::from(false), &a, &b, &mut out);
+    assert_eq!(out, b);
+
+    let a = [u128::MAX, 1 << 100];
+    let mut out = [0u128; 2];
+    ct_select_many(Choice::from(true), &a, &[0, 0], &mut out);
+    assert_eq!(out, a);
+
+    // Types without a mask use the per-element default.
+    let mut out = [false; 2];
+    ct_select_many(Choice::from(false), &[true, true], &[false, true], &mut out);
+    assert_eq!(out, [false, true]);
+    ct_select_many::<i8>(Choice::from(true), &[], &[], &mut []);
+}
+
+#[test]
+#[should_panic]
+fn test_ct_select_many_len_mismatch() {
+    ct_select_many(Choice::from(true), &[1u8, 2], &[3u8, 4], &mut [0u8; 3]);
+}
+
+#[test]
+fn test_ct_montgomery() {
+    // p = 2^127 - 1, with R = 2^128 and R mod p = 2.
+    let p = [u64::MAX, u64::MAX >> 1];
+    let n_prime = mont_n_prime(p[0]);
+    assert_eq!(p[0].wrapping_mul(n_prime), u64::MAX);
+    // R^2 mod p = 4.
+    let r2 = [4, 0];
+
+    let to_mont = |x: [u64; 2]| {
+        let mut out = [0; 2];
+        ct_mont_mul(&x, &r2, &p, n_prime, &mut out);
+        out
+    };
+    let from_mont = |x: [u64; 2]| {
+        let mut out = [0; 2];
+        ct_mont_reduce(&[x[0], x[1], 0, 0], &p, n_prime, &mut out);
+        out
+    };
+
+    // (p - 1)^2 = 1, which needs the final subtraction.
+    let a = to_mont([p[0] - 1, p[1]]);
+    let mut sq = [0; 2];
+    ct_mont_mul(&a, &a, &p, n_prime, &mut sq);
+    assert_eq!(from_mont(sq), [1, 0]);
+
+    // 2^64 * 2^64 = 2^128 = 2.
+    let b = to_mont([0, 1]);
+    let mut prod = [0; 2];
+    ct_mont_mul(&b, &b, &p, n_prime, &mut prod);
+    assert_eq!(from_mont(prod), [2, 0]);
+
+    let mut zero = [1; 2];
+    ct_mont_mul(&[0, 0], &a, &p, n_prime, &mut zero);
+    assert_eq!(zero, [0, 0]);
+}
+
+#[test]
+#[should_panic]
+fn test_ct_mont_mul_len_mismatch() {
+    ct_mont_mul(&[1], &[1, 0], &[7, 1], mont_n_prime(7), &mut [0; 2]);
+}
+
+#[test]
+fn test_bitslice_transpose() {
+    use core::select::bitslice::{transpose_8x8, transpose_32x32};
+
+    let x = 0x0123_4567_89ab_cdefu64;
+    let t = transpose_8x8(x);
+    for r in 0..8 {
+        for c in 0..8 {
+            assert_eq!((t >> (8 * r + c)) & 1, (x >> (8 * c + r)) & 1);
+        }
+    }
+    assert_eq!(transpose_8x8(t), x);
+
+    let rows: [u32; 32] = core::array::from_fn(|i| (i as u32 + 1).wrapping_mul(0x9e37_79b9));
+    let mut t = rows;
+    transpose_32x32(&mut t);
+    for r in 0..32 {
+        for c in 0..32 {
+            assert_eq!((t[r] >> c) & 1, (rows[c] >> r) & 1);
+        }
+    }
+    transpose_32x32(&mut t);
+    assert_eq!(t, rows);
+}
+
+#[test]
+fn test_bitslice_planes() {
+    use core::select::bitslice::{pack_planes, select_planes, unpack_planes};
+
+    let bytes: [u8; 32] = core::array::from_fn(|i| (i as u8).wrapping_mul(37) ^ 0x5a);
+    let planes = pack_planes(&bytes);
+    for i in 0..8 {
+        for j in 0..32 {
+            assert_eq!((planes[i] >> j) & 1, ((bytes[j] >> i) & 1) as u32);
+        }
+    }
+    assert_eq!(unpack_planes(&planes), bytes);
+
+    // Take the even lanes from `bytes` and the odd lanes from all ones.
+    let ones = pack_planes(&[0xff; 32]);
+    let mixed = unpack_planes(&select_planes(0x5555_5555, &planes, &ones));
+    for (j, &b) in mixed.iter().enumerate() {
+        assert_eq!(b, if j % 2 == 0 { bytes[j] } else { 0xff });
+    }
+}
+
+#[test]
+fn test_verify_tag() {
+    let tag = [0xa5u8; 16];
+    assert!(verify_tag(&tag, &tag).declassify());
+    for i in 0..16 {
+        for bit in 0..8 {
+            let mut forged = tag;
+            forged[i] ^= 1 << bit;
+            assert!(!verify_tag(&tag, &forged).declassify());
+        }
+    }
+    assert!(!verify_tag(&tag, &tag[..15]).declassify());
+    assert!(!verify_tag(&tag[..0], &[]).declassify());
+}
+
+#[test]
+fn test_ct_uint() {
+    let to_u128 = |x: CtUint<2>| {
+        let [lo, hi] = x.to_words();
+        (hi as u128) << 64 | lo as u128
+    };
+    let from_u128 = |x: u128| CtUint::<2>::from_words([x as u64, (x >> 64) as u64]);
+
+    let values =
+        [0, 1, 2, u64::MAX as u128, 1 << 64, 0x1234_5678_9abc_def0_0fed_cba9_8765_4321, u128::MAX];
+    for &a in &values {
+        for &b in &values {
+            let (x, y) = (from_u128(a), from_u128(b));
+            let (sum, carry) = x.overflowing_add(&y);
+            assert_eq!((to_u128(sum), carry.declassify()), a.overflowing_add(b));
+            let (diff, borrow) = x.overflowing_sub(&y);
+            assert_eq!((to_u128(diff), borrow.declassify()), a.overflowing_sub(b));
+            assert_eq!(to_u128(x.wrapping_mul(&y)), a.wrapping_mul(b));
+            let (lo, hi) = x.widening_mul(&y);
+            assert_eq!(to_u128(lo), a.wrapping_mul(b));
+            // The wider product agrees with the double-width one.
+            let wide = CtUint::<4>::from_words([x.to_words()[0], x.to_words()[1], 0, 0])
+                .wrapping_mul(&CtUint::from_words([y.to_words()[0], y.to_words()[1], 0, 0]));
+            assert_eq!(wide.to_words()[2..], hi.to_words());
+            assert_eq!(x.ct_lt(&y).declassify(), a < b);
+            assert_eq!(x.ct_eq(&y).declassify(), a == b);
+            assert_eq!(x.ct_cmp(&y), a.cmp(&b));
+        }
+    }
+
+    let mut x = from_u128(5);
+    assert!(!x.conditional_add(&from_u128(3), Choice::from(false)).declassify());
+    assert_eq!(to_u128(x), 5);
+    assert!(x.conditional_sub(&from_u128(6), Choice::from(true)).declassify());
+    assert_eq!(to_u128(x), u128::MAX);
+    assert_eq!(to_u128(x.wrapping_neg()), 1);
+
+    let (mut a, mut b) = (CtUint::<3>::ONE, CtUint::<3>::MAX);
+    ct_swap(Choice::from(true), &mut a, &mut b);
+    assert_eq!((a.to_words(), b.to_words()), ([u64::MAX; 3], [1, 0, 0]));
+    assert_eq!(CtUint::<0>::ONE.to_words(), []);
+    assert_eq!(CtUint::<4>::default().to_words(), [0; 4]);
+}
+
+#[test]
+fn test_ct_cmp_limbs() {
+    let values = [[0, 0], [1, 0], [u64::MAX, 0], [0, 1], [7, 1], [0, u64::MAX], [u64::MAX; 2]];
+    let to_u128 = |x: &[u64; 2]| (x[1] as u128) << 64 | x[0] as u128;
+    for a in &values {
+        for b in &values {
+            let (x, y) = (to_u128(a), to_u128(b));
+            assert_eq!(ct_cmp_limbs(a, b), x.cmp(&y));
+            assert_eq!(ct_lt_limbs(a, b).declassify(), x < y);
+            assert_eq!(ct_gt_limbs(a, b).declassify(), x > y);
+            assert_eq!(ct_eq_limbs(a, b).declassify(), x == y);
+        }
+    }
+    assert!(ct_eq_limbs(&[], &[]).declassify());
+}
+
+#[test]
+#[should_panic]
+fn test_ct_cmp_limbs_len_mismatch() {
+    ct_cmp_limbs(&[1, 0], &[1]);
+}
+
+#[test]
+fn test_ct_cond_add_sub_mod() {
+    // p = 2^127 - 1.
+    let p = [u64::MAX, u64::MAX >> 1];
+    let mut x = [5, 0];
+    assert!(!ct_cond_add_mod(&mut x, &p, Choice::from(false)).declassify());
+    assert_eq!(x, [5, 0]);
+    assert!(!ct_cond_add_mod(&mut x, &p, Choice::from(true)).declassify());
+    assert_eq!(x, [4, 1 << 63]);
+    assert!(!ct_cond_sub_mod(&mut x, &p, Choice::from(true)).declassify());
+    assert_eq!(x, [5, 0]);
+    assert!(ct_cond_sub_mod(&mut x, &p, Choice::from(true)).declassify());
+    assert_eq!(x, [6, 1 << 63]);
+
+    let mut y = [u64::MAX; 2];
+    assert!(ct_cond_add_mod(&mut y, &[1, 0], Choice::from(true)).declassify());
+    assert_eq!(y, [0, 0]);
+}
+
+#[test]
+fn test_ct_lookup() {
+    let table = [10u32, 20, 30, 40];
+    for i in 0..4 {
+        assert_eq!(ct_lookup(&table, i), table[i]);
+    }
+    assert_eq!(ct_lookup(&table, 4), 10);
+    assert_eq!(ct_lookup(&[[1u8; 3], [2; 3]], 1), [2; 3]);
+}
+
+#[test]
+fn test_ct_pow() {
+    const P: u64 = 0xffff_ffff_0000_0001;
+    let p = Barrett::<u64>::new(P);
+    let mul = |a, b| p.mul_mod(a, b);
+    let naive = |base: u64, exp: &[u64]| {
+        let mut acc = 1;
+        for &limb in exp.iter().rev() {
+            for bit in (0..64).rev() {
+                acc = mul(acc, acc);
+                if (limb >> bit) & 1 == 1 {
+                    acc = mul(acc, base);
+                }
+            }
+        }
+        acc
+    };
+
+    let exps: [&[u64]; 5] =
+        [&[0], &[1], &[P - 2], &[0x0123_4567_89ab_cdef, 0xfedc_ba98], &[0, 0, 1]];
+    for exp in exps {
+        let expected = naive(7, exp);
+        assert_eq!(ct_pow::<_, 2>(7, 1, exp, mul), expected);
+        assert_eq!(ct_pow::<_, 16>(7, 1, exp, mul), expected);
+        // A 5-bit window straddles the limb boundaries.
+        assert_eq!(ct_pow::<_, 32>(7, 1, exp, mul), expected);
+        assert_eq!(ct_pow::<_, 256>(7, 1, exp, mul), expected);
+    }
+    assert_eq!(mul(ct_pow::<_, 8>(7, 1, &[P - 2], mul), 7), 1);
+}
+
+#[test]
+fn test_ct_bitset() {
+    let mut set = CtBitSet::<3>::new();
+    assert_eq!(CtBitSet::<3>::CAPACITY, 192);
+    assert!(!set.any().declassify());
+    for i in (0..192).step_by(7) {
+        set.set(i, Choice::from(true));
+    }
+    for i in 0..200 {
+        assert_eq!(set.get(i).declassify(), i < 192 && i % 7 == 0, "{i}");
+    }
+    assert_eq!(set.count(), 28);
+    set.set(7, Choice::from(false));
+    set.set(14, Choice::from(true));
+    set.set(500, Choice::from(true));
+    assert_eq!(set.count(), 27);
+    assert!(!set.get(7).declassify());
+
+    let inverted = !set;
+    assert_eq!(inverted.count(), 192 - 27);
+    assert!((set | inverted).all().declassify());
+    assert!(!(set & inverted).any().declassify());
+    assert!((set ^ inverted).ct_eq(&!CtBitSet::new()).declassify());
+
+    let mut other = CtBitSet::from_words([1, 0, 0]);
+    other &= set;
+    assert_eq!(other.to_words(), [1, 0, 0]);
+    assert!(CtBitSet::<0>::new().all().declassify());
+    assert!(!CtBitSet::<0>::new().get(0).declassify());
+}
+
+#[test]
+fn test_ct_slice_min_max() {
+    let values = [3i64, -7, 12, -7, 12, 0];
+    assert_eq!(ct_slice_min(&values), -7);
+    assert_eq!(ct_slice_max(&values), 12);
+    assert_eq!(ct_slice_argmin(&values).unwrap(), 1);
+    assert_eq!(ct_slice_argmax(&values).unwrap(), 2);
+
+    assert_eq!(ct_slice_min(&[u128::MAX]), u128::MAX);
+    assert_eq!(ct_slice_argmax(&[u8::MAX, 0]).unwrap(), 0);
+    assert!(ct_slice_argmin::<u8>(&[]).is_none().declassify());
+    assert!(ct_slice_argmax::<u8>(&[]).is_none().declassify());
+}
+
+#[test]
+#[should_panic]
+fn test_ct_slice_min_empty() {
+    ct_slice_min::<u8>(&[]);
+}
+
+#[test]
+fn test_ct_result() {
+    let ok = CtResult::new(4u32, 0u8, Choice::from(true));
+    let bad_padding = CtResult::new(0u32, 1u8, Choice::from(false));
+    let check_mac = |v: u32| CtResult::new(v + 1, 2u8, Choice::from(v != 0));
+
+    assert_eq!(ok.and_then(check_mac).into_result(), Ok(5));
+    assert_eq!(bad_padding.and_then(check_mac).into_result(), Err(1));
+    assert_eq!(ok.map(|v| v - 4).and_then(check_mac).into_result(), Err(2));
+
+    assert!(ok.is_ok().declassify());
+    assert!(bad_padding.is_err().declassify());
+    assert_eq!(ok.ok().unwrap(), 4);
+    assert!(ok.err().is_none().declassify());
+    assert_eq!(bad_padding.err().unwrap(), 1);
+    assert_eq!(bad_padding.map_err(|e| e * 10).unwrap_err(), 10);
+    assert_eq!(bad_padding.unwrap_or(9), 9);
+    assert_eq!(ok.unwrap_or(9), 4);
+
+    let picked = ct_select(Choice::from(false), ok, bad_padding);
+    assert_eq!(picked.into_result(), Err(1));
+}
+
+#[test]
+#[should_panic = "on a failure"]
+fn test_ct_result_unwrap_failure() {
+    CtResult::new(0u8, 0u8, Choice::from(false)).unwrap();
+}
+
+#[test]
+fn test_ct_cell() {
+    let cell = CtCell::new([1u8, 2, 3]);
+    cell.conditional_store(Choice::from(false), [9; 3]);
+    assert_eq!(cell.get(), [1, 2, 3]);
+    cell.conditional_store(Choice::from(true), [9; 3]);
+    assert_eq!(cell.get(), [9; 3]);
+    cell.set([4; 3]);
+
+    let copy = cell.clone();
+    copy.set([0; 3]);
+    assert_eq!(cell.get(), [4; 3]);
+
+    let mut cell = cell;
+    cell.get_mut()[1] = 7;
+    assert_eq!(cell.into_inner(), [4, 7, 4]);
+    assert_eq!(CtCell::<u64>::default().get(), 0);
+    assert_eq!(format!("{:?}", CtCell::new(5u8)), "CtCell { .. }");
+}
+
+#[test]
+#[cfg(target_arch = "aarch64")]
+fn test_dit_guard() {
+    use core::select::{DitGuard, dit_enabled};
+
+    if !std::arch::is_aarch64_feature_detected!("dit") {
+        return;
+    }
+    // SAFETY: the CPU supports FEAT_DIT.
+    unsafe {
+        let before = dit_enabled();
+        {
+            let _outer = DitGuard::new();
+            let inner = DitGuard::new();
+            assert!(dit_enabled());
+            drop(inner);
+            assert!(dit_enabled());
+        }
+        assert_eq!(dit_enabled(), before);
+    }
+}
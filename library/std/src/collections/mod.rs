@@ -421,6 +421,8 @@
 pub use alloc_crate::collections::{BTreeMap, BTreeSet, BinaryHeap};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::collections::{LinkedList, VecDeque};
+#[unstable(feature = "ct_select", issue = "none")]
+pub use alloc_crate::collections::{ObliviousRingBuffer, oblivious_ring_buffer};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::collections::{binary_heap, btree_map, btree_set};
 #[stable(feature = "rust1", since = "1.0.0")]
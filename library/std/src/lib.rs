@@ -538,6 +538,8 @@
 pub use core::range;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::result;
+#[unstable(feature = "ct_select", issue = "none")]
+pub use core::select;
 #[stable(feature = "rust1", since = "1.0.0")]
 #[allow(deprecated, deprecated_in_future)]
 pub use core::u8;
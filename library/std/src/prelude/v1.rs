@@ -121,6 +121,13 @@ mod panic {}
 #[unstable(feature = "derive_const", issue = "118304")]
 pub use core::prelude::v1::derive_const;
 
+#[unstable(
+    feature = "ct_test",
+    issue = "none",
+    reason = "`ct_test` is a part of custom test frameworks which are unstable"
+)]
+pub use core::prelude::v1::ct_test;
+
 // Do not `doc(no_inline)` either.
 #[unstable(
     feature = "cfg_accessible",
@@ -0,0 +1,249 @@
+//! Statistical timing tests for constant-time code, in the style of dudect.
+//!
+//! A `#[ct_test]` function receives a [`CtTester`], and gives it a generator of inputs and the
+//! code to measure. The tester runs the code on inputs of two classes, interleaved at random, and
+//! compares the distributions of the running times of the two classes with Welch's t-test: the
+//! running time of constant-time code does not depend on its input, so both classes have the same
+//! distribution.
+//!
+//! See: Reparaz, Balasch and Verbauwhede, "Dude, is my code constant time?", DATE 2017.
+
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Termination;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+use crate::assert_test_result;
+use crate::stats::Stats;
+
+/// Number of measurements of each test, unless overridden by `RUST_CT_TEST_SAMPLES`.
+const DEFAULT_SAMPLES: usize = 100_000;
+
+/// Largest absolute t statistic that is not reported as a timing difference, unless overridden by
+/// `RUST_CT_TEST_THRESHOLD`. dudect uses 10 as well, which keeps false positives rare even with
+/// the millions of measurements of a long test.
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Number of inputs generated ahead of their measurements.
+const BATCH_SIZE: usize = 1_000;
+
+/// Number of percentiles at which the measurements are cropped.
+const CROPS: usize = 100;
+
+/// The class of the input of a measurement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputClass {
+    /// An input that is the same for every measurement, such as all zeroes.
+    Fixed,
+    /// An input that is drawn at random for each measurement.
+    Random,
+}
+
+/// A pseudorandom number generator for the inputs of the measurements.
+///
+/// This is xorshift64*, which is fast enough not to dominate the tests, but which is not suitable
+/// for anything else that needs random numbers, and in particular not for cryptography.
+pub struct CtRng(u64);
+
+impl CtRng {
+    fn new(seed: u64) -> Self {
+        // The state must not be zero.
+        CtRng(seed | 1)
+    }
+
+    /// Returns the next pseudorandom `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Fills `dest` with pseudorandom bytes.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+/// Measures the running time of a function on inputs of the two [`InputClass`]es.
+pub struct CtTester {
+    samples: usize,
+    rng: CtRng,
+    /// The durations of the measurements, in cycles, for each class.
+    timings: [Vec<f64>; 2],
+}
+
+impl CtTester {
+    fn new(samples: usize, seed: u64) -> Self {
+        CtTester { samples, rng: CtRng::new(seed), timings: [Vec::new(), Vec::new()] }
+    }
+
+    /// Measures `f` on inputs returned by `input`, for classes chosen at random.
+    ///
+    /// The inputs are generated before they are measured, so `input` may take any time; only the
+    /// calls of `f` are timed. `input` should return the same value whenever it is called with
+    /// [`InputClass::Fixed`].
+    pub fn measure<I, O>(
+        &mut self,
+        mut input: impl FnMut(InputClass, &mut CtRng) -> I,
+        mut f: impl FnMut(I) -> O,
+    ) {
+        // The first batch warms up the caches and the branch predictors, and is discarded.
+        let mut warm_up = true;
+        let mut remaining = self.samples;
+        while remaining > 0 {
+            let len = BATCH_SIZE.min(remaining);
+            let batch: Vec<_> = (0..len)
+                .map(|_| {
+                    let class = if self.rng.next_u64() & 1 == 0 {
+                        InputClass::Fixed
+                    } else {
+                        InputClass::Random
+                    };
+                    (class, input(class, &mut self.rng))
+                })
+                .collect();
+            for (class, input) in batch {
+                let input = black_box(input);
+                let start = cycles();
+                let output = f(input);
+                let end = cycles();
+                black_box(output);
+                if !warm_up {
+                    self.timings[class as usize].push(end.wrapping_sub(start) as f64);
+                }
+            }
+            if warm_up {
+                warm_up = false;
+            } else {
+                remaining -= len;
+            }
+        }
+    }
+
+    /// Returns the largest absolute t statistic of the measurements, and of the measurements
+    /// below a range of percentiles.
+    ///
+    /// The long tail of the distributions, from interrupts and context switches, can hide a small
+    /// difference between the classes, which cropping it reveals.
+    fn max_t(&self) -> f64 {
+        let [fixed, random] = &self.timings;
+        let mut max = welch_t(fixed, random).abs();
+        let mut sorted: Vec<f64> = fixed.iter().chain(random).copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        for i in 0..CROPS {
+            // The percentiles get closer together towards 100, like in dudect.
+            let pct = 1.0 - 0.5f64.powf(10.0 * (i + 1) as f64 / CROPS as f64);
+            let limit = sorted[((sorted.len() - 1) as f64 * pct) as usize];
+            let fixed: Vec<f64> = fixed.iter().copied().filter(|&t| t <= limit).collect();
+            let random: Vec<f64> = random.iter().copied().filter(|&t| t <= limit).collect();
+            if fixed.len() > 1 && random.len() > 1 {
+                max = max.max(welch_t(&fixed, &random).abs());
+            }
+        }
+        max
+    }
+
+    /// Writes the measurements to `<dir>/<name>.csv`, one `class,cycles` line per measurement,
+    /// for the `ct-timing` tool.
+    fn dump(&self, dir: &Path, name: &str) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.csv", name.replace("::", "-")));
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        writeln!(file, "class,cycles")?;
+        for (class, timings) in ["fixed", "random"].iter().zip(&self.timings) {
+            for cycles in timings {
+                writeln!(file, "{class},{cycles}")?;
+            }
+        }
+        file.flush()
+    }
+}
+
+/// Welch's t statistic of two samples, which is large when their means differ.
+pub(crate) fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let diff = a.mean() - b.mean();
+    let deviation = (a.var() / a.len() as f64 + b.var() / b.len() as f64).sqrt();
+    if deviation == 0.0 { if diff == 0.0 { 0.0 } else { f64::INFINITY } } else { diff / deviation }
+}
+
+/// Runs a `#[ct_test]` function, and fails if the running time of the code it measured depends
+/// on the class of its input.
+pub fn run_ct_test<T: Termination>(
+    name: &str,
+    f: impl FnOnce(&mut CtTester) -> T,
+) -> Result<(), String> {
+    let samples = env_var("RUST_CT_TEST_SAMPLES")?.unwrap_or(DEFAULT_SAMPLES);
+    let threshold = env_var("RUST_CT_TEST_THRESHOLD")?.unwrap_or(DEFAULT_THRESHOLD);
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+    let mut tester = CtTester::new(samples, seed);
+    assert_test_result(f(&mut tester))?;
+
+    if tester.timings.iter().any(|timings| timings.len() < 2) {
+        return Err(format!(
+            "not enough measurements of each class: `{name}` must call `CtTester::measure`"
+        ));
+    }
+    if let Some(dir) = env::var_os("RUST_CT_TEST_DUMP") {
+        tester
+            .dump(Path::new(&dir), name)
+            .map_err(|e| format!("failed to write the measurements of `{name}`: {e}"))?;
+    }
+    let t = tester.max_t();
+    if t > threshold {
+        Err(format!(
+            "the running time depends on the input: |t| = {t:.2} is above {threshold} \
+             ({} fixed and {} random inputs)",
+            tester.timings[0].len(),
+            tester.timings[1].len(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn env_var<T: std::str::FromStr>(name: &str) -> Result<Option<T>, String> {
+    match env::var(name) {
+        Ok(value) => {
+            value.parse().map(Some).map_err(|_| format!("invalid value for {name}: `{value}`"))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Reads the cycle counter, or a clock in nanoseconds on targets without one.
+#[inline(always)]
+fn cycles() -> u64 {
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: `lfence` and `rdtsc` are available on every x86_64 processor. The fence waits for
+    // the measured code to complete before the counter is read.
+    unsafe {
+        std::arch::x86_64::_mm_lfence();
+        std::arch::x86_64::_rdtsc()
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        let count: u64;
+        // SAFETY: the virtual counter can be read at EL0 on every OS that Rust supports, and
+        // reading it has no side effects.
+        unsafe {
+            std::arch::asm!("isb", "mrs {}, cntvct_el0", out(reg) count, options(nomem, nostack))
+        };
+        count
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        use std::sync::OnceLock;
+        use std::time::Instant;
+
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+    }
+}
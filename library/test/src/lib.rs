@@ -35,6 +35,7 @@
 pub use self::ColorConfig::*;
 pub use self::bench::{Bencher, black_box};
 pub use self::console::run_tests_console;
+pub use self::ct::{CtRng, CtTester, InputClass, run_ct_test};
 pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
 pub use self::types::TestName::*;
 pub use self::types::*;
@@ -43,6 +44,7 @@
 pub mod test {
     pub use crate::bench::Bencher;
     pub use crate::cli::{TestOpts, parse_opts};
+    pub use crate::ct::{CtTester, run_ct_test};
     pub use crate::helpers::metrics::{Metric, MetricMap};
     pub use crate::options::{Options, RunIgnored, RunStrategy, ShouldPanic};
     pub use crate::test_result::{TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk};
@@ -67,6 +69,7 @@ pub mod test {
 pub mod bench;
 mod cli;
 mod console;
+pub mod ct;
 mod event;
 mod formatters;
 mod helpers;
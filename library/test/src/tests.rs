@@ -912,3 +912,29 @@ fn f(_: &mut Bencher) -> Result<(), String> {
     let result = rx.recv().unwrap().result;
     assert_eq!(result, TrFailed);
 }
+
+#[test]
+fn test_welch_t() {
+    let a = [1.0, 2.0, 3.0, 4.0];
+    assert_eq!(ct::welch_t(&a, &a), 0.0);
+    assert_eq!(ct::welch_t(&[2.0, 2.0], &[2.0, 2.0]), 0.0);
+    assert_eq!(ct::welch_t(&[3.0, 3.0], &[2.0, 2.0]), f64::INFINITY);
+    // The means differ by 10, and the standard error of the difference is sqrt(2 * 5/3 / 4).
+    let b = [11.0, 12.0, 13.0, 14.0];
+    assert!((ct::welch_t(&b, &a) - 10.0 / (5.0f64 / 6.0).sqrt()).abs() < 1e-9);
+    assert!(ct::welch_t(&a, &b) < 0.0);
+}
+
+#[test]
+fn test_ct_test_without_measurements_fails() {
+    let result = ct::run_ct_test("no_measurements", |_: &mut CtTester| {});
+    assert!(result.unwrap_err().contains("`CtTester::measure`"));
+}
+
+#[test]
+fn test_ct_test_returning_err_fails() {
+    let result = ct::run_ct_test("returns_err", |_: &mut CtTester| -> Result<(), String> {
+        Err("An error".into())
+    });
+    assert!(result.is_err());
+}
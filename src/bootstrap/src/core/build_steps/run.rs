@@ -466,6 +466,34 @@ fn run(self, builder: &Builder<'_>) {
     }
 }
 
+/// Step to run the ct-timing tool (`./x run ct-timing -- <file.csv>...`), which analyzes the
+/// timings written by `#[ct_test]` functions.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CtTiming;
+
+impl Step for CtTiming {
+    type Output = ();
+    const IS_HOST: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("src/tools/ct-timing")
+    }
+
+    fn is_default_step(_builder: &Builder<'_>) -> bool {
+        false
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(Self {});
+    }
+
+    fn run(self, builder: &Builder<'_>) {
+        let mut cmd = builder.tool_cmd(Tool::CtTiming);
+        cmd.args(&builder.config.free_args);
+        cmd.run(builder);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Rustfmt;
 
@@ -64,6 +64,7 @@ fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
         run.path("src/tools/jsondoclint")
             .path("src/tools/replace-version-placeholder")
             .path("src/tools/coverage-dump")
+            .path("src/tools/ct-timing")
             // We want `./x test tidy` to _run_ the tidy tool, not its tests.
             // So we need a separate alias to test the tidy tool itself.
             .alias("tidyselftest")
@@ -1647,6 +1648,13 @@ fn run(self, builder: &Builder<'_>) {
     default: true
 });
 
+test!(AssemblyCt {
+    path: "tests/assembly-ct",
+    mode: CompiletestMode::AssemblyCt,
+    suite: "assembly-ct",
+    default: true
+});
+
 /// Runs the coverage test suite at `tests/coverage` in some or all of the
 /// coverage test modes.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
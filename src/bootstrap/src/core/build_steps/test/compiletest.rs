@@ -11,6 +11,7 @@
 pub(crate) enum CompiletestMode {
     // tidy-alphabetical-start
     Assembly,
+    AssemblyCt,
     Codegen,
     CodegenUnits,
     CoverageMap,
@@ -39,6 +40,7 @@ pub(crate) const fn as_str(self) -> &'static str {
         match self {
             // tidy-alphabetical-start
             Self::Assembly => "assembly",
+            Self::AssemblyCt => "assembly-ct",
             Self::Codegen => "codegen",
             Self::CodegenUnits => "codegen-units",
             Self::CoverageMap => "coverage-map",
@@ -19,6 +19,7 @@
         "tests",
         &[
             // tidy-alphabetical-start
+            "tests/assembly-ct",
             "tests/assembly-llvm",
             "tests/build-std",
             "tests/codegen-llvm",
@@ -783,6 +783,7 @@ macro_rules! describe {
                 tool::RustdocGUITest,
                 tool::OptimizedDist,
                 tool::CoverageDump,
+                tool::CtTiming,
                 tool::LlvmBitcodeLinker,
                 tool::RustcPerf,
                 tool::WasmComponentLd,
@@ -858,6 +859,7 @@ macro_rules! describe {
                 test::CodegenLlvm,
                 test::CodegenUnits,
                 test::AssemblyLlvm,
+                test::AssemblyCt,
                 test::Incremental,
                 test::Debuginfo,
                 test::UiFullDeps,
@@ -1008,6 +1010,7 @@ macro_rules! describe {
                 run::FeaturesStatusDump,
                 run::CyclicStep,
                 run::CoverageDump,
+                run::CtTiming,
                 run::Rustfmt,
                 run::GenerateHelp,
             ),
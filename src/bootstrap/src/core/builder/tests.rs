@@ -2095,6 +2095,7 @@ fn test_all_stage_1() {
         [test] compiletest-codegen-llvm 1 <host>
         [test] compiletest-codegen-units 1 <host>
         [test] compiletest-assembly-llvm 1 <host>
+        [test] compiletest-assembly-ct %s <host>
         [test] compiletest-incremental 1 <host>
         [test] compiletest-debuginfo 1 <host>
         [test] compiletest-ui-fulldeps 1 <host>
@@ -2107,6 +2108,7 @@ fn test_all_stage_1() {
         [test] rustc 0 <host> -> CrateLibrustc 1 <host>
         [build] rustc 1 <host> -> rustc 2 <host>
         [test] crate-bootstrap <host> src/tools/coverage-dump
+        [test] crate-bootstrap <host> src/tools/ct-timing
         [test] crate-bootstrap <host> src/tools/jsondoclint
         [test] crate-bootstrap <host> src/tools/replace-version-placeholder
         [test] crate-bootstrap <host> tidyselftest
@@ -2275,6 +2277,7 @@ fn test_all_stage_2() {
         [test] compiletest-codegen-llvm 2 <host>
         [test] compiletest-codegen-units 2 <host>
         [test] compiletest-assembly-llvm 2 <host>
+        [test] compiletest-assembly-ct %s <host>
         [test] compiletest-incremental 2 <host>
         [test] compiletest-debuginfo 2 <host>
         [build] rustc 2 <host> -> rustc 3 <host>
@@ -2288,6 +2291,7 @@ fn test_all_stage_2() {
         [build] rustdoc 1 <host>
         [test] rustc 1 <host> -> CrateLibrustc 2 <host>
         [test] crate-bootstrap <host> src/tools/coverage-dump
+        [test] crate-bootstrap <host> src/tools/ct-timing
         [test] crate-bootstrap <host> src/tools/jsondoclint
         [test] crate-bootstrap <host> src/tools/replace-version-placeholder
         [test] crate-bootstrap <host> tidyselftest
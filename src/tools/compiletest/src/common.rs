@@ -27,6 +27,7 @@ pub enum TestMode {
         RustdocJs => "rustdoc-js",
         MirOpt => "mir-opt",
         Assembly => "assembly",
+        AssemblyCt => "assembly-ct",
         CoverageMap => "coverage-map",
         CoverageRun => "coverage-run",
         Crashes => "crashes",
@@ -57,6 +58,7 @@ pub fn output_dir_disambiguator(self) -> &'static str {
 string_enum! {
     #[derive(Clone, Copy, PartialEq, Debug)]
     pub enum TestSuite {
+        AssemblyCt => "assembly-ct",
         AssemblyLlvm => "assembly-llvm",
         CodegenLlvm => "codegen-llvm",
         CodegenUnits => "codegen-units",
@@ -165,6 +167,7 @@ pub(crate) fn for_miropt_test_tools(&self) -> miropt_test_tools::PanicStrategy {
 pub enum Sanitizer {
     Address,
     Cfi,
+    ConstantTime,
     Dataflow,
     Kcfi,
     KernelAddress,
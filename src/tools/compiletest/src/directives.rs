@@ -199,6 +199,12 @@ pub(crate) struct TestProps {
     pub llvm_cov_flags: Vec<String>,
     /// Extra flags to pass to LLVM's `filecheck` tool, in tests that use it.
     pub filecheck_flags: Vec<String>,
+    /// Functions that must not contain any conditional branch.
+    /// Only used by the "assembly-ct" test mode.
+    pub ct_no_branches: Vec<String>,
+    /// Functions that must contain exactly the given number of conditional branches.
+    /// Only used by the "assembly-ct" test mode.
+    pub ct_branches: Vec<(String, usize)>,
     /// Don't automatically insert any `--check-cfg` args
     pub no_auto_check_cfg: bool,
     /// Build and use `minicore` as `core` stub for `no_core` tests in cross-compilation scenarios
@@ -256,6 +262,8 @@ mod directives {
     pub const REMAP_SRC_BASE: &'static str = "remap-src-base";
     pub const LLVM_COV_FLAGS: &'static str = "llvm-cov-flags";
     pub const FILECHECK_FLAGS: &'static str = "filecheck-flags";
+    pub const CT_NO_BRANCHES: &'static str = "ct-no-branches";
+    pub const CT_BRANCHES: &'static str = "ct-branches";
     pub const NO_AUTO_CHECK_CFG: &'static str = "no-auto-check-cfg";
     pub const ADD_MINICORE: &'static str = "add-minicore";
     pub const MINICORE_COMPILE_FLAGS: &'static str = "minicore-compile-flags";
@@ -313,6 +321,8 @@ pub fn new() -> Self {
             remap_src_base: false,
             llvm_cov_flags: vec![],
             filecheck_flags: vec![],
+            ct_no_branches: vec![],
+            ct_branches: vec![],
             no_auto_check_cfg: false,
             add_minicore: false,
             minicore_compile_flags: vec![],
@@ -492,7 +502,10 @@ pub fn local_pass_mode(&self) -> Option<PassMode> {
     fn update_add_minicore(&mut self, ln: &DirectiveLine<'_>, config: &Config) {
         let add_minicore = config.parse_name_directive(ln, directives::ADD_MINICORE);
         if add_minicore {
-            if !matches!(config.mode, TestMode::Ui | TestMode::Codegen | TestMode::Assembly) {
+            if !matches!(
+                config.mode,
+                TestMode::Ui | TestMode::Codegen | TestMode::Assembly | TestMode::AssemblyCt
+            ) {
                 panic!(
                     "`add-minicore` is currently only supported for ui, codegen and assembly test modes"
                 );
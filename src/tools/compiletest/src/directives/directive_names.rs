@@ -27,6 +27,8 @@
     "check-test-line-numbers-match",
     "compare-output-by-lines",
     "compile-flags",
+    "ct-branches",
+    "ct-no-branches",
     "disable-gdb-pretty-printers",
     "doc-flags",
     "dont-check-compiler-stderr",
@@ -173,6 +175,7 @@
     "needs-rustc-debug-assertions",
     "needs-sanitizer-address",
     "needs-sanitizer-cfi",
+    "needs-sanitizer-constant-time",
     "needs-sanitizer-dataflow",
     "needs-sanitizer-hwaddress",
     "needs-sanitizer-kcfi",
@@ -320,6 +320,23 @@ fn make_directive_handlers_map() -> HashMap<&'static str, Handler> {
                 props.filecheck_flags.extend(split_flags(&flags));
             }
         }),
+        handler(CT_NO_BRANCHES, |config, ln, props| {
+            if let Some(symbols) = config.parse_name_value_directive(ln, CT_NO_BRANCHES) {
+                props.ct_no_branches.extend(symbols.split_whitespace().map(str::to_owned));
+            }
+        }),
+        handler(CT_BRANCHES, |config, ln, props| {
+            if let Some(entries) = config.parse_name_value_directive(ln, CT_BRANCHES) {
+                props.ct_branches.extend(entries.split_whitespace().map(|entry| {
+                    entry
+                        .split_once('=')
+                        .and_then(|(symbol, count)| Some((symbol.to_owned(), count.parse().ok()?)))
+                        .unwrap_or_else(|| {
+                            panic!("expected `symbol=count` in `ct-branches`, found `{entry}`")
+                        })
+                }));
+            }
+        }),
         handler(NO_AUTO_CHECK_CFG, |config, ln, props| {
             config.set_name_directive(ln, NO_AUTO_CHECK_CFG, &mut props.no_auto_check_cfg);
         }),
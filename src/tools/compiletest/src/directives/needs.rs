@@ -31,6 +31,11 @@ pub(super) fn handle_needs(
             condition: cache.sanitizer_cfi,
             ignore_reason: "ignored on targets without CFI sanitizer",
         },
+        Need {
+            name: "needs-sanitizer-constant-time",
+            condition: cache.sanitizer_constant_time,
+            ignore_reason: "ignored on targets without constant-time sanitizer",
+        },
         Need {
             name: "needs-sanitizer-dataflow",
             condition: cache.sanitizer_dataflow,
@@ -329,6 +334,7 @@ pub(super) struct CachedNeedsConditions {
     sanitizer_support: bool,
     sanitizer_address: bool,
     sanitizer_cfi: bool,
+    sanitizer_constant_time: bool,
     sanitizer_dataflow: bool,
     sanitizer_kcfi: bool,
     sanitizer_kasan: bool,
@@ -356,6 +362,7 @@ pub(super) fn load(config: &Config) -> Self {
             sanitizer_support: std::env::var_os("RUSTC_SANITIZER_SUPPORT").is_some(),
             sanitizer_address: sanitizers.contains(&Sanitizer::Address),
             sanitizer_cfi: sanitizers.contains(&Sanitizer::Cfi),
+            sanitizer_constant_time: sanitizers.contains(&Sanitizer::ConstantTime),
             sanitizer_dataflow: sanitizers.contains(&Sanitizer::Dataflow),
             sanitizer_kcfi: sanitizers.contains(&Sanitizer::Kcfi),
             sanitizer_kasan: sanitizers.contains(&Sanitizer::KernelAddress),
@@ -29,6 +29,7 @@
 // Helper modules that implement test running logic for each test suite.
 // tidy-alphabetical-start
 mod assembly;
+mod assembly_ct;
 mod codegen;
 mod codegen_units;
 mod coverage;
@@ -284,6 +285,7 @@ fn run_revision(&self) {
             TestMode::Ui => self.run_ui_test(),
             TestMode::MirOpt => self.run_mir_opt_test(),
             TestMode::Assembly => self.run_assembly_test(),
+            TestMode::AssemblyCt => self.run_assembly_ct_test(),
             TestMode::RustdocJs => self.run_rustdoc_js_test(),
             TestMode::CoverageMap => self.run_coverage_map_test(), // see self::coverage
             TestMode::CoverageRun => self.run_coverage_run_test(), // see self::coverage
@@ -1578,7 +1580,8 @@ fn compiler_kind_for_non_aux(&self) -> CompilerKind {
             // Exhaustively match all other suites.
             // Note that some suites never actually use this method, so the
             // return value for those suites is not necessarily meaningful.
-            TestSuite::AssemblyLlvm
+            TestSuite::AssemblyCt
+            | TestSuite::AssemblyLlvm
             | TestSuite::BuildStd
             | TestSuite::CodegenLlvm
             | TestSuite::CodegenUnits
@@ -1802,7 +1805,7 @@ fn make_compile_args(
                 // overridden by `compile-flags`.
                 compiler.arg("-Copt-level=2");
             }
-            TestMode::Assembly | TestMode::Codegen => {
+            TestMode::Assembly | TestMode::AssemblyCt | TestMode::Codegen => {
                 compiler.arg("-Cdebug-assertions=no");
                 // For assembly and codegen tests, we want to use the same order
                 // of the items of a codegen unit as the source order, so that
@@ -19,7 +19,7 @@ pub(super) fn run_assembly_test(&self) {
         }
     }
 
-    fn compile_test_and_save_assembly(&self) -> (ProcRes, Utf8PathBuf) {
+    pub(super) fn compile_test_and_save_assembly(&self) -> (ProcRes, Utf8PathBuf) {
         // This works with both `--emit asm` (as default output name for the assembly)
         // and `ptx-linker` because the latter can write output at requested location.
         let output_path = self.output_base_name().with_extension("s");
@@ -0,0 +1,191 @@
+//! The "assembly-ct" test mode, which checks that the functions named by `//@ ct-no-branches`
+//! contain no conditional branch, so that their running time cannot depend on their arguments,
+//! and that those named by `//@ ct-branches` contain no other conditional branches than the given
+//! number, such as the latches of their loops.
+
+use std::fs;
+
+use crate::runtest::TestCx;
+
+#[cfg(test)]
+mod tests;
+
+impl TestCx<'_> {
+    pub(super) fn run_assembly_ct_test(&self) {
+        if self.props.ct_no_branches.is_empty() && self.props.ct_branches.is_empty() {
+            self.fatal("missing 'ct-no-branches' or 'ct-branches' directive");
+        }
+
+        let target = self.assembly_ct_target();
+        let Some(arch) = BranchArch::from_target(target) else {
+            self.fatal(&format!("assembly-ct tests are not supported on `{target}`"));
+        };
+
+        let (proc_res, output_path) = self.compile_test_and_save_assembly();
+        if !proc_res.status.success() {
+            self.fatal_proc_rec("compilation failed!", &proc_res);
+        }
+        let asm = fs::read_to_string(&output_path)
+            .unwrap_or_else(|e| self.fatal(&format!("failed to read {output_path}: {e}")));
+
+        let expected_branches = self
+            .props
+            .ct_no_branches
+            .iter()
+            .map(|symbol| (symbol, 0))
+            .chain(self.props.ct_branches.iter().map(|(symbol, count)| (symbol, *count)));
+        let mut failed = false;
+        for (symbol, expected) in expected_branches {
+            let Some(body) = function_body(&asm, symbol) else {
+                writeln!(self.stdout, "\nfunction `{symbol}` was not found in {output_path}");
+                failed = true;
+                continue;
+            };
+            let branches: Vec<&str> =
+                body.iter().copied().filter(|line| arch.is_conditional_branch(line)).collect();
+            if branches.len() != expected {
+                if expected == 0 {
+                    writeln!(self.stdout, "\n`{symbol}` contains conditional branches:\n");
+                } else {
+                    writeln!(
+                        self.stdout,
+                        "\n`{symbol}` contains {} conditional branches instead of {expected}:\n",
+                        branches.len()
+                    );
+                }
+                for branch in branches {
+                    writeln!(self.stdout, "    {}", branch.trim());
+                }
+                failed = true;
+            }
+        }
+        if failed {
+            self.fatal_proc_rec("functions contain unexpected conditional branches", &proc_res);
+        }
+    }
+
+    /// The target that the test is compiled for, which `compile-flags` can override.
+    fn assembly_ct_target(&self) -> &str {
+        let mut flags = self.props.compile_flags.iter();
+        while let Some(flag) = flags.next() {
+            if let Some(target) = flag.strip_prefix("--target=") {
+                return target;
+            } else if flag == "--target"
+                && let Some(target) = flags.next()
+            {
+                return target;
+            }
+        }
+        &self.config.target
+    }
+}
+
+/// Returns the instruction lines of the function `symbol`, from its label to the end of the
+/// function, without directives, labels and comments.
+fn function_body<'a>(asm: &'a str, symbol: &str) -> Option<Vec<&'a str>> {
+    let mut lines = asm.lines();
+    // Mach-O prefixes symbols with an underscore.
+    lines.find(|line| {
+        let line = line.trim_end();
+        line.strip_suffix(':')
+            .is_some_and(|label| label == symbol || label.strip_prefix('_') == Some(symbol))
+    })?;
+    let mut body = Vec::new();
+    for line in lines {
+        let trimmed = line.trim();
+        // LLVM ends every function with this comment in its textual assembly, and with a `.size`
+        // directive on ELF targets.
+        if trimmed.ends_with("-- End function") || trimmed.starts_with(".size") {
+            break;
+        }
+        let is_comment = ["#", "//", ";", "@"].iter().any(|c| trimmed.starts_with(c));
+        if trimmed.is_empty() || is_comment || trimmed.starts_with('.') || trimmed.ends_with(':') {
+            continue;
+        }
+        body.push(line);
+    }
+    Some(body)
+}
+
+/// The architectures whose conditional branches are recognized.
+#[derive(Clone, Copy)]
+enum BranchArch {
+    X86,
+    AArch64,
+    Arm,
+    RiscV,
+    LoongArch,
+    PowerPc,
+}
+
+/// The condition codes of ARM and AArch64.
+const ARM_CONDITIONS: &[&str] = &[
+    "eq", "ne", "cs", "hs", "cc", "lo", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le",
+];
+
+/// The conditions of PowerPC's extended branch mnemonics.
+const POWERPC_CONDITIONS: &[&str] =
+    &["lt", "le", "eq", "ge", "gt", "nl", "ne", "ng", "so", "ns", "un", "nu"];
+
+impl BranchArch {
+    fn from_target(target: &str) -> Option<BranchArch> {
+        let arch = target.split('-').next().unwrap_or(target);
+        let arch = match arch {
+            "x86_64" | "i386" | "i586" | "i686" => BranchArch::X86,
+            "aarch64" | "arm64ec" | "arm64_32" | "arm64e" => BranchArch::AArch64,
+            _ if arch.starts_with("arm") || arch.starts_with("thumb") => BranchArch::Arm,
+            _ if arch.starts_with("riscv") => BranchArch::RiscV,
+            _ if arch.starts_with("loongarch") => BranchArch::LoongArch,
+            _ if arch.starts_with("powerpc") => BranchArch::PowerPc,
+            _ => return None,
+        };
+        Some(arch)
+    }
+
+    fn is_conditional_branch(self, line: &str) -> bool {
+        let Some(mnemonic) = line.split_whitespace().next() else {
+            return false;
+        };
+        let mnemonic = mnemonic.to_ascii_lowercase();
+        match self {
+            // Every jump other than `jmp` is conditional, including `jecxz` and `loop`.
+            BranchArch::X86 => {
+                (mnemonic.starts_with('j') && !mnemonic.starts_with("jmp"))
+                    || mnemonic.starts_with("loop")
+            }
+            BranchArch::AArch64 => {
+                mnemonic.starts_with("b.")
+                    || mnemonic.starts_with("bc.")
+                    || ["cbz", "cbnz", "tbz", "tbnz"].contains(&mnemonic.as_str())
+            }
+            BranchArch::Arm => {
+                let mnemonic = mnemonic.trim_end_matches(".w").trim_end_matches(".n");
+                ["cbz", "cbnz"].contains(&mnemonic)
+                    || ["blx", "bx", "bl", "b"].iter().any(|branch| {
+                        mnemonic
+                            .strip_prefix(branch)
+                            .is_some_and(|condition| ARM_CONDITIONS.contains(&condition))
+                    })
+            }
+            BranchArch::RiscV => [
+                "beq", "bne", "blt", "bge", "bltu", "bgeu", "beqz", "bnez", "blez", "bgez", "bltz",
+                "bgtz", "bgt", "ble", "bgtu", "bleu", "c.beqz", "c.bnez",
+            ]
+            .contains(&mnemonic.as_str()),
+            BranchArch::LoongArch => {
+                ["beq", "bne", "blt", "bge", "bltu", "bgeu", "beqz", "bnez", "bceqz", "bcnez"]
+                    .contains(&mnemonic.as_str())
+            }
+            BranchArch::PowerPc => {
+                // The static prediction hints are not part of the condition.
+                let mnemonic = mnemonic.trim_end_matches(['+', '-']);
+                (mnemonic.starts_with("bc") && !mnemonic.starts_with("bctr"))
+                    || mnemonic.starts_with("bdnz")
+                    || mnemonic.starts_with("bdz")
+                    || mnemonic.strip_prefix('b').is_some_and(|rest| {
+                        POWERPC_CONDITIONS.iter().any(|condition| rest.starts_with(condition))
+                    })
+            }
+        }
+    }
+}
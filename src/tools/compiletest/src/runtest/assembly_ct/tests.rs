@@ -0,0 +1,125 @@
+use super::*;
+
+fn conditional_branches(arch: BranchArch, lines: &[&str]) -> Vec<String> {
+    lines
+        .iter()
+        .filter(|line| arch.is_conditional_branch(line))
+        .map(|line| line.trim().to_string())
+        .collect::<Vec<_>>()
+}
+
+#[test]
+fn arm_branches() {
+    let lines = [
+        "\tbls\t.LBB0_2",
+        "\tbne\t.LBB0_1",
+        "\tbne.w\t.LBB0_1",
+        "\tcbz\tr0, .LBB0_3",
+        "\tbl\tfoo",
+        "\tblx\tr3",
+        "\tbx\tlr",
+        "\tb\t.LBB0_4",
+        "\tb.w\t.LBB0_4",
+    ];
+    assert_eq!(
+        conditional_branches(BranchArch::Arm, &lines),
+        ["bls\t.LBB0_2", "bne\t.LBB0_1", "bne.w\t.LBB0_1", "cbz\tr0, .LBB0_3"]
+    );
+}
+
+#[test]
+fn aarch64_branches() {
+    let lines = ["\tb.ne\t.LBB0_2", "\tcbnz\tx0, .LBB0_1", "\tb\t.LBB0_3", "\tbl\tfoo", "\tret"];
+    assert_eq!(
+        conditional_branches(BranchArch::AArch64, &lines),
+        ["b.ne\t.LBB0_2", "cbnz\tx0, .LBB0_1"]
+    );
+}
+
+#[test]
+fn powerpc_branches() {
+    let lines = [
+        "\tblt\t0, .LBB0_2",
+        "\tbne-\t0, .LBB0_1",
+        "\tbdnz\t.LBB0_1",
+        "\tbc\t12, 2, .LBB0_3",
+        "\tblr",
+        "\tbl\tfoo",
+        "\tbctrl",
+        "\tb\t.LBB0_4",
+    ];
+    assert_eq!(
+        conditional_branches(BranchArch::PowerPc, &lines),
+        ["blt\t0, .LBB0_2", "bne-\t0, .LBB0_1", "bdnz\t.LBB0_1", "bc\t12, 2, .LBB0_3"]
+    );
+}
+
+#[test]
+fn riscv_branches() {
+    let lines = [
+        "\tbeqz\ta0, .LBB0_2",
+        "\tbltu\ta0, a1, .LBB0_1",
+        "\tj\t.LBB0_3",
+        "\tjal\tra, foo",
+        "\tjalr\ta0",
+        "\tret",
+    ];
+    assert_eq!(
+        conditional_branches(BranchArch::RiscV, &lines),
+        ["beqz\ta0, .LBB0_2", "bltu\ta0, a1, .LBB0_1"]
+    );
+}
+
+#[test]
+fn x86_branches() {
+    let lines = ["\tjne\t.LBB0_2", "\tjecxz\t.LBB0_1", "\tjmp\t.LBB0_3", "\tcallq\tfoo", "\tretq"];
+    assert_eq!(conditional_branches(BranchArch::X86, &lines), ["jne\t.LBB0_2", "jecxz\t.LBB0_1"]);
+}
+
+#[test]
+fn labels_and_comments_are_not_branches() {
+    let asm = "\
+\t.globl\tselect
+select:
+\t.cfi_startproc
+# %bb.0:
+\ttestl\t%edi, %edi
+\tjne\t.LBB0_2
+# %bb.1:                                # jump over the move
+\tmovl\t%edx, %esi
+jump_target:
+.LBB0_2:
+\tmovl\t%esi, %eax
+\tretq
+.Lfunc_end0:
+\t.size\tselect, .Lfunc_end0-select
+\tjne\t.LBB1_1
+";
+    let body = function_body(asm, "select").unwrap();
+    assert_eq!(
+        body,
+        [
+            "\ttestl\t%edi, %edi",
+            "\tjne\t.LBB0_2",
+            "\tmovl\t%edx, %esi",
+            "\tmovl\t%esi, %eax",
+            "\tretq"
+        ]
+    );
+    assert_eq!(conditional_branches(BranchArch::X86, &body), ["jne\t.LBB0_2"]);
+
+    // ARM and AArch64 comments start with `@` and `//`.
+    let asm = "\
+_select:
+@ %bb.0:
+\tcmp\tr0, #0
+// jump if zero
+\tbx\tlr
+\t@ -- End function
+\tbeq\t.LBB1_1
+";
+    let body = function_body(asm, "select").unwrap();
+    assert_eq!(body, ["\tcmp\tr0, #0", "\tbx\tlr"]);
+    assert!(conditional_branches(BranchArch::Arm, &body).is_empty());
+    assert!(function_body(asm, "missing").is_none());
+}
@@ -0,0 +1,176 @@
+//! Analyzes the timings written by `#[ct_test]` functions, see the README.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context as _, bail};
+
+#[cfg(test)]
+mod tests;
+
+/// Number of percentiles at which the measurements are cropped, as in libtest.
+const CROPS: usize = 100;
+
+enum InputClass {
+    Fixed,
+    Random,
+}
+
+/// The timings of a test, in cycles, for the fixed and the random inputs.
+#[derive(Default)]
+struct Timings {
+    fixed: Vec<f64>,
+    random: Vec<f64>,
+}
+
+impl Timings {
+    fn read(path: &PathBuf) -> anyhow::Result<Timings> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("couldn't read {}", path.display()))?;
+        let mut timings = Timings::default();
+        let mut lines = contents.lines().enumerate();
+        if lines.next().map(|(_, header)| header) != Some("class,cycles") {
+            bail!("{}: expected a `class,cycles` header", path.display());
+        }
+        for (i, line) in lines {
+            let sample = line.split_once(',').and_then(|(class, cycles)| {
+                let class = match class {
+                    "fixed" => InputClass::Fixed,
+                    "random" => InputClass::Random,
+                    _ => return None,
+                };
+                Some((class, cycles.parse::<f64>().ok()?))
+            });
+            match sample {
+                Some((InputClass::Fixed, cycles)) => timings.fixed.push(cycles),
+                Some((InputClass::Random, cycles)) => timings.random.push(cycles),
+                None => bail!(
+                    "{}:{}: expected `fixed,<cycles>` or `random,<cycles>`",
+                    path.display(),
+                    i + 1
+                ),
+            }
+        }
+        Ok(timings)
+    }
+
+    fn extend(&mut self, other: Timings) {
+        self.fixed.extend(other.fixed);
+        self.random.extend(other.random);
+    }
+
+    /// Prints the statistics of the timings, and returns the largest absolute t statistic of the
+    /// measurements and of the measurements below the cropping percentiles.
+    fn report(&self, name: &str) -> f64 {
+        let max = max_t(&self.fixed, &self.random);
+        println!("{name}:");
+        println!(
+            "    fixed:  {} measurements, mean {:.1} cycles",
+            self.fixed.len(),
+            mean(&self.fixed)
+        );
+        println!(
+            "    random: {} measurements, mean {:.1} cycles",
+            self.random.len(),
+            mean(&self.random)
+        );
+        match max.1 {
+            Some(pct) => println!("    max |t| = {:.2}, below the {pct:.2}th percentile", max.0),
+            None => println!("    max |t| = {:.2}, over all the measurements", max.0),
+        }
+        max.0
+    }
+}
+
+fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// The sample variance.
+fn var(samples: &[f64]) -> f64 {
+    let mean = mean(samples);
+    samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (samples.len() - 1) as f64
+}
+
+/// Welch's t statistic of two samples, which is large when their means differ.
+///
+/// The same as `welch_t` in `library/test/src/ct.rs`, which measured the timings.
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let diff = mean(a) - mean(b);
+    let deviation = (var(a) / a.len() as f64 + var(b) / b.len() as f64).sqrt();
+    if deviation == 0.0 { if diff == 0.0 { 0.0 } else { f64::INFINITY } } else { diff / deviation }
+}
+
+/// Returns the largest absolute t statistic of the measurements and of the measurements below
+/// the cropping percentiles, with the percentile it was found below, if any.
+///
+/// The same as `max_t` in `library/test/src/ct.rs`, which measured the timings.
+fn max_t(fixed: &[f64], random: &[f64]) -> (f64, Option<f64>) {
+    let mut sorted: Vec<f64> = fixed.iter().chain(random).copied().collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mut max = (welch_t(fixed, random).abs(), None);
+    for i in 0..CROPS {
+        // The percentiles get closer together towards 100, like in dudect.
+        let pct = 1.0 - 0.5f64.powf(10.0 * (i + 1) as f64 / CROPS as f64);
+        let limit = sorted[((sorted.len() - 1) as f64 * pct) as usize];
+        let fixed: Vec<f64> = fixed.iter().copied().filter(|&t| t <= limit).collect();
+        let random: Vec<f64> = random.iter().copied().filter(|&t| t <= limit).collect();
+        if fixed.len() > 1 && random.len() > 1 {
+            let t = welch_t(&fixed, &random).abs();
+            if t > max.0 {
+                max = (t, Some(pct * 100.0));
+            }
+        }
+    }
+    max
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let mut threshold = 10.0;
+    let mut merge = false;
+    let mut paths = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let value = args.next().context("`--threshold` requires a value")?;
+                threshold =
+                    value.parse().with_context(|| format!("invalid threshold `{value}`"))?;
+            }
+            "--merge" => merge = true,
+            "-h" | "--help" => {
+                println!("usage: ct-timing [--threshold <t>] [--merge] <file.csv>...");
+                return Ok(ExitCode::SUCCESS);
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        bail!("no timings file specified");
+    }
+
+    let mut tests = Vec::new();
+    for path in &paths {
+        let timings = Timings::read(path)?;
+        if timings.fixed.len() < 2 || timings.random.len() < 2 {
+            bail!("{}: not enough measurements of each class", path.display());
+        }
+        tests.push((path.display().to_string(), timings));
+    }
+    if merge {
+        let mut merged = Timings::default();
+        for (_, timings) in tests.drain(..) {
+            merged.extend(timings);
+        }
+        tests.push((format!("{} merged files", paths.len()), merged));
+    }
+
+    let mut leaks = 0;
+    for (name, timings) in &tests {
+        if timings.report(name) > threshold {
+            println!("    the running time depends on the input");
+            leaks += 1;
+        }
+    }
+    Ok(if leaks > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
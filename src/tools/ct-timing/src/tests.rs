@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn test_welch_t() {
+    let a = [1.0, 2.0, 3.0, 4.0];
+    assert_eq!(welch_t(&a, &a), 0.0);
+    assert_eq!(welch_t(&[2.0, 2.0], &[2.0, 2.0]), 0.0);
+    assert_eq!(welch_t(&[3.0, 3.0], &[2.0, 2.0]), f64::INFINITY);
+    // The means differ by 10, and the standard error of the difference is sqrt(2 * 5/3 / 4).
+    let b = [11.0, 12.0, 13.0, 14.0];
+    assert!((welch_t(&b, &a) - 10.0 / (5.0f64 / 6.0).sqrt()).abs() < 1e-9);
+    assert!(welch_t(&a, &b) < 0.0);
+}
+
+#[test]
+fn test_max_t_without_difference() {
+    let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+    assert_eq!(max_t(&a, &a), (0.0, None));
+}
+
+#[test]
+fn test_max_t_crops_the_tail() {
+    // Over all the measurements, the outlier hides the difference: |t| is about 1.005.
+    let fixed = [1.0, 2.0, 3.0, 4.0];
+    let random = [2.0, 3.0, 4.0, 5.0, 1000.0];
+    assert!((welch_t(&fixed, &random).abs() - 1.005).abs() < 1e-3);
+    // Below the 87.5th percentile, at 5 cycles, the outlier is cropped and the means differ by 1.
+    let (t, pct) = max_t(&fixed, &random);
+    assert!((t - 1.0 / (5.0f64 / 6.0).sqrt()).abs() < 1e-9);
+    assert!((pct.unwrap() - 87.5).abs() < 1e-9);
+}
@@ -626,6 +626,8 @@ fn main() -> ExitCode {
             miri_config.tracked_alloc_ids.extend(ids.into_iter().map(miri::AllocId));
         } else if arg == "-Zmiri-track-alloc-accesses" {
             miri_config.track_alloc_accesses = true;
+        } else if arg == "-Zmiri-track-secrets" {
+            miri_config.track_secrets = true;
         } else if let Some(param) = arg.strip_prefix("-Zmiri-address-reuse-rate=") {
             miri_config.address_reuse_rate = parse_rate(param)
                 .unwrap_or_else(|err| fatal_error!("-Zmiri-address-reuse-rate {err}"));
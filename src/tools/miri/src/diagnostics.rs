@@ -56,6 +56,8 @@ pub enum TerminationInfo {
         retag_explain: bool,
     },
     UnsupportedForeignItem(String),
+    /// A secret value affected control flow or a memory access, under `-Zmiri-track-secrets`.
+    ConstantTimeViolation(String),
 }
 
 pub struct RacingOp {
@@ -96,6 +98,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                     op2.thread_info
                 ),
             UnsupportedForeignItem(msg) => write!(f, "{msg}"),
+            ConstantTimeViolation(msg) => write!(f, "{msg}"),
         }
     }
 }
@@ -248,6 +251,7 @@ pub fn report_result<'tcx>(
                 Some("unsupported operation"),
             StackedBorrowsUb { .. } | TreeBorrowsUb { .. } | DataRace { .. } =>
                 Some("Undefined Behavior"),
+            ConstantTimeViolation(_) => Some("constant-time violation"),
             LocalDeadlock => {
                 labels.push(format!("thread got stuck here"));
                 None
@@ -329,6 +333,11 @@ pub fn report_result<'tcx>(
                 ],
             SymbolShimClashing { link_name, span } =>
                 vec![note_span!(*span, "the `{link_name}` symbol is defined here")],
+            ConstantTimeViolation(_) =>
+                vec![
+                    note!("this value is derived from a `Choice` or a `Secret`, so the timing of this operation can reveal it"),
+                    note!("if the value is public, declassify it first with `Choice::declassify`; otherwise, use the `std::select` operations instead"),
+                ],
             Int2PtrWithStrictProvenance =>
                 vec![note!("use Strict Provenance APIs (https://doc.rust-lang.org/nightly/std/ptr/index.html#strict-provenance, https://crates.io/crates/sptr) instead")],
             DataRace { op1, extra, retag_explain, .. } => {
@@ -64,6 +64,8 @@ pub struct MiriConfig {
     pub tracked_alloc_ids: FxHashSet<AllocId>,
     /// For the tracked alloc ids, also report read/write accesses.
     pub track_alloc_accesses: bool,
+    /// Whether to report branches, indexing and memory accesses that depend on secret values.
+    pub track_secrets: bool,
     /// Determine if data race detection should be enabled.
     pub data_race_detector: bool,
     /// Determine if weak memory emulation should be enabled. Requires data race detection to be enabled.
@@ -135,6 +137,7 @@ fn default() -> MiriConfig {
             tracked_pointer_tags: FxHashSet::default(),
             tracked_alloc_ids: FxHashSet::default(),
             track_alloc_accesses: false,
+            track_secrets: false,
             data_race_detector: true,
             weak_memory_emulation: true,
             genmc_config: None,
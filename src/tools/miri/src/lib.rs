@@ -86,6 +86,7 @@
 mod math;
 mod operator;
 mod provenance_gc;
+mod secrets;
 mod shims;
 
 // Establish a "crate-wide prelude": we often import `crate::*`.
@@ -150,6 +151,7 @@ pub mod native_lib {
 };
 pub use crate::operator::EvalContextExt as _;
 pub use crate::provenance_gc::{EvalContextExt as _, LiveAllocs, VisitProvenance, VisitWith};
+pub use crate::secrets::EvalContextExt as _;
 pub use crate::shims::EmulateItemResult;
 pub use crate::shims::env::{EnvVars, EvalContextExt as _};
 pub use crate::shims::foreign_items::{DynSym, EvalContextExt as _};
@@ -148,26 +148,42 @@ pub struct FrameExtra<'tcx> {
 
     /// Data race detector per-frame data.
     pub data_race: Option<data_race::FrameState>,
+
+    /// Secrecy of the locals, if `-Zmiri-track-secrets` is enabled.
+    pub secrets: Option<secrets::FrameState>,
 }
 
 impl<'tcx> std::fmt::Debug for FrameExtra<'tcx> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Omitting `timing`, it does not support `Debug`.
-        let FrameExtra { borrow_tracker, catch_unwind, timing: _, user_relevance, data_race } =
-            self;
+        let FrameExtra {
+            borrow_tracker,
+            catch_unwind,
+            timing: _,
+            user_relevance,
+            data_race,
+            secrets,
+        } = self;
         f.debug_struct("FrameData")
             .field("borrow_tracker", borrow_tracker)
             .field("catch_unwind", catch_unwind)
             .field("user_relevance", user_relevance)
             .field("data_race", data_race)
+            .field("secrets", secrets)
             .finish()
     }
 }
 
 impl VisitProvenance for FrameExtra<'_> {
     fn visit_provenance(&self, visit: &mut VisitWith<'_>) {
-        let FrameExtra { catch_unwind, borrow_tracker, timing: _, user_relevance: _, data_race: _ } =
-            self;
+        let FrameExtra {
+            catch_unwind,
+            borrow_tracker,
+            timing: _,
+            user_relevance: _,
+            data_race: _,
+            secrets: _,
+        } = self;
 
         catch_unwind.visit_provenance(visit);
         borrow_tracker.visit_provenance(visit);
@@ -390,6 +406,8 @@ pub struct AllocExtra<'tcx> {
     /// freed.
     /// This maps offsets to synchronization-primitive-specific data.
     pub sync_objs: BTreeMap<Size, Box<dyn SyncObj>>,
+    /// Secrecy of the bytes, if `-Zmiri-track-secrets` is enabled.
+    pub secrets: Option<secrets::AllocState>,
 }
 
 // We need a `Clone` impl because the machine passes `Allocation` through `Cow`...
@@ -402,7 +420,7 @@ fn clone(&self) -> Self {
 
 impl VisitProvenance for AllocExtra<'_> {
     fn visit_provenance(&self, visit: &mut VisitWith<'_>) {
-        let AllocExtra { borrow_tracker, data_race, backtrace: _, sync_objs: _ } = self;
+        let AllocExtra { borrow_tracker, data_race, backtrace: _, sync_objs: _, secrets: _ } = self;
 
         borrow_tracker.visit_provenance(visit);
         data_race.visit_provenance(visit);
@@ -498,6 +516,9 @@ pub struct MiriMachine<'tcx> {
     /// Ptr-int-cast module global data.
     pub alloc_addresses: alloc_addresses::GlobalState,
 
+    /// Global data for tracking secret values, if `-Zmiri-track-secrets` is enabled.
+    pub(crate) secrets: Option<secrets::GlobalState>,
+
     /// Environment variables.
     pub(crate) env_vars: EnvVars<'tcx>,
 
@@ -738,6 +759,7 @@ pub(crate) fn new(
             borrow_tracker,
             data_race,
             alloc_addresses,
+            secrets: config.track_secrets.then(secrets::GlobalState::default),
             // `env_vars` depends on a full interpreter so we cannot properly initialize it yet.
             env_vars: EnvVars::default(),
             main_fn_ret_place: None,
@@ -982,11 +1004,14 @@ fn init_allocation(
                 .insert(id, (ecx.machine.current_user_relevant_span(), None));
         }
 
+        let secrets = ecx.machine.secrets.as_ref().map(|_| secrets::AllocState::new(size));
+
         interp_ok(AllocExtra {
             borrow_tracker,
             data_race,
             backtrace,
             sync_objs: BTreeMap::default(),
+            secrets,
         })
     }
 }
@@ -1008,6 +1033,7 @@ fn visit_provenance(&self, visit: &mut VisitWith<'_>) {
             borrow_tracker,
             data_race,
             alloc_addresses,
+            secrets: _,
             fds,
             epoll_interests:_,
             tcx: _,
@@ -1537,6 +1563,9 @@ fn before_memory_read(
         if let Some(borrow_tracker) = &alloc_extra.borrow_tracker {
             borrow_tracker.before_memory_read(alloc_id, prov_extra, range, machine)?;
         }
+        if let Some(secrets) = &alloc_extra.secrets {
+            secrets.before_memory_read(range, machine);
+        }
         // Check if there are any sync objects that would like to prevent reading this memory.
         for (_offset, obj) in alloc_extra.sync_objs.range(range.start..range.end()) {
             obj.on_access(concurrency::sync::AccessKind::Read)?;
@@ -1582,6 +1611,9 @@ fn before_memory_write(
         if let Some(borrow_tracker) = &mut alloc_extra.borrow_tracker {
             borrow_tracker.before_memory_write(alloc_id, prov_extra, range, machine)?;
         }
+        if let Some(secrets) = &mut alloc_extra.secrets {
+            secrets.before_memory_write(range, machine);
+        }
         // Delete sync objects that don't like writes.
         // Most of the time, we can just skip this.
         if !alloc_extra.sync_objs.is_empty() {
@@ -1723,6 +1755,7 @@ fn init_frame(
                 .data_race
                 .as_vclocks_ref()
                 .map(|_| data_race::FrameState::default()),
+            secrets: ecx.machine.secrets.as_ref().map(|_| secrets::FrameState::default()),
         };
 
         interp_ok(frame.with_extra(extra))
@@ -1740,7 +1773,17 @@ fn stack_mut<'a>(
         ecx.active_thread_stack_mut()
     }
 
+    fn before_statement(ecx: &mut InterpCx<'tcx, Self>) -> InterpResult<'tcx> {
+        if ecx.machine.secrets.is_some() {
+            ecx.secrets_before_step()?;
+        }
+        interp_ok(())
+    }
+
     fn before_terminator(ecx: &mut InterpCx<'tcx, Self>) -> InterpResult<'tcx> {
+        if ecx.machine.secrets.is_some() {
+            ecx.secrets_before_step()?;
+        }
         ecx.machine.basic_block_count += 1u64; // a u64 that is only incremented by 1 will "never" overflow
         ecx.machine.since_gc += 1;
         // Possibly report our progress. This will point at the terminator we are about to execute.
@@ -1779,6 +1822,9 @@ fn after_stack_push(ecx: &mut InterpCx<'tcx, Self>) -> InterpResult<'tcx> {
             let stack_len = ecx.active_thread_stack().len();
             ecx.active_thread_mut().set_top_user_relevant_frame(stack_len - 1);
         }
+        if ecx.machine.secrets.is_some() {
+            ecx.secrets_after_stack_push();
+        }
         interp_ok(())
     }
 
@@ -1842,6 +1888,9 @@ fn after_local_read(
             let _trace = enter_trace_span!(data_race::after_local_read);
             data_race.local_read(local, &ecx.machine);
         }
+        if frame.extra.secrets.is_some() {
+            ecx.secrets_after_local_read(frame, local);
+        }
         interp_ok(())
     }
 
@@ -1854,6 +1903,9 @@ fn after_local_write(
             let _trace = enter_trace_span!(data_race::after_local_write);
             data_race.local_write(local, storage_live, &ecx.machine);
         }
+        if ecx.machine.secrets.is_some() {
+            ecx.secrets_after_local_write(local, storage_live);
+        }
         interp_ok(())
     }
 
@@ -1881,6 +1933,9 @@ fn after_local_moved_to_memory(
                 machine,
             );
         }
+        if ecx.machine.secrets.is_some() {
+            ecx.secrets_after_local_moved_to_memory(local, alloc_id)?;
+        }
         interp_ok(())
     }
 
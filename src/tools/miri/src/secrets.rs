@@ -0,0 +1,355 @@
+//! Tracking of secret values, for `-Zmiri-track-secrets`.
+//!
+//! Values created as a `Choice` or a `Secret` are secret, and so is every value computed from a
+//! secret one. Miri reports an error when the program branches on a secret value, indexes with
+//! one, or accesses memory through a pointer computed from one, as the timing of these operations
+//! can reveal the secret.
+//!
+//! The tracking is an approximation at the granularity of a step, that is, of a statement or a
+//! terminator: everything a step writes is secret if anything it read was. Secrecy is tracked per
+//! byte in memory, but per local for the locals that are not in memory. The arguments of a call
+//! are tracked one by one, so that a secret argument does not make the others secret. What
+//! `Choice::declassify`, `CtOption::into_option` and `CtResult::into_result` return is public.
+
+use std::cell::{Cell, RefCell};
+
+use rustc_data_structures::either::Either;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir;
+use rustc_middle::mir::visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::sym;
+
+use crate::*;
+
+#[derive(Debug, Default)]
+pub struct GlobalState {
+    /// Whether the current step has read a secret value.
+    read_secret: Cell<bool>,
+    /// Whether the current step creates a `Choice` or a `Secret`.
+    creates_secret: Cell<bool>,
+    /// Whether the current step is part of a declassification, which makes what it writes public.
+    declassifies: Cell<bool>,
+    /// The secrecy of the arguments of the call that the current step performs.
+    pending_call: RefCell<Option<PendingCall>>,
+}
+
+#[derive(Debug)]
+struct PendingCall {
+    thread: ThreadId,
+    /// The index that the frame of the callee will have in the stack of `thread`.
+    frame_idx: usize,
+    args: Vec<bool>,
+}
+
+impl GlobalState {
+    /// Whether the values written by the current step are secret.
+    fn writes_secret(&self) -> bool {
+        !self.declassifies.get() && (self.read_secret.get() || self.creates_secret.get())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FrameState {
+    /// The locals that hold a secret value and are not in memory.
+    locals: FxHashSet<mir::Local>,
+    /// The secrecy of the arguments, while they are passed to this frame.
+    args: Option<Vec<bool>>,
+}
+
+impl FrameState {
+    /// Whether the value that is written to the given argument while it is passed is secret.
+    fn arg_is_secret(&self, local: mir::Local, arg_count: usize) -> Option<bool> {
+        let args = self.args.as_ref()?;
+        let index = local.as_usize().checked_sub(1).filter(|&index| index < arg_count)?;
+        // The arguments of "rust-call" functions are untupled, so they do not match the operands
+        // of the call one by one.
+        Some(if args.len() == arg_count { args[index] } else { args.contains(&true) })
+    }
+}
+
+#[derive(Debug)]
+pub struct AllocState(DedupRangeMap<bool>);
+
+impl AllocState {
+    pub fn new(size: Size) -> Self {
+        AllocState(DedupRangeMap::new(size, false))
+    }
+
+    fn is_secret(&self, range: AllocRange) -> bool {
+        range.end() <= self.0.size() && self.0.iter(range.start, range.size).any(|(_, &s)| s)
+    }
+
+    fn set_all(&mut self, secret: bool) {
+        for (_, s) in self.0.iter_mut_all() {
+            *s = secret;
+        }
+    }
+
+    pub fn before_memory_read(&self, range: AllocRange, machine: &MiriMachine<'_>) {
+        if self.is_secret(range) {
+            machine.secrets.as_ref().unwrap().read_secret.set(true);
+        }
+    }
+
+    pub fn before_memory_write(&mut self, range: AllocRange, machine: &MiriMachine<'_>) {
+        let secrets = machine.secrets.as_ref().unwrap();
+        // While the arguments are passed to a frame, the only writes are those of the arguments.
+        let args = machine
+            .threads
+            .active_thread_stack()
+            .last()
+            .and_then(|frame| frame.extra.secrets.as_ref()?.args.as_ref());
+        let secret = match args {
+            Some(args) => args.contains(&true),
+            None => secrets.writes_secret(),
+        };
+        for (_, s) in self.0.iter_mut(range.start, range.size) {
+            *s = secret;
+        }
+    }
+}
+
+/// Whether the function is one whose return value is declassified.
+fn is_declassification(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    matches!(
+        tcx.get_diagnostic_name(def_id),
+        Some(sym::choice_declassify | sym::ct_option_into_option | sym::ct_result_into_result)
+    )
+}
+
+/// Collects the places that a statement or terminator accesses.
+struct AccessedPlaces<'tcx>(Vec<mir::Place<'tcx>>);
+
+impl<'tcx> Visitor<'tcx> for AccessedPlaces<'tcx> {
+    fn visit_place(&mut self, place: &mir::Place<'tcx>, context: PlaceContext, _: mir::Location) {
+        // Borrows and retags only compute the address of the place.
+        let accessed = context.is_use()
+            && !context.may_observe_address()
+            && !matches!(
+                context,
+                PlaceContext::MutatingUse(MutatingUseContext::Retag)
+                    | PlaceContext::NonMutatingUse(NonMutatingUseContext::PlaceMention)
+            );
+        if accessed {
+            self.0.push(*place);
+        }
+    }
+}
+
+impl<'tcx> EvalContextExt<'tcx> for crate::MiriInterpCx<'tcx> {}
+pub trait EvalContextExt<'tcx>: crate::MiriInterpCxExt<'tcx> {
+    /// Checks that the statement or terminator that is about to be executed does not branch on,
+    /// index with or dereference a secret value, and starts tracking what it reads.
+    fn secrets_before_step(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let frame = this.frame();
+        let Either::Left(loc) = frame.current_loc() else {
+            return interp_ok(());
+        };
+        let body = frame.body();
+        let step = body.stmt_at(loc);
+
+        // Inlining may have moved the code of a declassification into its caller.
+        let mut declassifies = is_declassification(*this.tcx, frame.instance().def_id());
+        let mut scope = Some(step.either(|stmt| stmt.source_info, |term| term.source_info).scope);
+        while let Some(current) = scope
+            && !declassifies
+        {
+            let data = &body.source_scopes[current];
+            declassifies = data
+                .inlined
+                .is_some_and(|(callee, _)| is_declassification(*this.tcx, callee.def_id()));
+            scope = data.parent_scope;
+        }
+
+        if !declassifies {
+            let mut places = AccessedPlaces(Vec::new());
+            match step {
+                Either::Left(stmt) => places.visit_statement(stmt, loc),
+                Either::Right(term) => places.visit_terminator(term, loc),
+            }
+            for place in places.0 {
+                for (base, elem) in place.iter_projections() {
+                    let leak = match elem {
+                        mir::ProjectionElem::Index(index)
+                            if this.secrets_place_is_secret(index.into())? =>
+                            "indexing with a secret value",
+                        mir::ProjectionElem::Deref
+                            if this.secrets_place_is_secret(base.to_place(*this.tcx))? =>
+                            "accessing memory at an address that depends on a secret value",
+                        _ => continue,
+                    };
+                    throw_machine_stop!(TerminationInfo::ConstantTimeViolation(leak.to_owned()));
+                }
+            }
+            if let Either::Right(term) = step {
+                let leak = match &term.kind {
+                    mir::TerminatorKind::SwitchInt { discr, .. }
+                        if this.secrets_operand_is_secret(discr)? =>
+                        Some("branching on a secret value"),
+                    mir::TerminatorKind::Assert { cond, msg, .. }
+                        if this.secrets_operand_is_secret(cond)? =>
+                        Some(if matches!(**msg, mir::AssertKind::BoundsCheck { .. }) {
+                            "indexing with a secret value"
+                        } else {
+                            "branching on a secret value"
+                        }),
+                    _ => None,
+                };
+                if let Some(leak) = leak {
+                    throw_machine_stop!(TerminationInfo::ConstantTimeViolation(leak.to_owned()));
+                }
+            }
+        }
+
+        // Record the secrecy of the arguments of a call, for the frame of the callee.
+        let call = match step {
+            Either::Right(term) =>
+                match &term.kind {
+                    mir::TerminatorKind::Call { args, .. } => Some((args, this.frame_idx() + 1)),
+                    // A tail call replaces the frame of the caller.
+                    mir::TerminatorKind::TailCall { args, .. } => Some((args, this.frame_idx())),
+                    _ => None,
+                },
+            Either::Left(_) => None,
+        };
+        let pending_call = match call {
+            Some((args, frame_idx)) => {
+                let mut secret_args = Vec::with_capacity(args.len());
+                for arg in args {
+                    secret_args.push(this.secrets_operand_is_secret(&arg.node)?);
+                }
+                Some(PendingCall { thread: this.active_thread(), frame_idx, args: secret_args })
+            }
+            None => None,
+        };
+
+        // Writing a whole local replaces its secrecy. (The secrecy of memory is replaced by the
+        // write itself.)
+        let (dest, creates_secret) = match step {
+            Either::Left(mir::Statement { kind: mir::StatementKind::Assign(assign), .. }) => {
+                let (place, rvalue) = &**assign;
+                let creates_secret = match rvalue {
+                    mir::Rvalue::Aggregate(kind, _) =>
+                        match **kind {
+                            mir::AggregateKind::Adt(def_id, ..) =>
+                                matches!(
+                                    this.tcx.get_diagnostic_name(def_id),
+                                    Some(sym::Choice | sym::Secret)
+                                ),
+                            _ => false,
+                        },
+                    _ => false,
+                };
+                (Some(*place), creates_secret)
+            }
+            Either::Right(mir::Terminator {
+                kind: mir::TerminatorKind::Call { destination, .. },
+                ..
+            }) => (Some(*destination), false),
+            _ => (None, false),
+        };
+        let state = this.frame_mut().extra.secrets.as_mut().unwrap();
+        state.args = None;
+        if let Some(local) = dest.and_then(|dest| dest.as_local()) {
+            state.locals.remove(&local);
+        }
+
+        // The checks above may have read secret values themselves.
+        let secrets = this.machine.secrets.as_ref().unwrap();
+        secrets.read_secret.set(false);
+        secrets.creates_secret.set(creates_secret);
+        secrets.declassifies.set(declassifies);
+        secrets.pending_call.replace(pending_call);
+        interp_ok(())
+    }
+
+    fn secrets_after_stack_push(&mut self) {
+        let this = self.eval_context_mut();
+        let pending_call = this.machine.secrets.as_ref().unwrap().pending_call.take();
+        let thread = this.active_thread();
+        let frame_idx = this.frame_idx();
+        // The frames that are not pushed by a call of the program, such as those of drop glue and
+        // of new threads, get public arguments.
+        let args = pending_call
+            .filter(|call| call.thread == thread && call.frame_idx == frame_idx)
+            .map_or_else(Vec::new, |call| call.args);
+        this.frame_mut().extra.secrets.as_mut().unwrap().args = Some(args);
+    }
+
+    fn secrets_after_local_read(
+        &self,
+        frame: &Frame<'tcx, Provenance, FrameExtra<'tcx>>,
+        local: mir::Local,
+    ) {
+        let this = self.eval_context_ref();
+        if frame.extra.secrets.as_ref().unwrap().locals.contains(&local) {
+            this.machine.secrets.as_ref().unwrap().read_secret.set(true);
+        }
+    }
+
+    fn secrets_after_local_write(&mut self, local: mir::Local, storage_live: bool) {
+        let this = self.eval_context_mut();
+        let secrets = this.machine.secrets.as_ref().unwrap();
+        let frame = this.machine.threads.active_thread_stack_mut().last_mut().unwrap();
+        let arg_count = frame.body().arg_count;
+        let state = frame.extra.secrets.as_mut().unwrap();
+        let secret = if storage_live {
+            false
+        } else {
+            state.arg_is_secret(local, arg_count).unwrap_or_else(|| secrets.writes_secret())
+        };
+        if secret {
+            state.locals.insert(local);
+        } else if storage_live {
+            state.locals.remove(&local);
+        }
+    }
+
+    fn secrets_after_local_moved_to_memory(
+        &mut self,
+        local: mir::Local,
+        alloc_id: AllocId,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let secret = this.frame_mut().extra.secrets.as_mut().unwrap().locals.remove(&local);
+        let (alloc_extra, _machine) = this.get_alloc_extra_mut(alloc_id)?;
+        alloc_extra.secrets.as_mut().unwrap().set_all(secret);
+        interp_ok(())
+    }
+
+    /// Whether the value of a place of the current frame is secret.
+    fn secrets_place_is_secret(&self, place: mir::Place<'tcx>) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_ref();
+        let place = this.eval_place(place)?;
+        match place.as_mplace_or_local() {
+            Either::Left(mplace) => {
+                let Some((size, _align)) = this.size_and_align_of_val(&mplace)? else {
+                    return interp_ok(false);
+                };
+                let Ok((alloc_id, offset, _)) =
+                    this.ptr_try_get_alloc_id(mplace.ptr(), size.bytes().try_into().unwrap())
+                else {
+                    return interp_ok(false);
+                };
+                let alloc_extra = this.get_alloc_extra(alloc_id)?;
+                interp_ok(
+                    alloc_extra.secrets.as_ref().unwrap().is_secret(alloc_range(offset, size)),
+                )
+            }
+            Either::Right((local, ..)) =>
+                interp_ok(this.frame().extra.secrets.as_ref().unwrap().locals.contains(&local)),
+        }
+    }
+
+    fn secrets_operand_is_secret(&self, operand: &mir::Operand<'tcx>) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_ref();
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) =>
+                this.secrets_place_is_secret(*place),
+            mir::Operand::Constant(_) | mir::Operand::RuntimeChecks(_) => interp_ok(false),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//@compile-flags: -Zmiri-track-secrets
+#![feature(ct_select)]
+use std::select::Secret;
+
+fn main() {
+    let key = Secret::new(7u8);
+    let x = *key.expose();
+    if x == 7 {
+        //~^ ERROR: branching on a secret value
+        println!("the key is 7");
+    }
+}
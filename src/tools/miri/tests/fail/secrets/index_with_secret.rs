@@ -0,0 +1,9 @@
+//@compile-flags: -Zmiri-track-secrets
+#![feature(ct_select)]
+use std::select::Secret;
+
+fn main() {
+    let squares = [0u8, 1, 4, 9, 16, 25, 36, 49];
+    let index = *Secret::new(3usize).expose();
+    let _square = squares[index]; //~ ERROR: indexing with a secret value
+}
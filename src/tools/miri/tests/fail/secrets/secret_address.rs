@@ -0,0 +1,13 @@
+//@compile-flags: -Zmiri-track-secrets
+#![feature(ct_select)]
+use std::select::Secret;
+
+fn main() {
+    let squares = [0u8, 1, 4, 9, 16, 25, 36, 49];
+    let index = *Secret::new(3usize).expose();
+    // Unlike indexing, pointer arithmetic does not branch on the index...
+    let entry = squares.as_ptr().wrapping_add(index);
+    // ...but the address that is accessed still depends on it.
+    let _square = unsafe { *entry };
+    //~^ ERROR: accessing memory at an address that depends on a secret value
+}
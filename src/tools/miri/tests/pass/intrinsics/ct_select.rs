@@ -0,0 +1,69 @@
+//@compile-flags: -Zmiri-strict-provenance
+//! Check the constant-time selection intrinsics and the `core::select` API built on them.
+#![feature(core_intrinsics, ct_select, portable_simd)]
+#![allow(deprecated, internal_features)]
+use std::intrinsics;
+use std::select::{Choice, ConstantTimeEq, ct_black_box, ct_select, ct_swap, ct_swap_slices};
+use std::simd::prelude::*;
+
+fn main() {
+    intrinsic();
+    deprecated_aliases();
+    pointers();
+    api();
+}
+
+fn intrinsic() {
+    assert_eq!(intrinsics::ct_select(true, 1u8, 2), 1);
+    assert_eq!(intrinsics::ct_select(false, 1u8, 2), 2);
+    assert_eq!(intrinsics::ct_select(true, -1i64, i64::MIN), -1);
+    assert_eq!(intrinsics::ct_select(false, u128::MAX, 1), 1);
+    assert_eq!(intrinsics::ct_select(true, 1.5f32, -0.0), 1.5);
+    assert_eq!(intrinsics::ct_select(false, 1.5f64, f64::INFINITY), f64::INFINITY);
+    assert!(intrinsics::ct_select(false, 'a', 'b') == 'b');
+    assert!(intrinsics::ct_select(true, true, false));
+    // Both halves of a pair come from the same operand.
+    assert_eq!(intrinsics::ct_select(true, (1u64, 2u8), (3, 4)), (1, 2));
+    assert_eq!(intrinsics::ct_select(false, (1u64, 2u8), (3, 4)), (3, 4));
+    let v = intrinsics::ct_select(false, u32x4::splat(1), u32x4::from_array([1, 2, 3, 4]));
+    assert_eq!(v.to_array(), [1, 2, 3, 4]);
+    assert_eq!(intrinsics::ct_black_box(0x5au8), 0x5a);
+}
+
+fn deprecated_aliases() {
+    assert_eq!(intrinsics::ct_select_i8(true, -1, 1), -1);
+    assert_eq!(intrinsics::ct_select_i16(false, -1, 1), 1);
+    assert_eq!(intrinsics::ct_select_i32(true, i32::MIN, 0), i32::MIN);
+    assert_eq!(intrinsics::ct_select_i64(false, 0, i64::MAX), i64::MAX);
+    assert!(!intrinsics::ct_select_bool(false, true, false));
+}
+
+fn pointers() {
+    let (a, b) = (1u32, 2u32);
+    // The selected pointer keeps its provenance and can be dereferenced.
+    let p = intrinsics::ct_select(false, &a as *const u32, &b as *const u32);
+    assert_eq!(unsafe { *p }, 2);
+    let p = intrinsics::ct_select_ptr(true, &a as *const u32, &b as *const u32);
+    assert_eq!(unsafe { *p }, 1);
+    let s = intrinsics::ct_select(true, &[1u8, 2, 3][..], &[4u8][..]);
+    assert_eq!(s, [1, 2, 3]);
+    let r = ct_select(Choice::from(false), &a as *const u32, ct_black_box(&b as *const u32));
+    assert_eq!(unsafe { *r }, 2);
+}
+
+fn api() {
+    let set = 7u8.ct_eq(&7);
+    let unset = 7u8.ct_eq(&8);
+    assert_eq!(ct_select(set, 1u128, 2), 1);
+    assert_eq!(ct_select(unset, 1.0f64, 2.0), 2.0);
+    assert_eq!(ct_select(unset, [1u16, 2, 3], [4, 5, 6]), [4, 5, 6]);
+    let (mut a, mut b) = (1u64, 2u64);
+    ct_swap(set, &mut a, &mut b);
+    assert_eq!((a, b), (2, 1));
+    let (mut a, mut b) = (true, false);
+    ct_swap(unset, &mut a, &mut b);
+    assert_eq!((a, b), (true, false));
+    let (mut x, mut y) = ([1u32, 2], [3u32, 4]);
+    ct_swap_slices(set, &mut x, &mut y);
+    assert_eq!((x, y), ([3, 4], [1, 2]));
+}
@@ -0,0 +1,42 @@
+//@compile-flags: -Zmiri-track-secrets
+//! Constant-time code, and code that branches on declassified values, is accepted.
+#![feature(ct_select)]
+use std::select::{Choice, ConstantTimeEq, CtOption, Secret, ct_select};
+
+/// Looks up a table entry by scanning the whole table, so that the index does not determine the
+/// memory that is accessed.
+fn lookup(table: &[u32; 8], index: Secret<usize>) -> Secret<u32> {
+    let mut entry = Secret::new(0);
+    for (i, value) in table.iter().enumerate() {
+        entry.conditional_assign(&Secret::new(*value), i.ct_eq(index.expose()));
+    }
+    entry
+}
+
+fn checked_sub(a: Secret<u32>, b: u32) -> CtOption<u32> {
+    let a = *a.expose();
+    let diff = a.wrapping_sub(b);
+    // The top bit of this is the borrow out of the subtraction.
+    let overflowed = Choice::from(((!a & b | !(a ^ b) & diff) >> 31) as u8);
+    CtOption::new(diff, !overflowed)
+}
+
+fn main() {
+    let table = [1, 2, 4, 8, 16, 32, 64, 128];
+    let entry = lookup(&table, Secret::new(5));
+    if !entry.ct_eq(&Secret::new(32)).declassify() {
+        panic!("wrong entry");
+    }
+
+    let max = ct_select(entry.ct_eq(&Secret::new(0)), 0u32, u32::MAX);
+    assert!(max.ct_eq(&u32::MAX).declassify());
+
+    assert_eq!(checked_sub(entry, 2).into_option(), Some(30));
+    assert_eq!(checked_sub(entry, 33).into_option(), None);
+
+    // The payload is read from the same address whether the `Option` is `Some` or `None`, and
+    // only then selected, so the address of the load does not depend on the variant.
+    assert!(Some(7u32).ct_unwrap_or(9).ct_eq(&7).declassify());
+    assert!(None.ct_unwrap_or(9u32).ct_eq(&9).declassify());
+    assert!(None.ct_unwrap_or([1u8, 2, 3]).ct_eq(&[1, 2, 3]).declassify());
+}
@@ -0,0 +1,22 @@
+// Check that the loop vectorizer does not guard the loops of `#[constant_time]` functions with
+// runtime checks on the addresses that they access, which leaves them with the conditional branch
+// of their latch only.
+
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3
+//@ ct-branches: xor_blocks=1
+//@ only-x86_64
+
+#![feature(constant_time)]
+
+// The destination may overlap the sources, which the vectorizer would otherwise check before
+// choosing between the vector loop and the original one.
+#[no_mangle]
+#[constant_time]
+pub unsafe fn xor_blocks(dst: *mut u8, a: *const u8, b: *const u8) {
+    let mut i = 0;
+    while i < 1024 {
+        unsafe { *dst.add(i) = *a.add(i) ^ *b.add(i) };
+        i += 1;
+    }
+}
@@ -0,0 +1,61 @@
+// Check that `ct_select` is not lowered to a conditional branch without optimizations either,
+// where the backends do not run the passes that usually form conditional moves.
+
+//@ add-minicore
+//@ revisions: x86_64 aarch64 thumbv6m riscv64
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=0
+//@ ct-no-branches: select_i8 select_i32 select_i64 select_ptr select_loaded
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+//@ [thumbv6m] compile-flags: --target thumbv6m-none-eabi
+//@ [thumbv6m] needs-llvm-components: arm
+//@ [riscv64] compile-flags: --target riscv64gc-unknown-linux-gnu
+//@ [riscv64] needs-llvm-components: riscv
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    ct_select_i8(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    ct_select_i32(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    ct_select_i64(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    ct_select_ptr(p, a, b)
+}
+
+// The selected values are loaded from memory, which makes a branch look cheaper to the backends
+// than a conditional move.
+#[no_mangle]
+pub unsafe fn select_loaded(p: bool, a: *const i64, b: *const i64) -> i64 {
+    ct_select_i64(p, *a, *b)
+}
@@ -0,0 +1,44 @@
+// Check that `-Cforce-ct-select` lowers `select_unpredictable` without conditional branches, even
+// without optimizations and for values passed in memory.
+
+//@ add-minicore
+//@ revisions: x86_64 aarch64 riscv64
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=0 -Cforce-ct-select -Zunstable-options
+//@ ct-no-branches: select_i32 select_i64 select_pair select_array
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+//@ [riscv64] compile-flags: --target riscv64gc-unknown-linux-gnu
+//@ [riscv64] needs-llvm-components: riscv
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn select_unpredictable<T>(b: bool, true_val: T, false_val: T) -> T;
+
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    select_unpredictable(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    select_unpredictable(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_pair(p: bool, a: (u32, u64), b: (u32, u64)) -> (u32, u64) {
+    select_unpredictable(p, a, b)
+}
+
+// Values passed in memory are selected piece by piece rather than by their addresses.
+#[no_mangle]
+pub fn select_array(p: bool, a: [u64; 4], b: [u64; 4]) -> [u64; 4] {
+    select_unpredictable(p, a, b)
+}
@@ -0,0 +1,59 @@
+// Check that `ct_select` always becomes a `csel` on AArch64, never a conditional branch.
+
+//@ add-minicore
+//@ revisions: opt0 opt3
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib --target aarch64-unknown-linux-gnu
+//@ [opt0] compile-flags: -Copt-level=0
+//@ [opt3] compile-flags: -Copt-level=3
+//@ needs-llvm-components: aarch64
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+// CHECK-LABEL: select_i8:
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-NOT: b.{{[a-z]+}}
+    // CHECK-NOT: {{cbn?z|tbn?z}}
+    // CHECK: csel
+    // CHECK-NOT: b.{{[a-z]+}}
+    // CHECK: ret
+    ct_select_i8(p, a, b)
+}
+
+// CHECK-LABEL: select_ptr:
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-NOT: b.{{[a-z]+}}
+    // CHECK-NOT: {{cbn?z|tbn?z}}
+    // CHECK: csel
+    // CHECK-NOT: b.{{[a-z]+}}
+    // CHECK: ret
+    ct_select_ptr(p, a, b)
+}
+
+// `core::select` selects 128-bit integers as two 64-bit halves.
+// CHECK-LABEL: select_128:
+#[no_mangle]
+pub fn select_128(p: bool, a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    // CHECK-NOT: b.{{[a-z]+}}
+    // CHECK-NOT: {{cbn?z|tbn?z}}
+    // CHECK: csel
+    // CHECK: csel
+    // CHECK-NOT: b.{{[a-z]+}}
+    // CHECK: ret
+    (ct_select_i64(p, a.0, b.0), ct_select_i64(p, a.1, b.1))
+}
@@ -0,0 +1,45 @@
+// Check that `ct_select` becomes a `v_cndmask_b32` on AMDGPU, never a branch, so that the lanes
+// of a wavefront do not diverge on the condition.
+
+//@ add-minicore
+//@ revisions: wave64 wave32
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib --target amdgcn-amd-amdhsa -Copt-level=3
+//@ [wave64] compile-flags: -Ctarget-cpu=gfx900
+//@ [wave32] compile-flags: -Ctarget-cpu=gfx1100
+//@ needs-llvm-components: amdgpu
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK-LABEL: select_i32:
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-NOT: s_cbranch
+    // wave64: v_cmp_ne_u32_e32 vcc, 0,
+    // wave32: v_cmp_ne_u32_e32 vcc_lo, 0,
+    // CHECK: v_cndmask_b32_e32
+    // CHECK-NOT: s_cbranch
+    // CHECK: s_setpc_b64
+    ct_select_i32(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: s_cbranch
+    // CHECK: v_cndmask_b32_e32
+    // CHECK: v_cndmask_b32_e32
+    // CHECK-NOT: s_cbranch
+    // CHECK: s_setpc_b64
+    ct_select_i64(p, a, b)
+}
@@ -0,0 +1,62 @@
+// Check that `-Zct-metadata` describes the `#[constant_time]` functions and the functions with
+// secret parameters, without delimiting the code of each `ct_select`.
+
+//@ add-minicore
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3 -Zct-metadata -Zcodegen-source-order
+//@ compile-flags: --target x86_64-unknown-linux-gnu
+//@ needs-llvm-components: x86
+
+#![feature(no_core, intrinsics, constant_time)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK: .pushsection .rust_ct_functions,"",%progbits
+// CHECK-NEXT: .asciz "constant_time"
+// CHECK-NEXT: .byte 1
+// CHECK-NEXT: .uleb128 0
+// CHECK-NEXT: .popsection
+#[no_mangle]
+#[constant_time]
+pub fn constant_time(a: i64) -> i64 {
+    a
+}
+
+// CHECK: .pushsection .rust_ct_functions,"",%progbits
+// CHECK-NEXT: .asciz "secret_params"
+// CHECK-NEXT: .byte 1
+// CHECK-NEXT: .uleb128 2
+// CHECK-NEXT: .uleb128 0
+// CHECK-NEXT: .uleb128 2
+// CHECK-NEXT: .popsection
+#[no_mangle]
+#[constant_time]
+pub fn secret_params(#[secret] p: bool, a: i64, #[secret] b: i64) -> i64 {
+    ct_select_i64(p, a, b)
+}
+
+// Functions with secret parameters are described even if they are not `#[constant_time]`.
+// CHECK: .pushsection .rust_ct_functions,"",%progbits
+// CHECK-NEXT: .asciz "not_constant_time"
+// CHECK-NEXT: .byte 0
+// CHECK-NEXT: .uleb128 1
+// CHECK-NEXT: .uleb128 1
+// CHECK-NEXT: .popsection
+#[no_mangle]
+pub fn not_constant_time(p: bool, #[secret] b: i64) -> i64 {
+    ct_select_i64(p, b, 0)
+}
+
+// CHECK-NOT: .asciz "public"
+#[no_mangle]
+pub fn public(a: i64) -> i64 {
+    a
+}
+
+// CHECK-NOT: 7710:
+// CHECK-NOT: .debug_rust_ct_selects
@@ -0,0 +1,49 @@
+// Check that `-Zct-regions` records the `#[constant_time]` functions and the code of each
+// `ct_select`.
+
+//@ revisions: x86_64 aarch64
+//@ add-minicore
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3 -Zct-regions
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+
+#![feature(no_core, intrinsics, constant_time)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK: .pushsection .rust_ct_functions,"",%progbits
+// CHECK-NEXT: .asciz "marked"
+// CHECK-NEXT: .byte 1
+// CHECK-NEXT: .uleb128 0
+// CHECK-NEXT: .popsection
+// CHECK-NOT: .asciz "unmarked"
+
+// CHECK-LABEL: marked:
+#[no_mangle]
+#[constant_time]
+pub fn marked(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK: 7710:
+    // CHECK: 7711:
+    // CHECK-NEXT: .pushsection .debug_rust_ct_selects,"",%progbits
+    // CHECK-NEXT: .quad 7710b
+    // CHECK-NEXT: .quad 7711b
+    // CHECK-NEXT: .popsection
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: unmarked:
+#[no_mangle]
+pub fn unmarked(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK: 7710:
+    // CHECK: 7711:
+    // CHECK-NEXT: .pushsection .debug_rust_ct_selects,"",%progbits
+    ct_select_i64(p, a, b)
+}
@@ -0,0 +1,42 @@
+// Check that `ct_select` stays branchless in debug builds.
+
+//@ revisions: x86_64 aarch64
+//@ add-minicore
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=0
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu -Cllvm-args=-x86-asm-syntax=intel
+//@ [x86_64] needs-llvm-components: x86
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK-LABEL: select_i8:
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // x86_64-NOT: j{{[a-z]+}}
+    // aarch64-NOT: b.{{[a-z]+}}
+    // aarch64-NOT: {{cbn?z|tbn?z}}
+    // CHECK: ret
+    ct_select_i8(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // x86_64-NOT: j{{[a-z]+}}
+    // aarch64-NOT: b.{{[a-z]+}}
+    // aarch64-NOT: {{cbn?z|tbn?z}}
+    // CHECK: ret
+    ct_select_i64(p, a, b)
+}
@@ -0,0 +1,61 @@
+// Check that `-Zct-select-lowering` chooses the lowering of every `ct_select`, and that
+// `#[ct_select_lowering]` overrides it for a function.
+
+//@ add-minicore
+//@ revisions: cmov masking asm
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3 --target x86_64-unknown-linux-gnu
+//@ [cmov] compile-flags: -Zct-select-lowering=cmov
+//@ [masking] compile-flags: -Zct-select-lowering=masking
+//@ [asm] compile-flags: -Zct-select-lowering=asm
+//@ needs-llvm-components: x86
+
+#![feature(no_core, intrinsics, constant_time)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK-LABEL: select_flag:
+#[no_mangle]
+pub fn select_flag(p: bool, a: i64, b: i64) -> i64 {
+    // cmov-NOT: #APP
+    // cmov: cmov{{[a-z]+}}
+    // cmov-NOT: #APP
+
+    // masking-NOT: cmov
+    // masking: andq
+    // masking-NOT: cmov
+
+    // asm: #APP
+    // asm-NEXT: test
+    // asm-NEXT: cmovne
+
+    // CHECK: retq
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_masking:
+#[no_mangle]
+#[ct_select_lowering(masking)]
+pub fn select_masking(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: cmov
+    // CHECK: andq
+    // CHECK-NOT: cmov
+    // CHECK: retq
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_asm:
+#[no_mangle]
+#[ct_select_lowering(asm)]
+pub fn select_asm(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK: #APP
+    // CHECK-NEXT: test
+    // CHECK-NEXT: cmovne
+    // CHECK: retq
+    ct_select_i64(p, a, b)
+}
@@ -0,0 +1,62 @@
+// Check that `ct_select_spec` places the speculation barrier of the target between the
+// branch-free selection and the load through the selected pointer.
+
+//@ add-minicore
+//@ revisions: x86_64 aarch64 powerpc64
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+//@ [powerpc64] compile-flags: --target powerpc64le-unknown-linux-gnu -Ctarget-cpu=pwr9
+//@ [powerpc64] needs-llvm-components: powerpc
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_spec<T: Copy>(b: bool, true_val: T, false_val: T) -> T;
+
+// CHECK-LABEL: load_selected:
+#[no_mangle]
+pub unsafe fn load_selected(p: bool, a: *const u64, b: *const u64) -> u64 {
+    // x86_64: cmov
+    // x86_64: lfence
+    // x86_64-NEXT: #NO_APP
+    // x86_64-NEXT: movq ({{%[a-z0-9]+}}), %rax
+
+    // aarch64: csel
+    // aarch64: hint #20
+    // aarch64-NEXT: //NO_APP
+    // aarch64-NEXT: ldr x0, [x{{[0-9]+}}]
+
+    // powerpc64: isel
+    // powerpc64: ori 31, 31, 0
+    // powerpc64-NEXT: #NO_APP
+    // powerpc64-NEXT: ld 3, 0(
+    unsafe { *ct_select_spec(p, a, b) }
+}
+
+// Both halves of a wide pointer go through a single barrier.
+// CHECK-LABEL: select_slice:
+#[no_mangle]
+pub fn select_slice<'a>(p: bool, a: &'a [u8], b: &'a [u8]) -> &'a [u8] {
+    // x86_64: cmov
+    // x86_64: cmov
+    // x86_64: lfence
+    // x86_64-NOT: lfence
+
+    // aarch64: csel
+    // aarch64: csel
+    // aarch64: hint #20
+    // aarch64-NOT: hint #20
+
+    // powerpc64: ori 31, 31, 0
+    // powerpc64-NOT: ori 31, 31, 0
+    // CHECK: {{ret|blr}}
+    ct_select_spec(p, a, b)
+}
@@ -0,0 +1,68 @@
+// Check that `ct_select` is lowered to masking of each 16-bit register on MSP430, with no jump,
+// even for values several registers wide.
+
+//@ add-minicore
+//@ revisions: opt0 opt3
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib --target msp430-none-elf
+//@ [opt0] compile-flags: -Copt-level=0
+//@ [opt3] compile-flags: -Copt-level=3
+//@ needs-llvm-components: msp430
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i16(b: bool, true_val: i16, false_val: i16) -> i16;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+// CHECK-LABEL: select_i8:
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: {{and|bic}}
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: ret
+    ct_select_i8(p, a, b)
+}
+
+// CHECK-LABEL: select_i16:
+#[no_mangle]
+pub fn select_i16(p: bool, a: i16, b: i16) -> i16 {
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: {{and|bic}}
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: ret
+    ct_select_i16(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: {{and|bic}}
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: ret
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_ptr:
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: {{and|bic}}
+    // CHECK-NOT: {{j[a-z]+}}
+    // CHECK: ret
+    ct_select_ptr(p, a, b)
+}
@@ -0,0 +1,68 @@
+// Check that `ct_select` becomes a predicated `selp` on NVPTX, never a branch, so that the
+// threads of a warp do not diverge on the condition.
+
+//@ add-minicore
+//@ revisions: opt0 opt3
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib --target nvptx64-nvidia-cuda
+//@ [opt0] compile-flags: -Copt-level=0
+//@ [opt3] compile-flags: -Copt-level=3
+//@ needs-llvm-components: nvptx
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+// CHECK-LABEL: select_i8(
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-NOT: bra
+    // CHECK: selp.b32
+    // CHECK-NOT: bra
+    // CHECK: ret;
+    ct_select_i8(p, a, b)
+}
+
+// CHECK-LABEL: select_i32(
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-NOT: bra
+    // CHECK: selp.b32
+    // CHECK-NOT: bra
+    // CHECK: ret;
+    ct_select_i32(p, a, b)
+}
+
+// CHECK-LABEL: select_i64(
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: bra
+    // CHECK: selp.b64
+    // CHECK-NOT: bra
+    // CHECK: ret;
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_ptr(
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-NOT: bra
+    // CHECK: selp.b64
+    // CHECK-NOT: bra
+    // CHECK: ret;
+    ct_select_ptr(p, a, b)
+}
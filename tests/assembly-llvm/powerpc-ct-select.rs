@@ -0,0 +1,57 @@
+// Check that `ct_select` becomes an `isel` on PowerPC targets that have it, never a branch.
+
+//@ add-minicore
+//@ revisions: powerpc powerpc64 powerpc64le
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3 -Ctarget-cpu=pwr9
+//@ [powerpc] compile-flags: --target powerpc-unknown-linux-gnu
+//@ [powerpc64] compile-flags: --target powerpc64-unknown-linux-gnu
+//@ [powerpc64le] compile-flags: --target powerpc64le-unknown-linux-gnu
+//@ needs-llvm-components: powerpc
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+// CHECK-LABEL: select_i8:
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-NOT: .LBB
+    // CHECK: isel
+    // CHECK-NOT: .LBB
+    // CHECK: blr
+    ct_select_i8(p, a, b)
+}
+
+// On 32-bit PowerPC, each half of an `i64` takes its own `isel`.
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: .LBB
+    // CHECK: isel
+    // powerpc: isel
+    // CHECK-NOT: .LBB
+    // CHECK: blr
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_ptr:
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-NOT: .LBB
+    // CHECK: isel
+    // CHECK-NOT: .LBB
+    // CHECK: blr
+    ct_select_ptr(p, a, b)
+}
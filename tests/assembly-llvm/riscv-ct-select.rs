@@ -0,0 +1,54 @@
+// Check that `ct_select` is lowered to Zicond's conditional-zero instructions when available,
+// and to masking otherwise, and never to a branch.
+
+//@ add-minicore
+//@ revisions: rv32 rv32zicond rv64 rv64zicond
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3
+//@ [rv32] compile-flags: --target riscv32imac-unknown-none-elf
+//@ [rv32zicond] compile-flags: --target riscv32imac-unknown-none-elf -Ctarget-feature=+zicond
+//@ [rv64] compile-flags: --target riscv64gc-unknown-linux-gnu
+//@ [rv64zicond] compile-flags: --target riscv64gc-unknown-linux-gnu -Ctarget-feature=+zicond
+//@ needs-llvm-components: riscv
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK-LABEL: select_i32:
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-NOT: .LBB
+    // rv32zicond: czero.eqz
+    // rv32zicond: czero.nez
+    // rv64zicond: czero.eqz
+    // rv64zicond: czero.nez
+    // rv32: and
+    // rv64: and
+    // CHECK: ret
+    ct_select_i32(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: .LBB
+    // rv32zicond: czero
+    // rv32zicond: czero
+    // rv32zicond: czero
+    // rv32zicond: czero
+    // rv64zicond: czero.eqz
+    // rv64zicond: czero.nez
+    // rv32: and
+    // rv64: and
+    // CHECK: ret
+    ct_select_i64(p, a, b)
+}
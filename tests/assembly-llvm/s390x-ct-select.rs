@@ -0,0 +1,58 @@
+// Check that `ct_select` becomes a load on condition on s390x CPUs that have it, and masking on
+// those that do not, never a compare and branch.
+
+//@ add-minicore
+//@ revisions: z10 z196
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib --target s390x-unknown-linux-gnu -Copt-level=3
+//@ [z10] compile-flags: -Ctarget-cpu=z10
+//@ [z196] compile-flags: -Ctarget-cpu=z196
+//@ needs-llvm-components: systemz
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+// CHECK-LABEL: select_i32:
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-NOT: .LBB
+    // z10: {{ngr|nr}}
+    // z196: locgrne
+    // CHECK-NOT: .LBB
+    // CHECK: br %r14
+    ct_select_i32(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: .LBB
+    // z10: ngr
+    // z196: locgrne
+    // CHECK-NOT: .LBB
+    // CHECK: br %r14
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_ptr:
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-NOT: .LBB
+    // z10: ngr
+    // z196: locgrne
+    // CHECK-NOT: .LBB
+    // CHECK: br %r14
+    ct_select_ptr(p, a, b)
+}
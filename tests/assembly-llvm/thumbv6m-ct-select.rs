@@ -0,0 +1,70 @@
+// Check that `ct_select` is lowered to masking on Thumb-1-only targets such as ARMv6-M, which
+// have no conditional execution, rather than to a branch.
+
+//@ add-minicore
+//@ revisions: v6m_opt0 v6m_opt3 v8m_base
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib
+//@ [v6m_opt0] compile-flags: --target thumbv6m-none-eabi -Copt-level=0
+//@ [v6m_opt3] compile-flags: --target thumbv6m-none-eabi -Copt-level=3
+//@ [v8m_base] compile-flags: --target thumbv8m.base-none-eabi -Copt-level=3
+//@ needs-llvm-components: arm
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+// CHECK-LABEL: select_i8:
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-NOT: .LBB
+    // CHECK: ands
+    // CHECK-NOT: .LBB
+    // CHECK: {{bx lr|pop}}
+    ct_select_i8(p, a, b)
+}
+
+// CHECK-LABEL: select_i32:
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-NOT: .LBB
+    // CHECK: ands
+    // CHECK-NOT: .LBB
+    // CHECK: {{bx lr|pop}}
+    ct_select_i32(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: .LBB
+    // CHECK: ands
+    // CHECK: ands
+    // CHECK-NOT: .LBB
+    // CHECK: {{bx lr|pop}}
+    ct_select_i64(p, a, b)
+}
+
+// CHECK-LABEL: select_ptr:
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-NOT: .LBB
+    // CHECK: ands
+    // CHECK-NOT: .LBB
+    // CHECK: {{bx lr|pop}}
+    ct_select_ptr(p, a, b)
+}
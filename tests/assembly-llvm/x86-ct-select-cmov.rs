@@ -0,0 +1,27 @@
+// Check that `ct_select` becomes a `cmov` that the x86 backend does not convert back into a
+// branch, even when the selected values come from memory and a branch would look cheaper.
+
+//@ add-minicore
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3 -Cllvm-args=-x86-asm-syntax=intel
+//@ compile-flags: --target x86_64-unknown-linux-gnu
+//@ needs-llvm-components: x86
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK-LABEL: select_loaded:
+#[no_mangle]
+pub unsafe fn select_loaded(p: bool, a: *const i64, b: *const i64) -> i64 {
+    // CHECK-NOT: j{{[a-z]+}}
+    // CHECK: cmovne
+    // CHECK-NOT: j{{[a-z]+}}
+    // CHECK: ret
+    ct_select_i64(p, *a, *b)
+}
@@ -0,0 +1,43 @@
+// Check that `ct_select` stays branchless on x86 CPUs without `cmov`.
+
+//@ add-minicore
+//@ revisions: i586 x86_64
+//@ assembly-output: emit-asm
+//@ compile-flags: --crate-type=lib -Copt-level=3 -Cllvm-args=-x86-asm-syntax=intel
+//@ [i586] compile-flags: --target i586-unknown-linux-gnu
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu -Ctarget-feature=-cmov
+//@ needs-llvm-components: x86
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+// CHECK-LABEL: select_i8:
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-NOT: j{{[a-z]+}}
+    // CHECK-NOT: cmov
+    // CHECK: and
+    // CHECK-NOT: j{{[a-z]+}}
+    // CHECK: ret
+    ct_select_i8(p, a, b)
+}
+
+// CHECK-LABEL: select_i64:
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-NOT: j{{[a-z]+}}
+    // CHECK-NOT: cmov
+    // CHECK: and
+    // CHECK-NOT: j{{[a-z]+}}
+    // CHECK: ret
+    ct_select_i64(p, a, b)
+}
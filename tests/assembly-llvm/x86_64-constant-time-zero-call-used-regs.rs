@@ -0,0 +1,28 @@
+// Test that `#[constant_time]` functions clear the registers they used before returning, other
+// than the one holding their return value.
+
+//@ assembly-output: emit-asm
+//@ compile-flags: -Copt-level=3 -Zmerge-functions=disabled
+//@ only-x86_64
+//@ ignore-sgx Tests incompatible with LVI mitigations
+
+#![crate_type = "lib"]
+#![feature(constant_time)]
+
+// CHECK-LABEL: marked:
+#[no_mangle]
+#[constant_time]
+pub fn marked(a: u64, b: u64) -> u64 {
+    // CHECK-DAG: xorl %esi, %esi
+    // CHECK-DAG: xorl %edi, %edi
+    // CHECK: retq
+    a.wrapping_mul(b) ^ (b >> 3)
+}
+
+// CHECK-LABEL: unmarked:
+#[no_mangle]
+pub fn unmarked(a: u64, b: u64) -> u64 {
+    // CHECK-NOT: xorl %esi, %esi
+    // CHECK: retq
+    a.wrapping_mul(b) ^ (b >> 3)
+}
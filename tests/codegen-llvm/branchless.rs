@@ -0,0 +1,26 @@
+// Test that an `if`/`else` on a `Choice` in a `#[branchless]` function becomes an inlined
+// selection, even without optimizations, while the same code elsewhere keeps its branch.
+
+//@ compile-flags: -Copt-level=0
+
+#![crate_type = "lib"]
+#![feature(constant_time, ct_select)]
+
+use std::select::Choice;
+
+// CHECK-LABEL: @branchless
+#[no_mangle]
+#[branchless]
+pub fn branchless(c: Choice, a: u32, b: u32) -> u32 {
+    // CHECK-NOT: br i1
+    // CHECK-NOT: call {{.*}}ct_select
+    // CHECK: ret i32
+    if c.declassify() { a } else { b }
+}
+
+// CHECK-LABEL: @branching
+#[no_mangle]
+pub fn branching(c: Choice, a: u32, b: u32) -> u32 {
+    // CHECK: br i1
+    if c.declassify() { a } else { b }
+}
@@ -0,0 +1,25 @@
+// Test that functions marked `#[constant_time]` get the `no-jump-tables` attribute, so that a
+// switch cannot become a load from an address that depends on the value switched on.
+
+//@ add-minicore
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-linux-gnu -Copt-level=3 -Zmerge-functions=disabled
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, constant_time)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// CHECK: @marked() unnamed_addr #[[MARKED:[0-9]+]]
+#[no_mangle]
+#[constant_time]
+pub fn marked() {}
+
+// CHECK: @unmarked() unnamed_addr #[[UNMARKED:[0-9]+]]
+#[no_mangle]
+pub fn unmarked() {}
+
+// CHECK: attributes #[[MARKED]] = { {{.*}}"no-jump-tables"="true"{{.*}} }
+// CHECK-NOT: attributes #[[UNMARKED]] = { {{.*}}"no-jump-tables"="true"{{.*}} }
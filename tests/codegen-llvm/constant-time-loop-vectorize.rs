@@ -0,0 +1,46 @@
+// Check that the loops of `#[constant_time]` functions are only vectorized when the vector loop
+// needs no runtime checks on the addresses that it accesses.
+
+//@ compile-flags: -Copt-level=3 -Zmerge-functions=disabled
+//@ only-64bit
+
+#![crate_type = "lib"]
+#![feature(constant_time)]
+
+// CHECK-LABEL: @marked_overlapping(
+#[no_mangle]
+#[constant_time]
+pub unsafe fn marked_overlapping(dst: *mut u8, a: *const u8, b: *const u8, len: usize) {
+    // CHECK-NOT: vector.memcheck
+    // CHECK-NOT: vector.body
+    // CHECK: ret void
+    let mut i = 0;
+    while i < len {
+        unsafe { *dst.add(i) = *a.add(i) ^ *b.add(i) };
+        i += 1;
+    }
+}
+
+// CHECK-LABEL: @unmarked_overlapping(
+#[no_mangle]
+pub unsafe fn unmarked_overlapping(dst: *mut u8, a: *const u8, b: *const u8, len: usize) {
+    // CHECK: vector.memcheck
+    // CHECK: vector.body
+    let mut i = 0;
+    while i < len {
+        unsafe { *dst.add(i) = *a.add(i) ^ *b.add(i) };
+        i += 1;
+    }
+}
+
+// References cannot overlap, so the loop is vectorized without runtime checks.
+// CHECK-LABEL: @marked_disjoint(
+#[no_mangle]
+#[constant_time]
+pub fn marked_disjoint(dst: &mut [u8], a: &[u8], b: &[u8]) {
+    // CHECK-NOT: vector.memcheck
+    // CHECK: vector.body
+    for ((d, a), b) in dst.iter_mut().zip(a).zip(b) {
+        *d = a ^ b;
+    }
+}
@@ -0,0 +1,27 @@
+// Check that `#[constant_time]` functions and the functions of `core::select` cannot have their
+// comparisons turned into calls to `memcmp` or `bcmp`, which return at the first difference.
+
+//@ compile-flags: -Copt-level=0
+
+#![crate_type = "lib"]
+#![feature(ct_select, constant_time)]
+
+use std::select::ConstantTimeEq;
+
+// CHECK-DAG: define {{.*}}@marked({{.*}} #[[MARKED:[0-9]+]]
+#[no_mangle]
+#[constant_time]
+pub fn marked(a: u64, b: u64) -> u64 {
+    a ^ b
+}
+
+#[no_mangle]
+pub fn compare(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).declassify()
+}
+
+// The comparison of the slices, instantiated in this crate.
+// CHECK-DAG: define {{.*}}ct_eq{{.*}} #[[CT_EQ:[0-9]+]]
+
+// CHECK-DAG: attributes #[[MARKED]] = { {{.*}}"no-builtin-bcmp" "no-builtin-memcmp"{{.*}} }
+// CHECK-DAG: attributes #[[CT_EQ]] = { {{.*}}"no-builtin-bcmp" "no-builtin-memcmp"{{.*}} }
@@ -0,0 +1,36 @@
+// Check that the functions that compute conditions from secrets are marked for the pass
+// instrumentation that keeps loop unswitching and jump threading from turning these conditions
+// into branches, and that the other functions are not.
+
+//@ compile-flags: -Copt-level=3 -Zmerge-functions=disabled
+
+#![crate_type = "lib"]
+#![feature(ct_select)]
+
+use std::select::{Choice, ConstantTimeEq, ct_select};
+
+// CHECK: define {{.*}}@choose({{.*}} #[[CHOOSE:[0-9]+]]
+#[no_mangle]
+pub fn choose(c: Choice, a: u32, b: u32) -> u32 {
+    ct_select(c, a, b)
+}
+
+// CHECK: define {{.*}}@select_in_loop({{.*}} #[[SELECT_IN_LOOP:[0-9]+]]
+#[no_mangle]
+pub fn select_in_loop(xs: &mut [u32], a: u32, b: u32) {
+    // Unswitching would test `c` once, before the loop.
+    let c = a.ct_eq(&b);
+    for x in xs {
+        *x = ct_select(c, *x, a);
+    }
+}
+
+// CHECK: define {{.*}}@public({{.*}} #[[PUBLIC:[0-9]+]]
+#[no_mangle]
+pub fn public(a: u32, b: u32) -> u32 {
+    if a == b { a } else { b }
+}
+
+// CHECK-DAG: attributes #[[CHOOSE]] = { {{.*}}"rust-secret-conditions"{{.*}} }
+// CHECK-DAG: attributes #[[SELECT_IN_LOOP]] = { {{.*}}"rust-secret-conditions"{{.*}} }
+// CHECK-NOT: attributes #[[PUBLIC]] = { {{.*}}"rust-secret-conditions"{{.*}} }
@@ -0,0 +1,26 @@
+// Test that `#[constant_time(speculative_load_hardening)]` enables speculative load hardening for
+// the function it is applied to, and only for that function.
+
+//@ add-minicore
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-linux-gnu -Copt-level=3 -Zmerge-functions=disabled
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, constant_time)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// CHECK: @hardened() unnamed_addr #[[HARDENED:[0-9]+]]
+#[no_mangle]
+#[constant_time(speculative_load_hardening)]
+pub fn hardened() {}
+
+// CHECK: @unhardened() unnamed_addr #[[UNHARDENED:[0-9]+]]
+#[no_mangle]
+#[constant_time]
+pub fn unhardened() {}
+
+// CHECK: attributes #[[HARDENED]] = { {{.*}}speculative_load_hardening{{.*}} }
+// CHECK-NOT: attributes #[[UNHARDENED]] = { {{.*}}speculative_load_hardening{{.*}} }
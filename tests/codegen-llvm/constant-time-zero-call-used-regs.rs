@@ -0,0 +1,36 @@
+// Test that `#[constant_time]` functions clear the registers they used before returning on the
+// targets where LLVM implements it, and that the other functions do not.
+
+//@ add-minicore
+//@ revisions: x86_64 i686 aarch64 riscv64
+//@ compile-flags: -Copt-level=3 -Zmerge-functions=disabled
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [i686] compile-flags: --target i686-unknown-linux-gnu
+//@ [i686] needs-llvm-components: x86
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+//@ [riscv64] compile-flags: --target riscv64gc-unknown-linux-gnu
+//@ [riscv64] needs-llvm-components: riscv
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, constant_time)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// CHECK: @marked() unnamed_addr #[[MARKED:[0-9]+]]
+#[no_mangle]
+#[constant_time]
+pub fn marked() {}
+
+// CHECK: @unmarked() unnamed_addr #[[UNMARKED:[0-9]+]]
+#[no_mangle]
+pub fn unmarked() {}
+
+// x86_64: attributes #[[MARKED]] = { {{.*}}"zero-call-used-regs"="used"{{.*}} }
+// i686: attributes #[[MARKED]] = { {{.*}}"zero-call-used-regs"="used"{{.*}} }
+// aarch64: attributes #[[MARKED]] = { {{.*}}"zero-call-used-regs"="used"{{.*}} }
+// riscv64-NOT: zero-call-used-regs
+// CHECK-NOT: attributes #[[UNMARKED]] = { {{.*}}zero-call-used-regs{{.*}} }
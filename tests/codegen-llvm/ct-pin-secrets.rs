@@ -0,0 +1,37 @@
+// Check that `-Zct-pin-secrets` gives no stack slot to the scalar values of `Secret` for their
+// debuginfo, and marks the functions that hold them so that their spills are reported.
+
+//@ compile-flags: -Copt-level=0 -Cdebuginfo=2 -Zct-pin-secrets
+
+#![crate_type = "lib"]
+#![feature(ct_select)]
+
+use std::select::Secret;
+
+extern "Rust" {
+    fn use_secret(secret: Secret<u64>);
+    fn use_public(public: u64);
+}
+
+// CHECK: define {{.*}}void @pinned({{.*}} #[[PINNED:[0-9]+]]
+#[no_mangle]
+pub fn pinned(x: u64) {
+    // CHECK-NOT: %secret.dbg.spill
+    // CHECK: call void @use_secret(
+    // CHECK-NOT: %secret.dbg.spill
+    // CHECK: ret void
+    let secret = Secret::new(x);
+    unsafe { use_secret(secret) };
+}
+
+// CHECK: define {{.*}}void @public({{.*}} #[[PUBLIC:[0-9]+]]
+#[no_mangle]
+pub fn public(x: u64) {
+    // CHECK: %public.dbg.spill = alloca
+    // CHECK: call void @use_public(
+    let public = x;
+    unsafe { use_public(public) };
+}
+
+// CHECK: attributes #[[PINNED]] = { {{.*}}"rust-pin-secrets"{{.*}} }
+// CHECK-NOT: attributes #[[PUBLIC]] = { {{.*}}"rust-pin-secrets"{{.*}} }
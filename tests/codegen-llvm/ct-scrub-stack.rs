@@ -0,0 +1,56 @@
+// Check that `-Zct-scrub-stack` moves the body of each `#[constant_time]` function to another
+// function, and leaves the function zeroing the frame of its body once it returns or unwinds.
+
+//@ revisions: abort unwind
+//@ compile-flags: -Copt-level=3 -Zct-scrub-stack
+//@ [abort] compile-flags: -Cpanic=abort
+//@ [unwind] compile-flags: -Cpanic=unwind
+//@ only-linux
+//@ only-64bit
+
+#![crate_type = "lib"]
+#![feature(constant_time)]
+
+extern "Rust" {
+    fn opaque(x: &mut [u64; 4]);
+}
+
+// CHECK-LABEL: define {{.*}}void @marked(
+#[no_mangle]
+#[constant_time]
+pub fn marked(a: u64, b: u64) {
+    // CHECK: %body_sp = alloca
+    // CHECK: call ptr @llvm.stacksave.p0()
+    // abort: call void @marked.ct_scrubbed(i64 {{.*}}%a, i64 {{.*}}%b, ptr {{.*}}%body_sp)
+    // unwind: invoke void @marked.ct_scrubbed(i64 {{.*}}%a, i64 {{.*}}%b, ptr {{.*}}%body_sp)
+
+    // unwind: landingpad
+    // unwind-NEXT: cleanup
+    // unwind: store volatile i64 0
+    // unwind: resume
+
+    // CHECK: store volatile i64 0
+    // CHECK: ret void
+    let mut words = [a, b, a ^ b, a & b];
+    unsafe { opaque(&mut words) };
+}
+
+// CHECK-LABEL: define {{.*}}void @unmarked(
+#[no_mangle]
+pub fn unmarked(a: u64, b: u64) {
+    // CHECK-NOT: @llvm.stacksave
+    // CHECK-NOT: store volatile
+    // CHECK: ret void
+    let mut words = [a, b, a ^ b, a & b];
+    unsafe { opaque(&mut words) };
+}
+
+// The body reports its stack pointer once its frame is allocated.
+// CHECK-LABEL: define internal void @marked.ct_scrubbed(
+// CHECK-SAME: ptr {{.*}}%body_sp)
+// CHECK-SAME: #[[BODY:[0-9]+]]
+// CHECK: %sp = {{.*}}call ptr @llvm.stacksave.p0()
+// CHECK-NEXT: store ptr %sp, ptr %body_sp
+// CHECK: call void @opaque(
+
+// CHECK: attributes #[[BODY]] = { {{.*}}noinline{{.*}}noredzone{{.*}} }
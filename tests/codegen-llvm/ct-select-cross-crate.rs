@@ -0,0 +1,49 @@
+// Check that the constant-time selections of the standard library are inlined into the crates
+// that use them at every optimization level, leaving no call behind which the selection could
+// be turned back into a branch. Inline assembly used by the lowering is not a call to a function.
+
+//@ revisions: O0 O1 O3
+//@ [O0] compile-flags: -Copt-level=0
+//@ [O1] compile-flags: -Copt-level=1
+//@ [O3] compile-flags: -Copt-level=3
+
+#![crate_type = "lib"]
+#![feature(ct_select)]
+
+use std::select::{Choice, ConstantTimeSelect, ct_select, ct_swap};
+
+// CHECK-LABEL: @select_u32
+#[no_mangle]
+pub fn select_u32(c: Choice, a: u32, b: u32) -> u32 {
+    // CHECK-NOT: call {{.*}}@
+    // CHECK-NOT: br i1
+    // CHECK: ret i32
+    ct_select(c, a, b)
+}
+
+// CHECK-LABEL: @select_method_u64
+#[no_mangle]
+pub fn select_method_u64(c: Choice, a: u64, b: u64) -> u64 {
+    // CHECK-NOT: call {{.*}}@
+    // CHECK-NOT: br i1
+    // CHECK: ret i64
+    u64::ct_select(c, a, b)
+}
+
+// CHECK-LABEL: @select_bool
+#[no_mangle]
+pub fn select_bool(c: Choice, a: bool, b: bool) -> bool {
+    // CHECK-NOT: call {{.*}}@
+    // CHECK-NOT: br i1
+    // CHECK: ret i1
+    ct_select(c, a, b)
+}
+
+// CHECK-LABEL: @swap_u32
+#[no_mangle]
+pub fn swap_u32(c: Choice, a: &mut u32, b: &mut u32) {
+    // CHECK-NOT: call {{.*}}@
+    // CHECK-NOT: br i1
+    // CHECK: ret void
+    ct_swap(c, a, b)
+}
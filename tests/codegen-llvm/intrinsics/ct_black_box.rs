@@ -0,0 +1,60 @@
+//@ add-minicore
+//@ compile-flags: --target x86_64-unknown-linux-gnu -Copt-level=3
+//@ needs-llvm-components: x86
+
+#![feature(no_core, intrinsics)]
+#![crate_type = "lib"]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// Values that fit in a register go through an empty assembly statement that has side effects,
+// so that neither the value nor the identity of two barriers on the same value is known to the
+// optimizer. Others go through memory, as with `black_box`.
+
+#[rustc_intrinsic]
+pub fn ct_black_box<T>(value: T) -> T;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[no_mangle]
+pub fn black_box_i32(a: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}} @black_box_i32
+    // CHECK: [[WIDE:%.*]] = zext i32 %a to i64
+    // CHECK: [[OPAQUE:%.*]] = {{.*}}call i64 asm sideeffect "", "=r,0"(i64 [[WIDE]])
+    // CHECK: trunc i64 [[OPAQUE]] to i32
+    ct_black_box(a)
+}
+
+#[no_mangle]
+pub fn black_box_bool(a: bool) -> bool {
+    // CHECK-LABEL: define{{.*}} @black_box_bool
+    // CHECK: call i64 asm sideeffect "", "=r,0"
+    ct_black_box(a)
+}
+
+#[no_mangle]
+pub fn black_box_ptr(a: *const u8) -> *const u8 {
+    // CHECK-LABEL: define{{.*}} @black_box_ptr
+    // CHECK: call ptr asm sideeffect "", "=r,0"(ptr %a)
+    ct_black_box(a)
+}
+
+#[no_mangle]
+pub fn black_box_u128(a: u128) -> u128 {
+    // CHECK-LABEL: define{{.*}} @black_box_u128
+    // CHECK: call void asm sideeffect "", "r,~{memory}"(ptr
+    ct_black_box(a)
+}
+
+#[no_mangle]
+pub fn select_equal_operands(p: bool, a: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}} @select_equal_operands
+    // The two barriers are kept apart, so the selection is not folded into `a`.
+    // CHECK: call i64 asm sideeffect "", "=r,0"
+    // CHECK: call i64 asm sideeffect "", "=r,0"
+    // CHECK-NOT: ret i32 %a
+    ct_select_i32(p, ct_black_box(a), ct_black_box(a))
+}
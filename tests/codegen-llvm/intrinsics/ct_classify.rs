@@ -0,0 +1,67 @@
+//@ add-minicore
+//@ revisions: NONE MSAN VALGRIND-X86_64 VALGRIND-AARCH64
+//@ compile-flags: -Copt-level=1
+//@[NONE] compile-flags: --target x86_64-unknown-linux-gnu
+//@[NONE] needs-llvm-components: x86
+//@[MSAN] compile-flags: --target x86_64-unknown-linux-gnu -Zsanitizer=memory
+//@[MSAN] compile-flags: -Cunsafe-allow-abi-mismatch=sanitizer
+//@[MSAN] needs-llvm-components: x86
+//@[VALGRIND-X86_64] compile-flags: --target x86_64-unknown-linux-gnu -Zvalgrind-ct-annotations
+//@[VALGRIND-X86_64] needs-llvm-components: x86
+//@[VALGRIND-AARCH64] compile-flags: --target aarch64-unknown-linux-gnu -Zvalgrind-ct-annotations
+//@[VALGRIND-AARCH64] needs-llvm-components: aarch64
+
+#![feature(no_core, intrinsics)]
+#![crate_type = "lib"]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// The value is stored to memory, which is then poisoned for MemorySanitizer or marked through a
+// Memcheck client request, and nothing is emitted without either.
+
+#[rustc_intrinsic]
+pub fn ct_classify<T>(value: T) -> T;
+
+#[rustc_intrinsic]
+pub fn ct_declassify<T>(value: T) -> T;
+
+#[no_mangle]
+pub fn classify_u32(a: u32) -> u32 {
+    // CHECK-LABEL: define{{.*}} @classify_u32
+    // NONE-NOT: call
+    // NONE-NOT: asm
+    // MSAN: call void @__msan_poison(ptr {{.*}}, i64 4)
+    // VALGRIND-X86_64: store i64 1296236545
+    // VALGRIND-X86_64: call i64 asm sideeffect "rolq $$3, %rdi; {{.*}}; xchgq %rbx, %rbx"
+    // VALGRIND-X86_64-SAME: "={rdx},{rax},0,~{cc},~{memory}"
+    // VALGRIND-AARCH64: store i64 1296236545
+    // VALGRIND-AARCH64: call i64 asm sideeffect "ror x12, x12, #3; {{.*}}; orr x10, x10, x10"
+    // VALGRIND-AARCH64-SAME: "={x3},{x4},0,~{cc},~{memory}"
+    // CHECK: ret i32
+    ct_classify(a)
+}
+
+#[no_mangle]
+pub fn declassify_pair(a: (u64, u8)) -> (u64, u8) {
+    // CHECK-LABEL: define{{.*}} @declassify_pair
+    // NONE-NOT: call
+    // NONE-NOT: asm
+    // MSAN: call void @__msan_unpoison(ptr {{.*}}, i64 16)
+    // VALGRIND-X86_64: store i64 1296236546
+    // VALGRIND-X86_64: call i64 asm sideeffect "rolq
+    // VALGRIND-AARCH64: store i64 1296236546
+    // VALGRIND-AARCH64: call i64 asm sideeffect "ror
+    ct_declassify(a)
+}
+
+#[no_mangle]
+pub fn classify_unit(a: ()) -> () {
+    // CHECK-LABEL: define{{.*}} @classify_unit
+    // MSAN-NOT: @__msan_poison
+    // VALGRIND-X86_64-NOT: asm
+    // VALGRIND-AARCH64-NOT: asm
+    // CHECK: ret void
+    ct_classify(a)
+}
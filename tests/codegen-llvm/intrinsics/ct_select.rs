@@ -0,0 +1,122 @@
+//@ add-minicore
+//@ revisions: sparc64 s390x aarch64 x86_64 i686 nvptx64 amdgpu powerpc64
+//@ compile-flags: -Copt-level=3 -Zmerge-functions=disabled
+//@ [sparc64] compile-flags: --target sparc64-unknown-linux-gnu
+//@ [sparc64] needs-llvm-components: sparc
+//@ [s390x] compile-flags: --target s390x-unknown-linux-gnu -Ctarget-cpu=z196
+//@ [s390x] needs-llvm-components: systemz
+//@ [aarch64] compile-flags: --target aarch64-unknown-linux-gnu
+//@ [aarch64] needs-llvm-components: aarch64
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [i686] compile-flags: --target i686-unknown-linux-gnu
+//@ [i686] needs-llvm-components: x86
+//@ [nvptx64] compile-flags: --target nvptx64-nvidia-cuda
+//@ [nvptx64] needs-llvm-components: nvptx
+//@ [amdgpu] compile-flags: --target amdgcn-amd-amdhsa -Ctarget-cpu=gfx900
+//@ [amdgpu] needs-llvm-components: amdgpu
+//@ [powerpc64] compile-flags: --target powerpc64le-unknown-linux-gnu -Ctarget-cpu=pwr9
+//@ [powerpc64] needs-llvm-components: powerpc
+
+#![feature(no_core, intrinsics)]
+#![crate_type = "lib"]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// The constant-time selects must be lowered to something no later pass turns back into a branch:
+// a `select` marked `!unpredictable` in general, and a conditional move in inline assembly on
+// targets where the backend would otherwise still use a branch for some `select`s, or where a
+// branch would make the threads of a GPU warp diverge.
+
+#[rustc_intrinsic]
+pub fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+pub fn ct_select_i64(b: bool, true_val: i64, false_val: i64) -> i64;
+
+#[rustc_intrinsic]
+pub fn ct_select_bool(b: bool, true_val: bool, false_val: bool) -> bool;
+
+#[rustc_intrinsic]
+pub fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+#[no_mangle]
+pub fn select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-LABEL: define{{.*}} @select_i8
+    // CHECK-NOT: br
+    // sparc64: select i1 %p, i8 %a, i8 %b, !unpredictable
+    // aarch64: call i64 asm "cmp $1, #0\0A\09csel $0, $2, $3, ne", "=r,r,r,r,~{cc}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // i686: call i32 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // nvptx64: call i32 asm "{{.*}}setp.ne.b32 %p, $1, 0; selp.b32 $0, $2, $3, %p; }", "=r,r,r,r"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // powerpc64: call i64 asm "cmplwi $1, 0\0A\09isel $0, $3, $2, 2", "=r,r,r,b,~{cr0}"
+    // s390x: call i64 asm "chi $1, 0\0A\09locgrne $0, $2", "=r,r,r,0,~{cc}"
+    ct_select_i8(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-LABEL: define{{.*}} @select_i64
+    // CHECK-NOT: br
+    // sparc64: select i1 %p, i64 %a, i64 %b, !unpredictable
+    // aarch64: call i64 asm "cmp $1, #0\0A\09csel $0, $2, $3, ne", "=r,r,r,r,~{cc}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // i686: call i32 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // i686: call i32 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // nvptx64: call i64 asm "{{.*}}setp.ne.b32 %p, $1, 0; selp.b64 $0, $2, $3, %p; }", "=l,r,l,l"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // powerpc64: call i64 asm "cmplwi $1, 0\0A\09isel $0, $3, $2, 2", "=r,r,r,b,~{cr0}"
+    // s390x: call i64 asm "chi $1, 0\0A\09locgrne $0, $2", "=r,r,r,0,~{cc}"
+    ct_select_i64(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_bool(p: bool, a: bool, b: bool) -> bool {
+    // CHECK-LABEL: define{{.*}} @select_bool
+    // CHECK-NOT: br
+    // sparc64: select i1 %p, i1 %a, i1 %b, !unpredictable
+    // aarch64: call i64 asm "cmp $1, #0\0A\09csel $0, $2, $3, ne", "=r,r,r,r,~{cc}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // i686: call i32 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // nvptx64: call i32 asm "{{.*}}setp.ne.b32 %p, $1, 0; selp.b32 $0, $2, $3, %p; }", "=r,r,r,r"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // powerpc64: call i64 asm "cmplwi $1, 0\0A\09isel $0, $3, $2, 2", "=r,r,r,b,~{cr0}"
+    // s390x: call i64 asm "chi $1, 0\0A\09locgrne $0, $2", "=r,r,r,0,~{cc}"
+    ct_select_bool(p, a, b)
+}
+
+#[no_mangle]
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-LABEL: define{{.*}} @select_ptr
+    // CHECK-NOT: br
+    // sparc64: select i1 %p, ptr %a, ptr %b, !unpredictable
+    // aarch64: call i64 asm "cmp $1, #0\0A\09csel $0, $2, $3, ne", "=r,r,r,r,~{cc}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // i686: call i32 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // nvptx64: call i64 asm "{{.*}}setp.ne.b32 %p, $1, 0; selp.b64 $0, $2, $3, %p; }", "=l,r,l,l"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // powerpc64: call i64 asm "cmplwi $1, 0\0A\09isel $0, $3, $2, 2", "=r,r,r,b,~{cr0}"
+    // s390x: call i64 asm "chi $1, 0\0A\09locgrne $0, $2", "=r,r,r,0,~{cc}"
+    ct_select_ptr(p, a, b)
+}
+
+// 128-bit integers are selected as two 64-bit halves, as `ct_select` does for `u128`.
+#[no_mangle]
+pub fn select_128(p: bool, a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    // CHECK-LABEL: define{{.*}} @select_128
+    // CHECK-NOT: br
+    // aarch64: call i64 asm "cmp $1, #0\0A\09csel $0, $2, $3, ne", "=r,r,r,r,~{cc}"
+    // aarch64: call i64 asm "cmp $1, #0\0A\09csel $0, $2, $3, ne", "=r,r,r,r,~{cc}"
+    // nvptx64: call i64 asm "{{.*}}setp.ne.b32 %p, $1, 0; selp.b64 $0, $2, $3, %p; }", "=l,r,l,l"
+    // nvptx64: call i64 asm "{{.*}}setp.ne.b32 %p, $1, 0; selp.b64 $0, $2, $3, %p; }", "=l,r,l,l"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    // amdgpu: call i32 asm "v_cmp_ne_u32_e32 vcc, 0, $1\0A\09v_cndmask_b32_e32 $0, $3, $2, vcc"
+    (ct_select_i64(p, a.0, b.0), ct_select_i64(p, a.1, b.1))
+}
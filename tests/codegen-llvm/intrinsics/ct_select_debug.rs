@@ -0,0 +1,49 @@
+//@ compile-flags: -Copt-level=0
+// AArch64 uses inline assembly at every optimization level.
+//@ ignore-aarch64
+
+#![feature(core_intrinsics)]
+#![crate_type = "lib"]
+
+// Without optimizations, the constant-time selects are lowered to masking, since FastISel may
+// turn a `select` into a branch.
+
+#[no_mangle]
+pub fn ct_select_i8(p: bool, a: i8, b: i8) -> i8 {
+    // CHECK-LABEL: define{{.*}} @ct_select_i8
+    // CHECK-NOT: br
+    // CHECK-NOT: select
+    // CHECK: [[MASK:%.*]] = sext i1 {{.*}} to i8
+    // CHECK: [[DIFF:%.*]] = xor i8 %a, %b
+    // CHECK: [[MASKED:%.*]] = and i8 [[MASK]], [[DIFF]]
+    // CHECK: xor i8 %b, [[MASKED]]
+    core::intrinsics::ct_select(p, a, b)
+}
+
+#[no_mangle]
+pub fn ct_select_i64(p: bool, a: i64, b: i64) -> i64 {
+    // CHECK-LABEL: define{{.*}} @ct_select_i64
+    // CHECK-NOT: br
+    // CHECK-NOT: select
+    // CHECK: sext i1 {{.*}} to i64
+    core::intrinsics::ct_select(p, a, b)
+}
+
+#[no_mangle]
+pub fn ct_select_bool(p: bool, a: bool, b: bool) -> bool {
+    // CHECK-LABEL: define{{.*}} @ct_select_bool
+    // CHECK-NOT: br
+    // CHECK-NOT: select
+    // CHECK: xor i1
+    core::intrinsics::ct_select(p, a, b)
+}
+
+#[no_mangle]
+pub fn ct_select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    // CHECK-LABEL: define{{.*}} @ct_select_ptr
+    // CHECK-NOT: br
+    // CHECK-NOT: select
+    // CHECK: ptrtoint ptr %a
+    // CHECK: inttoptr
+    core::intrinsics::ct_select(p, a, b)
+}
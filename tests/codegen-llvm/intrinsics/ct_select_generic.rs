@@ -0,0 +1,78 @@
+// Check that the generic `ct_select` legalizes the values it selects into integers of at most 64
+// bits and pointers, which every lowering supports.
+
+//@ add-minicore
+//@ revisions: x86_64 sparc64
+//@ compile-flags: -Copt-level=3 -Zmerge-functions=disabled
+//@ [x86_64] compile-flags: --target x86_64-unknown-linux-gnu
+//@ [x86_64] needs-llvm-components: x86
+//@ [sparc64] compile-flags: --target sparc64-unknown-linux-gnu
+//@ [sparc64] needs-llvm-components: sparc
+
+#![feature(no_core, intrinsics, repr_simd)]
+#![crate_type = "lib"]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select<T: Copy>(b: bool, true_val: T, false_val: T) -> T;
+
+#[repr(simd)]
+pub struct U32x4([u32; 4]);
+
+impl Copy for U32x4 {}
+
+// Integers wider than 64 bits are selected 64 bits at a time.
+#[no_mangle]
+pub fn select_u128(p: bool, a: u128, b: u128) -> u128 {
+    // CHECK-LABEL: define{{.*}} @select_u128
+    // CHECK-NOT: br
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // sparc64: select i1 %p, i64 {{.*}}, !unpredictable
+    // sparc64: select i1 %p, i64 {{.*}}, !unpredictable
+    // CHECK-NOT: br
+    // CHECK: ret i128
+    ct_select(p, a, b)
+}
+
+// Floating-point numbers are selected as their bits.
+#[no_mangle]
+pub fn select_f64(p: bool, a: f64, b: f64) -> f64 {
+    // CHECK-LABEL: define{{.*}} @select_f64
+    // CHECK-NOT: br
+    // CHECK: bitcast double %a to i64
+    // CHECK: bitcast double %b to i64
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // sparc64: select i1 %p, i64 {{.*}}, !unpredictable
+    // CHECK: bitcast i64 {{.*}} to double
+    ct_select(p, a, b)
+}
+
+// Both halves of a pair are selected on the same condition.
+#[no_mangle]
+pub fn select_slice<'a>(p: bool, a: &'a [u8], b: &'a [u8]) -> &'a [u8] {
+    // CHECK-LABEL: define{{.*}} @select_slice
+    // CHECK-NOT: br
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // sparc64: select i1 %p, ptr %a.0, ptr %b.0, !unpredictable
+    // sparc64: select i1 %p, i64 %a.1, i64 %b.1, !unpredictable
+    ct_select(p, a, b)
+}
+
+// Vectors are selected as an integer of the same width.
+#[no_mangle]
+pub fn select_vector(p: bool, a: U32x4, b: U32x4) -> U32x4 {
+    // CHECK-LABEL: define{{.*}} @select_vector
+    // CHECK-NOT: br
+    // CHECK: bitcast <4 x i32> {{.*}} to i128
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // x86_64: call i64 asm "test $1, $1\0A\09cmovne $2, $0", "=r,r,r,0,~{flags}"
+    // sparc64: select i1 %p, i64 {{.*}}, !unpredictable
+    // sparc64: select i1 %p, i64 {{.*}}, !unpredictable
+    // CHECK: bitcast i128 {{.*}} to <4 x i32>
+    ct_select(p, a, b)
+}
@@ -0,0 +1,36 @@
+// Check that `-Zopaque-ct-select` keeps selections whose condition is known at compile time.
+
+//@ add-minicore
+//@ revisions: default opaque
+//@ compile-flags: --target wasm32-unknown-unknown -Copt-level=3
+//@ [opaque] compile-flags: -Zopaque-ct-select
+//@ needs-llvm-components: webassembly
+
+#![feature(no_core, intrinsics)]
+#![crate_type = "lib"]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[no_mangle]
+pub fn select_known(a: i32, b: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}} @select_known
+    // default-NOT: select
+    // default: ret i32 %a
+    // opaque: [[COND:%.*]] = {{.*}}call i32 asm "", "=r,0"(i32 1)
+    // opaque: [[BOOL:%.*]] = icmp ne i32 [[COND]], 0
+    // opaque: select i1 [[BOOL]], i32 %a, i32 %b, !unpredictable
+    ct_select_i32(true, a, b)
+}
+
+#[no_mangle]
+pub fn select_unknown(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}} @select_unknown
+    // opaque: call i32 asm "", "=r,0"
+    // CHECK: select i1 {{.*}}, i32 %a, i32 %b, !unpredictable
+    ct_select_i32(p, a, b)
+}
@@ -0,0 +1,36 @@
+// Test that `#[rustc_no_branch]` functions are marked for the pass instrumentation that keeps
+// the optimizer from introducing branches into them, and that their selects are marked
+// unpredictable, so that the code generator does not turn them into branches either.
+
+//@ add-minicore
+//@ needs-llvm-components: x86
+//@ compile-flags: --target x86_64-unknown-linux-gnu -Copt-level=3 -Zmerge-functions=disabled
+
+#![crate_type = "lib"]
+#![feature(no_core, lang_items, rustc_attrs)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+// CHECK-LABEL: @marked(
+// CHECK-SAME: #[[MARKED:[0-9]+]]
+#[no_mangle]
+#[rustc_no_branch]
+pub fn marked(c: bool, a: u32, b: u32) -> u32 {
+    // CHECK: select i1 %c, i32 %a, i32 %b, !unpredictable
+    if c { a } else { b }
+}
+
+// CHECK-LABEL: @unmarked(
+// CHECK-SAME: #[[UNMARKED:[0-9]+]]
+#[no_mangle]
+pub fn unmarked(c: bool, a: u32, b: u32) -> u32 {
+    // CHECK: select i1 %c, i32 %a, i32 %b
+    // CHECK-NOT: !unpredictable
+    // CHECK: ret i32
+    if c { a } else { b }
+}
+
+// CHECK: attributes #[[MARKED]] = { {{.*}}"no-jump-tables"="true"{{.*}}"rust-no-branch"{{.*}} }
+// CHECK-NOT: attributes #[[UNMARKED]] = { {{.*}}"rust-no-branch"{{.*}} }
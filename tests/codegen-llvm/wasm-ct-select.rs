@@ -0,0 +1,32 @@
+// Check that `-Zwasm-ct-select` chooses between the `select` instruction and masking.
+
+//@ add-minicore
+//@ revisions: default select mask
+//@ compile-flags: --target wasm32-unknown-unknown -Copt-level=3
+//@ [select] compile-flags: -Zwasm-ct-select=select
+//@ [mask] compile-flags: -Zwasm-ct-select=mask
+//@ needs-llvm-components: webassembly
+
+#![feature(no_core, intrinsics)]
+#![crate_type = "lib"]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[no_mangle]
+pub fn select_i32(p: bool, a: i32, b: i32) -> i32 {
+    // CHECK-LABEL: define{{.*}} @select_i32
+    // CHECK-NOT: br
+    // default: select i1 %p, i32 %a, i32 %b, !unpredictable
+    // select: select i1 %p, i32 %a, i32 %b, !unpredictable
+    // mask-NOT: select
+    // mask: call i32 asm "", "=r,0"
+    // mask: xor i32
+    // mask: and i32
+    // mask: xor i32
+    ct_select_i32(p, a, b)
+}
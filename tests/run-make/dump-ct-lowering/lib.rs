@@ -0,0 +1,15 @@
+#![feature(ct_select)]
+
+use std::select::{Choice, ct_eq, ct_select, ct_swap};
+
+pub fn select(c: Choice, a: u32, b: u32) -> u32 {
+    ct_select(c, a, b)
+}
+
+pub fn swap(c: Choice, a: &mut u64, b: &mut u64) {
+    ct_swap(c, a, b)
+}
+
+pub fn eq(a: u16, b: u16) -> Choice {
+    ct_eq(&a, &b)
+}
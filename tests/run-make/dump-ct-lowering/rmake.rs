@@ -0,0 +1,47 @@
+//@ needs-target-std
+//
+// `-Zdump-ct-lowering` writes a report of every constant-time selection, swap and comparison
+// in the crate, with the instructions that the backend lowers the selections to. This checks
+// that each call is reported once, at its call site, whether or not it was inlined.
+
+use run_make_support::serde_json::{self, Value};
+use run_make_support::{cwd, rfs, rustc};
+
+fn main() {
+    for opt_level in ["0", "2"] {
+        rustc()
+            .crate_type("lib")
+            .input("lib.rs")
+            .opt_level(opt_level)
+            .arg(format!("-Zdump-ct-lowering={}", cwd().display()))
+            .run();
+        let report: Value =
+            serde_json::from_str(&rfs::read_to_string("lib.ct_lowering.json")).unwrap();
+
+        assert_eq!(report["crate"], "lib");
+        assert!(!report["instructions"].as_array().unwrap().is_empty());
+
+        let sites = report["sites"].as_array().unwrap();
+        let site = |function: &str| {
+            let mut found = sites.iter().filter(|site| site["function"] == function);
+            let site = found.next().unwrap_or_else(|| panic!("no site in `{function}`"));
+            assert!(found.next().is_none(), "more than one site in `{function}`");
+            assert_eq!(site["crate"], "lib");
+            site
+        };
+
+        let select = site("select");
+        assert_eq!(select["kind"], "ct_select");
+        assert!(select["span"].as_str().unwrap().starts_with("lib.rs:6:"));
+        let selections = select["selections"].as_array().unwrap();
+        assert_eq!(selections.len(), 1);
+        assert_eq!(selections[0]["intrinsic"], "ct_select");
+        assert_eq!(selections[0]["ty"], "u32");
+
+        assert_eq!(site("swap")["kind"], "ct_swap");
+
+        let eq = site("eq");
+        assert_eq!(eq["kind"], "ct_eq");
+        assert!(eq["selections"].as_array().unwrap().is_empty());
+    }
+}
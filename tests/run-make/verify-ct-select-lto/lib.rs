@@ -0,0 +1,7 @@
+#![feature(ct_select)]
+
+use std::select::{Choice, ct_select};
+
+pub fn select(c: Choice, a: u64, b: u64) -> u64 {
+    ct_select(c, a, b)
+}
@@ -0,0 +1,11 @@
+#![feature(ct_select)]
+
+extern crate lib;
+
+use std::hint::black_box;
+use std::select::Choice;
+
+fn main() {
+    let c = Choice::from(black_box(1u8));
+    println!("{}", lib::select(c, black_box(1), black_box(2)));
+}
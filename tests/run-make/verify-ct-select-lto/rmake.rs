@@ -0,0 +1,40 @@
+//@ needs-target-std
+//@ ignore-cross-compile
+//
+// `-Zverify-ct-select` checks the code that is finally emitted, which under LTO is the module
+// that LTO produced. This checks that the markers of a library compiled with the flag reach the
+// module that LTO builds from it and its dependent, where they are checked again, and that the
+// check warns that it can't cover the code generated by the linker under linker-plugin LTO.
+
+use run_make_support::{rfs, run, rustc};
+
+fn main() {
+    rustc().input("lib.rs").crate_type("rlib").opt_level("3").arg("-Zverify-ct-select").run();
+
+    for lto in ["fat", "thin"] {
+        rustc()
+            .input("main.rs")
+            .opt_level("3")
+            .lto(lto)
+            .codegen_units(1)
+            .emit("asm,link")
+            .arg("-Zverify-ct-select")
+            .run();
+        run("main").assert_stdout_equals("1\n");
+        if lto == "fat" {
+            // The selection was inlined from the library into `main`, along with its markers.
+            assert!(rfs::read_to_string("main.s").contains("ct_select.begin"));
+        }
+    }
+
+    rustc()
+        .input("lib.rs")
+        .crate_type("rlib")
+        .opt_level("3")
+        .linker_plugin_lto("on")
+        .arg("-Zverify-ct-select")
+        .run()
+        .assert_stderr_contains(
+            "`-Zverify-ct-select` cannot check the code generated by the linker",
+        );
+}
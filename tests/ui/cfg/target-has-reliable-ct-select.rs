@@ -0,0 +1,39 @@
+// Test that `cfg(target_has_reliable_ct_select)` is set only on targets where selections are
+// lowered without branches.
+
+//@ add-minicore
+//@ revisions: m68k x86_64 i586 aarch64
+//@ compile-flags: --crate-type=lib -Copt-level=2
+//@[m68k] compile-flags: --target=m68k-unknown-linux-gnu
+//@[m68k] needs-llvm-components: m68k
+//@[x86_64] compile-flags: --target=x86_64-unknown-linux-gnu
+//@[x86_64] needs-llvm-components: x86
+//@[i586] compile-flags: --target=i586-unknown-linux-gnu
+//@[i586] needs-llvm-components: x86
+//@[aarch64] compile-flags: --target=aarch64-unknown-linux-gnu
+//@[aarch64] needs-llvm-components: aarch64
+//@ check-pass
+//@ ignore-backends: gcc
+
+#![feature(cfg_target_has_reliable_ct_select, no_core)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[cfg(target_has_reliable_ct_select)]
+fn reliable() {}
+
+#[cfg(not(target_has_reliable_ct_select))]
+fn unreliable() {}
+
+#[cfg(m68k)]
+pub fn check() {
+    unreliable()
+}
+
+// Without `cmov`, selections are lowered to masking, which is as reliable.
+#[cfg(any(x86_64, i586, aarch64))]
+pub fn check() {
+    reliable()
+}
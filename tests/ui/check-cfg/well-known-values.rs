@@ -14,6 +14,7 @@
 #![feature(cfg_sanitize)]
 #![feature(cfg_target_has_atomic)]
 #![feature(cfg_target_has_atomic_equal_alignment)]
+#![feature(cfg_target_has_reliable_ct_select)]
 #![feature(cfg_target_thread_local)]
 #![feature(cfg_ub_checks)]
 #![feature(fmt_debug)]
@@ -68,6 +69,8 @@
     //~^ WARN unexpected `cfg` condition value
     target_has_atomic_load_store = "_UNEXPECTED_VALUE",
     //~^ WARN unexpected `cfg` condition value
+    target_has_reliable_ct_select = "_UNEXPECTED_VALUE",
+    //~^ WARN unexpected `cfg` condition value
     target_os = "_UNEXPECTED_VALUE",
     //~^ WARN unexpected `cfg` condition value
     target_pointer_width = "_UNEXPECTED_VALUE",
@@ -0,0 +1,18 @@
+// Check that `-Zct-regions` and `-Zct-metadata` are rejected on targets whose object files are
+// not ELF.
+
+//@ revisions: regions metadata
+//@ compile-flags: --target x86_64-apple-darwin
+//@ [regions] compile-flags: -Zct-regions
+//@ [metadata] compile-flags: -Zct-metadata
+//@ needs-llvm-components: x86
+//@ add-minicore
+
+#![crate_type = "lib"]
+#![feature(no_core)]
+#![no_core]
+
+extern crate minicore;
+
+//[regions]~? ERROR `-Zct-regions` is not supported on `x86_64-apple-darwin`
+//[metadata]~? ERROR `-Zct-metadata` is not supported on `x86_64-apple-darwin`
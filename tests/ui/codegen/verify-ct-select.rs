@@ -0,0 +1,61 @@
+// Check that `-Zverify-ct-select` accepts the lowering of `ct_select` for every kind of type that
+// the backend legalizes, with and without optimizations.
+
+//@ build-pass
+//@ revisions: opt0 opt3
+//@[opt0] compile-flags: -Zverify-ct-select -Copt-level=0
+//@[opt3] compile-flags: -Zverify-ct-select -Copt-level=3
+//@ ignore-backends: gcc
+
+#![feature(core_intrinsics, ct_select, portable_simd)]
+#![crate_type = "lib"]
+
+use std::intrinsics;
+use std::select::{Choice, ConstantTimeEq, ct_select};
+use std::simd::u32x4;
+
+pub fn select_ints(p: bool, a: u64, b: u64) -> (i8, i16, i32, i64, u128) {
+    (
+        intrinsics::ct_select(p, a as i8, b as i8),
+        intrinsics::ct_select(p, a as i16, b as i16),
+        intrinsics::ct_select(p, a as i32, b as i32),
+        intrinsics::ct_select(p, a as i64, b as i64),
+        intrinsics::ct_select(p, (a as u128) << 64, b as u128),
+    )
+}
+
+pub fn select_bool(p: bool, a: bool, b: bool) -> bool {
+    intrinsics::ct_select(p, a, b)
+}
+
+pub fn select_ptr(p: bool, a: *const u8, b: *const u8) -> *const u8 {
+    intrinsics::ct_select(p, a, b)
+}
+
+pub fn select_floats(p: bool, a: f32, b: f64) -> (f32, f64) {
+    (intrinsics::ct_select(p, a, -a), intrinsics::ct_select(p, b, -b))
+}
+
+pub fn select_pair(p: bool, a: (u64, u8), b: (u64, u8)) -> (u64, u8) {
+    intrinsics::ct_select(p, a, b)
+}
+
+pub fn select_slice<'a>(p: bool, a: &'a [u8], b: &'a [u8]) -> &'a [u8] {
+    intrinsics::ct_select(p, a, b)
+}
+
+pub fn select_vector(p: bool, a: u32x4, b: u32x4) -> u32x4 {
+    intrinsics::ct_select(p, a, b)
+}
+
+pub fn lookup(table: &[u32; 16], secret: usize) -> u32 {
+    let mut found = 0;
+    for (i, &entry) in table.iter().enumerate() {
+        found = ct_select(i.ct_eq(&secret), entry, found);
+    }
+    found
+}
+
+pub fn select_choice(c: Choice, a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    ct_select(c, a, b)
+}
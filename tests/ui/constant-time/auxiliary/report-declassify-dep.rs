@@ -0,0 +1,9 @@
+//@ compile-flags: -Zreport-declassify
+
+#![feature(ct_select)]
+
+use std::select::{ConstantTimeEq, Secret};
+
+pub fn is_zero(key: &Secret<u32>) -> bool {
+    key.expose().ct_eq(&0).declassify()
+}
@@ -0,0 +1,7 @@
+#![feature(ct_select)]
+
+use std::select::{ConstantTimeEq, Secret};
+
+pub fn is_one(key: &Secret<u32>) -> bool {
+    key.expose().ct_eq(&1).declassify()
+}
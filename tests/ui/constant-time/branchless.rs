@@ -0,0 +1,107 @@
+// Test that rewriting the branches of `#[branchless]` functions into selections keeps their
+// results, whether or not a branch can be rewritten.
+
+//@ run-pass
+//@ revisions: opt noopt
+//@[opt] compile-flags: -Copt-level=3
+//@[noopt] compile-flags: -Copt-level=0
+
+#![feature(constant_time, ct_select)]
+
+use std::cmp::Ordering;
+use std::select::{Choice, ConstantTimeEq, ConstantTimeSelect};
+
+#[branchless]
+fn pick(c: Choice, a: u32, b: u32) -> u32 {
+    if c.declassify() { a } else { b }
+}
+
+#[branchless]
+fn mix(c: Choice, a: u64, b: u64) -> u64 {
+    if c.declassify() { a ^ b } else { !a & b }
+}
+
+#[branchless]
+fn swap_if(c: Choice, a: u8, b: u8) -> (u8, u8) {
+    let x;
+    let y;
+    if c.declassify() {
+        x = b;
+        y = a;
+    } else {
+        x = a;
+        y = b;
+    }
+    (x, y)
+}
+
+#[branchless]
+fn overwrite(c: Choice, mut a: i16, b: i16) -> i16 {
+    if c.declassify() {
+        a = b;
+    } else {
+        a = -a;
+    }
+    a
+}
+
+#[branchless]
+fn ordering(c: Choice) -> Ordering {
+    if c.declassify() { Ordering::Less } else { Ordering::Greater }
+}
+
+#[branchless]
+fn generic<T: ConstantTimeSelect>(c: Choice, a: T, b: T) -> T {
+    if c.declassify() { a } else { b }
+}
+
+// Not rewritten: the arms have side effects.
+#[branchless]
+fn side_effect(c: Choice, log: &mut Vec<u32>) -> u32 {
+    if c.declassify() {
+        log.push(1);
+        1
+    } else {
+        2
+    }
+}
+
+// Not rewritten: the arm dereferences a pointer that is only valid when it is taken.
+#[branchless]
+fn deref(c: Choice, p: *const u32) -> u32 {
+    if c.declassify() { unsafe { *p } } else { 0 }
+}
+
+// Not rewritten: the arms produce a value that cannot be selected.
+#[branchless]
+fn string(c: Choice) -> &'static str {
+    if c.declassify() { "yes" } else { "no" }
+}
+
+fn main() {
+    let yes = 1u8.ct_eq(&1);
+    let no = 1u8.ct_eq(&2);
+
+    assert_eq!(pick(yes, 1, 2), 1);
+    assert_eq!(pick(no, 1, 2), 2);
+    assert_eq!(mix(yes, 0b1100, 0b1010), 0b0110);
+    assert_eq!(mix(no, 0b1100, 0b1010), 0b0010);
+    assert_eq!(swap_if(yes, 3, 4), (4, 3));
+    assert_eq!(swap_if(no, 3, 4), (3, 4));
+    assert_eq!(overwrite(yes, 5, 6), 6);
+    assert_eq!(overwrite(no, 5, 6), -5);
+    assert_eq!(ordering(yes), Ordering::Less);
+    assert_eq!(ordering(no), Ordering::Greater);
+    assert_eq!(generic(yes, 7i32, 8), 7);
+    assert_eq!(generic(no, true, false), false);
+
+    let mut log = Vec::new();
+    assert_eq!(side_effect(yes, &mut log), 1);
+    assert_eq!(side_effect(no, &mut log), 2);
+    assert_eq!(log, [1]);
+
+    assert_eq!(deref(yes, &9), 9);
+    assert_eq!(deref(no, std::ptr::null()), 0);
+    assert_eq!(string(yes), "yes");
+    assert_eq!(string(no), "no");
+}
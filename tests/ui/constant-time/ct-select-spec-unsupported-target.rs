@@ -0,0 +1,20 @@
+// Test that `ct_select_spec` is rejected on targets without a speculation barrier.
+
+//@ build-fail
+//@ compile-flags: --target riscv64gc-unknown-linux-gnu --crate-type=lib
+//@ needs-llvm-components: riscv
+//@ add-minicore
+
+#![feature(no_core, intrinsics)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+pub fn ct_select_spec<T: Copy>(b: bool, true_val: T, false_val: T) -> T;
+
+#[no_mangle]
+pub fn select_ptr(b: bool, x: *const u8, y: *const u8) -> *const u8 {
+    ct_select_spec(b, x, y) //~ ERROR is not supported on `riscv64gc-unknown-linux-gnu`
+}
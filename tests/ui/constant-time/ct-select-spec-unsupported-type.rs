@@ -0,0 +1,29 @@
+// Test that `ct_select_spec` rejects the types that are not passed as one or two integers or
+// pointers that each fit in a register, which the speculation barrier holds the value in.
+
+//@ build-fail
+//@ only-x86_64
+//@ ignore-backends: gcc
+
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+use std::intrinsics::ct_select_spec;
+
+pub fn select_u128(b: bool, x: u128, y: u128) -> u128 {
+    ct_select_spec(b, x, y) //~ ERROR expected a type passed as one or two integers or pointers
+}
+
+pub fn select_f64(b: bool, x: f64, y: f64) -> f64 {
+    ct_select_spec(b, x, y) //~ ERROR expected a type passed as one or two integers or pointers
+}
+
+pub fn select_array(b: bool, x: [u64; 4], y: [u64; 4]) -> [u64; 4] {
+    ct_select_spec(b, x, y) //~ ERROR expected a type passed as one or two integers or pointers
+}
+
+fn main() {
+    select_u128(true, 0, 1);
+    select_f64(true, 0.0, 1.0);
+    select_array(true, [0; 4], [1; 4]);
+}
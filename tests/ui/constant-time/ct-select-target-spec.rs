@@ -0,0 +1,44 @@
+// Test that the support for constant-time selections declared by a target specification decides
+// both `cfg(target_has_reliable_ct_select)` and whether selections are reported.
+
+//@ add-minicore
+//@ revisions: unsupported masking
+//@ compile-flags: --crate-type=lib -Copt-level=2 -Zunstable-options
+//@[unsupported] compile-flags: --target={{src-base}}/constant-time/ct-select-unsupported.json
+//@[masking] compile-flags: --target={{src-base}}/constant-time/ct-select-masking.json
+//@ needs-llvm-components: x86
+//@ build-pass
+//@ ignore-backends: gcc
+
+#![feature(cfg_target_has_reliable_ct_select, intrinsics, no_core, rustc_attrs)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+#[rustc_nounwind]
+fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[cfg(target_has_reliable_ct_select)]
+fn reliable() {}
+
+#[cfg(not(target_has_reliable_ct_select))]
+fn unreliable() {}
+
+// x86-64 has `cmov`, but the target says it cannot be relied on.
+#[cfg(unsupported)]
+pub fn check() {
+    unreliable()
+}
+
+#[cfg(masking)]
+pub fn check() {
+    reliable()
+}
+
+#[no_mangle]
+pub fn select(c: bool, a: i32, b: i32) -> i32 {
+    ct_select_i32(c, a, b)
+    //[unsupported]~^ WARN this selection of `i32` may be compiled to a branch
+}
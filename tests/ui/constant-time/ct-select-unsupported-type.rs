@@ -0,0 +1,26 @@
+// Test that `ct_select` rejects the types that are not passed as one or two scalars or as a SIMD
+// vector, which the backends can't select without going through memory.
+
+//@ build-fail
+//@ ignore-backends: gcc
+
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+use std::intrinsics::ct_select;
+
+pub fn select_array(b: bool, x: [u64; 4], y: [u64; 4]) -> [u64; 4] {
+    ct_select(b, x, y) //~ ERROR expected a type passed as one or two scalars or as a SIMD vector
+}
+
+#[derive(Clone, Copy)]
+pub struct Triple(u32, u32, u32);
+
+pub fn select_triple(b: bool, x: Triple, y: Triple) -> Triple {
+    ct_select(b, x, y) //~ ERROR expected a type passed as one or two scalars or as a SIMD vector
+}
+
+fn main() {
+    select_array(true, [0; 4], [1; 4]);
+    select_triple(true, Triple(0, 0, 0), Triple(1, 1, 1));
+}
@@ -0,0 +1,50 @@
+// Check that `#[declassify]` exempts the branches and indexing within the expression it marks
+// from the checks of `#[constant_time]` functions and from the `secret_dependent_branch` lint,
+// and only within that expression.
+
+#![feature(constant_time, ct_select)]
+#![deny(secret_dependent_branch)]
+#![crate_type = "lib"]
+
+use std::select::Secret;
+
+#[constant_time]
+pub fn verify(tag: &Secret<u32>, expected: u32) -> u32 {
+    #[declassify = "whether the tag is valid is public"]
+    if *tag.expose() == expected {
+        return 1;
+    }
+    if *tag.expose() == 0 { 2 } else { 3 }
+    //~^ ERROR branch on a secret value in a `#[constant_time]` function
+}
+
+#[constant_time]
+pub fn block(key: &Secret<u32>, table: &[u8; 16]) -> u8 {
+    #[declassify = "the first byte of the key is public"]
+    {
+        let index = (*key.expose() & 15) as usize;
+        if index == 0 { 0 } else { table[index] }
+    }
+}
+
+pub fn lint(key: &Secret<u32>) -> u32 {
+    #[declassify = "the zero key is rejected in public"]
+    if *key.expose() == 0 {
+        return 0;
+    }
+    if *key.expose() == 1 { 1 } else { 2 }
+    //~^ ERROR branch on a value derived from a secret
+}
+
+macro_rules! check {
+    ($cond:expr) => {
+        if $cond { 1 } else { 0 }
+    };
+}
+
+pub fn macros(key: &Secret<u32>) -> u32 {
+    #[declassify = "the parity of the key is public"]
+    {
+        check!(*key.expose() % 2 == 0)
+    }
+}
@@ -0,0 +1,42 @@
+// Check that `-Zreport-declassify` lists the calls to the functions that declassify secrets and
+// the expressions marked `#[declassify]`, in the crate and in the crates it depends on that were
+// compiled with the flag.
+
+//@ check-pass
+//@ compile-flags: -Zreport-declassify
+//@ aux-build: report-declassify-dep.rs
+//@ aux-build: report-declassify-unflagged.rs
+
+#![feature(constant_time, ct_select)]
+#![crate_type = "lib"]
+
+extern crate report_declassify_dep;
+extern crate report_declassify_unflagged;
+
+use std::select::{Choice, ConstantTimeEq, CtOption, CtResult, Secret};
+
+pub fn methods(key: &Secret<u32>, option: CtOption<u32>, result: CtResult<u32, ()>) -> bool {
+    let _ = option.into_option();
+    let _ = result.into_result();
+    key.expose().ct_eq(&0).declassify()
+}
+
+pub fn paths(choices: &[Choice]) -> Vec<bool> {
+    choices.iter().copied().map(Choice::declassify).collect()
+}
+
+pub fn closure(key: Secret<u32>) -> impl Fn() -> u32 {
+    move || *Secret::expose(&key)
+}
+
+pub fn upstream(key: &Secret<u32>) -> bool {
+    report_declassify_dep::is_zero(key) && report_declassify_unflagged::is_one(key)
+}
+
+pub fn attribute(key: &Secret<u32>) -> u32 {
+    #[declassify = "the zero key is rejected in public"]
+    if *key.expose() == 0 {
+        return 0;
+    }
+    1
+}
@@ -0,0 +1,36 @@
+// Check that functions marked `#[constant_time]` may not compute addresses from secret values,
+// whether by indexing through the `Index` traits, `get_unchecked` or pointer arithmetic.
+
+#![feature(constant_time, ct_select)]
+#![crate_type = "lib"]
+
+use std::select::{Secret, ct_lookup};
+
+#[constant_time]
+pub fn vec_index(table: &Vec<u8>, key: &Secret<usize>) -> u8 {
+    table[*key.expose()]
+    //~^ ERROR indexing with a secret value in a `#[constant_time]` function
+}
+
+#[constant_time]
+pub unsafe fn get_unchecked(table: &[u8], key: &Secret<usize>) -> u8 {
+    unsafe { *table.get_unchecked(*key.expose()) }
+    //~^ ERROR indexing with a secret value in a `#[constant_time]` function
+}
+
+#[constant_time]
+pub unsafe fn pointer_add(table: *const u8, key: &Secret<usize>) -> u8 {
+    unsafe { *table.add(*key.expose()) }
+    //~^ ERROR pointer offset by a secret value in a `#[constant_time]` function
+}
+
+#[constant_time]
+pub fn lookup(table: &[u8], key: &Secret<usize>) -> u8 {
+    ct_lookup(table, *key.expose())
+}
+
+#[constant_time]
+pub unsafe fn public_offset(table: *const u8, key: &Secret<usize>, offset: usize) -> u8 {
+    let _ = key;
+    unsafe { *table.add(offset) }
+}
@@ -0,0 +1,48 @@
+// Check that functions marked `#[constant_time]` may neither branch nor index on secret values,
+// but may do both on public ones and on declassified secrets.
+
+#![feature(constant_time, ct_select)]
+#![crate_type = "lib"]
+
+use std::select::{ConstantTimeEq, Secret, ct_select};
+
+#[constant_time]
+pub fn select(a: u32, b: u32) -> u32 {
+    ct_select(a.ct_eq(&b), a, b)
+}
+
+#[constant_time]
+pub fn declassified(a: u32, b: u32) -> u32 {
+    if a.ct_eq(&b).declassify() { 1 } else { 2 }
+}
+
+#[constant_time]
+pub fn public_index(table: &[u8; 16], index: usize) -> u8 {
+    table[index]
+}
+
+#[constant_time]
+pub fn branch(key: &Secret<u32>) -> u32 {
+    if *key.expose() == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a secret value in a `#[constant_time]` function
+}
+
+#[constant_time]
+pub fn index(table: &[u8; 16], key: Secret<usize>) -> u8 {
+    table[*key.expose()]
+    //~^ ERROR indexing with a secret value in a `#[constant_time]` function
+}
+
+// A local stops being secret once it is overwritten with a public value.
+#[constant_time]
+pub fn overwritten(key: &Secret<u32>, public: u32) -> u32 {
+    let mut x = *key.expose();
+    x = x.wrapping_add(1);
+    x = public;
+    if x == 0 { 1 } else { 2 }
+}
+
+// Without the attribute, nothing is checked.
+pub fn unchecked(key: &Secret<u32>) -> u32 {
+    if *key.expose() == 0 { 1 } else { 2 }
+}
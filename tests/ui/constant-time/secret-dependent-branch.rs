@@ -0,0 +1,49 @@
+// Check that the `secret_dependent_branch` lint catches branches on secret values outside of
+// `#[constant_time]` functions, and leaves declassified secrets alone.
+
+#![feature(constant_time, ct_select)]
+#![deny(secret_dependent_branch)]
+#![crate_type = "lib"]
+
+use std::select::{ConstantTimeEq, CtOption, Secret};
+
+pub fn branch(key: &Secret<u32>) -> u32 {
+    if *key.expose() == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a value derived from a secret
+}
+
+pub fn lazy_and(key: &Secret<u32>, public: bool) -> bool {
+    *key.expose() == 0 && public
+    //~^ ERROR branch on a value derived from a secret
+}
+
+pub fn lazy_or(key: &Secret<u32>, public: bool) -> bool {
+    *key.expose() == 0 || public
+    //~^ ERROR branch on a value derived from a secret
+}
+
+pub fn declassified(key: &Secret<u32>) -> u32 {
+    if key.expose().ct_eq(&0).declassify() { 1 } else { 2 }
+}
+
+pub fn option_branch(option: CtOption<u32>) -> u32 {
+    if option.unwrap_or(0) == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a value derived from a secret
+}
+
+pub fn option_declassified(option: CtOption<u32>) -> u32 {
+    match option.into_option() {
+        Some(value) => value,
+        None => 0,
+    }
+}
+
+pub fn public(key: &Secret<u32>, public: u32) -> u32 {
+    let _ = key;
+    if public == 0 { 1 } else { 2 }
+}
+
+#[allow(secret_dependent_branch)]
+pub fn allowed(key: &Secret<u32>) -> u32 {
+    if *key.expose() == 0 { 1 } else { 2 }
+}
@@ -0,0 +1,54 @@
+// Check that `#[secret]` parameters are secret in the body of their function, and that secrets
+// passed by a caller make the parameters they are passed to secret, including through other
+// calls and into generic functions.
+
+#![feature(constant_time, ct_select)]
+#![deny(secret_dependent_branch)]
+#![crate_type = "lib"]
+
+use std::select::Secret;
+
+#[constant_time]
+pub fn declared(#[secret] x: u32) -> u32 {
+    if x == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a secret value in a `#[constant_time]` function
+}
+
+pub fn caller(key: &Secret<u32>, table: &[u8; 16]) -> u32 {
+    let k = *key.expose();
+    helper(k, 0) + forward(k) + generic(k) + lookup(table, k as usize) as u32
+}
+
+fn helper(x: u32, public: u32) -> u32 {
+    if public == 0 { 0 } else if x == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a value derived from a secret
+}
+
+fn forward(x: u32) -> u32 {
+    nested(x.wrapping_add(1))
+}
+
+fn nested(y: u32) -> u32 {
+    if y == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a value derived from a secret
+}
+
+fn generic<T: Into<u64>>(x: T) -> u32 {
+    if x.into() == 0 { 1 } else { 2 }
+    //~^ ERROR branch on a value derived from a secret
+}
+
+#[constant_time]
+fn lookup(table: &[u8; 16], index: usize) -> u8 {
+    table[index]
+    //~^ ERROR indexing with a secret value in a `#[constant_time]` function
+}
+
+// Only called with public values.
+fn public(x: u32) -> u32 {
+    if x == 0 { 1 } else { 2 }
+}
+
+pub fn public_caller() -> u32 {
+    public(3)
+}
@@ -0,0 +1,38 @@
+// Test that the selections of a function marked `#[ct_select_lowering(cmov)]` are reported when
+// that lowering may branch, here without optimizations, even though the lowering chosen for the
+// target does not.
+
+//@ add-minicore
+//@ compile-flags: --crate-type=lib -Copt-level=0
+//@ compile-flags: --target=x86_64-unknown-linux-gnu
+//@ needs-llvm-components: x86
+//@ build-pass
+//@ ignore-backends: gcc
+
+#![feature(constant_time, intrinsics, no_core, rustc_attrs)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+#[rustc_nounwind]
+fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[no_mangle]
+#[ct_select_lowering(cmov)]
+pub fn select_cmov(c: bool, a: i32, b: i32) -> i32 {
+    ct_select_i32(c, a, b)
+    //~^ WARN this selection of `i32` may be compiled to a branch
+}
+
+#[no_mangle]
+#[ct_select_lowering(masking)]
+pub fn select_masking(c: bool, a: i32, b: i32) -> i32 {
+    ct_select_i32(c, a, b)
+}
+
+#[no_mangle]
+pub fn select_default(c: bool, a: i32, b: i32) -> i32 {
+    ct_select_i32(c, a, b)
+}
@@ -0,0 +1,54 @@
+// Test that selections are reported after monomorphization on targets where they may be
+// compiled to branches, and only there.
+
+//@ add-minicore
+//@ revisions: m68k x86_64
+//@ compile-flags: --crate-type=lib -Copt-level=2
+//@[m68k] compile-flags: --target=m68k-unknown-linux-gnu
+//@[m68k] needs-llvm-components: m68k
+//@[x86_64] compile-flags: --target=x86_64-unknown-linux-gnu
+//@[x86_64] needs-llvm-components: x86
+//@ build-pass
+//@ ignore-backends: gcc
+
+#![feature(intrinsics, no_core, rustc_attrs)]
+#![no_core]
+
+extern crate minicore;
+use minicore::*;
+
+#[rustc_intrinsic]
+#[rustc_nounwind]
+fn ct_select_i8(b: bool, true_val: i8, false_val: i8) -> i8;
+
+#[rustc_intrinsic]
+#[rustc_nounwind]
+fn ct_select_i32(b: bool, true_val: i32, false_val: i32) -> i32;
+
+#[rustc_intrinsic]
+#[rustc_nounwind]
+fn ct_select_ptr<T>(b: bool, true_val: *const T, false_val: *const T) -> *const T;
+
+#[no_mangle]
+pub fn select_i8(c: bool, a: i8, b: i8) -> i8 {
+    ct_select_i8(c, a, b)
+    //[m68k]~^ WARN this selection of `i8` may be compiled to a branch
+}
+
+#[no_mangle]
+pub fn select_i32(c: bool, a: i32, b: i32) -> i32 {
+    ct_select_i32(c, a, b)
+    //[m68k]~^ WARN this selection of `i32` may be compiled to a branch
+}
+
+#[inline(never)]
+fn select_ptr<T>(c: bool, a: *const T, b: *const T) -> *const T {
+    ct_select_ptr(c, a, b)
+    //[m68k]~^ WARN this selection of `*const u16` may be compiled to a branch
+}
+
+// Only instances that are compiled are reported, so `select_ptr` is reported once, for `u16`.
+#[no_mangle]
+pub fn select_u16_ptr(c: bool, a: *const u16, b: *const u16) -> *const u16 {
+    select_ptr(c, a, b)
+}
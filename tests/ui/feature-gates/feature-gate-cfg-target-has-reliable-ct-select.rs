@@ -0,0 +1,4 @@
+fn main() {
+    cfg!(target_has_reliable_ct_select);
+    //~^ ERROR `cfg(target_has_reliable_ct_select)` is experimental and subject to change
+}
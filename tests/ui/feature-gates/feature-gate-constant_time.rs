@@ -0,0 +1,17 @@
+#![crate_type = "lib"]
+
+#[constant_time] //~ ERROR the `#[constant_time]` attribute is an experimental feature
+pub fn foo() {}
+
+pub fn bar(#[secret] x: u32) {} //~ ERROR the `#[secret]` attribute is an experimental feature
+
+#[branchless] //~ ERROR the `#[branchless]` attribute is an experimental feature
+pub fn baz() {}
+
+#[ct_select_lowering(masking)] //~ ERROR the `#[ct_select_lowering]` attribute is an experimental
+pub fn qux() {}
+
+pub fn quux(x: bool) -> u32 {
+    #[declassify = "x is public"] //~ ERROR the `#[declassify]` attribute is an experimental feature
+    if x { 1 } else { 0 }
+}
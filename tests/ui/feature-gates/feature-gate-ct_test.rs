@@ -0,0 +1,7 @@
+//@ edition:2018
+
+#[ct_test] //~ ERROR use of unstable library feature `ct_test`
+fn ct_test() {}
+
+use ct_test as _; //~ ERROR use of unstable library feature `ct_test`
+fn main() {}
@@ -0,0 +1,10 @@
+// Verifies that `-Zsanitizer=constant-time` requires `-Zsanitizer-dataflow-abilist`.
+//
+//@ compile-flags: -Zsanitizer=constant-time --target x86_64-unknown-linux-gnu
+//@ needs-llvm-components: x86
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
+
+//~? ERROR `-Zsanitizer=constant-time` requires `-Zsanitizer-dataflow-abilist`
@@ -0,0 +1,23 @@
+// Verifies that a branch on a secret is reported.
+//
+//@ needs-sanitizer-support
+//@ needs-sanitizer-constant-time
+//@ run-fail
+//@ compile-flags: -Zsanitizer=constant-time -Zsanitizer-dataflow-abilist={{src-base}}/sanitizer/dataflow-abilist.txt -C unsafe-allow-abi-mismatch=sanitizer
+//@ error-pattern: ConstantTimeSanitizer: a branch depends on a secret in `constant_time::unlock
+//@ ignore-backends: gcc
+
+#![feature(ct_select)]
+
+use std::hint::black_box;
+use std::select::Secret;
+
+#[inline(never)]
+fn unlock(pin: &Secret<u16>, guess: u16) -> u32 {
+    if *pin.expose() == guess { 1 } else { 2 }
+}
+
+fn main() {
+    let pin = Secret::new(black_box(1234));
+    black_box(unlock(&pin, black_box(4321)));
+}
@@ -0,0 +1,11 @@
+//@ compile-flags: --test
+
+//! Test that makes sure wrongly-typed constant-time test functions aren't ignored
+
+#![feature(ct_test, test)]
+
+#[ct_test]
+fn foo() { } //~ ERROR functions used as constant-time tests
+
+#[ct_test]
+fn bar(x: isize, y: isize) { } //~ ERROR functions used as constant-time tests
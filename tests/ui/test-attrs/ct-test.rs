@@ -0,0 +1,48 @@
+//@ no-prefer-dynamic
+//@ compile-flags: --test
+//@ run-flags: --test-threads=1
+//@ run-pass
+//@ exec-env: RUST_CT_TEST_SAMPLES=1000
+//@ exec-env: RUST_CT_TEST_THRESHOLD=inf
+//@ needs-unwind
+
+// Tests that `#[ct_test]` functions are run by the test harness. The threshold is infinite, so
+// that the timings of this test cannot make it fail.
+
+#![feature(ct_test, test)]
+
+extern crate test;
+
+use test::{CtTester, InputClass};
+
+#[ct_test]
+fn xor(t: &mut CtTester) {
+    t.measure(
+        |class, rng| match class {
+            InputClass::Fixed => 0,
+            InputClass::Random => rng.next_u64(),
+        },
+        |x| x ^ 0x5555_5555_5555_5555,
+    );
+}
+
+#[ct_test]
+fn bytes(t: &mut CtTester) -> Result<(), String> {
+    t.measure(
+        |class, rng| {
+            let mut bytes = [0; 32];
+            if class == InputClass::Random {
+                rng.fill_bytes(&mut bytes);
+            }
+            bytes
+        },
+        |bytes| bytes.iter().fold(0u8, |acc, b| acc | b),
+    );
+    Ok(())
+}
+
+#[ct_test]
+#[should_panic]
+fn panics(_: &mut CtTester) {
+    panic!();
+}